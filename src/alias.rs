@@ -0,0 +1,185 @@
+//! `[alias]` expansion, applied to the raw arguments before clap parsing.
+//!
+//! Built-in subcommands always win, so an alias can't shadow `list` etc.
+
+use crate::error::{MemoError, MemoResult};
+use clap::Command;
+use std::collections::{BTreeMap, HashSet};
+
+/// Replace a leading alias in `args` (including the program name) with its definition
+pub fn expand(
+    args: Vec<String>,
+    aliases: &BTreeMap<String, String>,
+    cli: &Command,
+) -> MemoResult<Vec<String>> {
+    let mut args = args;
+    let mut seen = HashSet::new();
+
+    loop {
+        let Some(index) = subcommand_index(&args, cli) else {
+            return Ok(args);
+        };
+        let name = &args[index];
+        if is_builtin(cli, name) {
+            return Ok(args);
+        }
+        let Some(definition) = aliases.get(name) else {
+            return Ok(args);
+        };
+        if !seen.insert(name.clone()) {
+            return Err(MemoError::ConfigError(format!(
+                "alias '{}' expands to itself",
+                name
+            )));
+        }
+
+        let replacement = split_words(definition)
+            .map_err(|e| MemoError::ConfigError(format!("invalid alias '{}': {}", name, e)))?;
+        if replacement.is_empty() {
+            return Err(MemoError::ConfigError(format!("alias '{}' is empty", name)));
+        }
+        args.splice(index..=index, replacement);
+    }
+}
+
+/// サブコマンド位置（先頭のグローバルオプションの次）
+fn subcommand_index(args: &[String], cli: &Command) -> Option<usize> {
+    let mut index = 1;
+    while let Some(arg) = args.get(index) {
+        if arg == "--" {
+            return None;
+        }
+        if !arg.starts_with('-') {
+            return Some(index);
+        }
+        let takes_value = arg.strip_prefix("--").is_some_and(|long| {
+            cli.get_arguments()
+                .any(|a| a.get_long() == Some(long) && a.get_action().takes_values())
+        });
+        index += if takes_value { 2 } else { 1 };
+    }
+    None
+}
+
+fn is_builtin(cli: &Command, name: &str) -> bool {
+    cli.get_subcommands()
+        .any(|c| c.get_name() == name || c.get_all_aliases().any(|a| a == name))
+}
+
+/// Split a command line with shell-like quoting (`'...'`, `"..."`, `\`)
+pub fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => match chars.next() {
+                Some(escaped) => current.push(escaped),
+                None => return Err("trailing backslash".to_string()),
+            },
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+                continue;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+                continue;
+            }
+            (None, c) => current.push(c),
+        }
+        in_word = true;
+    }
+
+    if quote.is_some() {
+        return Err("unterminated quote".to_string());
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+
+    fn test_cli() -> Command {
+        Command::new("memo")
+            .arg(Arg::new("color").long("color").global(true))
+            .subcommand(Command::new("list"))
+            .subcommand(Command::new("search"))
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn aliases(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_alias() {
+        let aliases = aliases(&[
+            ("ls", "list --table"),
+            ("todo", "search tags:/@todo"),
+            ("t", "todo"),
+            ("list", "search shadowed"),
+        ]);
+        let cli = test_cli();
+
+        assert_eq!(
+            expand(args(&["memo", "ls", "--json"]), &aliases, &cli).unwrap(),
+            args(&["memo", "list", "--table", "--json"])
+        );
+        assert_eq!(
+            expand(args(&["memo", "--color", "never", "t"]), &aliases, &cli).unwrap(),
+            args(&["memo", "--color", "never", "search", "tags:/@todo"])
+        );
+        // 組み込みコマンドは上書きできない
+        assert_eq!(
+            expand(args(&["memo", "list"]), &aliases, &cli).unwrap(),
+            args(&["memo", "list"])
+        );
+        assert_eq!(
+            expand(args(&["memo", "unknown"]), &aliases, &cli).unwrap(),
+            args(&["memo", "unknown"])
+        );
+    }
+
+    #[test]
+    fn test_expand_alias_loop() {
+        let aliases = aliases(&[("a", "b"), ("b", "a --x")]);
+        assert!(matches!(
+            expand(args(&["memo", "a"]), &aliases, &test_cli()),
+            Err(MemoError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(r#"search "meeting notes" 'it''s' a\ b"#).unwrap(),
+            args(&["search", "meeting notes", "its", "a b"])
+        );
+        assert_eq!(
+            split_words(r#"list --where "x == ''""#).unwrap(),
+            args(&["list", "--where", "x == ''"])
+        );
+        assert_eq!(split_words("  ").unwrap(), Vec::<String>::new());
+        assert!(split_words("\"open").is_err());
+    }
+}
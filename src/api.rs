@@ -0,0 +1,197 @@
+//! Supported library surface for programs embedding memo.
+//!
+//! The other public modules are the CLI's internals and may change at any time;
+//! the functions here are kept stable.
+//!
+//! ```no_run
+//! use memo::api::MemoFilter;
+//! use memo::MemoApp;
+//!
+//! let app = MemoApp::builder().notebook("work").build()?;
+//! let memo = app.create("# Standup\n- deploy")?;
+//! let todo = app.list(&MemoFilter::default().with_tag("@todo"))?;
+//! let hits = app.search("deploy")?;
+//! app.archive(&memo.id.to_string())?;
+//! # Ok::<(), memo::MemoError>(())
+//! ```
+//!
+//! The same operations are available as free functions taking a `MemoContext`
+//! (`api::create_memo` and so on).
+//!
+//! Unlike the CLI these never open an editor, print, or send webhooks.
+
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::memo::{MemoDocument, MemoFile};
+use crate::ops::{Action, Batch};
+use crate::repository::MemoRepository;
+use crate::search::{SearchManager, SearchParams, SearchResult};
+use std::path::PathBuf;
+
+/// メモの作成・取得・一覧・検索・アーカイブをまとめた入口
+///
+/// `MemoContext` を持つだけで、各メソッドはこのモジュールの関数と同じ
+#[derive(Debug, Clone)]
+pub struct MemoApp {
+    context: MemoContext,
+}
+
+impl MemoApp {
+    pub fn builder() -> MemoAppBuilder {
+        MemoAppBuilder::default()
+    }
+
+    /// 組み立て済みのコンテキストを使う
+    pub fn from_context(context: MemoContext) -> Self {
+        Self { context }
+    }
+
+    pub fn context(&self) -> &MemoContext {
+        &self.context
+    }
+
+    pub fn create(&self, content: &str) -> MemoResult<MemoFile> {
+        create_memo(&self.context, content)
+    }
+
+    pub fn get(&self, id: &str) -> MemoResult<MemoFile> {
+        get_memo(&self.context, id)
+    }
+
+    pub fn list(&self, filter: &MemoFilter) -> MemoResult<Vec<MemoFile>> {
+        list_memos(&self.context, filter)
+    }
+
+    pub fn search(&self, query: &str) -> MemoResult<Vec<SearchResult>> {
+        search(&self.context, query)
+    }
+
+    pub fn archive(&self, id: &str) -> MemoResult<MemoFile> {
+        archive(&self.context, id)
+    }
+}
+
+/// `MemoApp::builder()`
+///
+/// 何も指定しなければ CLI と同じく `config.toml` と XDG の既定からコンテキストを作る
+#[derive(Debug, Clone, Default)]
+pub struct MemoAppBuilder {
+    memo_dir: Option<PathBuf>,
+    notebook: Option<String>,
+}
+
+impl MemoAppBuilder {
+    /// 設定ファイルを読まずに、このディレクトリをメモディレクトリとして使う
+    pub fn memo_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.memo_dir = Some(dir.into());
+        self
+    }
+
+    /// `config.toml` の `[notebooks]` にあるノートブックを使う（`--notebook` と同じ）
+    pub fn notebook(mut self, name: &str) -> Self {
+        self.notebook = Some(name.to_string());
+        self
+    }
+
+    pub fn build(self) -> MemoResult<MemoApp> {
+        let context = match (self.memo_dir, self.notebook) {
+            (Some(memo_dir), None) => MemoContext {
+                memo_dir,
+                ..Default::default()
+            },
+            (Some(_), Some(_)) => {
+                return Err(MemoError::InvalidArgument(
+                    "memo_dir and notebook cannot be combined".to_string(),
+                ));
+            }
+            (None, notebook) => {
+                let context = MemoContext::new()?;
+                match notebook {
+                    Some(name) => context.for_notebook(&name)?,
+                    None => context,
+                }
+            }
+        };
+        context.ensure_memo_dir()?;
+        Ok(MemoApp::from_context(context))
+    }
+}
+
+/// `list_memos` の絞り込み条件（全て指定なしなら全件）
+#[derive(Debug, Clone, Default)]
+pub struct MemoFilter {
+    /// Front matter `tags` must contain this entry
+    pub tag: Option<String>,
+    /// Front matter `priority` must be at least this
+    pub min_priority: Option<i64>,
+}
+
+impl MemoFilter {
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = Some(tag.to_string());
+        self
+    }
+
+    pub fn with_min_priority(mut self, priority: i64) -> Self {
+        self.min_priority = Some(priority);
+        self
+    }
+
+    pub fn matches(&self, memo: &MemoFile) -> bool {
+        if let Some(tag) = &self.tag
+            && !memo.tags().contains(&tag.as_str())
+        {
+            return false;
+        }
+        match self.min_priority {
+            Some(min) => memo.priority().is_some_and(|p| p >= min),
+            None => true,
+        }
+    }
+}
+
+/// 新しいメモを書き込み、検索インデックスにも追加する
+pub fn create_memo(ctx: &MemoContext, content: &str) -> MemoResult<MemoFile> {
+    ctx.ensure_memo_dir()?;
+    let repo = MemoRepository::new(ctx.clone());
+    let memo = repo.create_new_memo(false, |_| content.to_string())?;
+    update_search_index(ctx, &memo.path)?;
+    Ok(memo)
+}
+
+/// ID・ID の一部・相対パスのいずれかでメモを取得
+pub fn get_memo(ctx: &MemoContext, id: &str) -> MemoResult<MemoFile> {
+    MemoRepository::new(ctx.clone()).find_memo_by_id(id)
+}
+
+/// 条件に合うメモを新しい順に返す（アーカイブ済みは含まない）
+pub fn list_memos(ctx: &MemoContext, filter: &MemoFilter) -> MemoResult<Vec<MemoFile>> {
+    let memos = MemoRepository::new(ctx.clone()).list_all_memos()?;
+    Ok(memos
+        .into_iter()
+        .filter(|memo| filter.matches(memo))
+        .collect())
+}
+
+/// `memo search` と同じクエリ構文で検索する（インデックスが無ければ空）
+///
+/// The user's `config.toml` is not read; results use the default field boosts.
+pub fn search(ctx: &MemoContext, query: &str) -> MemoResult<Vec<SearchResult>> {
+    search_manager(ctx).search(query, &SearchParams::default())
+}
+
+/// メモを `.archive` に移し、検索インデックスから外す
+pub fn archive(ctx: &MemoContext, id: &str) -> MemoResult<MemoFile> {
+    let repo = MemoRepository::new(ctx.clone());
+    let memo = repo.find_memo_by_id(id)?;
+    let mut batch = Batch::new(ctx, Action::Archive);
+    let archived = batch.archive(&memo)?;
+    batch.commit()?;
+    search_manager(ctx).remove_memo(&MemoDocument::from_memo_file(&memo))?;
+    Ok(archived)
+}
+
+fn search_manager(ctx: &MemoContext) -> SearchManager {
+    ctx.search_manager()
+}
@@ -1,58 +1,78 @@
 use crate::context::MemoContext;
-use crate::error::MemoResult;
-use crate::memo::{MemoDocument, MemoFile};
+use crate::error::{MemoError, MemoResult};
 use crate::memo_id::MemoId;
-use crate::repository::MemoRepository;
-use crate::search::SearchManager;
+use crate::ops;
+use crate::template::{self, TemplateVars};
 use crate::utils::editor;
+use std::io::Read;
 
-fn template_content(context: &MemoContext, template: &str) -> String {
-    // .templates/{name}.md があればそれを使う
-    let template_path = context.templates_dir().join(format!("{}.md", template));
-    if template_path.exists() {
-        if let Ok(content) = std::fs::read_to_string(&template_path) {
-            return content;
-        }
-    }
-    // ビルトインにフォールバック
-    builtin_template(template)
+/// `memo add` のオプション
+#[derive(Debug, Clone, Default)]
+pub struct AddOptions {
+    /// Template name (`.templates/<name>.md` or a builtin)
+    pub template: Option<String>,
+    /// Value for the template's `{{title}}`
+    pub title: Option<String>,
+    /// Memo body given with `-m` or on stdin; the editor is skipped when set
+    pub body: Option<String>,
+    /// Store the memo encrypted as `<id>.md.age`
+    pub encrypt: bool,
 }
 
-pub fn builtin_template(template: &str) -> String {
-    match template {
-        "1on1" => "---\ntitle: \"\"\ntags: ['@1on1']\n---\n\n## 話したこと\n\n\n## ネクストアクション\n\n".to_string(),
-        "idea" => "---\ntitle: \"\"\ntags: ['@idea']\n---\n\n## アイデア\n\n\n## 背景・動機\n\n".to_string(),
-        "todo" => "---\ntitle: \"\"\ntags: ['@todo']\n---\n\n## やること\n\n- [ ] \n".to_string(),
-        "meeting" => "---\ntitle: \"\"\ntags: ['@meeting']\n---\n\n## 参加者\n\n\n## 議題\n\n\n## 決定事項\n\n\n## ネクストアクション\n\n".to_string(),
-        other => format!("---\ntitle: \"\"\ntags: ['@{}']\n---\n\n", other),
+/// `-m` の内容か、`-` の時は標準入力の内容
+pub fn resolve_body(message: Option<String>, from_stdin: bool) -> MemoResult<Option<String>> {
+    let body = if from_stdin {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        match message {
+            Some(message) => message,
+            None => return Ok(None),
+        }
+    };
+    if body.trim().is_empty() {
+        return Err(MemoError::InvalidArgument(
+            "the memo content is empty".to_string(),
+        ));
     }
+    Ok(Some(body))
 }
 
-pub fn run(context: &MemoContext, template: Option<&str>) -> MemoResult<()> {
-    let memo_id = MemoId::new();
-    let relative_path = memo_id.to_relative_path();
-
-    let initial_content = template.map_or(String::new(), |t| template_content(context, t));
-
-    let repo = MemoRepository::new(context.clone());
-    let memo = repo.create_memo(&relative_path, initial_content)?;
-
-    editor::open_editor(context, &memo.path)?;
-    update_search_index(context, &memo.path)?;
+pub fn run(context: &MemoContext, options: &AddOptions) -> MemoResult<()> {
+    if options.encrypt && context.encryption_key.is_none() {
+        return Err(MemoError::Encryption(
+            "--encrypt needs a key; set [encryption] identity in config.toml".to_string(),
+        ));
+    }
+    // 同じ秒に作ったメモがあれば ID をずらすので、テンプレートは ID が決まってから展開する
+    let memo = ops::create_memo(
+        context,
+        options.encrypt,
+        |memo_id| initial_content(context, options, memo_id),
+        |path| match options.body {
+            Some(_) => Ok(()),
+            None => editor::open_editor(context, path),
+        },
+    )?;
 
-    println!("Memo created: {}", memo_id);
+    println!("Memo created: {}", memo.id);
     Ok(())
 }
 
-fn update_search_index(context: &MemoContext, memo_path: &std::path::Path) -> MemoResult<()> {
-    let data_dir = context.memo_dir.clone();
-    let index_dir = context.index_dir();
-    let search_manager = SearchManager::new(data_dir, index_dir);
-
-    if let Ok(memo_file) = MemoFile::from_path(memo_path) {
-        let memo_doc = MemoDocument::from_memo_file(&memo_file);
-        let _ = search_manager.add_memo(&memo_doc)?;
+fn initial_content(context: &MemoContext, options: &AddOptions, memo_id: &MemoId) -> String {
+    let mut content = options.template.as_deref().map_or(String::new(), |name| {
+        template::expand(
+            &template::load(context, name),
+            &TemplateVars::new(memo_id, options.title.as_deref()),
+        )
+    });
+    if let Some(body) = &options.body {
+        // テンプレートの後ろに続ける
+        content.push_str(body);
+        if !body.ends_with('\n') {
+            content.push('\n');
+        }
     }
-
-    Ok(())
+    content
 }
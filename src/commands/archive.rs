@@ -1,61 +1,329 @@
 use crate::context::MemoContext;
+use crate::display::format::OutputFormat;
 use crate::error::{MemoError, MemoResult};
+use crate::git;
+use crate::links;
 use crate::memo::MemoDocument;
 use crate::memo::MemoFile;
-use crate::repository::MemoRepository;
-use crate::search::SearchManager;
-use crate::utils::id_resolver;
+use crate::memo_id::MemoId;
+use crate::ops::{Action, Batch};
+use crate::repository::{DateBasis, DateRange, MemoRepository, PlannedMove};
+use crate::utils::{datetime, id_resolver};
+use crate::webhook::{self, WebhookEvent};
+use chrono::Local;
+use std::collections::BTreeMap;
+
+/// `archive_memos` / `archive_targets` の結果
+#[derive(Debug, Default)]
+pub struct ArchiveReport {
+    pub archived: Vec<MemoFile>,
+    /// アーカイブできなかった対象（指定された順）
+    pub failures: Vec<ArchiveFailure>,
+    /// 残るメモからアーカイブしたメモへのリンク（`[[id]]` が切れる）の警告
+    pub warnings: Vec<String>,
+}
+
+/// アーカイブできなかった対象とその理由
+#[derive(Debug)]
+pub struct ArchiveFailure {
+    pub target: String,
+    pub error: MemoError,
+}
+
+pub fn run(context: &MemoContext, targets: &[String], dry_run: bool) -> MemoResult<()> {
+    if dry_run {
+        return run_dry(context, targets);
+    }
+
+    let report = archive_targets(context, targets)?;
+
+    for failure in &report.failures {
+        eprintln!("Error archiving '{}': {}", failure.target, failure.error);
+    }
+    if report.archived.is_empty() && !report.failures.is_empty() {
+        return Err(MemoError::ArchiveError(
+            "Failed to archive any targets".to_string(),
+        ));
+    }
 
-pub fn run(context: &MemoContext, targets: &[String]) -> MemoResult<()> {
+    if !report.archived.is_empty() {
+        println!("Archived {} memo(s)", report.archived.len());
+    }
+    print_warnings(&report.warnings);
+    Ok(())
+}
+
+/// `--dry-run`: 移動するファイルを表示するだけで何も動かさない
+fn run_dry(context: &MemoContext, targets: &[String]) -> MemoResult<()> {
+    let (moves, failures) = plan_targets(context, targets)?;
+
+    for failure in &failures {
+        eprintln!("Error archiving '{}': {}", failure.target, failure.error);
+    }
+    print_planned_moves(&moves);
+    let ids: Vec<MemoId> = moves
+        .iter()
+        .filter(|m| m.from.is_file())
+        .filter_map(|m| MemoId::from_path(&m.from).ok())
+        .collect();
+    print_warnings(&broken_link_warnings(context, &ids));
+    eprintln!(
+        "{} memo(s) would be archived. Run without --dry-run to apply.",
+        ids.len()
+    );
+    Ok(())
+}
+
+/// `archive_targets` が行う移動を、ファイルに触れずに対象ごとに集める
+pub fn plan_targets(
+    context: &MemoContext,
+    targets: &[String],
+) -> MemoResult<(Vec<PlannedMove>, Vec<ArchiveFailure>)> {
     if targets.is_empty() {
         return Err(MemoError::ArchiveError(
             "At least one target (ID, file path, or directory) is required".to_string(),
         ));
     }
 
-    let data_dir = context.memo_dir.clone();
-    let index_dir = context.index_dir();
-    let search_manager = SearchManager::new(data_dir, index_dir);
+    let repo = MemoRepository::new(context.clone());
+    let mut moves = Vec::new();
+    let mut failures = Vec::new();
+    for target in targets {
+        match resolve_memos(&repo, target) {
+            Ok(memos) => moves.extend(repo.plan_archive(&memos)),
+            Err(error) => failures.push(ArchiveFailure {
+                target: target.clone(),
+                error,
+            }),
+        }
+    }
+    Ok((moves, failures))
+}
+
+/// アーカイブするメモに、残るメモからリンクがあれば警告を作る（`[[id]]` が切れる）
+fn broken_link_warnings(context: &MemoContext, ids: &[MemoId]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for id in ids {
+        match links::backlinks(context, id) {
+            Ok(sources) => {
+                let sources: Vec<String> = sources
+                    .iter()
+                    .filter(|source| !ids.contains(source))
+                    .map(MemoId::as_str)
+                    .collect();
+                if !sources.is_empty() {
+                    warnings.push(format!(
+                        "{} is linked from {}; those links will break",
+                        id,
+                        sources.join(", ")
+                    ));
+                }
+            }
+            Err(e) => warnings.push(format!("could not check links to {}: {}", id, e)),
+        }
+    }
+    warnings
+}
+
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+}
+
+/// `memo archive --older-than <age>`: 作成（`by` が modified なら更新）から `age` 以上たった
+/// メモをまとめてアーカイブし、月ごとの件数を表示する
+///
+/// `age` / `by` が無ければ config.toml の `[archive]` を使う
+pub fn run_older_than(
+    context: &MemoContext,
+    age: Option<&str>,
+    by: Option<DateBasis>,
+    dry_run: bool,
+) -> MemoResult<()> {
+    let policy = &context.config.archive;
+    let age = age.or(policy.older_than.as_deref()).ok_or_else(|| {
+        MemoError::ArchiveError(
+            "--older-than needs an age (e.g. 90d) or [archive] older_than in config.toml"
+                .to_string(),
+        )
+    })?;
+    let by = by.unwrap_or(policy.by);
+    let cutoff = Local::now()
+        - datetime::parse_age(age).ok_or_else(|| {
+            MemoError::InvalidArgument(format!("invalid age '{}' (e.g. 90d, 12w)", age))
+        })?;
 
     let repo = MemoRepository::new(context.clone());
-    let mut archived_count = 0;
-    let mut errors = Vec::new();
+    let range = DateRange {
+        since: None,
+        until: Some(cutoff),
+    };
+    let memos = repo.select_memos(&range, by)?;
+    let basis = match by {
+        DateBasis::Created => "created",
+        DateBasis::Modified => "modified",
+    };
+    if memos.is_empty() {
+        println!(
+            "No memos {} before {}",
+            basis,
+            cutoff.format("%Y-%m-%d %H:%M")
+        );
+        return Ok(());
+    }
 
-    for target in targets {
-        match archive_target(&search_manager, &repo, target) {
-            Ok(count) => archived_count += count,
-            Err(e) => errors.push(format!("Error archiving '{}': {}", target, e)),
+    if dry_run {
+        let ids: Vec<MemoId> = memos.iter().map(|memo| memo.id.clone()).collect();
+        print_planned_moves(&repo.plan_archive(&memos));
+        print_summary(&memos);
+        print_warnings(&broken_link_warnings(context, &ids));
+        eprintln!(
+            "{} memo(s) would be archived. Run without --dry-run to apply.",
+            memos.len()
+        );
+        return Ok(());
+    }
+
+    let report = archive_memos(context, &memos)?;
+    for failure in &report.failures {
+        eprintln!("Error archiving '{}': {}", failure.target, failure.error);
+    }
+    if report.archived.is_empty() {
+        return Err(MemoError::ArchiveError(
+            "Failed to archive any memos".to_string(),
+        ));
+    }
+
+    println!(
+        "Archived {} memo(s) {} before {}",
+        report.archived.len(),
+        basis,
+        cutoff.format("%Y-%m-%d %H:%M")
+    );
+    print_summary(&report.archived);
+    print_warnings(&report.warnings);
+    Ok(())
+}
+
+/// 作成月ごとの件数（古い順）
+fn print_summary(memos: &[MemoFile]) {
+    let mut months: BTreeMap<String, usize> = BTreeMap::new();
+    for memo in memos {
+        let month = memo.id.get_datetime().format("%Y-%m").to_string();
+        *months.entry(month).or_default() += 1;
+    }
+    for (month, count) in months {
+        println!("  {}: {}", month, count);
+    }
+}
+
+/// `memo archive --list`: アーカイブしたメモを新しい順に表示
+pub fn run_list(context: &MemoContext, format: Option<OutputFormat>) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memos = repo.list_archived_memos()?;
+    format
+        .unwrap_or_default()
+        .formatter()
+        .print_list(&memos, "Archived memos")
+}
+
+/// `before -> after`（完全削除は `before -> (removed)`）を 1 行ずつ表示
+pub fn print_planned_moves(moves: &[PlannedMove]) {
+    for planned in moves {
+        match &planned.to {
+            Some(to) => println!("{} -> {}", planned.from.display(), to.display()),
+            None => println!("{} -> (removed)", planned.from.display()),
         }
     }
+}
 
-    if !errors.is_empty() {
-        for error in &errors {
-            eprintln!("{}", error);
+/// メモをアーカイブして検索インデックスから外し、webhook と git の自動コミットに知らせる
+///
+/// 1 つのメモの失敗で止めず、残りを続ける。切れるリンクの警告は `warnings` に入れ、表示は
+/// 呼び出し側に任せる（`memo archive`・`memo serve`・`memo tui` で共通）
+pub fn archive_memos(context: &MemoContext, memos: &[MemoFile]) -> MemoResult<ArchiveReport> {
+    let mut batch = Batch::new(context, Action::Archive);
+    let mut report = ArchiveReport::default();
+    let mut removed = Vec::new();
+    for memo in memos {
+        match batch.archive(memo) {
+            Ok(archived) => {
+                removed.push(MemoDocument::from_memo_file(memo));
+                report.archived.push(archived);
+            }
+            Err(error) => report.failures.push(ArchiveFailure {
+                target: memo.id.to_string(),
+                error,
+            }),
         }
-        if archived_count == 0 {
-            return Err(MemoError::ArchiveError(
-                "Failed to archive any targets".to_string(),
-            ));
+    }
+    batch.commit()?;
+    if report.archived.is_empty() {
+        return Ok(report);
+    }
+
+    context.search_manager().remove_memos(&removed)?;
+    let ids: Vec<MemoId> = report.archived.iter().map(|memo| memo.id.clone()).collect();
+    report.warnings = broken_link_warnings(context, &ids);
+    webhook::notify(&context.config, WebhookEvent::Archive, &report.archived);
+    git::auto_commit(
+        &context.config,
+        &context.memo_dir,
+        "archive",
+        &report.archived,
+    );
+    Ok(report)
+}
+
+/// ID・ファイルパス・ディレクトリごとにメモを探して [`archive_memos`] する
+///
+/// 見つからない対象があっても止めず、対象ごとの結果を返す
+pub fn archive_targets(context: &MemoContext, targets: &[String]) -> MemoResult<ArchiveReport> {
+    if targets.is_empty() {
+        return Err(MemoError::ArchiveError(
+            "At least one target (ID, file path, or directory) is required".to_string(),
+        ));
+    }
+
+    let repo = MemoRepository::new(context.clone());
+    let mut memos = Vec::new();
+    let mut failures = Vec::new();
+    for target in targets {
+        match resolve_memos(&repo, target) {
+            Ok(found) => memos.extend(found),
+            Err(error) => failures.push(ArchiveFailure {
+                target: target.clone(),
+                error,
+            }),
         }
     }
+    let mut report = archive_memos(context, &memos)?;
+    failures.append(&mut report.failures);
+    report.failures = failures;
+    Ok(report)
+}
 
-    if archived_count > 0 {
-        println!("Archived {} memo(s)", archived_count);
+/// 対象のメモ（ディレクトリなら中の全メモ）
+fn resolve_memos(repo: &MemoRepository, target: &str) -> MemoResult<Vec<MemoFile>> {
+    match resolve_target(repo, target)? {
+        ArchiveTarget::Memo(memo) => Ok(vec![memo]),
+        ArchiveTarget::Directory(dir_path) => repo.list_memos_in_directory(&dir_path),
     }
+}
 
-    Ok(())
+enum ArchiveTarget {
+    Memo(MemoFile),
+    /// relative to the memo directory
+    Directory(String),
 }
 
-fn archive_target(
-    search_manager: &SearchManager,
-    repo: &MemoRepository,
-    target: &str,
-) -> MemoResult<usize> {
-    if let Ok(file_path) = id_resolver::resolve_memo_id(repo.memo_dir(), target) {
-        let memo = MemoFile::from_path(&file_path)?;
-        repo.archive_memo(&memo)?;
-        search_manager.remove_memo(&MemoDocument::from_memo_file(&memo))?;
-        return Ok(1);
+/// ID・ファイルパス・ディレクトリの順に対象を探す
+fn resolve_target(repo: &MemoRepository, target: &str) -> MemoResult<ArchiveTarget> {
+    match id_resolver::resolve_memo_id(repo.memo_dir(), target) {
+        Ok(file_path) => return Ok(ArchiveTarget::Memo(MemoFile::from_path(&file_path)?)),
+        Err(e @ MemoError::AmbiguousId(_)) => return Err(e),
+        Err(_) => {}
     }
 
     let file_path = if target.ends_with(".md") {
@@ -65,19 +333,12 @@ fn archive_target(
     };
 
     if file_path.exists() && file_path.is_file() {
-        let memo = MemoFile::from_path(&file_path)?;
-        repo.archive_memo(&memo)?;
-        search_manager.remove_memo(&MemoDocument::from_memo_file(&memo))?;
-        return Ok(1);
+        return Ok(ArchiveTarget::Memo(MemoFile::from_path(&file_path)?));
     }
 
     let dir_path = target.trim_end_matches('/');
     if repo.memo_dir().join(dir_path).exists() && repo.memo_dir().join(dir_path).is_dir() {
-        let archived_memos = repo.archive_directory(dir_path)?;
-        for memo in &archived_memos {
-            search_manager.remove_memo(&MemoDocument::from_memo_file(memo))?;
-        }
-        return Ok(archived_memos.len());
+        return Ok(ArchiveTarget::Directory(dir_path.to_string()));
     }
 
     Err(MemoError::MemoNotFound(target.to_string()))
@@ -99,6 +360,7 @@ mod tests {
         let context = MemoContext {
             memo_dir,
             editor: "echo".to_string(),
+            ..Default::default()
         };
 
         (temp_dir, context)
@@ -121,7 +383,7 @@ mod tests {
         setup_test_memos(&context);
 
         let targets = vec!["2025-01/30/20250130143022".to_string()];
-        let result = run(&context, &targets);
+        let result = run(&context, &targets, false);
 
         assert!(result.is_ok());
 
@@ -139,7 +401,7 @@ mod tests {
             "2025-01/30/20250130143022.md".to_string(),
             "2025-01/30/20250130151545.md".to_string(),
         ];
-        let result = run(&context, &targets);
+        let result = run(&context, &targets, false);
 
         assert!(result.is_ok());
 
@@ -158,7 +420,7 @@ mod tests {
         setup_test_memos(&context);
 
         let targets = vec!["2025-01/30/".to_string()];
-        let result = run(&context, &targets);
+        let result = run(&context, &targets, false);
 
         assert!(result.is_ok());
 
@@ -177,17 +439,39 @@ mod tests {
         setup_test_memos(&context);
 
         let targets = vec!["999999".to_string()];
-        let result = run(&context, &targets);
+        let result = run(&context, &targets, false);
 
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_archive_targets_reports_each_failure() {
+        let (_temp_dir, context) = create_test_context();
+        setup_test_memos(&context);
+
+        let targets = vec![
+            "20250130143022".to_string(),
+            "999999".to_string(),
+            "2025-02/".to_string(),
+        ];
+        let report = archive_targets(&context, &targets).unwrap();
+
+        assert_eq!(report.archived.len(), 1);
+        assert_eq!(report.archived[0].id.as_str(), "20250130143022");
+        let failed: Vec<&str> = report.failures.iter().map(|f| f.target.as_str()).collect();
+        assert_eq!(failed, vec!["999999", "2025-02/"]);
+        assert!(matches!(
+            report.failures[0].error,
+            MemoError::MemoNotFound(_)
+        ));
+    }
+
     #[test]
     fn test_archive_no_arguments() {
         let (_temp_dir, context) = create_test_context();
 
         let targets = vec![];
-        let result = run(&context, &targets);
+        let result = run(&context, &targets, false);
 
         assert!(result.is_err());
         if let Err(MemoError::ArchiveError(msg)) = result {
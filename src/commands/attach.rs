@@ -0,0 +1,110 @@
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::encryption;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::front_matter;
+use crate::git;
+use crate::memo::{ATTACHMENTS_KEY, MemoFile};
+use crate::repository::MemoRepository;
+use crate::webhook::{self, WebhookEvent};
+use chrono::Local;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `memo attach <id> <file>...`: ファイルをメモの添付ディレクトリにコピーし、
+/// front matter の `attachments` に記録する
+pub fn run(context: &MemoContext, id: &str, files: &[PathBuf]) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(id)?;
+    // コピーを始める前に全て確かめる
+    for file in files {
+        if !file.is_file() {
+            return Err(MemoError::InvalidArgument(format!(
+                "'{}' is not a file",
+                file.display()
+            )));
+        }
+    }
+
+    let assets_dir = memo.assets_dir();
+    fs::create_dir_all(&assets_dir).with_path(&assets_dir)?;
+    let mut names: Vec<String> = memo.attachments().iter().map(|s| s.to_string()).collect();
+    for file in files {
+        let name = unused_name(&assets_dir, file);
+        fs::copy(file, assets_dir.join(&name)).with_path(file)?;
+        println!("Attached {} to {}", name, memo.id);
+        names.push(name);
+    }
+
+    let memo = set_attachments(&memo, &names)?.set_updated_at(Local::now())?;
+    update_search_index(context, &memo.path)?;
+    webhook::notify(
+        &context.config,
+        WebhookEvent::Edit,
+        std::slice::from_ref(&memo),
+    );
+    git::auto_commit(
+        &context.config,
+        &context.memo_dir,
+        "attach",
+        std::slice::from_ref(&memo),
+    );
+    Ok(())
+}
+
+fn set_attachments(memo: &MemoFile, names: &[String]) -> MemoResult<MemoFile> {
+    let value = names
+        .iter()
+        .map(|name| serde_json::to_string(name).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let raw = encryption::read_memo(&memo.path)?;
+    encryption::write_memo(
+        &memo.path,
+        &front_matter::set_field(&raw, ATTACHMENTS_KEY, &format!("[{}]", value)),
+    )?;
+    MemoFile::from_path(&memo.path)
+}
+
+/// 同じ名前の添付があれば `photo-1.png` のように番号を付ける
+fn unused_name(assets_dir: &Path, file: &Path) -> String {
+    let name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "attachment".to_string());
+    if !assets_dir.join(&name).exists() {
+        return name;
+    }
+    let path = Path::new(&name);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| format!("{}-{}{}", stem, n, extension))
+        .find(|candidate| !assets_dir.join(candidate).exists())
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unused_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        assert_eq!(unused_name(dir, Path::new("/tmp/photo.png")), "photo.png");
+
+        fs::write(dir.join("photo.png"), "").unwrap();
+        fs::write(dir.join("photo-1.png"), "").unwrap();
+        fs::write(dir.join("README"), "").unwrap();
+        assert_eq!(unused_name(dir, Path::new("/tmp/photo.png")), "photo-2.png");
+        assert_eq!(unused_name(dir, Path::new("/tmp/README")), "README-1");
+    }
+}
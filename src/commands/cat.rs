@@ -0,0 +1,108 @@
+use crate::commands::list::parse_date_option;
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::memo::MemoFile;
+use crate::repository::MemoRepository;
+use crate::utils::datetime;
+
+/// `memo cat` のオプション
+#[derive(Debug, Clone, Default)]
+pub struct CatOptions {
+    /// Also include every memo created on this date
+    pub date: Option<String>,
+    /// Line printed between memos (a blank line if not set)
+    pub separator: Option<String>,
+}
+
+/// `memo cat <id>...`: メモの本文を front matter を除いて古い順に続けて出力する
+pub fn run(context: &MemoContext, ids: &[String], options: &CatOptions) -> MemoResult<()> {
+    let memos = collect_memos(context, ids, options.date.as_deref())?;
+    if memos.is_empty() {
+        return Err(MemoError::InvalidArgument(match &options.date {
+            Some(date) => format!("no memos on {}", date),
+            None => "At least one memo ID or --date is required".to_string(),
+        }));
+    }
+    print!("{}", concat(&memos, options.separator.as_deref()));
+    Ok(())
+}
+
+/// ID で指定したメモと `date` の日のメモ（ID 順、重複なし）
+fn collect_memos(
+    context: &MemoContext,
+    ids: &[String],
+    date: Option<&str>,
+) -> MemoResult<Vec<MemoFile>> {
+    let repo = MemoRepository::new(context.clone());
+    let mut memos = ids
+        .iter()
+        .map(|id| repo.find_memo_by_id(id))
+        .collect::<MemoResult<Vec<_>>>()?;
+    if let Some(date) = date {
+        let date = parse_date_option(date, datetime::parse_datetime)?.date_naive();
+        memos.extend(repo.list_memos_for_date(date)?);
+    }
+    memos.sort_by(|a, b| a.id.cmp(&b.id));
+    memos.dedup_by(|a, b| a.id == b.id);
+    Ok(memos)
+}
+
+fn concat(memos: &[MemoFile], separator: Option<&str>) -> String {
+    let mut out = String::new();
+    for (i, memo) in memos.iter().enumerate() {
+        if i > 0 {
+            out.push_str(separator.unwrap_or(""));
+            out.push('\n');
+        }
+        out.push_str(&memo.content);
+        if !memo.content.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let memo_dir = temp_dir.path().join("memo");
+        fs::create_dir_all(&memo_dir).unwrap();
+
+        let context = MemoContext {
+            memo_dir,
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+
+        (temp_dir, context)
+    }
+
+    #[test]
+    fn test_collect_and_concat() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo(
+            "2025-01/30/20250130150000.md",
+            "---\ntags: [a]\n---\nafternoon".to_string(),
+        )
+        .unwrap();
+        repo.create_memo("2025-01/30/20250130090000.md", "morning\n".to_string())
+            .unwrap();
+        repo.create_memo("2025-01/31/20250131090000.md", "next day".to_string())
+            .unwrap();
+
+        let memos = collect_memos(
+            &context,
+            &["20250130150000".to_string()],
+            Some("2025-01-30"),
+        )
+        .unwrap();
+        assert_eq!(concat(&memos, None), "morning\n\nafternoon\n");
+        assert_eq!(concat(&memos, Some("---")), "morning\n---\nafternoon\n");
+    }
+}
@@ -0,0 +1,329 @@
+//! `memo clock in/out` and `memo timesheet`.
+//!
+//! `clock out` appends a work-log line to the memo body:
+//!
+//! ```text
+//! - clock: 2025-01-30T14:30:00+09:00 -- 2025-01-30T15:10:00+09:00 (40m)
+//! ```
+
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::display::date;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::memo::MemoFile;
+use crate::repository::MemoRepository;
+use crate::utils::datetime;
+use crate::webhook::{self, WebhookEvent};
+use chrono::{DateTime, Duration, Local, SecondsFormat};
+use std::collections::HashMap;
+use std::fs;
+
+const CLOCK_PREFIX: &str = "- clock: ";
+const RANGE_SEPARATOR: &str = " -- ";
+
+/// A finished work-log entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockEntry {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+impl ClockEntry {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    pub fn to_line(self) -> String {
+        format!(
+            "{}{}{}{} ({})",
+            CLOCK_PREFIX,
+            self.start.to_rfc3339_opts(SecondsFormat::Secs, false),
+            RANGE_SEPARATOR,
+            self.end.to_rfc3339_opts(SecondsFormat::Secs, false),
+            format_duration(self.duration())
+        )
+    }
+
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let range = line.trim().strip_prefix(CLOCK_PREFIX)?;
+        let (start, rest) = range.split_once(RANGE_SEPARATOR)?;
+        // 末尾の "(40m)" は表示用
+        let end = rest.split_once(" (").map_or(rest, |(end, _)| end);
+        Some(Self {
+            start: DateTime::parse_from_rfc3339(start)
+                .ok()?
+                .with_timezone(&Local),
+            end: DateTime::parse_from_rfc3339(end)
+                .ok()?
+                .with_timezone(&Local),
+        })
+    }
+}
+
+/// `memo clock in <id>`
+pub fn run_in(context: &MemoContext, id: &str) -> MemoResult<()> {
+    if let Some((running, _)) = read_running(context)? {
+        return Err(MemoError::InvalidArgument(format!(
+            "already clocked in to {} (run `memo clock out` first)",
+            running
+        )));
+    }
+
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(id)?;
+    let now = Local::now();
+    fs::write(
+        context.clock_file(),
+        format!(
+            "{} {}\n",
+            memo.id,
+            now.to_rfc3339_opts(SecondsFormat::Secs, false)
+        ),
+    )?;
+
+    println!("Clocked in: {} at {}", memo.id, date::format_datetime(&now));
+    Ok(())
+}
+
+/// `memo clock out`
+pub fn run_out(context: &MemoContext) -> MemoResult<()> {
+    let Some((id, start)) = read_running(context)? else {
+        return Err(MemoError::InvalidArgument(
+            "not clocked in (run `memo clock in <id>`)".to_string(),
+        ));
+    };
+
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(&id)?;
+    let entry = ClockEntry {
+        start,
+        end: Local::now(),
+    };
+    let memo = memo.append(&entry.to_line())?.set_updated_at(entry.end)?;
+    update_search_index(context, &memo.path)?;
+    webhook::notify(
+        &context.config,
+        WebhookEvent::Edit,
+        std::slice::from_ref(&memo),
+    );
+    let path = context.clock_file();
+    fs::remove_file(&path).with_path(&path)?;
+
+    println!(
+        "Clocked out: {} ({})",
+        memo.id,
+        format_duration(entry.duration())
+    );
+    Ok(())
+}
+
+/// `memo timesheet [--since DATE]`
+pub fn run_timesheet(context: &MemoContext, since: Option<&str>) -> MemoResult<()> {
+    let since = since
+        .map(|value| {
+            datetime::parse_datetime(value)
+                .ok_or_else(|| MemoError::InvalidArgument(format!("invalid date: {}", value)))
+        })
+        .transpose()?;
+
+    let repo = MemoRepository::new(context.clone());
+    let memos = repo.list_all_memos()?;
+    let sheet = Timesheet::collect(&memos, since);
+    if sheet.memos.is_empty() {
+        println!("No clock entries found.");
+        return Ok(());
+    }
+
+    for (memo, total) in &sheet.memos {
+        println!(
+            "{:>8}  {}  {}",
+            format_duration(*total),
+            memo.id,
+            memo.title()
+        );
+    }
+    if !sheet.tags.is_empty() {
+        println!();
+        println!("By tag:");
+        for (tag, total) in &sheet.tags {
+            println!("{:>8}  {}", format_duration(*total), tag);
+        }
+    }
+    println!();
+    println!("Total: {}", format_duration(sheet.total));
+    Ok(())
+}
+
+/// メモごと・タグごとの合計時間（長い順）
+#[derive(Debug)]
+pub struct Timesheet<'a> {
+    pub memos: Vec<(&'a MemoFile, Duration)>,
+    pub tags: Vec<(String, Duration)>,
+    pub total: Duration,
+}
+
+impl<'a> Timesheet<'a> {
+    /// `since` 以降に開始したエントリを集計
+    pub fn collect(memos: &'a [MemoFile], since: Option<DateTime<Local>>) -> Self {
+        let mut per_memo = Vec::new();
+        let mut per_tag: HashMap<String, Duration> = HashMap::new();
+        for memo in memos {
+            let total = memo
+                .content
+                .lines()
+                .filter_map(ClockEntry::parse_line)
+                .filter(|entry| since.is_none_or(|since| entry.start >= since))
+                .fold(Duration::zero(), |sum, entry| sum + entry.duration());
+            if total.is_zero() {
+                continue;
+            }
+            for tag in memo.tags() {
+                *per_tag
+                    .entry(tag.to_string())
+                    .or_insert_with(Duration::zero) += total;
+            }
+            per_memo.push((memo, total));
+        }
+
+        per_memo.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.id.cmp(&b.0.id)));
+        let mut tags: Vec<(String, Duration)> = per_tag.into_iter().collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let total = per_memo
+            .iter()
+            .fold(Duration::zero(), |sum, (_, total)| sum + *total);
+        Self {
+            memos: per_memo,
+            tags,
+            total,
+        }
+    }
+}
+
+/// `1h 05m` / `40m`
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+fn read_running(context: &MemoContext) -> MemoResult<Option<(String, DateTime<Local>)>> {
+    let path = context.clock_file();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).with_path(&path)?;
+    let running = content
+        .trim()
+        .split_once(' ')
+        .and_then(|(id, start)| Some((id.to_string(), datetime::parse_datetime(start)?)));
+    match running {
+        Some(running) => Ok(Some(running)),
+        None => Err(MemoError::InvalidArgument(format!(
+            "broken clock file: {}",
+            path.display()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let context = MemoContext {
+            memo_dir: temp_dir.path().to_path_buf(),
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+        (temp_dir, context)
+    }
+
+    fn at(hour: u32, min: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 1, 30, hour, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_clock_entry_roundtrip() {
+        let entry = ClockEntry {
+            start: at(14, 30),
+            end: at(15, 45),
+        };
+        let line = entry.to_line();
+        assert!(line.starts_with("- clock: 2025-01-30T14:30:00"));
+        assert!(line.ends_with(" (1h 15m)"));
+        assert_eq!(ClockEntry::parse_line(&line), Some(entry));
+        assert_eq!(ClockEntry::parse_line("- clock: soon"), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::minutes(40)), "40m");
+        assert_eq!(format_duration(Duration::minutes(65)), "1h 05m");
+    }
+
+    #[test]
+    fn test_timesheet_collect() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        let first = ClockEntry {
+            start: at(9, 0),
+            end: at(10, 0),
+        };
+        let second = ClockEntry {
+            start: at(14, 0),
+            end: at(14, 30),
+        };
+        repo.create_memo(
+            "2025-01/30/20250130143022.md",
+            format!(
+                "---\ntags: [\"@work\"]\n---\n# Report\n{}\n{}\n",
+                first.to_line(),
+                second.to_line()
+            ),
+        )
+        .unwrap();
+        repo.create_memo("2025-01/29/20250129120000.md", "no entries".to_string())
+            .unwrap();
+        let memos = repo.list_all_memos().unwrap();
+
+        let sheet = Timesheet::collect(&memos, None);
+        assert_eq!(sheet.memos.len(), 1);
+        assert_eq!(sheet.total, Duration::minutes(90));
+        assert_eq!(
+            sheet.tags,
+            vec![("@work".to_string(), Duration::minutes(90))]
+        );
+
+        let sheet = Timesheet::collect(&memos, Some(at(12, 0)));
+        assert_eq!(sheet.total, Duration::minutes(30));
+    }
+
+    #[test]
+    fn test_clock_in_twice() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo("2025-01/30/20250130143022.md", "memo".to_string())
+            .unwrap();
+
+        run_in(&context, "20250130143022").unwrap();
+        assert!(matches!(
+            run_in(&context, "20250130143022"),
+            Err(MemoError::InvalidArgument(_))
+        ));
+
+        run_out(&context).unwrap();
+        assert!(!context.clock_file().exists());
+        let memo = repo.find_memo_by_id("20250130143022").unwrap();
+        assert!(memo.content.contains("- clock: "));
+        assert!(matches!(
+            run_out(&context),
+            Err(MemoError::InvalidArgument(_))
+        ));
+    }
+}
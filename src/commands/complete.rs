@@ -0,0 +1,281 @@
+use crate::commands::help;
+use crate::context::MemoContext;
+use crate::error::MemoResult;
+use crate::repository::MemoRepository;
+use crate::tag_index::TagIndex;
+use clap::Command;
+use clap_complete::Shell;
+use std::io::{self, Write};
+
+/// メモIDを引数に取るサブコマンド
+const ID_COMMANDS: &[&str] = &["edit", "touch", "show", "archive", "delete", "status"];
+
+/// `memo __complete <words...>`
+///
+/// `words` はサブコマンド以降のコマンドラインで、最後の要素が補完中の単語（空文字列可）。
+/// 候補を1行ずつ出力する。
+pub fn run(context: &MemoContext, cli: &Command, words: &[String]) -> MemoResult<()> {
+    for candidate in candidates(context, cli, words)? {
+        println!("{}", candidate);
+    }
+    Ok(())
+}
+
+/// `memo completions <shell>`: clap_complete の補完スクリプトに、
+/// メモIDとタグを `__complete` で補う関数を付けて出力する
+pub fn run_script(mut cli: Command, shell: Shell) -> MemoResult<()> {
+    let mut out = Vec::new();
+    let name = cli.get_name().to_string();
+    clap_complete::generate(shell, &mut cli, &name, &mut out);
+    out.extend(dynamic_script(shell).into_bytes());
+    io::stdout().write_all(&out)?;
+    Ok(())
+}
+
+/// 生成されたスクリプトの後ろに足す、IDとタグを補完する関数
+fn dynamic_script(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => format!(
+            r#"
+_memo_dynamic() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    if [[ "$prev" == --tag ]] || [[ $COMP_CWORD -ge 2 && "$cur" != -* && "${{COMP_WORDS[1]}}" =~ ^({commands})$ ]]; then
+        COMPREPLY=($(memo __complete "${{COMP_WORDS[@]:1:COMP_CWORD}}" 2>/dev/null))
+        return 0
+    fi
+    _memo "$@"
+}}
+complete -F _memo_dynamic -o bashdefault -o default memo
+"#,
+            commands = ID_COMMANDS.join("|")
+        ),
+        Shell::Zsh => format!(
+            r#"
+_memo_dynamic() {{
+    if [[ "$words[CURRENT-1]" == --tag || ( $CURRENT -gt 2 && " {commands} " == *" $words[2] "* && "$words[CURRENT]" != -* ) ]]; then
+        compadd -- ${{(f)"$(memo __complete "${{(@)words[2,CURRENT]}}" 2>/dev/null)"}}
+    else
+        _memo "$@"
+    fi
+}}
+compdef _memo_dynamic memo
+"#,
+            commands = ID_COMMANDS.join(" ")
+        ),
+        Shell::Fish => format!(
+            r#"
+function __memo_complete
+    set -l words (commandline -opc) "$(commandline -ct)"
+    memo __complete $words[2..-1] 2>/dev/null
+end
+complete -c memo -n "__fish_seen_subcommand_from {commands}" -f -a "(__memo_complete)"
+complete -c memo -l tag -f -a "(__memo_complete)"
+"#,
+            commands = ID_COMMANDS.join(" ")
+        ),
+        // 他のシェルはサブコマンドとオプションだけ
+        _ => String::new(),
+    }
+}
+
+pub fn candidates(
+    context: &MemoContext,
+    cli: &Command,
+    words: &[String],
+) -> MemoResult<Vec<String>> {
+    let words = skip_global_options(cli, words);
+    let current = words.last().map(String::as_str).unwrap_or("");
+
+    let [subcommand, .., _] = words else {
+        // サブコマンド名の補完
+        return Ok(filter_prefix(
+            cli.get_subcommands()
+                .filter(|c| !c.is_hide_set())
+                .map(|c| c.get_name().to_string()),
+            current,
+        ));
+    };
+    let Some(command) = cli.find_subcommand(subcommand) else {
+        return Ok(Vec::new());
+    };
+    let previous = words[words.len() - 2].as_str();
+
+    if previous == "--tag" {
+        return Ok(filter_prefix(collect_tags(context)?, current));
+    }
+    if option_takes_value(command, previous) {
+        return Ok(Vec::new());
+    }
+
+    if current.starts_with('-') {
+        return Ok(filter_prefix(
+            command
+                .get_arguments()
+                .filter(|a| !a.is_hide_set())
+                .filter_map(|a| a.get_long())
+                .map(|long| format!("--{}", long)),
+            current,
+        ));
+    }
+
+    if command.get_name() == "search" {
+        // tags:@todo / tags:/@todo / tag:@todo
+        for field in ["tags:/", "tags:", "tag:"] {
+            if let Some(prefix) = current.strip_prefix(field) {
+                let tags = collect_tags(context)?
+                    .into_iter()
+                    .map(|t| format!("{}{}", field, t));
+                return Ok(filter_prefix(tags, &format!("{}{}", field, prefix)));
+            }
+        }
+        return Ok(Vec::new());
+    }
+
+    if command.get_name() == "tags" && previous == "rename" {
+        return Ok(filter_prefix(collect_tags(context)?, current));
+    }
+
+    if command.get_name() == "help" {
+        let topics = help::TOPICS.iter().map(|t| t.name.to_string());
+        let commands = cli
+            .get_subcommands()
+            .filter(|c| !c.is_hide_set())
+            .map(|c| c.get_name().to_string());
+        return Ok(filter_prefix(topics.chain(commands), current));
+    }
+
+    if ID_COMMANDS.contains(&command.get_name()) {
+        return Ok(filter_prefix(collect_ids(context)?, current));
+    }
+
+    Ok(Vec::new())
+}
+
+/// `memo --color never list ...` のようなサブコマンド前のオプションを読み飛ばす
+fn skip_global_options<'a>(cli: &Command, mut words: &'a [String]) -> &'a [String] {
+    while let [first, rest @ ..] = words
+        && !rest.is_empty()
+        && first.starts_with('-')
+    {
+        words = if option_takes_value(cli, first) && rest.len() > 1 {
+            &rest[1..]
+        } else {
+            rest
+        };
+    }
+    words
+}
+
+fn option_takes_value(command: &Command, word: &str) -> bool {
+    let Some(long) = word.strip_prefix("--") else {
+        return false;
+    };
+    command
+        .get_arguments()
+        .any(|a| a.get_long() == Some(long) && a.get_action().takes_values())
+}
+
+fn filter_prefix(candidates: impl IntoIterator<Item = String>, prefix: &str) -> Vec<String> {
+    candidates
+        .into_iter()
+        .filter(|c| c.starts_with(prefix))
+        .collect()
+}
+
+/// 最近更新したメモから順にID
+fn collect_ids(context: &MemoContext) -> MemoResult<Vec<String>> {
+    let repo = MemoRepository::new(context.clone());
+    let mut memos = repo.list_all_memos()?;
+    memos.sort_by_key(|memo| std::cmp::Reverse(memo.modified));
+    Ok(memos.iter().map(|memo| memo.id.as_str()).collect())
+}
+
+/// タグを使用回数の多い順に
+fn collect_tags(context: &MemoContext) -> MemoResult<Vec<String>> {
+    let repo = MemoRepository::new(context.clone());
+    let index = TagIndex::build(&repo.list_all_memos()?);
+    Ok(index.sorted().into_iter().map(|(tag, _)| tag).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, ArgAction};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let context = MemoContext {
+            memo_dir: temp_dir.path().to_path_buf(),
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+        (temp_dir, context)
+    }
+
+    fn test_cli() -> Command {
+        Command::new("memo")
+            .arg(Arg::new("color").long("color").global(true))
+            .subcommand(Command::new("help").arg(Arg::new("topic")))
+            .subcommand(Command::new("edit").arg(Arg::new("id")))
+            .subcommand(
+                Command::new("list")
+                    .arg(Arg::new("tag").long("tag"))
+                    .arg(Arg::new("json").long("json").action(ArgAction::SetTrue)),
+            )
+            .subcommand(Command::new("search").arg(Arg::new("query")))
+            .subcommand(Command::new("__complete").hide(true))
+    }
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_complete_candidates() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo(
+            "2025-01/30/20250130143022.md",
+            "---\ntags: [\"@todo\", \"@team\"]\n---\nbody".to_string(),
+        )
+        .unwrap();
+        repo.create_memo(
+            "2025-01/29/20250129120000.md",
+            "---\ntags: [\"@todo\"]\n---\nbody".to_string(),
+        )
+        .unwrap();
+        // 古いIDのメモを最近更新したことにする
+        let older = context.memo_dir.join("2025-01/29/20250129120000.md");
+        let file = fs::File::options().write(true).open(&older).unwrap();
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+
+        let cli = test_cli();
+        let complete = |w: &[&str]| candidates(&context, &cli, &words(w)).unwrap();
+
+        assert_eq!(complete(&["e"]), vec!["edit"]);
+        assert_eq!(complete(&[""]).len(), 4);
+        assert_eq!(
+            complete(&["edit", "2025"]),
+            vec!["20250129120000", "20250130143022"]
+        );
+        assert_eq!(complete(&["list", "--tag", ""]), vec!["@todo", "@team"]);
+        assert_eq!(complete(&["list", "--j"]), vec!["--json"]);
+        assert_eq!(complete(&["search", "tags:@te"]), vec!["tags:@team"]);
+        assert_eq!(complete(&["search", "tags:/@to"]), vec!["tags:/@todo"]);
+        assert_eq!(complete(&["search", "tag:@to"]), vec!["tag:@todo"]);
+        assert!(complete(&["search", "foo"]).is_empty());
+        assert_eq!(complete(&["--color", "never", "ed"]), vec!["edit"]);
+        assert!(complete(&["help", "query"]).contains(&"query-syntax".to_string()));
+    }
+
+    #[test]
+    fn test_dynamic_script() {
+        assert!(dynamic_script(Shell::Bash).contains("^(edit|touch|show|archive|delete|status)$"));
+        assert!(dynamic_script(Shell::Zsh).contains("compdef _memo_dynamic memo"));
+        assert!(dynamic_script(Shell::Fish).contains("memo __complete $words[2..-1]"));
+        assert!(dynamic_script(Shell::PowerShell).is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+use crate::config::{self, SETTINGS, Setting};
+use crate::context::MemoContext;
+use crate::error::MemoResult;
+use crate::utils::xdg;
+
+/// `memo config`: 設定ファイルの場所と、各項目の現在の値を表示
+pub fn run_show(context: &MemoContext) -> MemoResult<()> {
+    println!("# {}", xdg::get_config_path()?.display());
+    for setting in SETTINGS {
+        match effective_value(context, setting.key) {
+            Some(value) => println!("{} = {}", setting.key, value),
+            None => println!("{} =", setting.key),
+        }
+    }
+    Ok(())
+}
+
+/// `memo config get <key>`
+pub fn run_get(context: &MemoContext, key: &str) -> MemoResult<()> {
+    let setting = Setting::find(key)?;
+    if let Some(value) = effective_value(context, setting.key) {
+        println!("{}", value);
+    }
+    Ok(())
+}
+
+/// `memo config set <key> <value>`
+pub fn run_set(key: &str, value: &str) -> MemoResult<()> {
+    let setting = Setting::find(key)?;
+    let path = xdg::get_config_path()?;
+    config::set_value(&path, setting, value)?;
+    println!("Set {} = {} in {}", setting.key, value, path.display());
+    Ok(())
+}
+
+/// 設定ファイル・環境変数・既定値を反映した値（未設定なら `None`）
+fn effective_value(context: &MemoContext, key: &str) -> Option<String> {
+    match key {
+        "editor" => Some(context.editor.clone()),
+        "memo_dir" => Some(context.memo_dir.display().to_string()),
+        "date_format" => context.date_format.clone(),
+        "list.limit" => context.list_limit.map(|n| n.to_string()),
+        "index.heap_size" => Some(context.index_heap_size.to_string()),
+        "notebook" => Some(context.notebook.clone()),
+        _ => None,
+    }
+}
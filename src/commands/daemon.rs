@@ -0,0 +1,329 @@
+//! `memo daemon`: JSON-over-unix-socket protocol for editor plugins.
+//!
+//! One JSON request per line, answered by one JSON response line:
+//!
+//! ```text
+//! {"method": "query", "query": "meeting", "limit": 10}
+//! {"method": "get", "id": "20250130143022"}
+//! {"method": "create", "content": "# New memo\n"}
+//! {"method": "append", "id": "20250130143022", "text": "- follow up"}
+//! ```
+//!
+//! Responses are `{"ok": true, "result": ...}` or `{"ok": false, "error": "..."}`.
+
+use crate::commands::edit;
+use crate::context::MemoContext;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::git;
+use crate::memo::MemoFile;
+use crate::ops;
+use crate::render::MemoTemplateData;
+use crate::repository::MemoRepository;
+use crate::search::{CachedSearchIndex, SearchParams};
+use crate::webhook::{self, WebhookEvent};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "method", rename_all = "lowercase")]
+pub enum Request {
+    Query {
+        query: String,
+        #[serde(default)]
+        limit: Option<usize>,
+    },
+    Get {
+        id: String,
+    },
+    Create {
+        content: String,
+    },
+    Append {
+        id: String,
+        text: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct Response {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// リクエストを処理する状態（検索インデックスは開いたまま）
+pub struct Daemon {
+    context: MemoContext,
+    search: Mutex<CachedSearchIndex>,
+}
+
+impl Daemon {
+    pub fn new(context: MemoContext) -> Self {
+        let manager = context.search_manager();
+        Self {
+            context,
+            search: Mutex::new(CachedSearchIndex::new(manager)),
+        }
+    }
+
+    /// Handle one request line and return the response line (without newline)
+    pub fn handle_line(&self, line: &str) -> String {
+        let result = serde_json::from_str::<Request>(line)
+            .map_err(|e| MemoError::InvalidArgument(format!("invalid request: {}", e)))
+            .and_then(|request| self.handle(request));
+        let response = match result {
+            Ok(result) => Response {
+                ok: true,
+                result: Some(result),
+                error: None,
+            },
+            Err(e) => Response {
+                ok: false,
+                result: None,
+                error: Some(e.to_string()),
+            },
+        };
+        serde_json::to_string(&response).unwrap_or_else(|e| {
+            json!({"ok": false, "error": format!("failed to encode response: {}", e)}).to_string()
+        })
+    }
+
+    pub fn handle(&self, request: Request) -> MemoResult<Value> {
+        let repo = MemoRepository::new(self.context.clone());
+        match request {
+            Request::Query { query, limit } => {
+                let results = self
+                    .search
+                    .lock()
+                    .map_err(|_| MemoError::Search("search index is poisoned".to_string()))?
+                    .search(
+                        &query,
+                        &SearchParams {
+                            boost: self.context.config.search.boost,
+                            ..Default::default()
+                        },
+                    )?;
+                let memos: Vec<MemoTemplateData> = results
+                    .iter()
+                    .take(limit.unwrap_or(usize::MAX))
+                    .filter_map(|result| {
+                        let memo = MemoFile::from_path(&result.memo.path).ok()?;
+                        Some(MemoTemplateData::from_memo_file(&memo).with_score(result.score))
+                    })
+                    .collect();
+                Ok(serde_json::to_value(memos).unwrap_or_default())
+            }
+            Request::Get { id } => {
+                let memo = repo.find_memo_by_id(&id)?;
+                Ok(
+                    serde_json::to_value(MemoTemplateData::from_memo_file(&memo))
+                        .unwrap_or_default(),
+                )
+            }
+            Request::Create { content } => {
+                let memo = ops::create_memo(&self.context, false, |_| content, |_| Ok(()))?;
+                Ok(json!({ "id": memo.id.as_str(), "path": memo.path }))
+            }
+            Request::Append { id, text } => {
+                let memo = repo
+                    .find_memo_by_id(&id)?
+                    .append(&text)?
+                    .set_updated_at(Local::now())?;
+                edit::update_search_index(&self.context, &memo.path)?;
+                let result = json!({ "id": memo.id.as_str(), "path": memo.path });
+                let appended = [memo];
+                webhook::notify(&self.context.config, WebhookEvent::Edit, &appended);
+                git::auto_commit(
+                    &self.context.config,
+                    &self.context.memo_dir,
+                    "edit",
+                    &appended,
+                );
+                Ok(result)
+            }
+        }
+    }
+}
+
+/// `memo daemon [--socket PATH]`
+#[cfg(unix)]
+pub fn run(context: &MemoContext, socket: Option<PathBuf>) -> MemoResult<()> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::Arc;
+
+    let socket = socket.unwrap_or_else(|| context.socket_path());
+    if socket.exists() {
+        // 生きているデーモンがいれば二重起動しない。応答が無ければ古いソケット
+        if UnixStream::connect(&socket).is_ok() {
+            return Err(MemoError::InvalidArgument(format!(
+                "a daemon is already listening on {}",
+                socket.display()
+            )));
+        }
+        fs::remove_file(&socket).with_path(&socket)?;
+    }
+
+    let listener = UnixListener::bind(&socket)?;
+    eprintln!("Listening on {}", socket.display());
+
+    let daemon = Arc::new(Daemon::new(context.clone()));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Warning: failed to accept connection: {}", e);
+                continue;
+            }
+        };
+        let daemon = Arc::clone(&daemon);
+        std::thread::spawn(move || {
+            let mut writer = match stream.try_clone() {
+                Ok(writer) => writer,
+                Err(_) => return,
+            };
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else { break };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let response = daemon.handle_line(&line);
+                if writeln!(writer, "{}", response).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn run(_context: &MemoContext, _socket: Option<PathBuf>) -> MemoResult<()> {
+    Err(MemoError::InvalidArgument(
+        "memo daemon requires unix domain sockets".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let context = MemoContext {
+            memo_dir: temp_dir.path().to_path_buf(),
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+        (temp_dir, context)
+    }
+
+    fn response(daemon: &Daemon, line: &str) -> Value {
+        serde_json::from_str(&daemon.handle_line(line)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_request() {
+        let request: Request =
+            serde_json::from_str(r#"{"method": "query", "query": "meeting"}"#).unwrap();
+        assert_eq!(
+            request,
+            Request::Query {
+                query: "meeting".to_string(),
+                limit: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_and_append() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo("2025-01/30/20250130143022.md", "# Memo".to_string())
+            .unwrap();
+        let daemon = Daemon::new(context.clone());
+
+        let got = response(&daemon, r#"{"method": "get", "id": "20250130143022"}"#);
+        assert_eq!(got["ok"], true);
+        assert_eq!(got["result"]["content"], "# Memo");
+
+        let appended = response(
+            &daemon,
+            r#"{"method": "append", "id": "20250130143022", "text": "- item"}"#,
+        );
+        assert_eq!(appended["ok"], true);
+        let memo = repo.find_memo_by_id("20250130143022").unwrap();
+        assert!(memo.content.ends_with("# Memo\n- item\n"));
+        assert!(memo.updated_at().is_some());
+    }
+
+    #[test]
+    fn test_create_and_append_auto_commit() {
+        let (temp_dir, mut context) = create_test_context();
+        // まだ無いメモディレクトリにも作れる
+        context.memo_dir = temp_dir.path().join("memo");
+        let created = response(
+            &Daemon::new(context.clone()),
+            r##"{"method": "create", "content": "# First\n"}"##,
+        );
+        assert_eq!(created["ok"], true);
+
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .arg("-C")
+                .arg(&context.memo_dir)
+                .args(args)
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&output.stdout).to_string()
+        };
+        git(&["init", "--quiet"]);
+        git(&["config", "user.name", "memo"]);
+        git(&["config", "user.email", "memo@example.com"]);
+        context.config.git.auto_commit = true;
+        let daemon = Daemon::new(context.clone());
+
+        let created = response(
+            &daemon,
+            r##"{"method": "create", "content": "# Second\n"}"##,
+        );
+        assert_eq!(created["ok"], true);
+        let request = format!(
+            r#"{{"method": "append", "id": "{}", "text": "- item"}}"#,
+            created["result"]["id"].as_str().unwrap()
+        );
+        assert_eq!(response(&daemon, &request)["ok"], true);
+
+        let subjects = git(&["log", "--format=%s"]);
+        let subjects: Vec<&str> = subjects.lines().collect();
+        assert_eq!(subjects.len(), 2, "{:?}", subjects);
+        assert!(subjects[0].starts_with("memo: edit "));
+        assert!(subjects[1].starts_with("memo: add "));
+    }
+
+    #[test]
+    fn test_error_responses() {
+        let (_temp_dir, context) = create_test_context();
+        let daemon = Daemon::new(context);
+
+        let not_found = response(&daemon, r#"{"method": "get", "id": "20250130143022"}"#);
+        assert_eq!(not_found["ok"], false);
+        assert!(not_found["error"].as_str().unwrap().contains("not found"));
+
+        let invalid = response(&daemon, r#"{"method": "delete"}"#);
+        assert_eq!(invalid["ok"], false);
+        assert!(
+            invalid["error"]
+                .as_str()
+                .unwrap()
+                .contains("invalid request")
+        );
+    }
+}
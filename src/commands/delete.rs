@@ -0,0 +1,155 @@
+use crate::commands::archive::print_planned_moves;
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::memo::MemoDocument;
+use crate::ops::{Action, Batch};
+use crate::repository::MemoRepository;
+use crate::webhook::{self, WebhookEvent};
+
+/// メモを `.trash` に移す（`permanent` ならファイルごと消す）
+///
+/// `dry_run` なら移動・削除するファイルを表示するだけ
+pub fn run(
+    context: &MemoContext,
+    ids: &[String],
+    permanent: bool,
+    dry_run: bool,
+) -> MemoResult<()> {
+    if ids.is_empty() {
+        return Err(MemoError::InvalidArgument(
+            "At least one memo ID is required".to_string(),
+        ));
+    }
+
+    let repo = MemoRepository::new(context.clone());
+    let search_manager = context.search_manager();
+
+    // 途中で見つからない ID があれば何も消さない
+    let memos = ids
+        .iter()
+        .map(|id| repo.find_memo_by_id(id))
+        .collect::<MemoResult<Vec<_>>>()?;
+
+    if dry_run {
+        print_planned_moves(&repo.plan_delete(&memos, permanent));
+        eprintln!(
+            "{} memo(s) would be deleted. Run without --dry-run to apply.",
+            memos.len()
+        );
+        return Ok(());
+    }
+
+    let mut batch = Batch::new(context, Action::Delete);
+    for memo in &memos {
+        batch.delete(memo, permanent)?;
+        search_manager.remove_memo(&MemoDocument::from_memo_file(memo))?;
+    }
+    batch.commit()?;
+
+    if permanent {
+        println!("Permanently deleted {} memo(s)", memos.len());
+    } else {
+        println!("Moved {} memo(s) to trash", memos.len());
+    }
+    webhook::notify(&context.config, WebhookEvent::Delete, &memos);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let memo_dir = temp_dir.path().join("memo");
+        fs::create_dir_all(&memo_dir).unwrap();
+
+        let context = MemoContext {
+            memo_dir,
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+
+        (temp_dir, context)
+    }
+
+    #[test]
+    fn test_delete_moves_to_trash() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+
+        run(&context, &["20250130143022".to_string()], false, false).unwrap();
+
+        assert!(
+            !context
+                .memo_dir
+                .join("2025-01/30/20250130143022.md")
+                .exists()
+        );
+        assert!(
+            context
+                .trash_dir()
+                .join("2025-01/30/20250130143022.md")
+                .exists()
+        );
+        assert!(repo.list_all_memos().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_permanent() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+
+        run(&context, &["20250130143022".to_string()], true, false).unwrap();
+
+        assert!(
+            !context
+                .memo_dir
+                .join("2025-01/30/20250130143022.md")
+                .exists()
+        );
+        assert!(!context.trash_dir().exists());
+    }
+
+    #[test]
+    fn test_delete_unknown_id_deletes_nothing() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+
+        let ids = vec!["20250130143022".to_string(), "20250130999999".to_string()];
+        assert!(run(&context, &ids, false, false).is_err());
+        assert!(
+            context
+                .memo_dir
+                .join("2025-01/30/20250130143022.md")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_delete_dry_run_keeps_files() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+
+        run(&context, &["20250130143022".to_string()], true, true).unwrap();
+
+        assert!(
+            context
+                .memo_dir
+                .join("2025-01/30/20250130143022.md")
+                .exists()
+        );
+        assert!(!context.trash_dir().exists());
+    }
+}
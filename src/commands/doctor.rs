@@ -0,0 +1,264 @@
+use crate::commands::index;
+use crate::context::MemoContext;
+use crate::error::{IoResultExt, MemoResult};
+use crate::memo::MemoFile;
+use crate::memo_id::MemoId;
+use crate::repository::MemoRepository;
+use crate::search::IndexLock;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `memo doctor` が見つける問題
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// ファイル名から ID が読めない（一覧・検索に出ない）
+    InvalidPath(PathBuf),
+    /// front matter の YAML が読めない
+    BrokenFrontMatter { path: PathBuf, error: String },
+    /// 索引にあるが、ファイルが無い（検索が失敗する）
+    OrphanedIndexEntry { id: String, path: PathBuf },
+    /// どのプロセスも握っていないロックファイル
+    StaleLock(PathBuf),
+    /// `.index/version` が指すディレクトリが無い
+    MissingIndexVersion(String),
+}
+
+impl Problem {
+    /// `--fix` で直せるか（メモファイル自体には手を入れない）
+    pub fn is_fixable(&self) -> bool {
+        matches!(
+            self,
+            Problem::OrphanedIndexEntry { .. }
+                | Problem::StaleLock(_)
+                | Problem::MissingIndexVersion(_)
+        )
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::InvalidPath(path) => write!(
+                f,
+                "{}: file name is not a memo ID (YYYYMMDDHHmmss.md; old HHMMSS.md files can be renamed with `memo migrate`)",
+                path.display()
+            ),
+            Problem::BrokenFrontMatter { path, error } => {
+                write!(f, "{}: broken front matter: {}", path.display(), error)
+            }
+            Problem::OrphanedIndexEntry { id, path } => write!(
+                f,
+                "index entry {} points to a missing file {}",
+                id,
+                path.display()
+            ),
+            Problem::StaleLock(path) => write!(f, "{}: stale index lock", path.display()),
+            Problem::MissingIndexVersion(version) => write!(
+                f,
+                "index version {} does not exist (the search index needs a rebuild)",
+                version
+            ),
+        }
+    }
+}
+
+/// `memo doctor [--fix]`
+pub fn run(context: &MemoContext, fix: bool) -> MemoResult<()> {
+    let problems = check(context)?;
+    if problems.is_empty() {
+        println!("No problems found");
+        return Ok(());
+    }
+
+    for problem in &problems {
+        let marker = if problem.is_fixable() {
+            "fixable"
+        } else {
+            "manual"
+        };
+        println!("[{}] {}", marker, problem);
+    }
+
+    let fixable = problems.iter().filter(|p| p.is_fixable()).count();
+    if fix {
+        fix_problems(context, &problems)?;
+        eprintln!("Fixed {} of {} problem(s).", fixable, problems.len());
+    } else if fixable > 0 {
+        eprintln!(
+            "{} of {} problem(s) can be fixed. Run with --fix to apply.",
+            fixable,
+            problems.len()
+        );
+    }
+    Ok(())
+}
+
+/// メモディレクトリと検索インデックスを調べる（何も書き換えない）
+pub fn check(context: &MemoContext) -> MemoResult<Vec<Problem>> {
+    let mut problems = Vec::new();
+
+    let repo = MemoRepository::new(context.clone());
+    for path in repo.list_memo_paths()? {
+        // `.templates` などの隠しディレクトリはメモではない
+        if is_hidden(&path, &context.memo_dir) {
+            continue;
+        }
+        if MemoId::from_path(&path).is_err() {
+            problems.push(Problem::InvalidPath(path));
+            continue;
+        }
+        // 読めないメモ（鍵の無い暗号化メモなど）はここでは扱わない
+        if let Ok(memo) = MemoFile::from_path(&path)
+            && let Some(error) = memo.metadata_error
+        {
+            problems.push(Problem::BrokenFrontMatter { path, error });
+        }
+    }
+
+    let search_manager = context.search_manager();
+    if let Some(version) = search_manager.missing_version()? {
+        problems.push(Problem::MissingIndexVersion(version));
+    } else if let Some(index) = search_manager.get_current_index()? {
+        let _lock = IndexLock::acquire_shared(&index.index_dir)?;
+        for (id, path) in index.indexed_entries()? {
+            if !path.exists() {
+                problems.push(Problem::OrphanedIndexEntry { id, path });
+            }
+        }
+    }
+
+    problems.extend(
+        IndexLock::stale_locks(context.index_dir())?
+            .into_iter()
+            .map(Problem::StaleLock),
+    );
+    Ok(problems)
+}
+
+fn is_hidden(path: &Path, memo_dir: &Path) -> bool {
+    path.strip_prefix(memo_dir).is_ok_and(|relative| {
+        relative
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+    })
+}
+
+/// `is_fixable` な問題を直す
+pub fn fix_problems(context: &MemoContext, problems: &[Problem]) -> MemoResult<()> {
+    // 索引を開く前に古いロックを消す
+    for problem in problems {
+        if let Problem::StaleLock(path) = problem {
+            fs::remove_file(path).with_path(path)?;
+        }
+    }
+
+    let orphans: Vec<&str> = problems
+        .iter()
+        .filter_map(|problem| match problem {
+            Problem::OrphanedIndexEntry { id, .. } => Some(id.as_str()),
+            _ => None,
+        })
+        .collect();
+    let search_manager = context.search_manager();
+    let write = search_manager.lock_for_write()?;
+    if !orphans.is_empty()
+        && let Some(mut index) = search_manager.get_current_index()?
+    {
+        let _lock = IndexLock::acquire(&index.index_dir)?;
+        for id in orphans {
+            index.remove_id(id)?;
+        }
+        index.commit()?;
+    }
+    // 作り直しは自分で書き込みロックを取る
+    drop(write);
+
+    if problems
+        .iter()
+        .any(|problem| matches!(problem, Problem::MissingIndexVersion(_)))
+    {
+        index::run_index(context)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let memo_dir = temp_dir.path().join("memo");
+        fs::create_dir_all(&memo_dir).unwrap();
+
+        let context = MemoContext {
+            memo_dir,
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+
+        (temp_dir, context)
+    }
+
+    #[test]
+    fn test_check_and_fix() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo("2025-01/30/20250130143022.md", "# Fine".to_string())
+            .unwrap();
+        let orphan = repo
+            .create_memo("2025-01/30/20250130151545.md", "# Gone".to_string())
+            .unwrap();
+        repo.create_memo(
+            "2025-01/30/20250130160000.md",
+            "---\ntags: [oops\n---\n# Broken".to_string(),
+        )
+        .unwrap();
+        repo.create_memo("2025-01/30/notes.md", "# Misplaced".to_string())
+            .unwrap();
+        repo.create_memo(".templates/daily.md", "# {{date}}".to_string())
+            .unwrap();
+        index::run_index(&context).unwrap();
+        fs::remove_file(&orphan.path).unwrap();
+
+        let problems = check(&context).unwrap();
+        assert_eq!(problems.len(), 3);
+        assert!(problems.contains(&Problem::InvalidPath(
+            context.memo_dir.join("2025-01/30/notes.md")
+        )));
+        assert!(problems.iter().any(|p| matches!(
+            p,
+            Problem::BrokenFrontMatter { path, .. } if path.ends_with("20250130160000.md")
+        )));
+        assert!(problems.contains(&Problem::OrphanedIndexEntry {
+            id: "20250130151545".to_string(),
+            path: orphan.path.clone(),
+        }));
+
+        fix_problems(&context, &problems).unwrap();
+        let remaining = check(&context).unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|p| !p.is_fixable()));
+    }
+
+    #[test]
+    fn test_fix_missing_index_version() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo("2025-01/30/20250130143022.md", "# Fine".to_string())
+            .unwrap();
+        fs::create_dir_all(context.index_dir()).unwrap();
+        fs::write(context.index_dir().join("version"), "20250101_000000").unwrap();
+
+        let problems = check(&context).unwrap();
+        assert_eq!(
+            problems,
+            vec![Problem::MissingIndexVersion("20250101_000000".to_string())]
+        );
+
+        fix_problems(&context, &problems).unwrap();
+        assert!(check(&context).unwrap().is_empty());
+    }
+}
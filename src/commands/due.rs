@@ -0,0 +1,137 @@
+use crate::context::MemoContext;
+use crate::display::date;
+use crate::error::MemoResult;
+use crate::memo::MemoFile;
+use crate::repository::MemoRepository;
+use crate::utils::datetime;
+use chrono::{DateTime, Local};
+
+/// 期限・リマインド日時を表す front matter のキー
+const DUE_FIELDS: &[&str] = &["due", "remind"];
+
+/// A memo whose `due:` or `remind:` time has arrived
+#[derive(Debug)]
+pub struct DueMemo {
+    pub memo: MemoFile,
+    pub field: &'static str,
+    pub at: DateTime<Local>,
+}
+
+/// `memo due [--notify]`
+pub fn run(context: &MemoContext, notify: bool) -> MemoResult<()> {
+    let due = collect_due(context, Local::now())?;
+    if due.is_empty() {
+        println!("No memos are due.");
+        return Ok(());
+    }
+
+    for item in &due {
+        println!(
+            "{}  {:<6} {}  {}",
+            item.memo.id,
+            item.field,
+            date::format_datetime(&item.at),
+            item.memo.title()
+        );
+    }
+
+    if notify {
+        for item in &due {
+            if let Err(e) = send_notification(item) {
+                eprintln!("Warning: failed to send notification: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `now` の時点で期限を過ぎたメモを古い順に
+pub fn collect_due(context: &MemoContext, now: DateTime<Local>) -> MemoResult<Vec<DueMemo>> {
+    let repo = MemoRepository::new(context.clone());
+    let mut due = Vec::new();
+    for memo in repo.list_all_memos()? {
+        // due と remind の両方があれば早い方
+        let earliest = DUE_FIELDS
+            .iter()
+            .filter_map(|&field| {
+                let value = memo.metadata_value(field)?.as_str()?;
+                Some((field, datetime::parse_datetime(value)?))
+            })
+            .min_by_key(|&(_, at)| at);
+        if let Some((field, at)) = earliest
+            && at <= now
+        {
+            due.push(DueMemo { memo, field, at });
+        }
+    }
+    due.sort_by_key(|item| item.at);
+    Ok(due)
+}
+
+fn send_notification(item: &DueMemo) -> Result<(), notify_rust::error::Error> {
+    notify_rust::Notification::new()
+        .summary(&format!("memo: {}", item.memo.title()))
+        .body(&format!(
+            "{} {} ({})",
+            item.field,
+            date::format_datetime(&item.at),
+            item.memo.id
+        ))
+        .appname("memo")
+        .show()
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let context = MemoContext {
+            memo_dir: temp_dir.path().to_path_buf(),
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+        (temp_dir, context)
+    }
+
+    #[test]
+    fn test_collect_due() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo(
+            "2025-01/30/20250130143022.md",
+            "---\ndue: 2025-02-01\nremind: 2025-01-31 09:00\n---\nreport".to_string(),
+        )
+        .unwrap();
+        repo.create_memo(
+            "2025-01/29/20250129120000.md",
+            "---\ndue: 2025-01-31 08:00\n---\nslides".to_string(),
+        )
+        .unwrap();
+        repo.create_memo(
+            "2025-01/28/20250128090000.md",
+            "---\ndue: 2025-03-01\n---\nlater".to_string(),
+        )
+        .unwrap();
+
+        let now = Local.with_ymd_and_hms(2025, 1, 31, 12, 0, 0).unwrap();
+        let due = collect_due(&context, now).unwrap();
+
+        let summary: Vec<(String, &str)> = due
+            .iter()
+            .map(|item| (item.memo.id.as_str(), item.field))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("20250129120000".to_string(), "due"),
+                ("20250130143022".to_string(), "remind"),
+            ]
+        );
+    }
+}
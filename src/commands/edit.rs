@@ -1,31 +1,155 @@
 use crate::context::MemoContext;
-use crate::error::MemoResult;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::git;
 use crate::memo::{MemoDocument, MemoFile};
+use crate::memo_id::MemoId;
 use crate::repository::MemoRepository;
-use crate::search::SearchManager;
 use crate::utils::editor;
+use crate::webhook::{self, WebhookEvent};
+use chrono::{Local, NaiveTime};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
 
-pub fn run(context: &MemoContext, id: &str) -> MemoResult<()> {
+/// `memo edit today` で開く（無ければ作る）今日のメモ
+pub const TODAY: &str = "today";
+
+/// `memo edit <id>...` / `memo edit --last N`: 全てのメモを1回のエディタ起動で開く
+///
+/// 変更されたメモだけ `updated_at` を更新してインデックスに入れ直す。
+/// `create`（と `today`）なら、無い ID のメモを空で作ってから開く
+pub fn run(
+    context: &MemoContext,
+    ids: &[String],
+    last: Option<usize>,
+    create: bool,
+) -> MemoResult<()> {
     let repo = MemoRepository::new(context.clone());
-    let memo = repo.find_memo_by_id(id)?;
+    let targets = match last {
+        Some(count) => recent_memos(&repo, count)?
+            .into_iter()
+            .map(EditTarget::Memo)
+            .collect(),
+        // 途中で見つからない ID があればエディタを開かない（メモも作らない）
+        None => ids
+            .iter()
+            .map(|id| resolve_target(&repo, id, create))
+            .collect::<MemoResult<Vec<_>>>()?,
+    };
+    if targets.is_empty() {
+        return Err(MemoError::InvalidArgument("no memos to edit".to_string()));
+    }
+
+    let mut memos = Vec::new();
+    let mut created_ids = Vec::new();
+    for target in targets {
+        match target {
+            EditTarget::Memo(memo) => memos.push(memo),
+            EditTarget::Create(id) => {
+                memos.push(repo.create_memo(id.to_relative_path(), String::new())?);
+                created_ids.push(id);
+            }
+        }
+    }
+
+    let before = memos
+        .iter()
+        .map(|memo| content_hash(&memo.path))
+        .collect::<MemoResult<Vec<_>>>()?;
+    let paths: Vec<&Path> = memos.iter().map(|memo| memo.path.as_path()).collect();
+    editor::open_editor_with_files(context, &paths)?;
 
-    editor::open_editor(context, &memo.path)?;
-    update_search_index(context, &memo.path)?;
+    let mut created = Vec::new();
+    let mut edited = Vec::new();
+    for (memo, before) in memos.iter().zip(before) {
+        // 作ったメモは `memo add` と同じく、空のままでも残してインデックスに入れる
+        if created_ids.contains(&memo.id) {
+            let memo = MemoFile::from_path(&memo.path)?;
+            update_search_index(context, &memo.path)?;
+            println!("Memo created: {}", memo.id);
+            created.push(memo);
+            continue;
+        }
+        // エディタで変更されなかったメモはインデックス更新をスキップ
+        if content_hash(&memo.path)? == before {
+            continue;
+        }
+        let memo = MemoFile::from_path(&memo.path)?.set_updated_at(Local::now())?;
+        update_search_index(context, &memo.path)?;
+        println!("Memo edited: {}", memo.id);
+        edited.push(memo);
+    }
 
-    println!("Memo edited: {}", id);
+    if !created.is_empty() {
+        webhook::notify(&context.config, WebhookEvent::Create, &created);
+        git::auto_commit(&context.config, &context.memo_dir, "add", &created);
+    }
+    if !edited.is_empty() {
+        webhook::notify(&context.config, WebhookEvent::Edit, &edited);
+        git::auto_commit(&context.config, &context.memo_dir, "edit", &edited);
+    }
     Ok(())
 }
 
-fn update_search_index(context: &MemoContext, memo_path: &std::path::Path) -> MemoResult<()> {
-    let data_dir = context.memo_dir.clone();
-    let index_dir = context.index_dir();
-    let search_manager = SearchManager::new(data_dir, index_dir);
+enum EditTarget {
+    Memo(MemoFile),
+    /// まだ無いので作るメモ
+    Create(MemoId),
+}
+
+/// `today` は今日の 00:00:00 の ID。`create` でも、作れるのは 14 桁の ID だけ
+fn resolve_target(repo: &MemoRepository, id: &str, create: bool) -> MemoResult<EditTarget> {
+    let today = id == TODAY;
+    let id = if today {
+        today_id().as_str()
+    } else {
+        id.to_string()
+    };
+    match repo.find_memo_by_id(&id) {
+        Ok(memo) => Ok(EditTarget::Memo(memo)),
+        Err(MemoError::MemoNotFound(_)) if create || today => MemoId::from_str(&id)
+            .map(EditTarget::Create)
+            .map_err(|_| {
+                MemoError::InvalidArgument(format!(
+                    "cannot create '{}': --create needs a full 14-digit ID (e.g. 20250130000000) or 'today'",
+                    id
+                ))
+            }),
+        Err(e) => Err(e),
+    }
+}
+
+fn today_id() -> MemoId {
+    let midnight = Local::now()
+        .with_time(NaiveTime::MIN)
+        .single()
+        .unwrap_or_else(Local::now);
+    MemoId::from_datetime(midnight)
+}
+
+/// 最近更新した順に `count` 件
+fn recent_memos(repo: &MemoRepository, count: usize) -> MemoResult<Vec<MemoFile>> {
+    let mut memos = repo.list_all_memos()?;
+    memos.sort_by_key(|memo| std::cmp::Reverse(memo.modified));
+    memos.truncate(count);
+    Ok(memos)
+}
+
+fn content_hash(path: &Path) -> MemoResult<u64> {
+    let mut hasher = DefaultHasher::new();
+    fs::read(path).with_path(path)?.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+pub(crate) fn update_search_index(context: &MemoContext, memo_path: &Path) -> MemoResult<()> {
+    let search_manager = context.search_manager();
 
     if let Ok(memo_file) = MemoFile::from_path(memo_path) {
         let memo_doc = MemoDocument::from_memo_file(&memo_file);
 
-        let _ = search_manager.remove_memo(&memo_doc)?;
-        let _ = search_manager.add_memo(&memo_doc)?;
+        search_manager.remove_memo(&memo_doc)?;
+        search_manager.add_memo(&memo_doc)?;
     }
 
     Ok(())
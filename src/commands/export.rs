@@ -0,0 +1,378 @@
+//! `memo export`: write memos to a portable archive for backups and migration.
+//!
+//! ```text
+//! jsonl            1行1メモの JSON（id, created_at, path, metadata, content, attachments）
+//! markdown-bundle  全メモを1つの Markdown に連結（各メモの前に `<!-- memo: <id> <created_at> -->`）
+//! zip              メモディレクトリと同じ構成の .md と添付ファイル、memos.jsonl
+//! obsidian         Obsidian の vault として読めるディレクトリ（タグは #tag、front matter に created）
+//! ```
+//!
+//! Encrypted memos that can be decrypted are exported in plain text.
+
+use crate::commands::list::parse_date_option;
+use crate::context::MemoContext;
+use crate::encryption;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::memo::{MemoFile, yaml_to_json_value};
+use crate::repository::{DateRange, MemoRepository};
+use crate::tag_index;
+use crate::utils::datetime;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// `markdown-bundle` で各メモの前に置く行の書き出し
+pub const BUNDLE_MARKER: &str = "<!-- memo: ";
+/// zip に入れるメモ一覧
+const ZIP_MANIFEST: &str = "memos.jsonl";
+
+/// `memo export --to` の値
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// One JSON object per memo (JSON Lines)
+    #[default]
+    Jsonl,
+    /// All memos in one Markdown file, front matter included
+    MarkdownBundle,
+    /// A zip archive with the memo files and their attachments
+    Zip,
+    /// A directory that Obsidian can open as a vault
+    Obsidian,
+}
+
+/// `memo export` のオプション
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// Output file (directory for obsidian); stdout if omitted
+    pub output: Option<PathBuf>,
+    /// Only memos created at or after this date
+    pub since: Option<String>,
+    /// Only memos created before the end of this date
+    pub until: Option<String>,
+    /// Only memos with this front matter tag
+    pub tag: Option<String>,
+}
+
+/// `jsonl` と zip の `memos.jsonl` の1行
+#[derive(Debug, Serialize)]
+struct ExportRecord {
+    id: String,
+    created_at: String,
+    /// メモディレクトリからの相対パス
+    path: String,
+    metadata: serde_json::Value,
+    content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<String>,
+}
+
+impl ExportRecord {
+    fn from_memo_file(memo: &MemoFile) -> Self {
+        let assets_dir = memo
+            .id
+            .to_relative_path()
+            .with_file_name(memo.id.assets_dir_name());
+        Self {
+            id: memo.id.as_str(),
+            created_at: memo.id.get_datetime().to_rfc3339(),
+            path: memo.id.to_relative_path().to_string_lossy().to_string(),
+            metadata: memo
+                .metadata
+                .as_ref()
+                .map(yaml_to_json_value)
+                .unwrap_or_else(|| serde_json::json!({})),
+            content: memo.content.clone(),
+            attachments: memo
+                .attachments()
+                .iter()
+                .map(|name| assets_dir.join(name).to_string_lossy().to_string())
+                .collect(),
+        }
+    }
+}
+
+pub fn run(context: &MemoContext, options: &ExportOptions) -> MemoResult<()> {
+    let range = DateRange {
+        since: options
+            .since
+            .as_deref()
+            .map(|value| parse_date_option(value, datetime::parse_datetime))
+            .transpose()?,
+        until: options
+            .until
+            .as_deref()
+            .map(|value| parse_date_option(value, datetime::parse_end_datetime))
+            .transpose()?,
+    };
+    let repo = MemoRepository::new(context.clone());
+    let mut memos = repo.list_memos_filtered(&range, None, 0)?;
+    if let Some(tag) = options.tag.as_deref() {
+        memos.retain(|memo| memo.tags().contains(&tag));
+    }
+    // 古い順に書き出す
+    memos.reverse();
+
+    match (options.format, options.output.as_deref()) {
+        (ExportFormat::Jsonl, output) => write_output(output, |out| export_jsonl(&memos, out))?,
+        (ExportFormat::MarkdownBundle, output) => {
+            write_output(output, |out| export_markdown_bundle(&memos, out))?
+        }
+        (ExportFormat::Zip, Some(output)) => {
+            export_zip(&memos, File::create(output).with_path(output)?)?
+        }
+        (ExportFormat::Obsidian, Some(output)) => export_obsidian(&memos, output)?,
+        (format, None) => {
+            return Err(MemoError::InvalidArgument(format!(
+                "--output is required for --to {}",
+                clap::ValueEnum::to_possible_value(&format)
+                    .map(|value| value.get_name().to_string())
+                    .unwrap_or_default()
+            )));
+        }
+    }
+
+    if let Some(output) = &options.output {
+        println!("Exported {} memo(s) to {}", memos.len(), output.display());
+    }
+    Ok(())
+}
+
+/// `output` が無ければ標準出力に書く
+fn write_output(
+    output: Option<&Path>,
+    export: impl FnOnce(&mut dyn Write) -> MemoResult<()>,
+) -> MemoResult<()> {
+    match output {
+        Some(path) => {
+            let mut file = io::BufWriter::new(File::create(path).with_path(path)?);
+            export(&mut file)?;
+            file.flush()?;
+        }
+        None => export(&mut io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+fn export_jsonl(memos: &[MemoFile], out: &mut dyn Write) -> MemoResult<()> {
+    for memo in memos {
+        let line = serde_json::to_string(&ExportRecord::from_memo_file(memo))
+            .map_err(|e| MemoError::from(io::Error::other(e)))?;
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+fn export_markdown_bundle(memos: &[MemoFile], out: &mut dyn Write) -> MemoResult<()> {
+    for (i, memo) in memos.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        writeln!(
+            out,
+            "{}{} {} -->",
+            BUNDLE_MARKER,
+            memo.id,
+            memo.id.get_datetime().to_rfc3339()
+        )?;
+        let raw = encryption::read_memo(&memo.path)?;
+        write!(out, "{}", raw)?;
+        if !raw.ends_with('\n') {
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+fn export_zip<W: Write + io::Seek>(memos: &[MemoFile], writer: W) -> MemoResult<()> {
+    let mut zip = zip::ZipWriter::new(writer);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let mut manifest = Vec::new();
+    for memo in memos {
+        let path = memo.id.to_relative_path();
+        zip.start_file(zip_name(&path), options)
+            .map_err(zip_error)?;
+        zip.write_all(encryption::read_memo(&memo.path)?.as_bytes())?;
+
+        for (attachment, name) in memo.attachment_paths().iter().zip(memo.attachments()) {
+            if !attachment.is_file() {
+                continue;
+            }
+            let entry = path.with_file_name(memo.id.assets_dir_name()).join(name);
+            zip.start_file(zip_name(&entry), options)
+                .map_err(zip_error)?;
+            zip.write_all(&fs::read(attachment).with_path(attachment)?)?;
+        }
+        export_jsonl(std::slice::from_ref(memo), &mut manifest)?;
+    }
+    zip.start_file(ZIP_MANIFEST, options).map_err(zip_error)?;
+    zip.write_all(&manifest)?;
+    zip.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+/// zip の中のパスは OS に関係なく `/` 区切り
+fn zip_name(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn zip_error(e: zip::result::ZipError) -> MemoError {
+    MemoError::from(io::Error::other(e))
+}
+
+/// メモディレクトリと同じ構成で、Obsidian 向けに書き換えたメモと添付ファイルを置く
+fn export_obsidian(memos: &[MemoFile], vault: &Path) -> MemoResult<()> {
+    if vault.exists() && fs::read_dir(vault).with_path(vault)?.next().is_some() {
+        return Err(MemoError::InvalidArgument(format!(
+            "{} is not empty",
+            vault.display()
+        )));
+    }
+    for memo in memos {
+        let path = vault.join(memo.id.to_relative_path());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_path(parent)?;
+        }
+        fs::write(&path, obsidian_note(memo)?).with_path(&path)?;
+
+        let attachments = memo.attachment_paths();
+        if attachments.iter().any(|attachment| attachment.is_file()) {
+            let assets_dir = path.with_file_name(memo.id.assets_dir_name());
+            fs::create_dir_all(&assets_dir).with_path(&assets_dir)?;
+            for (attachment, name) in attachments.iter().zip(memo.attachments()) {
+                if attachment.is_file() {
+                    fs::copy(attachment, assets_dir.join(name)).with_path(attachment)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Obsidian のタグには `@` が使えないので `#tag` / `tags: [tag]` にし、作成日時を `created` に入れる
+fn obsidian_note(memo: &MemoFile) -> MemoResult<String> {
+    let mut metadata: BTreeMap<String, serde_yaml::Value> = memo
+        .metadata
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    let tags: Vec<serde_yaml::Value> = memo
+        .tags()
+        .iter()
+        .map(|tag| serde_yaml::Value::from(tag.trim_start_matches('@')))
+        .collect();
+    if !tags.is_empty() {
+        metadata.insert("tags".to_string(), serde_yaml::Value::Sequence(tags));
+    }
+    metadata
+        .entry("created".to_string())
+        .or_insert_with(|| memo.id.get_datetime().to_rfc3339().into());
+
+    let front_matter = serde_yaml::to_string(&metadata)?;
+    let body = tag_index::replace_inline_tags(&memo.content, |tag| format!("#{}", &tag[1..]));
+    Ok(format!("---\n{}---\n{}", front_matter, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+    use tempfile::TempDir;
+
+    fn create_memos() -> (TempDir, Vec<MemoFile>) {
+        let temp_dir = TempDir::new().unwrap();
+        let first = MemoFile::create(
+            temp_dir.path().join("2025-01/29/20250129120000.md"),
+            "# First\n".to_string(),
+        )
+        .unwrap();
+        let second = MemoFile::create(
+            temp_dir.path().join("2025-01/30/20250130143022.md"),
+            "---\ntags: [\"@todo\"]\nattachments: [\"a.txt\"]\n---\n# Second\nsee @todo"
+                .to_string(),
+        )
+        .unwrap();
+        fs::create_dir_all(second.assets_dir()).unwrap();
+        fs::write(second.assets_dir().join("a.txt"), "attached").unwrap();
+        (temp_dir, vec![first, second])
+    }
+
+    #[test]
+    fn test_export_jsonl() {
+        let (_temp_dir, memos) = create_memos();
+        let mut out = Vec::new();
+        export_jsonl(&memos, &mut out).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["id"], "20250129120000");
+        assert_eq!(lines[0]["metadata"], serde_json::json!({}));
+        assert!(lines[0].get("attachments").is_none());
+        assert_eq!(lines[1]["path"], "2025-01/30/20250130143022.md");
+        assert_eq!(lines[1]["metadata"]["tags"][0], "@todo");
+        assert_eq!(lines[1]["content"], "# Second\nsee @todo");
+        assert_eq!(lines[1]["attachments"][0], "2025-01/30/143022/a.txt");
+    }
+
+    #[test]
+    fn test_export_markdown_bundle() {
+        let (_temp_dir, memos) = create_memos();
+        let mut out = Vec::new();
+        export_markdown_bundle(&memos, &mut out).unwrap();
+
+        let bundle = String::from_utf8(out).unwrap();
+        assert!(bundle.starts_with("<!-- memo: 20250129120000 2025-01-29T12:00:00"));
+        assert!(bundle.contains("# First\n\n<!-- memo: 20250130143022 "));
+        assert!(bundle.ends_with("---\n# Second\nsee @todo\n"));
+    }
+
+    #[test]
+    fn test_export_zip() {
+        let (_temp_dir, memos) = create_memos();
+        let mut buffer = Cursor::new(Vec::new());
+        export_zip(&memos, &mut buffer).unwrap();
+
+        let mut archive = zip::ZipArchive::new(buffer).unwrap();
+        let mut read = |name: &str| {
+            let mut content = String::new();
+            archive
+                .by_name(name)
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+            content
+        };
+        assert_eq!(read("2025-01/29/20250129120000.md"), "# First\n");
+        assert_eq!(read("2025-01/30/143022/a.txt"), "attached");
+        assert_eq!(read(ZIP_MANIFEST).lines().count(), 2);
+    }
+
+    #[test]
+    fn test_export_obsidian() {
+        let (temp_dir, memos) = create_memos();
+        let vault = temp_dir.path().join("vault");
+        export_obsidian(&memos, &vault).unwrap();
+
+        let note = fs::read_to_string(vault.join("2025-01/30/20250130143022.md")).unwrap();
+        assert!(note.starts_with("---\n"));
+        assert!(note.contains("- todo\n"));
+        assert!(note.contains("created: "));
+        assert!(note.ends_with("---\n# Second\nsee #todo"));
+        assert!(vault.join("2025-01/30/143022/a.txt").exists());
+
+        // 既存の vault は上書きしない
+        assert!(export_obsidian(&memos, &vault).is_err());
+    }
+}
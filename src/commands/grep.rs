@@ -0,0 +1,138 @@
+use crate::context::MemoContext;
+use crate::display::color::{self, Style};
+use crate::display::theme::{self, Element};
+use crate::encryption;
+use crate::error::{MemoError, MemoResult};
+use crate::memo_id::MemoId;
+use crate::repository::MemoRepository;
+use regex::{Regex, RegexBuilder};
+
+/// `memo grep` のオプション
+#[derive(Debug, Clone, Default)]
+pub struct GrepOptions {
+    /// Match case-insensitively (`-i`)
+    pub ignore_case: bool,
+    /// Lines of context before and after each match (`-C`)
+    pub context: usize,
+}
+
+/// 表示する行（番号は 0 始まり）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrepLine {
+    Match(usize),
+    Context(usize),
+    /// 離れた箇所の区切り（`--`）
+    Separator,
+}
+
+/// `memo grep <pattern>`: 索引を使わずにメモファイルを新しい順に正規表現で検索
+pub fn run(context: &MemoContext, pattern: &str, options: &GrepOptions) -> MemoResult<()> {
+    let regex = RegexBuilder::new(pattern)
+        .case_insensitive(options.ignore_case)
+        .build()
+        .map_err(|e| MemoError::InvalidArgument(format!("invalid regex /{}/: {}", pattern, e)))?;
+
+    let repo = MemoRepository::new(context.clone());
+    let mut paths = repo.list_memo_paths()?;
+    paths.sort_by(|a, b| b.cmp(a));
+
+    let mut first = true;
+    for path in paths {
+        // 1ファイルずつ読む
+        let Ok(content) = encryption::read_memo(&path) else {
+            continue;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let matched = grep_lines(&lines, &regex, options.context);
+        if matched.is_empty() {
+            continue;
+        }
+
+        if !first {
+            println!();
+        }
+        first = false;
+        let name = MemoId::from_path(&path)
+            .map(|id| id.as_str())
+            .unwrap_or_else(|_| path.display().to_string());
+        println!("{}", color::paint(&name, Style::Bold));
+        for line in matched {
+            match line {
+                GrepLine::Match(n) => println!("{}:{}", n + 1, highlight(lines[n], &regex)),
+                GrepLine::Context(n) => println!("{}-{}", n + 1, lines[n]),
+                GrepLine::Separator => println!("--"),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 一致行と前後 `context` 行を、重なりをまとめて並べる
+pub fn grep_lines(lines: &[&str], regex: &Regex, context: usize) -> Vec<GrepLine> {
+    let mut result = Vec::new();
+    // 次に出力してよい行（これより前は出力済み）
+    let mut next = 0;
+    for (n, line) in lines.iter().enumerate() {
+        if !regex.is_match(line) {
+            continue;
+        }
+        let start = n.saturating_sub(context).max(next);
+        if context > 0 && next > 0 && start > next {
+            result.push(GrepLine::Separator);
+        }
+        result.extend((start..n).map(GrepLine::Context));
+        result.push(GrepLine::Match(n));
+        next = n + 1;
+
+        // 後ろの文脈は次の一致行の手前まで
+        let end = (n + 1 + context).min(lines.len());
+        while next < end && !regex.is_match(lines[next]) {
+            result.push(GrepLine::Context(next));
+            next += 1;
+        }
+    }
+    result
+}
+
+fn highlight(line: &str, regex: &Regex) -> String {
+    if !color::colors_enabled() {
+        return line.to_string();
+    }
+    regex
+        .replace_all(line, |caps: &regex::Captures| {
+            theme::paint(&caps[0], Element::Match)
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grep_lines() {
+        let lines = ["a", "deploy", "b", "c", "d", "e", "deploy", "deploy", "f"];
+        let regex = Regex::new("deploy").unwrap();
+
+        assert_eq!(
+            grep_lines(&lines, &regex, 0),
+            vec![GrepLine::Match(1), GrepLine::Match(6), GrepLine::Match(7)]
+        );
+        assert_eq!(
+            grep_lines(&lines, &regex, 1),
+            vec![
+                GrepLine::Context(0),
+                GrepLine::Match(1),
+                GrepLine::Context(2),
+                GrepLine::Separator,
+                GrepLine::Context(5),
+                GrepLine::Match(6),
+                GrepLine::Match(7),
+                GrepLine::Context(8),
+            ]
+        );
+        // 文脈が重なれば区切らない
+        assert!(!grep_lines(&lines, &regex, 2).contains(&GrepLine::Separator));
+        assert!(grep_lines(&lines, &Regex::new("zzz").unwrap(), 3).is_empty());
+    }
+}
@@ -0,0 +1,190 @@
+use crate::error::{MemoError, MemoResult};
+use clap::Command;
+use std::io::{self, Write};
+
+/// `memo help <topic>` で表示する補足ドキュメント
+pub struct HelpTopic {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub body: &'static str,
+}
+
+pub const TOPICS: &[HelpTopic] = &[
+    HelpTopic {
+        name: "query-syntax",
+        summary: "Search query syntax for `memo search`",
+        body: r#"Queries are parsed by tantivy and match the memo body, title, tags and front matter by default.
+
+  word                 memos containing the word
+  word1 word2          memos containing either word (better matches rank higher)
+  +word1 +word2        memos containing both words
+  word1 AND word2      same as above; OR and NOT are also supported
+  -word                exclude memos containing the word
+  "exact phrase"       words appearing next to each other
+  title:word           search a specific field
+  tags:@todo           memos tagged @todo in front matter
+  metadata.status:done search a front matter field
+  tag:@todo            memos tagged exactly @todo (also tag:"@team sync")
+  prio:>=3             memos whose `priority:` is at least 3 (also prio:2)
+  in:2025-01           memos created in a year, month or day (local time)
+  deploy*              words starting with "deploy" (`?` matches one character)
+  /inc-[0-9]+/         a word in the title or body matching a regex (case-insensitive;
+                       the whole word must match, write `/` in a pattern as `\/`)
+
+Searchable fields: content, title, tags, metadata.<key>.
+Japanese text is tokenized with lindera, so words can be searched without spaces.
+Full-width and half-width forms, hiragana and katakana, and a trailing long vowel mark
+are treated as the same (サーバ matches サーバー, ABC matches ＡＢＣ).
+Run `memo index` first to build the search index; `memo index --update` afterwards
+only re-indexes memos that were added, changed, or removed since then."#,
+    },
+    HelpTopic {
+        name: "where-syntax",
+        summary: "Front matter filter expressions for `memo list --where`",
+        body: r#"`memo list --where EXPR` keeps memos whose front matter matches EXPR.
+
+  priority >= 2                    compare numbers (==, !=, <, <=, >, >=)
+  status == 'done'                 compare strings (single or double quotes)
+  tags contains '@todo'            list membership or substring match
+  author.name == "Alice"           nested fields use dots
+  draft                            field is present and truthy
+  a && b, a || b, !a               combine with and/or/not (words also work)
+  (a || b) && c                    group with parentheses
+
+A missing field never matches, except with `!=`."#,
+    },
+    HelpTopic {
+        name: "layout",
+        summary: "How memos are stored on disk",
+        body: r#"Memos live in $XDG_DATA_HOME/memo (usually ~/.local/share/memo):
+
+  YYYY-MM/DD/YYYYMMDDhhmmss.md   one file per memo; the file name is its ID
+  .archive/                      archived memos, same layout as above (`memo restore`)
+  .trash/                        memos removed by `memo delete`, same layout
+  .templates/                    user templates for `memo add --template`
+  .index/                        search index built by `memo index`
+  .ignore                        keeps .archive and .trash out of tools like rg and fd
+
+A memo may start with YAML front matter between `---` lines.
+`memo dir` prints the memo directory."#,
+    },
+    HelpTopic {
+        name: "templates",
+        summary: "Variables available to `--template` output files",
+        body: r#"`list`, `show` and `search` accept `--template FILE` with a Handlebars template.
+
+Each memo provides:
+  id, path, created_at, modified     RFC 3339 timestamps for the dates
+  preview, content                   preview text and full body
+  metadata, metadata_error           front matter (e.g. {{metadata.title}})
+  outline                            headings as {level, text}
+  score                              search relevance (search only)
+
+`show` renders one memo at the top level. `list` renders once with
+`memos`, and `search` additionally gets `query`. Example:
+
+  {{#each memos}}- {{id}} {{metadata.title}}
+  {{/each}}"#,
+    },
+    HelpTopic {
+        name: "environment",
+        summary: "Environment variables that affect memo",
+        body: r#"  XDG_DATA_HOME      base directory for memo data (memo dir is $XDG_DATA_HOME/memo
+                     unless `memo_dir` is set in config.toml)
+  XDG_CONFIG_HOME    config.toml is read from $XDG_CONFIG_HOME/memo (see `memo config`)
+  EDITOR             editor used by `memo add` and `memo edit` (default: vi;
+                     `editor` in config.toml takes precedence)
+  COLUMNS            terminal width used for previews and tables
+  NO_COLOR           disable colors when `--color` is auto (or pass `--no-color`)
+  MEMO_DATE_FORMAT   strftime format for dates in listings (e.g. "%Y/%m/%d %H:%M");
+                     overrides `date_format` in config.toml
+  LC_ALL, LC_TIME, LANG
+                     Japanese locales (ja_*) default to Japanese-style dates"#,
+    },
+];
+
+pub fn find_topic(name: &str) -> Option<&'static HelpTopic> {
+    TOPICS.iter().find(|topic| topic.name == name)
+}
+
+/// `memo help [topic|command]`
+pub fn run(mut cli: Command, topic: Option<&str>) -> MemoResult<()> {
+    cli.build();
+
+    let Some(name) = topic else {
+        cli.print_long_help()?;
+        println!();
+        println!("Help topics:");
+        for topic in TOPICS {
+            println!("  {:<14}{}", topic.name, topic.summary);
+        }
+        return Ok(());
+    };
+
+    if let Some(topic) = find_topic(name) {
+        println!("{}", topic.body);
+        return Ok(());
+    }
+
+    match cli.find_subcommand_mut(name) {
+        Some(subcommand) => {
+            subcommand.print_long_help()?;
+            Ok(())
+        }
+        None => Err(MemoError::InvalidArgument(format!(
+            "unknown help topic or command '{}' (run `memo help` for a list)",
+            name
+        ))),
+    }
+}
+
+/// `memo man`: roff形式のマニュアルを標準出力に書き出す
+pub fn run_man(cli: Command) -> MemoResult<()> {
+    let mut out = Vec::new();
+    clap_mangen::Man::new(cli).render(&mut out)?;
+    for topic in TOPICS {
+        out.extend(render_man_section(topic).into_bytes());
+    }
+    io::stdout().write_all(&out)?;
+    Ok(())
+}
+
+fn render_man_section(topic: &HelpTopic) -> String {
+    let mut section = format!(".SH \"{}\"\n", topic.name.replace('-', " ").to_uppercase());
+    section.push_str(".nf\n");
+    for line in topic.body.lines() {
+        let line = line.replace('\\', "\\e");
+        // 行頭の . と ' は roff の制御文字
+        if line.starts_with('.') || line.starts_with('\'') {
+            section.push_str("\\&");
+        }
+        section.push_str(&line);
+        section.push('\n');
+    }
+    section.push_str(".fi\n");
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_topic() {
+        assert!(find_topic("query-syntax").is_some());
+        assert!(find_topic("nope").is_none());
+    }
+
+    #[test]
+    fn test_render_man_section_escapes() {
+        let topic = HelpTopic {
+            name: "query-syntax",
+            summary: "",
+            body: ".hidden\nback\\slash",
+        };
+        assert_eq!(
+            render_man_section(&topic),
+            ".SH \"QUERY SYNTAX\"\n.nf\n\\&.hidden\nback\\eslash\n.fi\n"
+        );
+    }
+}
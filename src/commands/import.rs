@@ -0,0 +1,365 @@
+use crate::commands::export::BUNDLE_MARKER;
+use crate::context::MemoContext;
+use crate::encryption;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::front_matter;
+use crate::git;
+use crate::memo_id::MemoId;
+use crate::repository::MemoRepository;
+use crate::utils::datetime;
+use crate::webhook::{self, WebhookEvent};
+use chrono::{DateTime, Local};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 作成日時として読む front matter のキー（先にあるものを優先）
+const DATE_KEYS: &[&str] = &["created_at", "created", "date"];
+
+/// 取り込む前のメモ1件
+#[derive(Debug)]
+struct ImportEntry {
+    /// 表示用の取り込み元
+    source: String,
+    id: MemoId,
+    content: String,
+}
+
+/// `memo export --to jsonl` の1行（使う項目だけ）
+#[derive(Debug, Deserialize)]
+struct ImportRecord {
+    id: Option<String>,
+    created_at: Option<String>,
+    #[serde(default)]
+    metadata: serde_json::Value,
+    content: String,
+}
+
+/// `memo import <path>...`: Markdown ファイル・ディレクトリ・`memo export --to jsonl|markdown-bundle` の出力を取り込む
+///
+/// ID はファイル名（`YYYYMMDDHHmmss.md` か旧形式の `YYYY-MM/DD/HHMMSS.md`）、
+/// front matter の日付、ファイルの更新日時の順に決める。
+pub fn run(context: &MemoContext, paths: &[PathBuf]) -> MemoResult<()> {
+    // 途中で読めないものがあれば何も書かない
+    let mut entries = Vec::new();
+    for path in paths {
+        entries.extend(read_entries(path)?);
+    }
+    if entries.is_empty() {
+        return Err(MemoError::InvalidArgument(
+            "no markdown files to import".to_string(),
+        ));
+    }
+
+    context.ensure_memo_dir()?;
+    let repo = MemoRepository::new(context.clone());
+    let mut taken = HashSet::new();
+    let mut imported = Vec::new();
+    for entry in entries {
+        let id = unused_id(context, entry.id, &taken);
+        taken.insert(id.as_str());
+        let memo = repo.create_memo(id.to_relative_path(), entry.content)?;
+        println!("Imported {} as {}", entry.source, memo.id);
+        imported.push(memo);
+    }
+
+    context
+        .search_manager()
+        .update_index(&repo.list_memo_paths()?)?;
+
+    println!("Imported {} memo(s)", imported.len());
+    webhook::notify(&context.config, WebhookEvent::Create, &imported);
+    git::auto_commit(&context.config, &context.memo_dir, "import", &imported);
+    Ok(())
+}
+
+fn read_entries(path: &Path) -> MemoResult<Vec<ImportEntry>> {
+    if path.is_dir() {
+        let mut files = Vec::new();
+        collect_markdown_files(path, &mut files)?;
+        files.sort();
+        return files.iter().map(|file| read_markdown_file(file)).collect();
+    }
+    let extension = path.extension().and_then(|e| e.to_str());
+    match extension {
+        Some("jsonl") => read_jsonl(path),
+        Some("md") => {
+            let content = fs::read_to_string(path).with_path(path)?;
+            if content.starts_with(BUNDLE_MARKER) {
+                read_bundle(path, &content)
+            } else {
+                read_markdown_file(path).map(|entry| vec![entry])
+            }
+        }
+        _ => Err(MemoError::InvalidArgument(format!(
+            "cannot import '{}' (expected a directory, .md or .jsonl file)",
+            path.display()
+        ))),
+    }
+}
+
+/// 隠しディレクトリ（`.archive` など）は読まない
+fn collect_markdown_files(dir: &Path, files: &mut Vec<PathBuf>) -> MemoResult<()> {
+    for entry in fs::read_dir(dir).with_path(dir)? {
+        let path = entry.with_path(dir)?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_markdown_files(&path, files)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn read_markdown_file(path: &Path) -> MemoResult<ImportEntry> {
+    let content = fs::read_to_string(path).with_path(path)?;
+    let id = match id_from_layout(path).or_else(|| id_from_front_matter(&content)) {
+        Some(id) => id,
+        None => MemoId::from_datetime(DateTime::<Local>::from(
+            fs::metadata(path)
+                .with_path(path)?
+                .modified()
+                .with_path(path)?,
+        )),
+    };
+    Ok(ImportEntry {
+        source: path.display().to_string(),
+        id,
+        content,
+    })
+}
+
+/// `20250130143022.md`、または旧形式の `2025-01/30/143022.md`
+fn id_from_layout(path: &Path) -> Option<MemoId> {
+    if let Ok(id) = MemoId::from_path(path) {
+        return Some(id);
+    }
+    let time = path.file_stem()?.to_str()?;
+    let day_dir = path.parent()?;
+    let day = day_dir.file_name()?.to_str()?;
+    let year_month = day_dir.parent()?.file_name()?.to_str()?;
+    if time.len() != 6 || day.len() != 2 || year_month.len() != 7 {
+        return None;
+    }
+    MemoId::from_str(&format!("{}{}{}", year_month.replace('-', ""), day, time)).ok()
+}
+
+fn id_from_front_matter(content: &str) -> Option<MemoId> {
+    let front_matter = front_matter::parse_memo_content(content).front_matter?;
+    DATE_KEYS.iter().find_map(|key| {
+        let value = front_matter.get(*key)?.as_str()?;
+        datetime::parse_datetime(value).map(MemoId::from_datetime)
+    })
+}
+
+fn read_jsonl(path: &Path) -> MemoResult<Vec<ImportEntry>> {
+    let content = fs::read_to_string(path).with_path(path)?;
+    let mut entries = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let source = format!("{}:{}", path.display(), i + 1);
+        let record: ImportRecord = serde_json::from_str(line)
+            .map_err(|e| MemoError::InvalidArgument(format!("{}: {}", source, e)))?;
+        let id = record
+            .id
+            .as_deref()
+            .and_then(|id| MemoId::from_str(id).ok())
+            .or_else(|| {
+                let created_at = record.created_at.as_deref()?;
+                datetime::parse_datetime(created_at).map(MemoId::from_datetime)
+            })
+            .unwrap_or_else(|| MemoId::from_datetime(Local::now()));
+        entries.push(ImportEntry {
+            source,
+            id,
+            content: record_content(&record)?,
+        });
+    }
+    Ok(entries)
+}
+
+/// metadata を front matter に戻す
+fn record_content(record: &ImportRecord) -> MemoResult<String> {
+    let has_metadata = record.metadata.as_object().is_some_and(|m| !m.is_empty());
+    if !has_metadata {
+        return Ok(record.content.clone());
+    }
+    let yaml = serde_yaml::to_string(&record.metadata)?;
+    Ok(format!("---\n{}---\n{}", yaml, record.content))
+}
+
+/// `memo export --to markdown-bundle` の出力を `<!-- memo: <id> <created_at> -->` の行で分ける
+fn read_bundle(path: &Path, content: &str) -> MemoResult<Vec<ImportEntry>> {
+    let mut entries: Vec<ImportEntry> = Vec::new();
+    for line in content.split_inclusive('\n') {
+        if let Some(header) = line.trim_end().strip_prefix(BUNDLE_MARKER) {
+            let id = header
+                .trim_end_matches("-->")
+                .split_whitespace()
+                .next()
+                .and_then(|id| MemoId::from_str(id).ok())
+                .ok_or_else(|| {
+                    MemoError::InvalidArgument(format!(
+                        "{}: invalid bundle header: {}",
+                        path.display(),
+                        line.trim_end()
+                    ))
+                })?;
+            entries.push(ImportEntry {
+                source: format!("{} ({})", path.display(), id),
+                id,
+                content: String::new(),
+            });
+        } else if let Some(entry) = entries.last_mut() {
+            entry.content.push_str(line);
+        }
+    }
+    // 区切りの空行を外す
+    for entry in &mut entries {
+        if entry.content.ends_with("\n\n") {
+            entry.content.pop();
+        }
+    }
+    Ok(entries)
+}
+
+/// 既にあるメモや、今回の取り込みで使った ID と重なれば1秒ずつずらす
+fn unused_id(context: &MemoContext, mut id: MemoId, taken: &HashSet<String>) -> MemoId {
+    loop {
+        let path = id.to_file_path(&context.memo_dir);
+        let exists = path.exists() || encryption::encrypted_path(&path).exists();
+        if !exists && !taken.contains(&id.as_str()) {
+            return id;
+        }
+        id = id.next();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let context = MemoContext {
+            memo_dir: temp_dir.path().join("memo"),
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+        (temp_dir, context)
+    }
+
+    #[test]
+    fn test_id_from_layout() {
+        let id = |path: &str| id_from_layout(Path::new(path)).map(|id| id.as_str());
+        assert_eq!(
+            id("/notes/20250130143022.md"),
+            Some("20250130143022".to_string())
+        );
+        assert_eq!(
+            id("/old/2025-01/30/143022.md"),
+            Some("20250130143022".to_string())
+        );
+        assert_eq!(id("/notes/2025-01/30/idea.md"), None);
+        assert_eq!(id("/notes/143022.md"), None);
+    }
+
+    #[test]
+    fn test_id_from_front_matter() {
+        let id = |content: &str| id_from_front_matter(content).map(|id| id.as_str());
+        assert_eq!(
+            id("---\ndate: 2025-01-30\ncreated_at: 2025-01-29 08:15:00\n---\n# A"),
+            Some("20250129081500".to_string())
+        );
+        assert_eq!(
+            id("---\ndate: 2025-01-30\n---\n"),
+            Some("20250130000000".to_string())
+        );
+        assert_eq!(id("---\ndate: someday\n---\n"), None);
+        assert_eq!(id("# No front matter"), None);
+    }
+
+    #[test]
+    fn test_read_jsonl() {
+        let (temp_dir, _context) = create_test_context();
+        let path = temp_dir.path().join("memos.jsonl");
+        fs::write(
+            &path,
+            concat!(
+                r##"{"id": "20250130143022", "metadata": {"tags": ["@a"]}, "content": "# A\n"}"##,
+                "\n\n",
+                r##"{"created_at": "2025-01-29T12:00:00+09:00", "metadata": {}, "content": "# B"}"##,
+                "\n"
+            ),
+        )
+        .unwrap();
+
+        let entries = read_jsonl(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id.as_str(), "20250130143022");
+        assert_eq!(entries[0].content, "---\ntags:\n- '@a'\n---\n# A\n");
+        assert_eq!(entries[1].content, "# B");
+
+        fs::write(&path, "not json\n").unwrap();
+        let error = read_jsonl(&path).unwrap_err().to_string();
+        assert!(error.contains("memos.jsonl:1"));
+    }
+
+    #[test]
+    fn test_read_bundle() {
+        let content = "<!-- memo: 20250129120000 2025-01-29T12:00:00+09:00 -->\n# First\n\n<!-- memo: 20250130143022 2025-01-30T14:30:22+09:00 -->\n---\ntags: [\"@a\"]\n---\n# Second\n";
+        let entries = read_bundle(Path::new("memos.md"), content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id.as_str(), "20250129120000");
+        assert_eq!(entries[0].content, "# First\n");
+        assert_eq!(entries[1].content, "---\ntags: [\"@a\"]\n---\n# Second\n");
+
+        assert!(read_bundle(Path::new("memos.md"), "<!-- memo: broken -->\n").is_err());
+    }
+
+    #[test]
+    fn test_import_avoids_existing_ids() {
+        let (temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo("2025-01/30/20250130143022.md", "# Existing".to_string())
+            .unwrap();
+        let source = temp_dir.path().join("source");
+        fs::create_dir_all(source.join(".hidden")).unwrap();
+        fs::write(source.join("20250130143022.md"), "# Imported").unwrap();
+        fs::write(
+            source.join("copy-20250130143022.md"),
+            "---\ndate: 2025-01-30 14:30:22\n---\n# Copy",
+        )
+        .unwrap();
+        fs::write(source.join(".hidden/20250101000000.md"), "# Hidden").unwrap();
+
+        run(&context, &[source]).unwrap();
+
+        let ids: Vec<String> = repo
+            .list_all_memos()
+            .unwrap()
+            .iter()
+            .map(|memo| memo.id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["20250130143024", "20250130143023", "20250130143022"]
+        );
+        assert_eq!(
+            repo.find_memo_by_id("20250130143022").unwrap().content,
+            "# Existing"
+        );
+    }
+}
@@ -1,37 +1,129 @@
 use crate::context::MemoContext;
 use crate::error::MemoError;
+use crate::memo::{MemoDocument, MemoFile};
 use crate::repository::MemoRepository;
-use crate::search::{IndexLock, SearchManager};
+use crate::search::{IndexLock, IndexManifest};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+/// 作り直した後に残す索引の数（今の索引と 1 つ前のもの）
+const KEEP_INDEXES: usize = 2;
+
+/// 読み込み済みで、書き込みを待っているメモの上限
+const PENDING_DOCUMENTS: usize = 256;
+
+/// `build_index` の進み具合を受け取る
+pub trait IndexProgress {
+    /// 索引するメモの数が分かった時
+    fn started(&mut self, _total: usize) {}
+
+    /// メモを 1 つ処理するたびに（読めずに飛ばしたものも数える）
+    fn indexed(&mut self, done: usize, total: usize);
+}
+
+/// `memo index` の表示（100 件ごとと最後に 1 行）
+struct PrintProgress;
+
+impl IndexProgress for PrintProgress {
+    fn started(&mut self, total: usize) {
+        println!("Indexing {} memos...", total);
+    }
+
+    fn indexed(&mut self, done: usize, total: usize) {
+        if done.is_multiple_of(100) || done == total {
+            println!("Indexed {}/{} memos", done, total);
+        }
+    }
+}
 
 pub fn run_index(ctx: &MemoContext) -> Result<(), MemoError> {
     println!("Building search index...");
 
-    let data_dir = ctx.memo_dir.clone();
-    let index_dir = ctx.index_dir();
+    let index_dir = build_index(ctx, &mut PrintProgress)?;
 
+    println!("Search index built successfully!");
+    println!("Index location: {}", index_dir.display());
+
+    ctx.search_manager().prune_old_indexes(KEEP_INDEXES)?;
+    Ok(())
+}
+
+/// 全てのメモから索引を作り直し、新しい索引のディレクトリを返す
+///
+/// メモの読み込みと front matter の解析は並列に行い、書き込みは 1 つの writer にまとめる
+pub fn build_index(
+    ctx: &MemoContext,
+    progress: &mut dyn IndexProgress,
+) -> Result<PathBuf, MemoError> {
     let repo = MemoRepository::new(ctx.clone());
-    let search_manager = SearchManager::new(data_dir, index_dir);
+    let search_manager = ctx.search_manager();
+
+    let paths: Vec<PathBuf> = repo.iter_memos()?.map(|entry| entry.path).collect();
+    // 読み込む前に mtime を取っておく（途中で変わったメモは次の --update で拾う）
+    let manifest = IndexManifest::scan(&paths)?;
+    let total = paths.len();
+    progress.started(total);
 
-    let mut index = search_manager.create_new_index()?;
-    let _lock = IndexLock::acquire(&index.index_dir)?;
-    let memos = repo.list_all_memo_documents()?;
-    let total = memos.len();
+    // 途中で失敗したら今の索引のまま
+    search_manager.create_new_index(|index| {
+        let _lock = IndexLock::acquire(&index.index_dir)?;
+        std::thread::scope(|scope| {
+            let (sender, receiver) = mpsc::sync_channel(PENDING_DOCUMENTS);
+            // 書き込みが失敗して受け手が居なくなったら、送れなくなった所で止まる
+            scope.spawn(|| {
+                let _ = paths.par_iter().try_for_each_with(sender, |sender, path| {
+                    let memo = MemoFile::from_path(path)
+                        .ok()
+                        .map(|memo| MemoDocument::from_memo_file(&memo));
+                    sender.send(memo).map_err(drop)
+                });
+            });
 
-    println!("Indexing {} memos...", total);
+            for (i, memo) in receiver.into_iter().enumerate() {
+                if let Some(memo) = memo {
+                    index.add_memo(&memo)?;
+                }
+                progress.indexed(i + 1, total);
+            }
+            Ok::<(), MemoError>(())
+        })?;
 
-    for (i, memo) in memos.iter().enumerate() {
-        index.add_memo(memo)?;
+        // コミット
+        index.commit()?;
+        manifest.save(&index.index_dir)
+    })
+}
 
-        if (i + 1) % 100 == 0 || i + 1 == total {
-            println!("Indexed {}/{} memos", i + 1, total);
+/// 1 つ前の索引に戻す（`memo index --rollback`）
+pub fn run_rollback(ctx: &MemoContext) -> Result<(), MemoError> {
+    match ctx.search_manager().rollback()? {
+        Some(version) => println!("Search index rolled back to {}", version),
+        None => {
+            return Err(MemoError::Search(
+                "no previous index to roll back to".to_string(),
+            ));
         }
     }
+    Ok(())
+}
 
-    // コミット
-    index.commit()?;
+/// 古い索引ディレクトリを消す（`memo index --gc`）
+pub fn run_gc(ctx: &MemoContext) -> Result<(), MemoError> {
+    let removed = ctx.search_manager().prune_old_indexes(KEEP_INDEXES)?;
+    println!("Removed {} old index(es)", removed.len());
+    Ok(())
+}
 
-    println!("Search index built successfully!");
-    println!("Index location: {}", index.index_dir.display());
+/// 前回から変わったメモだけを索引し直す（`memo index --update`）
+pub fn run_update(ctx: &MemoContext) -> Result<(), MemoError> {
+    let repo = MemoRepository::new(ctx.clone());
+    let search_manager = ctx.search_manager();
 
+    let update = search_manager.update_index(&repo.list_memo_paths()?)?;
+    println!(
+        "Search index updated: {} added, {} modified, {} removed",
+        update.added, update.modified, update.removed
+    );
     Ok(())
 }
@@ -0,0 +1,31 @@
+use crate::context::MemoContext;
+use crate::error::MemoResult;
+use crate::links;
+use crate::memo_id::MemoId;
+use crate::repository::MemoRepository;
+
+/// `memo links <id>`: リンク先と、このメモにリンクしているメモを表示
+pub fn run(context: &MemoContext, id: &str) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(id)?;
+
+    println!("links:");
+    print_memos(&repo, &links::parse_links(&memo.content), "->");
+    println!("backlinks:");
+    print_memos(&repo, &links::backlinks(context, &memo.id)?, "<-");
+    Ok(())
+}
+
+fn print_memos(repo: &MemoRepository, ids: &[MemoId], arrow: &str) {
+    if ids.is_empty() {
+        println!("  (none)");
+    }
+    for id in ids {
+        // アーカイブ・削除したメモへのリンクも表示する
+        let title = match repo.find_memo_by_id(&id.as_str()) {
+            Ok(memo) => memo.title(),
+            Err(_) => "(missing)".to_string(),
+        };
+        println!("  {} {}  {}", arrow, id, title);
+    }
+}
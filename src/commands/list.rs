@@ -1,79 +1,209 @@
 use crate::context::MemoContext;
 use crate::display::MemoDisplayFormatter;
-use crate::error::MemoResult;
-use crate::repository::MemoRepository;
+use crate::display::format::{Formatter, JsonFormatter, OutputFormat};
+use crate::error::{MemoError, MemoResult};
+use crate::filter::FilterExpr;
+use crate::projection::Projection;
+use crate::render::{MemoTemplateData, OutputTemplate};
+use crate::repository::{self, DateRange, MemoRepository, SortKey};
+use crate::tag_index;
+use crate::utils::datetime;
 use chrono::{DateTime, Local};
-use serde::{Serialize, Serializer};
-use serde_yaml::Value;
-use std::collections::HashMap;
-
-#[derive(Debug, Serialize)]
-pub struct MemoListItem {
-    pub id: String,
-    #[serde(serialize_with = "serialize_datetime")]
-    pub modified: DateTime<Local>,
-    pub preview: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub content: Option<String>,
-    pub metadata: Option<HashMap<String, Value>>,
-    pub metadata_error: Option<String>,
+use std::path::PathBuf;
+
+/// `--page` に件数（`--limit` / `[list] limit`）が無い時の 1 ページの件数
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+/// `memo list` の表示オプション
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    /// Output in JSONL format
+    pub json: bool,
+    /// Filter by tag (e.g. @1on1)
+    pub tag: Option<String>,
+    /// Show heading structure instead of preview
+    pub outline: bool,
+    /// Show an aligned table (ID, date, title, tags)
+    pub table: bool,
+    /// Sort by a front matter field (e.g. meta.priority)
+    pub sort_by: Option<String>,
+    /// Built-in ordering (e.g. priority)
+    pub sort: Option<SortKey>,
+    /// Reverse the order (after `sort` / `sort_by`)
+    pub reverse: bool,
+    /// Only memos with `priority:` >= this value
+    pub min_priority: Option<i64>,
+    /// Filter by front matter expression
+    pub where_expr: Option<String>,
+    /// Only output these comma separated fields in JSON
+    pub select: Option<String>,
+    /// Render with a Handlebars template file
+    pub template: Option<PathBuf>,
+    /// Only memos created at or after this date
+    pub since: Option<String>,
+    /// Only memos created before the end of this date
+    pub until: Option<String>,
+    /// Show at most this many memos
+    pub limit: Option<usize>,
+    /// Skip this many memos
+    pub offset: usize,
+    /// Show the Nth page of `limit` memos (1-based); overrides `offset`
+    pub page: Option<usize>,
+    /// Output format (`--format`); `json` and `table` take precedence
+    pub format: Option<OutputFormat>,
 }
 
-fn serialize_datetime<S>(dt: &DateTime<Local>, serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    serializer.serialize_str(&dt.to_rfc3339())
+impl ListOptions {
+    /// `--json` / `--table` は `--format json` / `--format table` の短縮形
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else if self.table {
+            OutputFormat::Table
+        } else {
+            self.format.unwrap_or_default()
+        }
+    }
 }
 
-pub fn run(context: &MemoContext, json_output: bool, tag_filter: Option<&str>) -> MemoResult<()> {
+pub fn run(context: &MemoContext, options: &ListOptions) -> MemoResult<()> {
+    // 式の誤りはメモを読む前に報告する
+    let filter = options
+        .where_expr
+        .as_deref()
+        .map(FilterExpr::parse)
+        .transpose()?;
+    let projection = options
+        .select
+        .as_deref()
+        .map(Projection::parse)
+        .transpose()?;
+    let template = options
+        .template
+        .as_deref()
+        .map(OutputTemplate::from_file)
+        .transpose()?;
+
+    let range = DateRange {
+        since: options
+            .since
+            .as_deref()
+            .map(|value| parse_date_option(value, datetime::parse_datetime))
+            .transpose()?,
+        until: options
+            .until
+            .as_deref()
+            .map(|value| parse_date_option(value, datetime::parse_end_datetime))
+            .transpose()?,
+    };
+
+    let mut limit = options.limit.or(context.list_limit);
+    if options.page.is_some() {
+        limit = Some(limit.unwrap_or(DEFAULT_PAGE_SIZE));
+    }
+    let offset = page_offset(
+        options.offset,
+        options.page,
+        limit.unwrap_or(DEFAULT_PAGE_SIZE),
+    );
     let repo = MemoRepository::new(context.clone());
-    let mut memos = repo.list_all_memos()?;
+    // 絞り込み・並び替えが無ければ、表示する分だけ読む
+    let narrowed = options.tag.is_some()
+        || filter.is_some()
+        || options.min_priority.is_some()
+        || options.sort.is_some()
+        || options.sort_by.is_some()
+        || options.reverse;
+    let mut memos = if narrowed {
+        repo.list_memos_filtered(&range, None, 0)?
+    } else {
+        repo.list_memos_filtered(&range, limit, offset)?
+    };
 
-    if let Some(tag) = tag_filter {
-        memos.retain(|memo| {
-            memo.metadata.as_ref().map_or(false, |m| {
-                m.get("tags").and_then(|v| v.as_sequence()).map_or(false, |tags| {
-                    tags.iter().any(|t| t.as_str() == Some(tag))
-                })
-            })
-        });
+    if let Some(tag) = options.tag.as_deref() {
+        memos.retain(|memo| tag_index::memo_tags(memo).contains(tag));
     }
 
-    if json_output {
-        if memos.is_empty() {
-            return Ok(());
-        }
+    if let Some(filter) = &filter {
+        memos.retain(|memo| filter.matches(memo));
+    }
 
-        for memo in &memos {
-            let list_item = MemoListItem {
-                id: memo.id.as_str(),
-                modified: memo.modified,
-                preview: memo.preview(100),
-                content: Some(memo.content.clone()), // JSON出力時は全文を含める
-                metadata: memo.metadata.clone(),
-                metadata_error: memo.metadata_error.clone(),
-            };
-
-            if let Ok(json) = serde_json::to_string(&list_item) {
-                println!("{}", json);
-            }
-        }
-    } else {
-        // 共通の表示機能を使用
-        MemoDisplayFormatter::display_memo_list(&memos, "Recent memos");
-
-        if memos.len() == 20 {
-            let total_count = repo.list_all_memos()?.len();
-            if total_count > 20 {
-                println!("... and {} more memos", total_count - 20);
-            }
-        }
+    if let Some(min_priority) = options.min_priority {
+        memos.retain(|memo| memo.priority().is_some_and(|p| p >= min_priority));
+    }
+
+    if let Some(sort) = options.sort {
+        repository::sort_memos(&mut memos, sort);
+    }
+
+    if let Some(sort_by) = options.sort_by.as_deref() {
+        let key = metadata_sort_key(sort_by)?;
+        repository::sort_memos_by_metadata(&mut memos, key);
+    }
+
+    if options.reverse {
+        memos.reverse();
+    }
+
+    if narrowed {
+        memos = memos
+            .into_iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+    }
+
+    if let Some(template) = &template {
+        let memos: Vec<MemoTemplateData> =
+            memos.iter().map(MemoTemplateData::from_memo_file).collect();
+        let data = serde_json::json!({ "memos": memos });
+        print!("{}", template.render(&data)?);
+        return Ok(());
     }
 
+    let format = options.output_format();
+    if options.outline && format == OutputFormat::Plain {
+        MemoDisplayFormatter::display_memo_outline_list(&memos, "Recent memos");
+        return Ok(());
+    }
+    let formatter: Box<dyn Formatter> = match format {
+        OutputFormat::Json => Box::new(JsonFormatter::with_projection(projection)),
+        format => format.formatter(),
+    };
+    formatter.print_list(&memos, "Recent memos")?;
+
     Ok(())
 }
 
+/// `--page N`（1 始まり）を読み飛ばす件数にする。`--page` が無ければ `--offset`
+pub(crate) fn page_offset(offset: usize, page: Option<usize>, page_size: usize) -> usize {
+    match page {
+        Some(page) => page.saturating_sub(1) * page_size,
+        None => offset,
+    }
+}
+
+pub(crate) fn parse_date_option(
+    value: &str,
+    parse: fn(&str) -> Option<DateTime<Local>>,
+) -> MemoResult<DateTime<Local>> {
+    parse(value).ok_or_else(|| MemoError::InvalidArgument(format!("invalid date: {}", value)))
+}
+
+/// `meta.priority` / `metadata.priority` から front matter のキーを取り出す
+fn metadata_sort_key(sort_by: &str) -> MemoResult<&str> {
+    sort_by
+        .strip_prefix("meta.")
+        .or_else(|| sort_by.strip_prefix("metadata."))
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| {
+            MemoError::InvalidArgument(format!(
+                "unsupported sort key '{}' (expected meta.<field>)",
+                sort_by
+            ))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,6 +220,7 @@ mod tests {
         let context = MemoContext {
             memo_dir,
             editor: "echo".to_string(),
+            ..Default::default()
         };
 
         (temp_dir, context)
@@ -98,7 +229,7 @@ mod tests {
     #[test]
     fn test_list_empty() {
         let (_temp_dir, context) = create_test_context();
-        let result = run(&context, false, None);
+        let result = run(&context, &ListOptions::default());
         assert!(result.is_ok());
     }
 
@@ -113,7 +244,7 @@ mod tests {
         repo.create_memo("2025-01/30/20250130151545.md", "Test memo 2".to_string())
             .unwrap();
 
-        let result = run(&context, false, None);
+        let result = run(&context, &ListOptions::default());
         assert!(result.is_ok());
     }
 
@@ -126,7 +257,42 @@ mod tests {
         repo.create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
             .unwrap();
 
-        let result = run(&context, true, None);
+        let options = ListOptions {
+            json: true,
+            ..Default::default()
+        };
+        let result = run(&context, &options);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_metadata_sort_key() {
+        assert_eq!(metadata_sort_key("meta.priority").unwrap(), "priority");
+        assert_eq!(metadata_sort_key("metadata.a.b").unwrap(), "a.b");
+        assert!(metadata_sort_key("priority").is_err());
+        assert!(metadata_sort_key("meta.").is_err());
+    }
+
+    #[test]
+    fn test_list_outline() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+
+        let memo = repo
+            .create_memo(
+                "2025-01/30/20250130143022.md",
+                "# Title\n## Section\nbody\n### Detail".to_string(),
+            )
+            .unwrap();
+
+        let options = ListOptions {
+            outline: true,
+            ..Default::default()
+        };
+        assert!(run(&context, &options).is_ok());
+        assert_eq!(
+            MemoDisplayFormatter::format_outline(&memo.outline()),
+            vec!["- Title", "  - Section", "    - Detail"]
+        );
+    }
 }
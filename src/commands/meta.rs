@@ -0,0 +1,89 @@
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::repository::MemoRepository;
+use crate::webhook::{self, WebhookEvent};
+use chrono::Local;
+use serde_yaml::Value;
+
+/// `memo meta get <id> <key>`: front matter の値を表示（未設定なら何も出さない）
+pub fn run_get(context: &MemoContext, id: &str, key: &str) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(id)?;
+
+    if let Some(value) = memo.metadata_value(key) {
+        println!("{}", format_value(value)?);
+    }
+    Ok(())
+}
+
+/// `memo meta set <id> key=value...`: front matter だけを書き換えてインデックスに入れ直す
+pub fn run_set(context: &MemoContext, id: &str, assignments: &[String]) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let mut memo = repo.find_memo_by_id(id)?;
+    // 書き換えを始める前に全て確かめる
+    let assignments = assignments
+        .iter()
+        .map(|assignment| parse_assignment(assignment))
+        .collect::<MemoResult<Vec<_>>>()?;
+
+    for (key, value) in &assignments {
+        memo = memo.set_metadata_value(key, value.clone())?;
+    }
+    let memo = memo.set_updated_at(Local::now())?;
+    update_search_index(context, &memo.path)?;
+    webhook::notify(
+        &context.config,
+        WebhookEvent::Edit,
+        std::slice::from_ref(&memo),
+    );
+
+    for (key, value) in &assignments {
+        println!("Set {} = {} in {}", key, format_value(value)?, memo.id);
+    }
+    Ok(())
+}
+
+/// `key=value` を分ける。値は YAML として読む（`2` は数値、`[a, b]` はリスト）
+fn parse_assignment(assignment: &str) -> MemoResult<(String, Value)> {
+    let (key, value) = assignment.split_once('=').ok_or_else(|| {
+        MemoError::InvalidArgument(format!("expected key=value, got '{}'", assignment))
+    })?;
+    let key = key.trim();
+    if key.is_empty() || key.split('.').any(str::is_empty) {
+        return Err(MemoError::InvalidArgument(format!(
+            "invalid front matter key '{}'",
+            key
+        )));
+    }
+    let value = serde_yaml::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+    Ok((key.to_string(), value))
+}
+
+/// 文字列はそのまま、それ以外は YAML で表示する
+fn format_value(value: &Value) -> MemoResult<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        _ => Ok(serde_yaml::to_string(value)?.trim_end().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment("priority=2").unwrap(),
+            ("priority".to_string(), Value::from(2))
+        );
+        assert_eq!(
+            parse_assignment("author.name=a=b").unwrap(),
+            ("author.name".to_string(), Value::from("a=b"))
+        );
+        assert!(parse_assignment("priority").is_err());
+        assert!(parse_assignment("=2").is_err());
+        assert!(parse_assignment("a..b=2").is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::context::MemoContext;
-use crate::error::MemoResult;
+use crate::error::{IoResultExt, MemoResult};
 use std::fs;
 use std::path::Path;
 
@@ -31,15 +31,15 @@ fn migrate_dir(dir: &Path, base_dir: &Path, dry_run: bool, count: &mut usize) ->
     };
 
     for entry in entries {
-        let entry = entry?;
+        let entry = entry.with_path(dir)?;
         let path = entry.path();
 
         if path.is_dir() {
             // skip hidden directories other than .archive (handled separately)
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with('.') {
-                    continue;
-                }
+            if let Some(name) = path.file_name().and_then(|n| n.to_str())
+                && name.starts_with('.')
+            {
+                continue;
             }
             migrate_dir(&path, base_dir, dry_run, count)?;
             continue;
@@ -91,7 +91,7 @@ fn migrate_dir(dir: &Path, base_dir: &Path, dry_run: bool, count: &mut usize) ->
             let new_rel = new_path.strip_prefix(base_dir).unwrap_or(&new_path);
             eprintln!("  {} -> {}", old_rel.display(), new_rel.display());
         } else {
-            fs::rename(&path, &new_path)?;
+            fs::rename(&path, &new_path).with_path(&path)?;
         }
         *count += 1;
     }
@@ -114,6 +114,7 @@ mod tests {
         let context = MemoContext {
             memo_dir,
             editor: "echo".to_string(),
+            ..Default::default()
         };
 
         (temp_dir, context)
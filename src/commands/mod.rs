@@ -1,11 +1,42 @@
 pub mod add;
 pub mod archive;
+pub mod attach;
+pub mod cat;
+pub mod clock;
+pub mod complete;
+pub mod config;
+pub mod daemon;
+pub mod delete;
 pub mod dir;
+pub mod doctor;
+pub mod due;
 pub mod edit;
+pub mod export;
+pub mod grep;
+pub mod help;
+pub mod import;
 pub mod index;
+pub mod links;
 pub mod list;
+pub mod meta;
 pub mod migrate;
+pub mod mv;
+pub mod notebook;
+pub mod open;
+pub mod recent;
+pub mod restore;
 pub mod search;
+pub mod serve;
+pub mod shell;
 pub mod show;
+pub mod similar;
+pub mod stats;
+pub mod status;
+pub mod sync;
 pub mod tags;
 pub mod template;
+pub mod touch;
+pub mod tui;
+pub mod undo;
+pub mod view;
+pub mod watch;
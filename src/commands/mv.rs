@@ -0,0 +1,92 @@
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::git;
+use crate::links;
+use crate::memo::MemoDocument;
+use crate::memo_id::MemoId;
+use crate::ops::{Action, Batch};
+use crate::repository::MemoRepository;
+use crate::utils::datetime;
+
+/// `memo mv <id> <new-datetime>`: メモの日時（ID）を変え、新しい ID の場所に移す
+///
+/// `created_at` なら front matter の `created_at` も新しい日時にする
+pub fn run(context: &MemoContext, id: &str, to: &str, created_at: bool) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(id)?;
+    let new_id = parse_new_id(to)?;
+
+    let mut batch = Batch::new(context, Action::Mv);
+    let moved = batch.move_memo(&memo, &new_id)?;
+    batch.commit()?;
+    let moved = if created_at {
+        moved.set_created_at(new_id.get_datetime())?
+    } else {
+        moved
+    };
+    context
+        .search_manager()
+        .remove_memo(&MemoDocument::from_memo_file(&memo))?;
+    update_search_index(context, &moved.path)?;
+
+    println!("Moved {} -> {}", memo.id, moved.id);
+    warn_old_links(context, &memo.id, &new_id);
+    git::auto_commit(
+        &context.config,
+        &context.memo_dir,
+        "mv",
+        std::slice::from_ref(&moved),
+    );
+    Ok(())
+}
+
+/// 14 桁の ID か、`YYYY-MM-DD HH:MM[:SS]` などの日時（秒未満は切り捨て）
+fn parse_new_id(to: &str) -> MemoResult<MemoId> {
+    if let Ok(id) = MemoId::from_str(to.trim()) {
+        return Ok(id);
+    }
+    datetime::parse_datetime(to)
+        .map(MemoId::from_datetime)
+        .ok_or_else(|| {
+            MemoError::InvalidArgument(format!(
+                "invalid date/time '{}' (expected e.g. 20250130143022 or \"2025-01-30 14:30:22\")",
+                to
+            ))
+        })
+}
+
+/// 古い ID への `[[id]]` リンクは書き換えないので、残っていれば知らせる
+fn warn_old_links(context: &MemoContext, old: &MemoId, new: &MemoId) {
+    match links::backlinks(context, old) {
+        Ok(sources) if !sources.is_empty() => {
+            let sources: Vec<String> = sources.iter().map(MemoId::as_str).collect();
+            eprintln!(
+                "Warning: {} is linked from {}; update those links to [[{}]]",
+                old,
+                sources.join(", "),
+                new
+            );
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Warning: could not check links to {}: {}", old, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_new_id() {
+        assert_eq!(
+            parse_new_id("20250130143022").unwrap().as_str(),
+            "20250130143022"
+        );
+        assert_eq!(
+            parse_new_id("2025-01-30 14:30").unwrap().as_str(),
+            "20250130143000"
+        );
+        assert!(parse_new_id("yesterday").is_err());
+    }
+}
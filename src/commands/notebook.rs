@@ -0,0 +1,96 @@
+use crate::config::{self, DEFAULT_NOTEBOOK, Setting};
+use crate::context::MemoContext;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::utils::xdg;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `memo notebook list`: ノートブックとそのディレクトリ。使用中のものに `*` を付ける
+pub fn run_list(context: &MemoContext) -> MemoResult<()> {
+    let names: Vec<&str> = std::iter::once(DEFAULT_NOTEBOOK)
+        .chain(context.config.notebooks.keys().map(String::as_str))
+        .collect();
+    let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+    for name in names {
+        let dir = context.for_notebook(name)?.memo_dir;
+        let marker = if name == context.notebook { '*' } else { ' ' };
+        println!("{} {:<width$}  {}", marker, name, dir.display());
+    }
+    Ok(())
+}
+
+/// `memo notebook create <name> [--path DIR]`: `[notebooks]` に登録してディレクトリを作る
+///
+/// `--path` が無ければ、`default` のメモディレクトリの隣の `<dir>-<name>` を使う
+pub fn run_create(context: &MemoContext, name: &str, path: Option<PathBuf>) -> MemoResult<()> {
+    validate_name(name)?;
+    if name == DEFAULT_NOTEBOOK || context.config.notebooks.contains_key(name) {
+        return Err(MemoError::InvalidArgument(format!(
+            "notebook '{}' already exists",
+            name
+        )));
+    }
+
+    let dir = match path {
+        Some(path) => std::path::absolute(path)?,
+        None => sibling_dir(&context.for_notebook(DEFAULT_NOTEBOOK)?.memo_dir, name),
+    };
+    config::add_notebook(&xdg::get_config_path()?, name, &dir)?;
+    fs::create_dir_all(&dir).with_path(&dir)?;
+
+    println!("Created notebook {} at {}", name, dir.display());
+    Ok(())
+}
+
+/// `memo notebook use <name>`: 設定ファイルの `notebook` を書き換える
+pub fn run_use(name: &str) -> MemoResult<()> {
+    config::set_value(&xdg::get_config_path()?, Setting::find("notebook")?, name)?;
+    println!("Now using notebook {}", name);
+    Ok(())
+}
+
+/// 設定ファイルのキーと、ディレクトリ名に使える名前
+fn validate_name(name: &str) -> MemoResult<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(MemoError::InvalidArgument(format!(
+            "invalid notebook name '{}' (use letters, digits, '-' and '_')",
+            name
+        )));
+    }
+    Ok(())
+}
+
+fn sibling_dir(memo_dir: &Path, name: &str) -> PathBuf {
+    let base = memo_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "memo".to_string());
+    let parent = memo_dir.parent().unwrap_or(memo_dir);
+    parent.join(format!("{}-{}", base, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("work").is_ok());
+        assert!(validate_name("side_project-2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("a.b").is_err());
+        assert!(validate_name("../x").is_err());
+    }
+
+    #[test]
+    fn test_sibling_dir() {
+        assert_eq!(
+            sibling_dir(Path::new("/home/me/.local/share/memo"), "work"),
+            PathBuf::from("/home/me/.local/share/memo-work")
+        );
+    }
+}
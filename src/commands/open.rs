@@ -0,0 +1,53 @@
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::repository::MemoRepository;
+use std::path::Path;
+use std::process::Command;
+
+/// OS の既定アプリでファイルを開くコマンド
+#[cfg(target_os = "macos")]
+const OPENER: &str = "open";
+#[cfg(not(target_os = "macos"))]
+const OPENER: &str = "xdg-open";
+
+/// `memo open` のオプション
+#[derive(Debug, Clone, Default)]
+pub struct OpenOptions {
+    /// Open with the system default app instead of printing the path
+    pub app: bool,
+    /// Use the directory that contains the memo
+    pub dir: bool,
+}
+
+/// `memo open <id>`: メモのファイル（`--dir` なら置き場所）の絶対パスを出力する
+pub fn run(context: &MemoContext, id: &str, options: &OpenOptions) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(id)?;
+
+    let path = std::path::absolute(&memo.path)?;
+    let target = match path.parent() {
+        Some(parent) if options.dir => parent.to_path_buf(),
+        _ => path,
+    };
+
+    if options.app {
+        launch(&target)
+    } else {
+        println!("{}", target.display());
+        Ok(())
+    }
+}
+
+fn launch(path: &Path) -> MemoResult<()> {
+    let status = Command::new(OPENER)
+        .arg(path)
+        .status()
+        .map_err(|e| MemoError::InvalidArgument(format!("failed to launch '{}': {}", OPENER, e)))?;
+    if !status.success() {
+        return Err(MemoError::InvalidArgument(format!(
+            "'{}' exited with non-zero status",
+            OPENER
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::commands::add::{self, AddOptions};
+use crate::commands::show::{self, ShowOptions};
+use crate::context::MemoContext;
+use crate::display::format::OutputFormat;
+use crate::error::{MemoError, MemoResult};
+use crate::repository::MemoRepository;
+use chrono::{Days, Local, NaiveDate};
+
+/// `memo today [--add]`: 今日作ったメモを一覧、`--add` なら新しく作る
+pub fn run_today(context: &MemoContext, add: bool, format: Option<OutputFormat>) -> MemoResult<()> {
+    if add {
+        return add::run(context, &AddOptions::default());
+    }
+    run_date(context, Local::now().date_naive(), format)
+}
+
+/// `memo yesterday`: 昨日作ったメモを一覧
+pub fn run_yesterday(context: &MemoContext, format: Option<OutputFormat>) -> MemoResult<()> {
+    let yesterday = Local::now()
+        .date_naive()
+        .checked_sub_days(Days::new(1))
+        .ok_or_else(|| MemoError::InvalidArgument("no date before today".to_string()))?;
+    run_date(context, yesterday, format)
+}
+
+/// `memo last`: 最後に更新したメモを表示
+pub fn run_last(context: &MemoContext) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo
+        .latest_modified_memo()?
+        .ok_or_else(|| MemoError::InvalidArgument("no memos yet".to_string()))?;
+    show::run(context, &memo.id.as_str(), &ShowOptions::default())
+}
+
+fn run_date(
+    context: &MemoContext,
+    date: NaiveDate,
+    format: Option<OutputFormat>,
+) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memos = repo.list_memos_for_date(date)?;
+    format
+        .unwrap_or_default()
+        .formatter()
+        .print_list(&memos, &format!("Memos on {}", date.format("%Y-%m-%d")))
+}
@@ -0,0 +1,86 @@
+use crate::commands::archive::print_planned_moves;
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::links;
+use crate::memo::MemoFile;
+use crate::ops::{Action, Batch};
+use crate::repository::MemoRepository;
+
+/// アーカイブしたメモを元の場所に戻し、インデックスに入れ直す
+///
+/// `dry_run` なら戻すファイルを表示するだけ
+pub fn run(context: &MemoContext, ids: &[String], dry_run: bool) -> MemoResult<()> {
+    if ids.is_empty() {
+        return Err(MemoError::ArchiveError(
+            "At least one memo ID is required".to_string(),
+        ));
+    }
+
+    let repo = MemoRepository::new(context.clone());
+    if dry_run {
+        let memos = ids
+            .iter()
+            .map(|id| repo.find_archived_memo(id))
+            .collect::<MemoResult<Vec<_>>>()?;
+        print_planned_moves(&repo.plan_restore(&memos)?);
+        warn_missing_links(&repo, &memos);
+        eprintln!(
+            "{} memo(s) would be restored. Run without --dry-run to apply.",
+            memos.len()
+        );
+        return Ok(());
+    }
+
+    let mut batch = Batch::new(context, Action::Restore);
+    let mut restored = Vec::new();
+    let mut errors = Vec::new();
+
+    for id in ids {
+        let result = repo
+            .find_archived_memo(id)
+            .and_then(|memo| batch.restore(&memo))
+            .and_then(|memo| {
+                update_search_index(context, &memo.path)?;
+                Ok(memo)
+            });
+        match result {
+            Ok(memo) => restored.push(memo),
+            Err(e) => errors.push(format!("Error restoring '{}': {}", id, e)),
+        }
+    }
+
+    batch.commit()?;
+
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+    if restored.is_empty() {
+        return Err(MemoError::ArchiveError(
+            "Failed to restore any memos".to_string(),
+        ));
+    }
+
+    println!("Restored {} memo(s)", restored.len());
+    warn_missing_links(&repo, &restored);
+    Ok(())
+}
+
+/// 戻したメモのリンク先がメモディレクトリに無ければ警告する（まだアーカイブにあるなど）
+fn warn_missing_links(repo: &MemoRepository, memos: &[MemoFile]) {
+    for memo in memos {
+        let missing: Vec<String> = links::parse_links(&memo.content)
+            .iter()
+            .filter(|target| !memos.iter().any(|other| &other.id == *target))
+            .filter(|target| repo.find_memo_by_id(&target.as_str()).is_err())
+            .map(|target| target.as_str())
+            .collect();
+        if !missing.is_empty() {
+            eprintln!(
+                "Warning: {} links to {}, which is not in the memo directory",
+                memo.id,
+                missing.join(", ")
+            );
+        }
+    }
+}
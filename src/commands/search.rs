@@ -1,28 +1,706 @@
+use crate::commands::list::page_offset;
+use crate::config::{Config, DEFAULT_NOTEBOOK};
 use crate::context::MemoContext;
 use crate::display::MemoDisplayFormatter;
+use crate::display::color::{self, Style};
+use crate::display::format::OutputFormat;
+use crate::display::theme::{self, Element};
 use crate::error::MemoError;
 use crate::memo::MemoFile;
-use crate::search::SearchManager;
+use crate::projection::Projection;
+use crate::render::{MemoTemplateData, OutputTemplate};
+use crate::repository::MemoRepository;
+use crate::search::{self, NotebookResult, SearchManager, SearchParams, SearchResult, SearchSort};
+use crate::views;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
-pub fn run_search(ctx: &MemoContext, query: &str) -> Result<(), MemoError> {
-    let data_dir = ctx.memo_dir.clone();
-    let index_dir = ctx.index_dir();
-    let search_manager = SearchManager::new(data_dir, index_dir);
+/// `memo search --group-by`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GroupBy {
+    /// Creation day, newest first
+    Day,
+    /// Creation month, newest first
+    Month,
+    /// Each tag (a memo appears under every tag it has), most hits first
+    Tag,
+}
+
+const NO_TAG_GROUP: &str = "(no tag)";
+/// `--include-archived` でアーカイブの索引を区別する名前
+const ARCHIVE_INDEX: &str = ".archive";
+
+/// `memo search` の表示オプション
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Render with a Handlebars template file
+    pub template: Option<PathBuf>,
+    /// Only memos with `priority:` >= this value
+    pub min_priority: Option<i64>,
+    /// Only memos tagged with all of these (`--tag`, repeatable)
+    pub tags: Vec<String>,
+    /// Result ordering
+    pub sort: SearchSort,
+    /// Boost newer memos with this half-life in days (`--boost-recent`)
+    pub boost_recent: Option<f64>,
+    /// Show at most this many results (default 100)
+    pub limit: Option<usize>,
+    /// Skip this many results
+    pub offset: usize,
+    /// Show the Nth page of `limit` results (1-based); overrides `offset`
+    pub page: Option<usize>,
+    /// Search every notebook in `[notebooks]` as well as the memo directory
+    pub all_notebooks: bool,
+    /// Also search archived memos (their own index under `.archive`)
+    pub include_archived: bool,
+    /// Show results under day, month or tag headings
+    pub group_by: Option<GroupBy>,
+    /// Print only the number of matching memos
+    pub count: bool,
+    /// One JSON object per result (JSON Lines)
+    pub json: bool,
+    /// Fields to keep in JSON output (`--select`)
+    pub select: Option<String>,
+    /// Output format (`--format`); `json` takes precedence
+    pub format: Option<OutputFormat>,
+}
+
+/// `memo search --json` の1行
+#[derive(Debug, Serialize)]
+pub struct SearchResultItem {
+    pub id: String,
+    pub path: String,
+    pub score: f32,
+    pub preview: String,
+    pub metadata: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notebook: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+}
+
+impl SearchOptions {
+    /// `--json` は `--format json` の短縮形
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else {
+            self.format.unwrap_or_default()
+        }
+    }
+
+    pub fn params(&self, config: &Config) -> SearchParams {
+        SearchParams {
+            min_priority: self.min_priority,
+            tags: self.tags.clone(),
+            sort: self.sort,
+            boost: config.search.boost,
+            recency_half_life: self.boost_recent,
+            limit: self.limit,
+            offset: page_offset(
+                self.offset,
+                self.page,
+                self.limit.unwrap_or(search::MAX_RESULTS),
+            ),
+        }
+    }
+}
+
+/// 引数のクエリか `--query-file` の中身（`-` は標準入力）に、`--view` のクエリを加えたもの
+///
+/// ファイルの改行は空白として扱う
+pub fn resolve_query(
+    config: &Config,
+    query: Option<String>,
+    query_file: Option<&Path>,
+    view: Option<&str>,
+) -> Result<String, MemoError> {
+    let query = read_query(query, query_file)?;
+    let Some(name) = view else {
+        return Ok(query);
+    };
+    Ok(views::combine(views::query(config, name)?, &query))
+}
+
+fn read_query(query: Option<String>, query_file: Option<&Path>) -> Result<String, MemoError> {
+    let Some(path) = query_file else {
+        return Ok(query.unwrap_or_default());
+    };
+    let content = if path == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        std::fs::read_to_string(path).map_err(|e| {
+            MemoError::InvalidArgument(format!("cannot read query file {}: {}", path.display(), e))
+        })?
+    };
+    let query = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if query.is_empty() {
+        return Err(MemoError::InvalidArgument(
+            "the query file is empty".to_string(),
+        ));
+    }
+    Ok(query)
+}
+
+/// `memo search --help-syntax`
+pub const SYNTAX_HELP: &str = "\
+Query syntax for `memo search`
+
+Words
+  deploy release          memos with deploy or release (memos with both rank higher)
+  +deploy +release        both words are required
+  deploy -draft           leave out memos with draft
+  \"release notes\"         the exact phrase
+  (a OR b) AND c          boolean operators and grouping
+  deploy*  ver?ion        wildcards
+  /inc-[0-9]+/            regular expression on the words of titles and bodies
+
+Fields (words without a field search title, content, tags and metadata)
+  title:release           the title
+  content:release         the body
+  tags:@todo              the tags
+  metadata.status:done    a front matter value (nested keys: metadata.a.b:x)
+  id:20250130143022       the memo ID
+  links:20250130143022    memos linking to that memo with [[id]]
+  priority:3              priority, also priority:>=3 or priority:[1 TO 3]
+  created_at:2025-01      created in a year, month or day (updated_at: for the last edit)
+  updated_at:>=2025-01-15 ranges and comparisons: [2025-01-01 TO 2025-01-31], {2025 TO *], <2025
+
+Shortcuts
+  tag:@todo               memos tagged @todo or a tag below it (@todo/work)
+  prio:>=3                priority:>=3
+  in:2025-01              created_at:2025-01
+
+Facets
+  tags.facet:\"/@proj/sub\"  a tag path; matches the tag and every tag below it
+
+Special characters
+  + - && || ! ( ) { } [ ] ^ \" ~ * ? : \\ /
+  quote the text (\"...\") or escape with \\ (e.g. C\\+\\+) to search for them literally
+";
+
+/// `memo search --help-syntax`: 検索クエリの書き方を表示
+pub fn print_syntax_help() {
+    print!("{}", SYNTAX_HELP);
+}
+
+/// `memo search` で使えるフィールド（`metadata.<key>` は別に見る）
+fn query_fields() -> impl Iterator<Item = &'static str> {
+    search::index::query_fields().chain(search::query::ALIASES)
+}
+
+/// tantivy に渡す前に、よくある誤り（閉じていない引用符・無いフィールド）を見つける
+///
+/// エラーは問題の位置と直し方を示す。それ以外の構文の誤りは tantivy のエラーにまかせる
+pub fn validate_query(query: &str) -> Result<(), MemoError> {
+    let mut open_quote = None;
+    let mut at_token_start = true;
+    let mut pos = 0;
+    while let Some(c) = query[pos..].chars().next() {
+        let rest = &query[pos..];
+        if open_quote.is_none() && at_token_start {
+            if let Some(field) = field_prefix(rest)
+                && !is_query_field(field)
+            {
+                return Err(unknown_field(query, pos, field));
+            }
+            // 正規表現の中の `"` や `:` は見ない
+            if c == '/'
+                && let Some(end) = rest[1..].find('/')
+            {
+                pos += end + 2;
+                at_token_start = false;
+                continue;
+            }
+        }
+
+        match c {
+            '\\' => {
+                pos += c.len_utf8();
+                pos += query[pos..].chars().next().map_or(0, char::len_utf8);
+                at_token_start = false;
+                continue;
+            }
+            // 開いていれば閉じる
+            '"' => open_quote = open_quote.xor(Some(pos)),
+            _ => {}
+        }
+        // 語頭の +/- の直後も語の先頭
+        at_token_start = open_quote.is_none()
+            && (c.is_whitespace() || c == '(' || (at_token_start && matches!(c, '+' | '-')));
+        pos += c.len_utf8();
+    }
+
+    match open_quote {
+        Some(position) => Err(search::query::query_error(
+            query,
+            "unclosed quote".to_string(),
+            Some(position),
+            "hint: close the quote, or escape it as \\\" to search for a literal quote",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// `rest` が `name:` で始まっていればその名前
+fn field_prefix(rest: &str) -> Option<&str> {
+    let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.')))?;
+    let name = &rest[..end];
+    (rest[end..].starts_with(':')
+        && name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_'))
+    .then_some(name)
+}
+
+fn is_query_field(name: &str) -> bool {
+    query_fields().any(|known| known == name) || name.starts_with("metadata.")
+}
+
+fn unknown_field(query: &str, position: usize, field: &str) -> MemoError {
+    // 短い名前はどれとも近くなるので、名前の半分（最大 2 文字）までの違いだけ候補にする
+    let max_distance = (field.len() / 2).min(2);
+    let hint = match query_fields()
+        .map(|known| (edit_distance(field, known), known))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+    {
+        Some((_, known)) => format!("hint: did you mean '{}:'?", known),
+        None => "hint: quote the text to search for a literal ':'".to_string(),
+    };
+    search::query::query_error(
+        query,
+        format!("unknown field '{}'", field),
+        Some(position),
+        &hint,
+    )
+}
+
+/// レーベンシュタイン距離（フィールド名の候補を出すだけなので短い文字列用）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substituted = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substituted.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+pub fn run_search(
+    ctx: &MemoContext,
+    query: &str,
+    options: &SearchOptions,
+) -> Result<(), MemoError> {
+    validate_query(query)?;
+    let config = &ctx.config;
+    if options.all_notebooks {
+        if options.count {
+            println!("{}", count_all_notebooks(ctx, config, query, options)?);
+            return Ok(());
+        }
+        let results = search_all_notebooks(ctx, config, query, options)?;
+        return display_notebook_results(query, &results, options);
+    }
+    if options.include_archived {
+        let indexes = with_archive_index(ctx)?;
+        let params = options.params(config);
+        if options.count {
+            let count = indexes
+                .iter()
+                .map(|(_, manager)| manager.count(query, &params))
+                .sum::<Result<usize, MemoError>>()?;
+            println!("{}", count);
+            return Ok(());
+        }
+        let results = search::search_notebooks(&indexes, query, &params)?;
+        return display_archive_results(query, &results, options);
+    }
+
+    let search_manager = ctx.search_manager();
+
+    if options.count {
+        println!("{}", search_manager.count(query, &options.params(config))?);
+        return Ok(());
+    }
+    let results =
+        search_manager.search_with_snippets(query, &options.params(config), snippet_chars())?;
+    display_results(query, &results, options)
+}
+
+/// 抜粋の最大文字数（`preview: ...` と同じく1行に収まる長さ）
+pub fn snippet_chars() -> usize {
+    MemoDisplayFormatter::preview_chars()
+}
+
+/// 一致箇所を強調した抜粋。本文に一致箇所が無ければ `None`
+fn snippet_preview(result: &SearchResult) -> Option<String> {
+    let snippet = result.snippet.as_ref().filter(|s| !s.is_empty())?;
+    Some(snippet.render(|text| theme::paint(text, Element::Match)))
+}
+
+/// メモディレクトリ（`default`）と `[notebooks]` の各ディレクトリを横断して検索
+fn search_all_notebooks(
+    ctx: &MemoContext,
+    config: &Config,
+    query: &str,
+    options: &SearchOptions,
+) -> Result<Vec<NotebookResult>, MemoError> {
+    search::search_notebooks(&notebooks(ctx, config)?, query, &options.params(config))
+}
+
+/// 全ノートブックのヒット件数の合計
+fn count_all_notebooks(
+    ctx: &MemoContext,
+    config: &Config,
+    query: &str,
+    options: &SearchOptions,
+) -> Result<usize, MemoError> {
+    let params = options.params(config);
+    notebooks(ctx, config)?
+        .iter()
+        .map(|(_, manager)| manager.count(query, &params))
+        .sum()
+}
+
+/// メモディレクトリの索引とアーカイブの索引
+///
+/// アーカイブの索引は `memo index` では作らないので、検索の前に変わった分だけ索引し直す
+fn with_archive_index(ctx: &MemoContext) -> Result<Vec<(String, SearchManager)>, MemoError> {
+    let archive = ctx.archive_search_manager();
+    let paths = MemoRepository::new(ctx.clone()).list_archived_memo_paths()?;
+    if !paths.is_empty() || archive.get_current_index()?.is_some() {
+        archive.update_index(&paths)?;
+    }
+    Ok(vec![
+        (DEFAULT_NOTEBOOK.to_string(), ctx.search_manager()),
+        (ARCHIVE_INDEX.to_string(), archive),
+    ])
+}
+
+fn notebooks(
+    ctx: &MemoContext,
+    config: &Config,
+) -> Result<Vec<(String, SearchManager)>, MemoError> {
+    std::iter::once(DEFAULT_NOTEBOOK)
+        .chain(config.notebooks.keys().map(String::as_str))
+        .map(|name| {
+            let notebook = ctx.for_notebook(name)?;
+            Ok((name.to_string(), notebook.search_manager()))
+        })
+        .collect()
+}
+
+/// 検索結果を表示（`memo shell` は開いたままのインデックスで検索して表示だけ使う）
+pub fn display_results(
+    query: &str,
+    results: &[SearchResult],
+    options: &SearchOptions,
+) -> Result<(), MemoError> {
+    let hits: Vec<Hit> = results
+        .iter()
+        .map(|result| Hit {
+            notebook: None,
+            archived: false,
+            result,
+        })
+        .collect();
+    display_hits(query, &hits, options)
+}
+
+/// ノートブック名付きで検索結果を表示
+fn display_notebook_results(
+    query: &str,
+    results: &[NotebookResult],
+    options: &SearchOptions,
+) -> Result<(), MemoError> {
+    let hits: Vec<Hit> = results
+        .iter()
+        .map(|r| Hit {
+            notebook: Some(r.notebook.as_str()),
+            archived: false,
+            result: &r.result,
+        })
+        .collect();
+    display_hits(query, &hits, options)
+}
+
+/// アーカイブしたメモに印を付けて検索結果を表示
+fn display_archive_results(
+    query: &str,
+    results: &[NotebookResult],
+    options: &SearchOptions,
+) -> Result<(), MemoError> {
+    let hits: Vec<Hit> = results
+        .iter()
+        .map(|r| Hit {
+            notebook: None,
+            archived: r.notebook == ARCHIVE_INDEX,
+            result: &r.result,
+        })
+        .collect();
+    display_hits(query, &hits, options)
+}
+
+/// 表示する検索結果 1 件
+struct Hit<'a> {
+    /// `--all-notebooks` の時のノートブック名
+    notebook: Option<&'a str>,
+    archived: bool,
+    result: &'a SearchResult,
+}
+
+fn display_hits(query: &str, hits: &[Hit], options: &SearchOptions) -> Result<(), MemoError> {
+    let template = options
+        .template
+        .as_deref()
+        .map(OutputTemplate::from_file)
+        .transpose()?;
+    let projection = options
+        .select
+        .as_deref()
+        .map(Projection::parse)
+        .transpose()?;
 
-    let results = search_manager.search(query)?;
-    if results.is_empty() {
+    let format = options.output_format();
+    // list --json と同じく、結果が無ければ何も出さない
+    if hits.is_empty() && format == OutputFormat::Json {
+        return Ok(());
+    }
+    if hits.is_empty() && template.is_none() && !format.is_machine_readable() {
         println!("No results found for query: {}", query);
         return Ok(());
     }
 
     // 検索結果をMemoFileに変換
-    let memos: Vec<MemoFile> = results
+    let memos: Vec<MemoFile> = hits
         .iter()
-        .map(|result| MemoFile::from_path(&result.memo.path))
+        .map(|hit| MemoFile::from_path(&hit.result.memo.path))
         .collect::<Result<Vec<_>, _>>()?;
 
-    let title = format!("Found {} results for query: {}", results.len(), query);
-    MemoDisplayFormatter::display_memo_list(&memos, &title);
+    if let Some(template) = &template {
+        let memos: Vec<MemoTemplateData> = memos
+            .iter()
+            .zip(hits)
+            .map(|(memo, hit)| {
+                let data = MemoTemplateData::from_memo_file(memo).with_score(hit.result.score);
+                match hit.notebook {
+                    Some(notebook) => data.with_notebook(notebook),
+                    None => data,
+                }
+            })
+            .collect();
+        let data = serde_json::json!({ "query": query, "memos": memos });
+        print!("{}", template.render(&data)?);
+        return Ok(());
+    }
 
+    let items = || {
+        memos.iter().zip(hits).map(|(memo, hit)| SearchResultItem {
+            id: memo.id.as_str(),
+            path: memo.path.to_string_lossy().to_string(),
+            score: hit.result.score,
+            preview: memo.preview(100),
+            metadata: memo.metadata.clone(),
+            notebook: hit.notebook.map(str::to_string),
+            archived: hit.archived,
+        })
+    };
+    let title = format!("Found {} results for query: {}", hits.len(), query);
+    match format {
+        OutputFormat::Json => {
+            for item in items() {
+                let json = match &projection {
+                    Some(projection) => serde_json::to_value(&item)
+                        .map(|value| projection.apply(&value))
+                        .and_then(|value| serde_json::to_string(&value)),
+                    None => serde_json::to_string(&item),
+                };
+                if let Ok(json) = json {
+                    println!("{}", json);
+                }
+            }
+            return Ok(());
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yaml::to_string(&items().collect::<Vec<_>>())?);
+            return Ok(());
+        }
+        OutputFormat::Compact | OutputFormat::Table => {
+            return format.formatter().print_list(&memos, &title);
+        }
+        OutputFormat::Plain => {}
+    }
+
+    let labeled = hits.iter().any(|hit| hit.notebook.is_some());
+    let display = |i: usize| {
+        if labeled {
+            println!("notebook: {}", hits[i].notebook.unwrap_or(DEFAULT_NOTEBOOK));
+        }
+        if hits[i].archived {
+            println!("{}", color::paint("(archived)", Style::Dim));
+        }
+        match snippet_preview(hits[i].result) {
+            Some(snippet) => MemoDisplayFormatter::display_memo_with_preview(&memos[i], &snippet),
+            None => MemoDisplayFormatter::display_memo(&memos[i]),
+        }
+    };
+    println!("{}:", title);
+    println!();
+    match options.group_by {
+        Some(group_by) => {
+            for (group, indices) in group_memos(&memos, group_by) {
+                println!(
+                    "{}",
+                    color::paint(&format!("{} ({})", group, indices.len()), Style::Bold)
+                );
+                indices.into_iter().for_each(display);
+                println!();
+            }
+        }
+        None => (0..memos.len()).for_each(display),
+    }
     Ok(())
 }
+
+/// 見出しごとのメモの位置（各グループ内は元の順位のまま）
+pub fn group_memos(memos: &[MemoFile], group_by: GroupBy) -> Vec<(String, Vec<usize>)> {
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    let mut push = |key: &str, i: usize| match groups.iter_mut().find(|(k, _)| k == key) {
+        Some((_, indices)) => indices.push(i),
+        None => groups.push((key.to_string(), vec![i])),
+    };
+
+    for (i, memo) in memos.iter().enumerate() {
+        let created_at = memo.id.get_datetime();
+        match group_by {
+            GroupBy::Day => push(&created_at.format("%Y-%m-%d").to_string(), i),
+            GroupBy::Month => push(&created_at.format("%Y-%m").to_string(), i),
+            GroupBy::Tag => {
+                let tags = memo.tags();
+                if tags.is_empty() {
+                    push(NO_TAG_GROUP, i);
+                }
+                for tag in tags {
+                    push(tag, i);
+                }
+            }
+        }
+    }
+
+    match group_by {
+        GroupBy::Day | GroupBy::Month => groups.sort_by(|a, b| b.0.cmp(&a.0)),
+        // タグなしは最後
+        GroupBy::Tag => groups.sort_by(|a, b| {
+            (a.0 == NO_TAG_GROUP)
+                .cmp(&(b.0 == NO_TAG_GROUP))
+                .then_with(|| b.1.len().cmp(&a.1.len()))
+                .then_with(|| a.0.cmp(&b.0))
+        }),
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::MemoRepository;
+    use tempfile::TempDir;
+
+    fn create_memos() -> (TempDir, Vec<MemoFile>) {
+        let temp_dir = TempDir::new().unwrap();
+        let context = MemoContext {
+            memo_dir: temp_dir.path().to_path_buf(),
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+        let repo = MemoRepository::new(context);
+        let memos = [
+            (
+                "2025-01/30/20250130143022.md",
+                "tags: [\"@meeting\", \"@1on1\"]",
+            ),
+            ("2024-12/31/20241231090000.md", "tags: [\"@meeting\"]"),
+            ("2025-01/30/20250130090000.md", "title: untagged"),
+            (
+                "2025-01/02/20250102100000.md",
+                "tags: [\"@1on1\", \"@meeting\"]",
+            ),
+        ]
+        .iter()
+        .map(|(path, front_matter)| {
+            repo.create_memo(path, format!("---\n{}\n---\nbody", front_matter))
+                .unwrap()
+        })
+        .collect();
+        (temp_dir, memos)
+    }
+
+    #[test]
+    fn test_group_memos_by_date() {
+        let (_temp_dir, memos) = create_memos();
+        assert_eq!(
+            group_memos(&memos, GroupBy::Day),
+            vec![
+                ("2025-01-30".to_string(), vec![0, 2]),
+                ("2025-01-02".to_string(), vec![3]),
+                ("2024-12-31".to_string(), vec![1]),
+            ]
+        );
+        assert_eq!(
+            group_memos(&memos, GroupBy::Month),
+            vec![
+                ("2025-01".to_string(), vec![0, 2, 3]),
+                ("2024-12".to_string(), vec![1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_memos_by_tag() {
+        let (_temp_dir, memos) = create_memos();
+        assert_eq!(
+            group_memos(&memos, GroupBy::Tag),
+            vec![
+                ("@meeting".to_string(), vec![0, 1, 3]),
+                ("@1on1".to_string(), vec![0, 3]),
+                ("(no tag)".to_string(), vec![2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_query() {
+        for query in [
+            "会議 +tag:@todo -draft",
+            "metadata.status:done \"a: b\" title:release",
+            "C\\+\\+ \\\" /inc-[0-9]+:\"/ 10:30",
+            "prio:>=3 in:2025-01 links:20250130143022 tags.facet:/@todo",
+        ] {
+            assert!(validate_query(query).is_ok(), "{}", query);
+        }
+
+        let message = |query: &str| match validate_query(query) {
+            Err(MemoError::InvalidQuery(message)) => message,
+            other => panic!("unexpected result: {:?}", other),
+        };
+        assert!(
+            message("会議 \"release notes")
+                .starts_with("unclosed quote\n  会議 \"release notes\n       ^\n")
+        );
+        let unknown = message("deploy tittle:v2");
+        assert!(unknown.starts_with("unknown field 'tittle'\n  deploy tittle:v2\n         ^\n"));
+        assert!(unknown.contains("hint: did you mean 'title:'?"));
+        assert!(message("http://example.com").contains("hint: quote the text"));
+        // 保存するだけのフィールドは検索できない
+        assert!(message("path:2025-01").starts_with("unknown field 'path'"));
+    }
+}
@@ -0,0 +1,462 @@
+//! `memo serve`: a small JSON REST API on a local port for browser extensions and editors.
+//!
+//! ```text
+//! GET  /memos?tag=@todo&limit=20     新しい順の一覧
+//! GET  /memos/20250130143022         1件（ID の一部でもよい）
+//! GET  /search?q=meeting&limit=10    memo search と同じクエリ構文
+//! POST /memos                        {"content": "# New memo\n"}
+//! POST /memos/20250130143022/archive   切れるリンクの警告は "warnings"
+//! ```
+//!
+//! Memos are returned in the same shape as `memo daemon`; errors are `{"error": "..."}`.
+//! The server only listens on 127.0.0.1 and sends no CORS headers, so web pages cannot
+//! read memos through it (browser extensions with host permissions can).
+//! Requests must use `Host: 127.0.0.1:<port>` or `localhost:<port>` and must not carry an
+//! `Origin` header, and POST bodies must be `Content-Type: application/json`; this keeps
+//! web pages from reaching the API through DNS rebinding or plain form posts.
+
+use crate::commands::archive;
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::memo::MemoFile;
+use crate::ops;
+use crate::render::MemoTemplateData;
+use crate::repository::MemoRepository;
+use crate::search::{CachedSearchIndex, SearchParams};
+use crate::tag_index;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+pub const DEFAULT_PORT: u16 = 8765;
+
+/// `POST /memos` の本文
+#[derive(Debug, Deserialize)]
+struct CreateRequest {
+    content: String,
+}
+
+/// リクエストを処理する状態（検索インデックスは開いたまま）
+pub struct Server {
+    context: MemoContext,
+    port: u16,
+    search: Mutex<CachedSearchIndex>,
+}
+
+impl Server {
+    pub fn new(context: MemoContext, port: u16) -> Self {
+        let manager = context.search_manager();
+        Self {
+            context,
+            port,
+            search: Mutex::new(CachedSearchIndex::new(manager)),
+        }
+    }
+
+    /// Route one request; returns the status code and the JSON body
+    pub fn handle(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &str,
+    ) -> (u16, Value) {
+        if let Err(rejected) = self.check_headers(method, headers) {
+            return rejected;
+        }
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let query = parse_query(query);
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let result = match (method, segments.as_slice()) {
+            ("GET", ["memos"]) => self.list(&query),
+            ("GET", ["memos", id]) => self.repo().find_memo_by_id(id).map(|memo| memo_json(&memo)),
+            ("GET", ["search"]) => self.search(&query),
+            ("POST", ["memos"]) => return self.create(body),
+            ("POST", ["memos", id, "archive"]) => self.archive(id),
+            (_, ["memos"] | ["memos", _] | ["search"] | ["memos", _, "archive"]) => {
+                return error(405, "method not allowed");
+            }
+            _ => return error(404, "not found"),
+        };
+        match result {
+            Ok(value) => (200, value),
+            Err(e) => error(status_for(&e), &e.to_string()),
+        }
+    }
+
+    /// ブラウザ上のページから届いたリクエストを断る
+    fn check_headers(&self, method: &str, headers: &[(&str, &str)]) -> Result<(), (u16, Value)> {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(field, _)| field.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim())
+        };
+
+        let allowed_hosts = [
+            format!("127.0.0.1:{}", self.port),
+            format!("localhost:{}", self.port),
+        ];
+        match header("Host") {
+            Some(host)
+                if allowed_hosts
+                    .iter()
+                    .any(|allowed| host.eq_ignore_ascii_case(allowed)) => {}
+            _ => return Err(error(403, "invalid Host header")),
+        }
+        if header("Origin").is_some() {
+            return Err(error(403, "cross-origin requests are not allowed"));
+        }
+        if method == "POST" {
+            let media_type = header("Content-Type")
+                .map(|value| value.split(';').next().unwrap_or_default().trim());
+            if !media_type
+                .is_some_and(|media_type| media_type.eq_ignore_ascii_case("application/json"))
+            {
+                return Err(error(415, "Content-Type must be application/json"));
+            }
+        }
+        Ok(())
+    }
+
+    fn repo(&self) -> MemoRepository {
+        MemoRepository::new(self.context.clone())
+    }
+
+    /// 新しい順
+    fn list(&self, query: &HashMap<String, String>) -> MemoResult<Value> {
+        let limit = parse_limit(query)?;
+        let mut memos = self.repo().list_all_memos()?;
+        if let Some(tag) = query.get("tag") {
+            memos.retain(|memo| tag_index::memo_tags(memo).contains(tag));
+        }
+        Ok(Value::Array(
+            memos.iter().take(limit).map(memo_json).collect(),
+        ))
+    }
+
+    fn search(&self, query: &HashMap<String, String>) -> MemoResult<Value> {
+        let q = query
+            .get("q")
+            .ok_or_else(|| MemoError::InvalidArgument("missing query parameter 'q'".to_string()))?;
+        let limit = parse_limit(query)?;
+        let results = self
+            .search
+            .lock()
+            .map_err(|_| MemoError::Search("search index is poisoned".to_string()))?
+            .search(
+                q,
+                &SearchParams {
+                    boost: self.context.config.search.boost,
+                    ..Default::default()
+                },
+            )?;
+        let memos: Vec<Value> = results
+            .iter()
+            .take(limit)
+            .filter_map(|result| {
+                let memo = MemoFile::from_path(&result.memo.path).ok()?;
+                let data = MemoTemplateData::from_memo_file(&memo).with_score(result.score);
+                serde_json::to_value(data).ok()
+            })
+            .collect();
+        Ok(Value::Array(memos))
+    }
+
+    fn create(&self, body: &str) -> (u16, Value) {
+        let request: CreateRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(e) => return error(400, &format!("invalid request: {}", e)),
+        };
+        match ops::create_memo(&self.context, false, |_| request.content, |_| Ok(())) {
+            Ok(memo) => (201, json!({ "id": memo.id.as_str(), "path": memo.path })),
+            Err(e) => error(status_for(&e), &e.to_string()),
+        }
+    }
+
+    fn archive(&self, id: &str) -> MemoResult<Value> {
+        let memo = self.repo().find_memo_by_id(id)?;
+        let mut report = archive::archive_memos(&self.context, std::slice::from_ref(&memo))?;
+        if let Some(failure) = report.failures.pop() {
+            return Err(failure.error);
+        }
+        let archived = &report.archived[0];
+        Ok(json!({
+            "id": archived.id.as_str(),
+            "path": archived.path,
+            "warnings": report.warnings,
+        }))
+    }
+}
+
+fn memo_json(memo: &MemoFile) -> Value {
+    serde_json::to_value(MemoTemplateData::from_memo_file(memo)).unwrap_or_default()
+}
+
+fn error(status: u16, message: &str) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
+
+fn status_for(error: &MemoError) -> u16 {
+    match error {
+        MemoError::MemoNotFound(_) => 404,
+        MemoError::InvalidId(_)
+        | MemoError::AmbiguousId(_)
+        | MemoError::InvalidArgument(_)
+        | MemoError::InvalidQuery(_) => 400,
+        _ => 500,
+    }
+}
+
+fn parse_limit(query: &HashMap<String, String>) -> MemoResult<usize> {
+    match query.get("limit") {
+        Some(limit) => limit.parse().map_err(|_| {
+            MemoError::InvalidArgument(format!("limit must be a number, got '{}'", limit))
+        }),
+        None => Ok(usize::MAX),
+    }
+}
+
+/// `a=1&b=x%20y` を読む（`+` は空白）
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 2;
+            }
+            (b'+', _) => decoded.push(b' '),
+            (b, _) => decoded.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// `memo serve [--port N]`
+pub fn run(context: &MemoContext, port: u16) -> MemoResult<()> {
+    let address = format!("127.0.0.1:{}", port);
+    let http = tiny_http::Server::http(&address).map_err(|e| {
+        MemoError::from(std::io::Error::other(format!(
+            "failed to listen on {}: {}",
+            address, e
+        )))
+    })?;
+    eprintln!("Listening on http://{}", address);
+
+    let server = Server::new(context.clone(), port);
+    let content_type = tiny_http::Header::from_bytes("Content-Type", "application/json")
+        .expect("static header is valid");
+    for mut request in http.incoming_requests() {
+        let mut body = String::new();
+        let headers: Vec<(String, String)> = request
+            .headers()
+            .iter()
+            .map(|header| (header.field.to_string(), header.value.to_string()))
+            .collect();
+        let headers: Vec<(&str, &str)> = headers
+            .iter()
+            .map(|(field, value)| (field.as_str(), value.as_str()))
+            .collect();
+        let (status, value) = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => server.handle(request.method().as_str(), request.url(), &headers, &body),
+            Err(e) => error(400, &format!("invalid request body: {}", e)),
+        };
+        let response = tiny_http::Response::from_string(value.to_string())
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(e) = request.respond(response) {
+            eprintln!("Warning: failed to send response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_server() -> (TempDir, Server) {
+        let temp_dir = TempDir::new().unwrap();
+        let context = MemoContext {
+            memo_dir: temp_dir.path().to_path_buf(),
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo(
+            "2025-01/30/20250130143022.md",
+            "---\ntags: [\"@todo\"]\n---\n# Deploy".to_string(),
+        )
+        .unwrap();
+        repo.create_memo(
+            "2025-01/29/20250129120000.md",
+            "# Other\n\n@other".to_string(),
+        )
+        .unwrap();
+        (temp_dir, Server::new(context, DEFAULT_PORT))
+    }
+
+    const GET_HEADERS: &[(&str, &str)] = &[("Host", "127.0.0.1:8765")];
+    const POST_HEADERS: &[(&str, &str)] = &[
+        ("Host", "localhost:8765"),
+        ("Content-Type", "application/json"),
+    ];
+
+    fn get(server: &Server, url: &str) -> (u16, Value) {
+        server.handle("GET", url, GET_HEADERS, "")
+    }
+
+    fn post(server: &Server, url: &str, body: &str) -> (u16, Value) {
+        server.handle("POST", url, POST_HEADERS, body)
+    }
+
+    #[test]
+    fn test_list_and_get() {
+        let (_temp_dir, server) = create_test_server();
+
+        let (status, all) = get(&server, "/memos");
+        assert_eq!(status, 200);
+        assert_eq!(all.as_array().unwrap().len(), 2);
+        assert_eq!(all[0]["id"], "20250130143022");
+
+        let (_, tagged) = get(&server, "/memos?tag=%40todo");
+        assert_eq!(tagged.as_array().unwrap().len(), 1);
+        // 本文の @tag でも絞り込める
+        let (_, inline) = get(&server, "/memos?tag=%40other");
+        assert_eq!(inline[0]["id"], "20250129120000");
+        assert_eq!(inline.as_array().unwrap().len(), 1);
+        let (_, limited) = get(&server, "/memos?limit=1");
+        assert_eq!(limited.as_array().unwrap().len(), 1);
+
+        let (status, memo) = get(&server, "/memos/20250129120000");
+        assert_eq!(status, 200);
+        assert_eq!(memo["content"], "# Other\n\n@other");
+    }
+
+    #[test]
+    fn test_create_and_archive() {
+        let (temp_dir, server) = create_test_server();
+
+        let (status, created) = post(&server, "/memos", r##"{"content": "# New"}"##);
+        assert_eq!(status, 201);
+        let id = created["id"].as_str().unwrap().to_string();
+        let (_, memo) = get(&server, &format!("/memos/{}", id));
+        assert_eq!(memo["content"], "# New");
+
+        let (status, archived) = post(&server, "/memos/20250130143022/archive", "");
+        assert_eq!(status, 200);
+        assert!(archived["path"].as_str().unwrap().contains(".archive"));
+        assert!(
+            temp_dir
+                .path()
+                .join(".archive/2025-01/30/20250130143022.md")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let (_temp_dir, server) = create_test_server();
+
+        let (status, body) = get(&server, "/memos/20250101000000");
+        assert_eq!(status, 404);
+        assert!(body["error"].as_str().unwrap().contains("not found"));
+        assert_eq!(get(&server, "/memos?limit=many").0, 400);
+        assert_eq!(get(&server, "/search").0, 400);
+        assert_eq!(post(&server, "/memos", "not json").0, 400);
+        assert_eq!(
+            server
+                .handle("DELETE", "/memos/20250130143022", GET_HEADERS, "")
+                .0,
+            405
+        );
+        assert_eq!(get(&server, "/unknown").0, 404);
+    }
+
+    #[test]
+    fn test_rejects_requests_from_web_pages() {
+        let (_temp_dir, server) = create_test_server();
+
+        assert_eq!(get(&server, "/memos").0, 200);
+        assert_eq!(
+            server
+                .handle("GET", "/memos", &[("host", "LOCALHOST:8765")], "")
+                .0,
+            200
+        );
+
+        // DNS リバインディング
+        for host in ["evil.example:8765", "127.0.0.1:9999", "localhost"] {
+            let (status, body) = server.handle("GET", "/memos", &[("Host", host)], "");
+            assert_eq!(status, 403, "host {}", host);
+            assert!(body["error"].as_str().unwrap().contains("Host"));
+        }
+        assert_eq!(server.handle("GET", "/memos", &[], "").0, 403);
+
+        let (status, _) = server.handle(
+            "GET",
+            "/memos",
+            &[
+                ("Host", "127.0.0.1:8765"),
+                ("Origin", "https://evil.example"),
+            ],
+            "",
+        );
+        assert_eq!(status, 403);
+
+        // フォームからの POST は Content-Type で弾く
+        let body = r##"{"content": "# New"}"##;
+        for content_type in [
+            None,
+            Some("text/plain"),
+            Some("application/x-www-form-urlencoded"),
+        ] {
+            let mut headers = vec![("Host", "127.0.0.1:8765")];
+            headers.extend(content_type.map(|value| ("Content-Type", value)));
+            let (status, _) = server.handle("POST", "/memos", &headers, body);
+            assert_eq!(status, 415, "content type {:?}", content_type);
+        }
+        let (status, _) = server.handle(
+            "POST",
+            "/memos",
+            &[
+                ("Host", "127.0.0.1:8765"),
+                ("Content-Type", "application/json; charset=utf-8"),
+            ],
+            body,
+        );
+        assert_eq!(status, 201);
+        assert_eq!(get(&server, "/memos").1.as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = parse_query("q=tags%3A%40todo+deploy&limit=5&flag&bad=%zz");
+        assert_eq!(query["q"], "tags:@todo deploy");
+        assert_eq!(query["limit"], "5");
+        assert_eq!(query["flag"], "");
+        assert_eq!(query["bad"], "%zz");
+    }
+}
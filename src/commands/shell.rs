@@ -0,0 +1,167 @@
+use crate::alias;
+use crate::commands::complete;
+use crate::commands::search::{self as search_cmd, SearchOptions};
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::search::CachedSearchIndex;
+use clap::Command;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::{Editor, Helper, Highlighter, Hinter, Validator};
+
+const PROMPT: &str = "memo> ";
+
+/// `memo shell` の間ずっと保持する状態
+///
+/// 検索インデックス（と Lindera の辞書）を開いたままにして、起動コストを毎回払わない
+pub struct ShellSession {
+    context: MemoContext,
+    search: CachedSearchIndex,
+}
+
+impl ShellSession {
+    pub fn new(context: &MemoContext) -> Self {
+        let manager = context.search_manager();
+        Self {
+            context: context.clone(),
+            search: CachedSearchIndex::new(manager),
+        }
+    }
+
+    pub fn search(&mut self, query: &str, options: &SearchOptions) -> MemoResult<()> {
+        if options.all_notebooks || options.include_archived {
+            // 他のノートブックやアーカイブのインデックスは開いたままにしない
+            return search_cmd::run_search(&self.context, query, options);
+        }
+        search_cmd::validate_query(query)?;
+        if options.count {
+            let count = self
+                .search
+                .count(query, &options.params(&self.context.config))?;
+            println!("{}", count);
+            return Ok(());
+        }
+        let results = self.search.search_with_snippets(
+            query,
+            &options.params(&self.context.config),
+            search_cmd::snippet_chars(),
+        )?;
+        search_cmd::display_results(query, &results, options)
+    }
+}
+
+#[derive(Helper, Hinter, Highlighter, Validator)]
+struct ShellHelper {
+    context: MemoContext,
+    cli: Command,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let (start, words) = completion_words(&line[..pos]);
+        let candidates = complete::candidates(&self.context, &self.cli, &words).unwrap_or_default();
+        Ok((start, candidates))
+    }
+}
+
+/// 補完対象の単語の開始位置と、`__complete` と同じ形式の単語列
+fn completion_words(line: &str) -> (usize, Vec<String>) {
+    let start = line
+        .rfind(char::is_whitespace)
+        .map(|i| i + line[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(0);
+    let mut words: Vec<String> = line[..start]
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    words.push(line[start..].to_string());
+    (start, words)
+}
+
+/// `memo shell`
+///
+/// 各行を `memo` の引数として解釈し、`execute` に渡す（先頭はプログラム名）。
+/// `exit` / `quit` / Ctrl-D で終了。
+pub fn run<F>(context: &MemoContext, cli: &Command, mut execute: F) -> MemoResult<()>
+where
+    F: FnMut(&mut ShellSession, Vec<String>) -> MemoResult<()>,
+{
+    let mut session = ShellSession::new(context);
+
+    let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new().map_err(readline_error)?;
+    editor.set_helper(Some(ShellHelper {
+        context: context.clone(),
+        cli: cli.clone(),
+    }));
+    let history_file = context.shell_history_file();
+    // 初回は履歴ファイルが無いので失敗してよい
+    let _ = editor.load_history(&history_file);
+
+    loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(readline_error(e)),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+        if line == "exit" || line == "quit" {
+            break;
+        }
+
+        let result = alias::split_words(line)
+            .map_err(MemoError::InvalidArgument)
+            .and_then(|words| {
+                let args = std::iter::once("memo".to_string()).chain(words).collect();
+                alias::expand(args, &context.config.alias, cli)
+            })
+            .and_then(|args| execute(&mut session, args));
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+        }
+    }
+
+    let _ = editor.save_history(&history_file);
+    Ok(())
+}
+
+fn readline_error(err: ReadlineError) -> MemoError {
+    match err {
+        ReadlineError::Io(err) => err.into(),
+        err => std::io::Error::other(err).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completion_words() {
+        assert_eq!(completion_words(""), (0, vec!["".to_string()]));
+        assert_eq!(completion_words("sh"), (0, vec!["sh".to_string()]));
+        assert_eq!(
+            completion_words("show 2025"),
+            (5, vec!["show".to_string(), "2025".to_string()])
+        );
+        assert_eq!(
+            completion_words("list  --tag "),
+            (
+                12,
+                vec!["list".to_string(), "--tag".to_string(), "".to_string()]
+            )
+        );
+    }
+}
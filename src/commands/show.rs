@@ -1,15 +1,82 @@
 use crate::context::MemoContext;
-use crate::error::MemoResult;
+use crate::display::format::OutputFormat;
+use crate::display::{self, MemoDisplayFormatter};
+use crate::encryption;
+use crate::error::{MemoError, MemoResult};
+use crate::markdown;
+use crate::render::{MemoTemplateData, OutputTemplate};
 use crate::repository::MemoRepository;
-use std::fs;
+use std::path::PathBuf;
 
-pub fn run(context: &MemoContext, id: &str) -> MemoResult<()> {
+/// `memo show` の表示オプション
+#[derive(Debug, Clone, Default)]
+pub struct ShowOptions {
+    /// Show heading structure instead of content
+    pub outline: bool,
+    /// Show only the section under this heading
+    pub section: Option<String>,
+    /// Render with a Handlebars template file
+    pub template: Option<PathBuf>,
+    /// Render markdown for the terminal instead of printing the file as is
+    pub pretty: bool,
+    /// Print the memo as one JSON object, like `memo list --json`
+    pub json: bool,
+}
+
+pub fn run(context: &MemoContext, id: &str, options: &ShowOptions) -> MemoResult<()> {
     let repo = MemoRepository::new(context.clone());
     let memo = repo.find_memo_by_id(id)?;
 
-    // ファイルの内容を読み込んで出力
-    let content = fs::read_to_string(&memo.path)?;
+    if options.json {
+        return OutputFormat::Json
+            .formatter()
+            .print_list(std::slice::from_ref(&memo), "");
+    }
+
+    if let Some(path) = options.template.as_deref() {
+        let template = OutputTemplate::from_file(path)?;
+        let data = MemoTemplateData::from_memo_file(&memo);
+        print!("{}", template.render(&data)?);
+        return Ok(());
+    }
+
+    if options.outline {
+        for line in MemoDisplayFormatter::format_outline(&memo.outline()) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    if let Some(heading) = options.section.as_deref() {
+        let section = markdown::extract_section(&memo.content, heading)
+            .ok_or_else(|| MemoError::SectionNotFound(heading.to_string()))?;
+        if options.pretty {
+            print!("{}", display::markdown::render(&section));
+        } else {
+            print!("{}", section);
+        }
+        return Ok(());
+    }
+
+    // 整形しない時はパイプで渡せるようファイルの内容をそのまま出力する
+    let content = if options.pretty {
+        display::markdown::render(&memo.content)
+    } else {
+        encryption::read_memo(&memo.path)?
+    };
     print!("{}", content);
 
+    let attachments = memo.attachment_paths();
+    if !attachments.is_empty() {
+        if !content.ends_with('\n') {
+            println!();
+        }
+        println!();
+        println!("Attachments:");
+        for path in attachments {
+            println!("  {}", path.display());
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,44 @@
+use crate::commands::search::{self as search_cmd, SearchOptions};
+use crate::context::MemoContext;
+use crate::display::MemoDisplayFormatter;
+use crate::display::format::OutputFormat;
+use crate::error::MemoResult;
+use crate::memo::MemoFile;
+use crate::repository::MemoRepository;
+
+/// `memo similar` の既定の件数
+pub const DEFAULT_LIMIT: usize = 10;
+
+/// `memo similar <id>`: 語が似ているメモをスコアの高い順に表示
+pub fn run(
+    context: &MemoContext,
+    id: &str,
+    limit: usize,
+    format: Option<OutputFormat>,
+) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(id)?;
+    let results = context.search_manager().similar(&memo.id.as_str(), limit)?;
+
+    let format = format.unwrap_or_default();
+    if format != OutputFormat::Plain {
+        let options = SearchOptions {
+            format: Some(format),
+            ..Default::default()
+        };
+        return search_cmd::display_results(&memo.id.as_str(), &results, &options);
+    }
+
+    if results.is_empty() {
+        println!("No memos similar to {}", memo.id);
+        return Ok(());
+    }
+    println!("Memos similar to {} ({}):", memo.id, memo.title());
+    println!();
+    for result in &results {
+        let similar = MemoFile::from_path(&result.memo.path)?;
+        println!("score: {:.2}", result.score);
+        MemoDisplayFormatter::display_memo(&similar);
+    }
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use crate::context::MemoContext;
+use crate::display::color::{self, Style};
+use crate::error::MemoResult;
+use crate::repository::{DiskUsage, MemoRepository, Period, TextTotals};
+use crate::tag_index::TagIndex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// テキスト表示で出す日・タグの数（`--json` は全件）
+const RECENT_DAYS: usize = 10;
+const TOP_TAGS: usize = 10;
+
+/// `memo stats --json` の出力
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub memos: usize,
+    pub words: usize,
+    pub chars: usize,
+    pub by_month: BTreeMap<String, usize>,
+    pub by_day: BTreeMap<String, usize>,
+    pub tags: Vec<TagCount>,
+    pub archive: Usage,
+    pub index: Usage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Usage {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+impl From<DiskUsage> for Usage {
+    fn from(usage: DiskUsage) -> Self {
+        Usage {
+            files: usage.files,
+            bytes: usage.bytes,
+        }
+    }
+}
+
+/// メモ全体の集計
+pub fn collect(context: &MemoContext) -> MemoResult<Stats> {
+    let repo = MemoRepository::new(context.clone());
+    // 1 つずつ読んで数える（全てのメモを同時にメモリに載せない）
+    let mut totals = TextTotals::default();
+    let mut tag_index = TagIndex::default();
+    for memo in repo.iter_memos()?.filter_map(|entry| entry.load().ok()) {
+        totals.add(&memo);
+        tag_index.add(&memo);
+    }
+    let tags = tag_index
+        .sorted()
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect();
+
+    Ok(Stats {
+        memos: totals.memos,
+        words: totals.words,
+        chars: totals.chars,
+        by_month: repo.count_memos_by(Period::Month)?,
+        by_day: repo.count_memos_by(Period::Day)?,
+        tags,
+        archive: repo.archive_usage()?.into(),
+        index: repo.index_usage()?.into(),
+    })
+}
+
+/// `memo stats`: 件数・文字数・タグ・アーカイブとインデックスのサイズ
+pub fn run(context: &MemoContext, json: bool) -> MemoResult<()> {
+    let stats = collect(context)?;
+    if json {
+        if let Ok(json) = serde_json::to_string(&stats) {
+            println!("{}", json);
+        }
+        return Ok(());
+    }
+
+    println!(
+        "memos: {} ({} words, {} chars)",
+        stats.memos, stats.words, stats.chars
+    );
+    println!(
+        "archive: {} memos, {}",
+        stats.archive.files,
+        format_bytes(stats.archive.bytes)
+    );
+    println!("index: {}", format_bytes(stats.index.bytes));
+
+    println!();
+    println!("{}", color::paint("by month", Style::Bold));
+    for (month, count) in stats.by_month.iter().rev() {
+        println!("  {}  {:>4}", month, count);
+    }
+
+    println!();
+    println!("{}", color::paint("recent days", Style::Bold));
+    for (day, count) in stats.by_day.iter().rev().take(RECENT_DAYS) {
+        println!("  {}  {:>4}", day, count);
+    }
+
+    if !stats.tags.is_empty() {
+        println!();
+        println!("{}", color::paint("tags", Style::Bold));
+        for tag in stats.tags.iter().take(TOP_TAGS) {
+            println!("  {:>4}  {}", tag.count, tag.tag);
+        }
+    }
+    Ok(())
+}
+
+/// 1024 単位で B / KiB / MiB / GiB
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 * 1024), "3072.0 GiB");
+    }
+}
@@ -0,0 +1,172 @@
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::display::color::{self, Style};
+use crate::encryption;
+use crate::error::{MemoError, MemoResult};
+use crate::front_matter;
+use crate::memo::MemoFile;
+use crate::repository::MemoRepository;
+use crate::webhook::{self, WebhookEvent};
+use chrono::Local;
+
+const STATUS_KEY: &str = "status";
+
+/// `memo status <id> [state]`: 状態を表示、または変更する
+pub fn run(context: &MemoContext, id: &str, state: Option<&str>) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(id)?;
+
+    let Some(state) = state else {
+        println!("{}", status_of(&memo).unwrap_or("(none)"));
+        return Ok(());
+    };
+
+    let memo = set_status(&memo, state, &context.config.status.states)?;
+    update_search_index(context, &memo.path)?;
+    webhook::notify(
+        &context.config,
+        WebhookEvent::Edit,
+        std::slice::from_ref(&memo),
+    );
+
+    println!("Memo {} is now {}", memo.id, state);
+    Ok(())
+}
+
+/// `memo board`: 状態ごとにメモをまとめて表示
+pub fn run_board(context: &MemoContext) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memos = repo.list_all_memos()?;
+
+    for (state, memos) in group_by_status(&memos, &context.config.status.states) {
+        println!(
+            "{}",
+            color::paint(&format!("{} ({})", state, memos.len()), Style::Bold)
+        );
+        for memo in memos {
+            println!("  {}  {}", memo.id, memo.title());
+        }
+        println!();
+    }
+    Ok(())
+}
+
+pub fn status_of(memo: &MemoFile) -> Option<&str> {
+    memo.metadata_value(STATUS_KEY)?.as_str()
+}
+
+pub fn set_status(memo: &MemoFile, state: &str, states: &[String]) -> MemoResult<MemoFile> {
+    if !states.iter().any(|s| s == state) {
+        return Err(MemoError::InvalidArgument(format!(
+            "unknown status '{}' (expected one of: {})",
+            state,
+            states.join(", ")
+        )));
+    }
+
+    let raw = encryption::read_memo(&memo.path)?;
+    encryption::write_memo(
+        &memo.path,
+        &front_matter::set_field(&raw, STATUS_KEY, state),
+    )?;
+    MemoFile::from_path(&memo.path)?.set_updated_at(Local::now())
+}
+
+/// 設定の順に状態ごとのメモを返す（新しい順）
+///
+/// `status:` の無いメモは含めず、設定に無い状態は末尾にまとめる
+pub fn group_by_status<'a>(
+    memos: &'a [MemoFile],
+    states: &[String],
+) -> Vec<(String, Vec<&'a MemoFile>)> {
+    let mut groups: Vec<(String, Vec<&MemoFile>)> =
+        states.iter().map(|s| (s.clone(), Vec::new())).collect();
+    for memo in memos {
+        let Some(status) = status_of(memo) else {
+            continue;
+        };
+        match groups.iter_mut().find(|(state, _)| state == status) {
+            Some((_, group)) => group.push(memo),
+            None => groups.push((status.to_string(), vec![memo])),
+        }
+    }
+    for (_, group) in &mut groups {
+        group.sort_by_key(|memo| std::cmp::Reverse(memo.id.get_datetime()));
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let context = MemoContext {
+            memo_dir: temp_dir.path().to_path_buf(),
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+        (temp_dir, context)
+    }
+
+    fn states() -> Vec<String> {
+        ["inbox", "active", "done"].map(String::from).to_vec()
+    }
+
+    #[test]
+    fn test_set_status() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        let memo = repo
+            .create_memo(
+                "2025-01/30/20250130143022.md",
+                "---\ntitle: Report\nstatus: inbox\n---\nbody".to_string(),
+            )
+            .unwrap();
+
+        let memo = set_status(&memo, "active", &states()).unwrap();
+        assert_eq!(status_of(&memo), Some("active"));
+        assert!(memo.updated_at().is_some());
+        assert_eq!(memo.content, "body");
+
+        assert!(matches!(
+            set_status(&memo, "archived", &states()),
+            Err(MemoError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_group_by_status() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo(
+            "2025-01/30/20250130143022.md",
+            "---\nstatus: done\n---\na".to_string(),
+        )
+        .unwrap();
+        repo.create_memo(
+            "2025-01/29/20250129120000.md",
+            "---\nstatus: blocked\n---\nb".to_string(),
+        )
+        .unwrap();
+        repo.create_memo("2025-01/28/20250128090000.md", "c".to_string())
+            .unwrap();
+        let memos = repo.list_all_memos().unwrap();
+
+        let groups: Vec<(String, Vec<String>)> = group_by_status(&memos, &states())
+            .into_iter()
+            .map(|(state, memos)| (state, memos.iter().map(|m| m.id.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("inbox".to_string(), vec![]),
+                ("active".to_string(), vec![]),
+                ("done".to_string(), vec!["20250130143022".to_string()]),
+                ("blocked".to_string(), vec!["20250129120000".to_string()]),
+            ]
+        );
+    }
+}
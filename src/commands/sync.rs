@@ -0,0 +1,39 @@
+use crate::context::MemoContext;
+use crate::error::MemoResult;
+use crate::git::GitRepo;
+use crate::repository::MemoRepository;
+
+/// `memo sync`: メモディレクトリを git にコミットし、リモートがあれば pull / push する
+pub fn run(context: &MemoContext) -> MemoResult<()> {
+    let remote = &context.config.git.remote;
+    let repo = GitRepo::new(&context.memo_dir);
+
+    let result = repo.sync(remote)?;
+    if result.initialized {
+        println!(
+            "Initialized git repository in {}",
+            context.memo_dir.display()
+        );
+    }
+    if result.committed {
+        println!("Committed local changes");
+    }
+    if result.pulled {
+        // pull で入ってきたメモを検索に反映する
+        let search_manager = context.search_manager();
+        let paths = MemoRepository::new(context.clone()).list_memo_paths()?;
+        search_manager.update_index(&paths)?;
+        println!("Pulled from {}", remote);
+    }
+    if result.pushed {
+        println!("Pushed to {}", remote);
+    } else if !repo.has_remote(remote)? {
+        println!(
+            "No remote '{}' configured; add one with: git -C {} remote add {} <url>",
+            remote,
+            context.memo_dir.display(),
+            remote
+        );
+    }
+    Ok(())
+}
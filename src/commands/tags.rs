@@ -1,18 +1,51 @@
+use crate::commands::edit::update_search_index;
 use crate::context::MemoContext;
-use crate::error::MemoResult;
-use crate::search::SearchManager;
+use crate::error::{MemoError, MemoResult};
+use crate::repository::MemoRepository;
+use crate::tag_index::{self, TagIndex};
+use crate::webhook::{self, WebhookEvent};
 
+/// `memo tags`: front matter と本文の `@tag` を使用メモ数付きで一覧
 pub fn run(context: &MemoContext) -> MemoResult<()> {
-    let search_manager = SearchManager::new(context.memo_dir.clone(), context.index_dir());
-    let tags = search_manager.list_tags()?;
+    let repo = MemoRepository::new(context.clone());
+    let index = TagIndex::build(&repo.list_all_memos()?);
 
-    if tags.is_empty() {
-        println!("No tags found. Run `memo index` first if you have tagged memos.");
+    if index.is_empty() {
+        println!("No tags found.");
         return Ok(());
     }
 
-    for (tag, count) in &tags {
+    for (tag, count) in index.sorted() {
         println!("{:>4}  {}", count, tag);
     }
     Ok(())
 }
+
+/// `memo tags rename <old> <new>`: 全メモのタグを書き換えてインデックスを更新
+pub fn run_rename(context: &MemoContext, old: &str, new: &str) -> MemoResult<()> {
+    if new.trim().is_empty() {
+        return Err(MemoError::InvalidArgument(
+            "new tag name must not be empty".to_string(),
+        ));
+    }
+
+    let repo = MemoRepository::new(context.clone());
+    let mut renamed = Vec::new();
+    for memo in repo.list_all_memos()? {
+        if let Some(memo) = tag_index::rename_tag(&memo, old, new)? {
+            update_search_index(context, &memo.path)?;
+            renamed.push(memo);
+        }
+    }
+
+    if renamed.is_empty() {
+        return Err(MemoError::InvalidArgument(format!(
+            "no memos are tagged '{}'",
+            old
+        )));
+    }
+    webhook::notify(&context.config, WebhookEvent::Edit, &renamed);
+
+    println!("Renamed {} to {} in {} memo(s)", old, new, renamed.len());
+    Ok(())
+}
@@ -1,15 +1,13 @@
-use crate::commands::add::builtin_template;
 use crate::context::MemoContext;
-use crate::error::MemoResult;
+use crate::error::{IoResultExt, MemoResult};
+use crate::template::{self, BUILTIN_TEMPLATES};
 use crate::utils::editor;
 use std::collections::BTreeSet;
 use std::fs;
 
-const BUILTIN_TEMPLATES: &[&str] = &["1on1", "idea", "meeting", "todo"];
-
 pub fn run_add(context: &MemoContext, name: &str) -> MemoResult<()> {
     let templates_dir = context.templates_dir();
-    fs::create_dir_all(&templates_dir)?;
+    fs::create_dir_all(&templates_dir).with_path(&templates_dir)?;
 
     let path = templates_dir.join(format!("{}.md", name));
     if path.exists() {
@@ -17,18 +15,18 @@ pub fn run_add(context: &MemoContext, name: &str) -> MemoResult<()> {
         return Ok(());
     }
 
-    fs::write(&path, builtin_template(name))?;
+    fs::write(&path, template::builtin(name)).with_path(&path)?;
     editor::open_editor(context, &path)?;
     Ok(())
 }
 
 pub fn run_edit(context: &MemoContext, name: &str) -> MemoResult<()> {
     let templates_dir = context.templates_dir();
-    fs::create_dir_all(&templates_dir)?;
+    fs::create_dir_all(&templates_dir).with_path(&templates_dir)?;
 
     let path = templates_dir.join(format!("{}.md", name));
     if !path.exists() {
-        fs::write(&path, builtin_template(name))?;
+        fs::write(&path, template::builtin(name)).with_path(&path)?;
     }
 
     editor::open_editor(context, &path)?;
@@ -42,12 +40,12 @@ pub fn run_list(context: &MemoContext) -> MemoResult<()> {
     }
 
     let templates_dir = context.templates_dir();
-    if templates_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&templates_dir) {
-            for entry in entries.flatten() {
-                if let Some(stem) = entry.path().file_stem().and_then(|s| s.to_str()) {
-                    names.insert(stem.to_string());
-                }
+    if templates_dir.exists()
+        && let Ok(entries) = fs::read_dir(&templates_dir)
+    {
+        for entry in entries.flatten() {
+            if let Some(stem) = entry.path().file_stem().and_then(|s| s.to_str()) {
+                names.insert(stem.to_string());
             }
         }
     }
@@ -0,0 +1,64 @@
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::error::MemoResult;
+use crate::repository::MemoRepository;
+use chrono::Local;
+
+/// 内容を変えずに updated_at と更新日時だけを進める
+pub fn run(context: &MemoContext, id: &str) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let memo = repo.find_memo_by_id(id)?;
+
+    let memo = memo.set_updated_at(Local::now())?;
+    update_search_index(context, &memo.path)?;
+
+    println!("Memo touched: {}", memo.id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let memo_dir = temp_dir.path().join("memo");
+        fs::create_dir_all(&memo_dir).unwrap();
+
+        let context = MemoContext {
+            memo_dir,
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+
+        (temp_dir, context)
+    }
+
+    #[test]
+    fn test_touch_updates_front_matter_and_mtime() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        let memo = repo
+            .create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+
+        // mtimeを過去に戻しておく
+        let old = SystemTime::now() - Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(&memo.path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        run(&context, "20250130143022").unwrap();
+
+        let touched = repo.find_memo_by_id("20250130143022").unwrap();
+        assert!(touched.updated_at().is_some());
+        assert_eq!(touched.content, "Test memo");
+        assert!(fs::metadata(&memo.path).unwrap().modified().unwrap() > old);
+    }
+}
@@ -0,0 +1,573 @@
+//! `memo tui`: an interactive browser with a list pane and a preview pane.
+//!
+//! ```text
+//! /            検索（入力するたびに絞り込む。Enter で確定、Esc で解除）
+//! j / k, ↑ / ↓ 選択を移動
+//! Enter, o     メモを全画面で開く（j / k でスクロール、Esc で戻る）
+//! e            $EDITOR で編集
+//! a            アーカイブ（y で確定）
+//! t            タグを追加
+//! q, Esc       終了
+//! ```
+
+use crate::commands::archive;
+use crate::commands::edit::{self, update_search_index};
+use crate::context::MemoContext;
+use crate::display::MemoDisplayFormatter;
+use crate::error::{MemoError, MemoResult};
+use crate::git;
+use crate::memo::MemoFile;
+use crate::repository::MemoRepository;
+use crate::search::{CachedSearchIndex, SearchParams};
+use crate::tag_index;
+use crate::webhook::{self, WebhookEvent};
+use chrono::Local;
+use ratatui::Frame;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListState, Paragraph, Wrap};
+use std::io::IsTerminal;
+
+/// 入力中のキーの解釈
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    Browse,
+    Query,
+    /// 追加するタグを入力中
+    Tag(String),
+    ConfirmArchive,
+    /// 選択中のメモを全画面で表示
+    Open,
+}
+
+/// イベントループに戻す指示
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Action {
+    Continue,
+    /// 端末を元に戻してエディタを開く
+    Edit(String),
+    Quit,
+}
+
+/// `memo tui` の状態（検索インデックスは開いたまま）
+pub struct Tui {
+    context: MemoContext,
+    search: CachedSearchIndex,
+    query: String,
+    memos: Vec<MemoFile>,
+    list: ListState,
+    mode: Mode,
+    scroll: u16,
+    status: String,
+}
+
+impl Tui {
+    pub fn new(context: MemoContext) -> Self {
+        let manager = context.search_manager();
+        let mut tui = Self {
+            context,
+            search: CachedSearchIndex::new(manager),
+            query: String::new(),
+            memos: Vec::new(),
+            list: ListState::default(),
+            mode: Mode::Browse,
+            scroll: 0,
+            status: String::new(),
+        };
+        tui.refresh();
+        tui
+    }
+
+    fn selected_memo(&self) -> Option<&MemoFile> {
+        self.list.selected().and_then(|i| self.memos.get(i))
+    }
+
+    /// クエリが空なら新しい順の全メモ、そうでなければ検索結果
+    ///
+    /// 入力途中の不完全なクエリはエラーを表示して、前の結果を残す
+    fn refresh(&mut self) {
+        match self.load_memos() {
+            Ok(memos) => {
+                self.memos = memos;
+                self.status.clear();
+            }
+            Err(e) => self.status = e.to_string(),
+        }
+        let selected = match self.list.selected() {
+            _ if self.memos.is_empty() => None,
+            Some(i) => Some(i.min(self.memos.len() - 1)),
+            None => Some(0),
+        };
+        self.list.select(selected);
+    }
+
+    fn load_memos(&mut self) -> MemoResult<Vec<MemoFile>> {
+        let query = self.query.trim();
+        if query.is_empty() {
+            return MemoRepository::new(self.context.clone()).list_all_memos();
+        }
+        let params = SearchParams {
+            boost: self.context.config.search.boost,
+            ..Default::default()
+        };
+        Ok(self
+            .search
+            .search(query, &params)?
+            .iter()
+            .filter_map(|result| MemoFile::from_path(&result.memo.path).ok())
+            .collect())
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.memos.is_empty() {
+            return;
+        }
+        let current = self.list.selected().unwrap_or(0);
+        let last = self.memos.len() - 1;
+        self.list
+            .select(Some(current.saturating_add_signed(delta).min(last)));
+        self.scroll = 0;
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> MemoResult<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Ok(Action::Quit);
+        }
+        match self.mode.clone() {
+            Mode::Browse => return self.handle_browse_key(key),
+            Mode::Query => match key.code {
+                KeyCode::Enter => self.mode = Mode::Browse,
+                KeyCode::Esc => {
+                    self.query.clear();
+                    self.mode = Mode::Browse;
+                    self.refresh();
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.refresh();
+                }
+                KeyCode::Up => self.move_selection(-1),
+                KeyCode::Down => self.move_selection(1),
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.refresh();
+                }
+                _ => {}
+            },
+            Mode::Tag(mut tag) => match key.code {
+                KeyCode::Enter => {
+                    self.mode = Mode::Browse;
+                    self.tag_selected(tag.trim())?;
+                }
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Backspace => {
+                    tag.pop();
+                    self.mode = Mode::Tag(tag);
+                }
+                KeyCode::Char(c) => {
+                    tag.push(c);
+                    self.mode = Mode::Tag(tag);
+                }
+                _ => {}
+            },
+            Mode::ConfirmArchive => {
+                self.mode = Mode::Browse;
+                if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    self.archive_selected()?;
+                } else {
+                    self.status = "Archive cancelled".to_string();
+                }
+            }
+            Mode::Open => match key.code {
+                KeyCode::Char('j') | KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+                KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::Char('e') => return Ok(self.edit_action()),
+                KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => {
+                    self.mode = Mode::Browse;
+                    self.scroll = 0;
+                }
+                _ => {}
+            },
+        }
+        Ok(Action::Continue)
+    }
+
+    fn handle_browse_key(&mut self, key: KeyEvent) -> MemoResult<Action> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Action::Quit),
+            // 絞り込み中の Esc はまず解除
+            KeyCode::Esc if !self.query.is_empty() => {
+                self.query.clear();
+                self.refresh();
+            }
+            KeyCode::Esc => return Ok(Action::Quit),
+            KeyCode::Char('/') => self.mode = Mode::Query,
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::Enter | KeyCode::Char('o') if self.selected_memo().is_some() => {
+                self.mode = Mode::Open;
+                self.scroll = 0;
+            }
+            KeyCode::Char('e') => return Ok(self.edit_action()),
+            KeyCode::Char('a') if self.selected_memo().is_some() => {
+                self.mode = Mode::ConfirmArchive;
+            }
+            KeyCode::Char('t') if self.selected_memo().is_some() => {
+                self.mode = Mode::Tag("@".to_string());
+            }
+            _ => {}
+        }
+        Ok(Action::Continue)
+    }
+
+    fn edit_action(&self) -> Action {
+        match self.selected_memo() {
+            Some(memo) => Action::Edit(memo.id.to_string()),
+            None => Action::Continue,
+        }
+    }
+
+    fn archive_selected(&mut self) -> MemoResult<()> {
+        let Some(memo) = self.selected_memo().cloned() else {
+            return Ok(());
+        };
+        let mut report = archive::archive_memos(&self.context, std::slice::from_ref(&memo))?;
+        if let Some(failure) = report.failures.pop() {
+            return Err(failure.error);
+        }
+
+        self.refresh();
+        // 切れるリンクの警告はステータス行に出す
+        self.status = std::iter::once(format!("Archived {}", memo.id))
+            .chain(report.warnings)
+            .collect::<Vec<_>>()
+            .join("; ");
+        Ok(())
+    }
+
+    fn tag_selected(&mut self, tag: &str) -> MemoResult<()> {
+        let Some(memo) = self.selected_memo().cloned() else {
+            return Ok(());
+        };
+        if tag.trim_start_matches('@').is_empty() {
+            return Ok(());
+        }
+        let Some(tagged) = tag_index::add_tag(&memo, tag)? else {
+            self.status = format!("{} is already tagged {}", memo.id, tag);
+            return Ok(());
+        };
+        let tagged = tagged.set_updated_at(Local::now())?;
+        update_search_index(&self.context, &tagged.path)?;
+        let tagged = [tagged];
+        webhook::notify(&self.context.config, WebhookEvent::Edit, &tagged);
+        git::auto_commit(&self.context.config, &self.context.memo_dir, "tag", &tagged);
+
+        self.refresh();
+        self.status = format!("Tagged {} with {}", memo.id, tag);
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [input_area, main_area, status_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let input = match &self.mode {
+            Mode::Tag(tag) => format!("Tag: {}", tag),
+            _ => format!("Search: {}", self.query),
+        };
+        frame.render_widget(Paragraph::new(input), input_area);
+
+        let preview = self
+            .selected_memo()
+            .map(|memo| MemoDisplayFormatter::format_memo_detail(memo).join("\n"))
+            .unwrap_or_default();
+        let preview = Paragraph::new(preview)
+            .block(Block::default().borders(Borders::ALL))
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+
+        if self.mode == Mode::Open {
+            frame.render_widget(preview, main_area);
+        } else {
+            let [list_area, preview_area] =
+                Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+                    .areas(main_area);
+            // 列揃えは memo list --format table と同じ（見出し行は使わない）
+            let rows: Vec<String> =
+                MemoDisplayFormatter::format_memo_table(&self.memos, list_area.width as usize)
+                    .into_iter()
+                    .skip(1)
+                    .collect();
+            let list = List::new(rows)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!("{} memo(s)", self.memos.len())),
+                )
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            frame.render_stateful_widget(list, list_area, &mut self.list);
+            frame.render_widget(preview, preview_area);
+        }
+
+        let status = match &self.mode {
+            Mode::ConfirmArchive => "Archive this memo? (y/n)".to_string(),
+            _ if !self.status.is_empty() => self.status.clone(),
+            Mode::Open => "j/k: scroll  e: edit  q: back".to_string(),
+            _ => "/: search  enter: open  e: edit  a: archive  t: tag  q: quit".to_string(),
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+}
+
+/// `memo tui`
+pub fn run(context: &MemoContext) -> MemoResult<()> {
+    if !std::io::stdout().is_terminal() {
+        return Err(MemoError::InvalidArgument(
+            "memo tui needs a terminal".to_string(),
+        ));
+    }
+    let mut tui = Tui::new(context.clone());
+    let mut terminal = ratatui::try_init()?;
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| tui.draw(frame)) {
+            break Err(e.into());
+        }
+        let key = match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => key,
+            Ok(_) => continue,
+            Err(e) => break Err(e.into()),
+        };
+        match tui.handle_key(key) {
+            Ok(Action::Continue) => {}
+            Ok(Action::Quit) => break Ok(()),
+            Ok(Action::Edit(id)) => {
+                ratatui::try_restore()?;
+                if let Err(e) = edit::run(context, &[id], None, false) {
+                    tui.status = e.to_string();
+                }
+                terminal = ratatui::try_init()?;
+                terminal.clear()?;
+                let status = std::mem::take(&mut tui.status);
+                tui.refresh();
+                if tui.status.is_empty() {
+                    tui.status = status;
+                }
+            }
+            Err(e) => tui.status = e.to_string(),
+        }
+    };
+    ratatui::try_restore()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use tempfile::TempDir;
+
+    fn create_test_tui() -> (TempDir, Tui) {
+        let temp_dir = TempDir::new().unwrap();
+        let context = MemoContext {
+            memo_dir: temp_dir.path().to_path_buf(),
+            editor: "echo".to_string(),
+            ..Default::default()
+        };
+        let repo = MemoRepository::new(context.clone());
+        repo.create_memo(
+            "2025-01/30/20250130143022.md",
+            "---\ntags: [\"@todo\"]\n---\n# Deploy\nship it".to_string(),
+        )
+        .unwrap();
+        repo.create_memo("2025-01/29/20250129120000.md", "# Other".to_string())
+            .unwrap();
+        (temp_dir, Tui::new(context))
+    }
+
+    fn press(tui: &mut Tui, code: KeyCode) -> Action {
+        tui.handle_key(KeyEvent::from(code)).unwrap()
+    }
+
+    fn type_text(tui: &mut Tui, text: &str) {
+        for c in text.chars() {
+            press(tui, KeyCode::Char(c));
+        }
+    }
+
+    fn selected_id(tui: &Tui) -> String {
+        tui.selected_memo().unwrap().id.to_string()
+    }
+
+    #[test]
+    fn test_navigation_and_quit() {
+        let (_temp_dir, mut tui) = create_test_tui();
+        assert_eq!(selected_id(&tui), "20250130143022");
+
+        press(&mut tui, KeyCode::Char('j'));
+        press(&mut tui, KeyCode::Down);
+        assert_eq!(selected_id(&tui), "20250129120000");
+        press(&mut tui, KeyCode::Up);
+        assert_eq!(selected_id(&tui), "20250130143022");
+
+        assert_eq!(
+            press(&mut tui, KeyCode::Char('e')),
+            Action::Edit("20250130143022".to_string())
+        );
+        assert_eq!(press(&mut tui, KeyCode::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn test_query_mode() {
+        let (_temp_dir, mut tui) = create_test_tui();
+
+        press(&mut tui, KeyCode::Char('/'));
+        type_text(&mut tui, "q");
+        assert_eq!(tui.query, "q");
+        // 'q' は入力として扱い、終了しない
+        assert_eq!(tui.mode, Mode::Query);
+
+        press(&mut tui, KeyCode::Esc);
+        assert_eq!(tui.mode, Mode::Browse);
+        assert!(tui.query.is_empty());
+        assert_eq!(tui.memos.len(), 2);
+    }
+
+    #[test]
+    fn test_archive_and_tag() {
+        let (temp_dir, mut tui) = create_test_tui();
+
+        press(&mut tui, KeyCode::Char('t'));
+        type_text(&mut tui, "idea");
+        press(&mut tui, KeyCode::Enter);
+        assert_eq!(tui.selected_memo().unwrap().tags(), vec!["@todo", "@idea"]);
+        assert_eq!(tui.status, "Tagged 20250130143022 with @idea");
+
+        press(&mut tui, KeyCode::Char('a'));
+        press(&mut tui, KeyCode::Char('n'));
+        assert_eq!(tui.memos.len(), 2);
+
+        press(&mut tui, KeyCode::Char('a'));
+        press(&mut tui, KeyCode::Char('y'));
+        assert_eq!(tui.memos.len(), 1);
+        assert_eq!(selected_id(&tui), "20250129120000");
+        assert!(
+            temp_dir
+                .path()
+                .join(".archive/2025-01/30/20250130143022.md")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_query_refreshes_results() {
+        let (_temp_dir, mut tui) = create_test_tui();
+        let paths = MemoRepository::new(tui.context.clone())
+            .list_memo_paths()
+            .unwrap();
+        tui.context.search_manager().update_index(&paths).unwrap();
+
+        press(&mut tui, KeyCode::Char('/'));
+        type_text(&mut tui, "deploy");
+        assert_eq!(tui.memos.len(), 1);
+        assert_eq!(selected_id(&tui), "20250130143022");
+
+        // 入力途中の不完全なクエリは前の結果を残してエラーを出す
+        type_text(&mut tui, " title:(");
+        assert_eq!(tui.memos.len(), 1);
+        assert!(!tui.status.is_empty());
+
+        for _ in 0..8 {
+            press(&mut tui, KeyCode::Backspace);
+        }
+        assert_eq!(tui.query, "deploy");
+        assert!(tui.status.is_empty());
+        assert_eq!(tui.memos.len(), 1);
+
+        press(&mut tui, KeyCode::Enter);
+        assert_eq!(tui.mode, Mode::Browse);
+        press(&mut tui, KeyCode::Esc);
+        assert!(tui.query.is_empty());
+        assert_eq!(tui.memos.len(), 2);
+    }
+
+    #[test]
+    fn test_tag_entry() {
+        let (_temp_dir, mut tui) = create_test_tui();
+
+        press(&mut tui, KeyCode::Char('t'));
+        assert_eq!(tui.mode, Mode::Tag("@".to_string()));
+        type_text(&mut tui, "ide");
+        press(&mut tui, KeyCode::Backspace);
+        assert_eq!(tui.mode, Mode::Tag("@id".to_string()));
+        press(&mut tui, KeyCode::Esc);
+        assert_eq!(tui.mode, Mode::Browse);
+        assert_eq!(tui.selected_memo().unwrap().tags(), vec!["@todo"]);
+
+        // 空のタグは何もしない
+        press(&mut tui, KeyCode::Char('t'));
+        press(&mut tui, KeyCode::Enter);
+        assert_eq!(tui.mode, Mode::Browse);
+        assert!(tui.status.is_empty());
+
+        press(&mut tui, KeyCode::Char('t'));
+        type_text(&mut tui, "todo");
+        press(&mut tui, KeyCode::Enter);
+        assert_eq!(tui.status, "20250130143022 is already tagged @todo");
+    }
+
+    #[test]
+    fn test_archive_confirm() {
+        let (temp_dir, mut tui) = create_test_tui();
+        let archived = temp_dir
+            .path()
+            .join(".archive/2025-01/30/20250130143022.md");
+
+        press(&mut tui, KeyCode::Char('a'));
+        assert_eq!(tui.mode, Mode::ConfirmArchive);
+        press(&mut tui, KeyCode::Char('n'));
+        assert_eq!(tui.mode, Mode::Browse);
+        assert_eq!(tui.status, "Archive cancelled");
+        assert!(!archived.exists());
+
+        // 残るメモからのリンクが切れることはステータス行で知らせる
+        MemoRepository::new(tui.context.clone())
+            .create_memo(
+                "2025-01/29/20250129130000.md",
+                "see [[20250130143022]]".to_string(),
+            )
+            .unwrap();
+        press(&mut tui, KeyCode::Char('a'));
+        press(&mut tui, KeyCode::Char('Y'));
+        assert!(archived.exists());
+        assert_eq!(
+            tui.status,
+            "Archived 20250130143022; 20250130143022 is linked from 20250129130000; \
+             those links will break"
+        );
+        assert_eq!(tui.memos.len(), 2);
+    }
+
+    #[test]
+    fn test_draw() {
+        let (_temp_dir, mut tui) = create_test_tui();
+        let mut terminal = Terminal::new(TestBackend::new(100, 12)).unwrap();
+
+        terminal.draw(|frame| tui.draw(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("2 memo(s)"));
+        assert!(screen.contains("Deploy"));
+        assert!(screen.contains("ship it"));
+    }
+}
@@ -0,0 +1,66 @@
+use crate::commands::archive::print_planned_moves;
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::error::MemoResult;
+use crate::git;
+use crate::memo::MemoDocument;
+use crate::ops;
+use std::path::Path;
+
+/// `memo undo`: 最後の archive / restore / delete / mv で動かしたファイルを元の場所に戻す
+///
+/// `dry_run` なら戻すファイルを表示するだけ
+pub fn run(context: &MemoContext, dry_run: bool) -> MemoResult<()> {
+    if dry_run {
+        match ops::last_entry(context)? {
+            Some(entry) => {
+                print_planned_moves(&entry.reverse_moves(&context.memo_dir));
+                eprintln!(
+                    "{} of {} memo(s) ({}) would be undone. Run without --dry-run to apply.",
+                    entry.action.as_str(),
+                    entry.memo_count(),
+                    entry.display_time()
+                );
+            }
+            None => println!("Nothing to undo"),
+        }
+        return Ok(());
+    }
+
+    let Some(undone) = ops::undo(context)? else {
+        println!("Nothing to undo");
+        return Ok(());
+    };
+
+    // メモディレクトリから出たメモは検索インデックスから外し、戻ってきたメモは入れ直す
+    let removed: Vec<MemoDocument> = undone
+        .before
+        .iter()
+        .filter(|memo| in_memo_tree(context, &memo.path))
+        .map(MemoDocument::from_memo_file)
+        .collect();
+    context.search_manager().remove_memos(&removed)?;
+    for memo in &undone.after {
+        if in_memo_tree(context, &memo.path) {
+            update_search_index(context, &memo.path)?;
+        }
+    }
+
+    let entry = &undone.entry;
+    println!(
+        "Undid {} of {} memo(s) ({})",
+        entry.action.as_str(),
+        entry.memo_count(),
+        entry.display_time()
+    );
+    git::auto_commit(&context.config, &context.memo_dir, "undo", &undone.after);
+    Ok(())
+}
+
+/// `.archive` や `.trash` ではなく、メモディレクトリのメモか
+fn in_memo_tree(context: &MemoContext, path: &Path) -> bool {
+    path.strip_prefix(&context.memo_dir)
+        .ok()
+        .and_then(|relative| relative.components().next())
+        .is_some_and(|first| !first.as_os_str().to_string_lossy().starts_with('.'))
+}
@@ -0,0 +1,55 @@
+use crate::commands::search::{self as search_cmd, SearchOptions};
+use crate::config;
+use crate::context::MemoContext;
+use crate::error::{MemoError, MemoResult};
+use crate::utils::xdg;
+use crate::views;
+
+/// `memo view <name>`: 保存した検索を `memo search` と同じように実行
+pub fn run(context: &MemoContext, name: &str, options: &SearchOptions) -> MemoResult<()> {
+    search_cmd::run_search(context, views::query(&context.config, name)?, options)
+}
+
+/// `memo view`: 保存した検索の一覧
+pub fn run_list(context: &MemoContext) -> MemoResult<()> {
+    let config = &context.config;
+    if config.views.is_empty() {
+        println!("No saved views (save one with `memo view save <name> \"<query>\"`)");
+        return Ok(());
+    }
+    let width = config.views.keys().map(String::len).max().unwrap_or(0);
+    for (name, query) in &config.views {
+        println!("{:<width$}  {}", name, query);
+    }
+    Ok(())
+}
+
+/// `memo view save <name> <query>`: 設定ファイルの `[views]` に書く
+///
+/// 同じ名前のビューは `force` の時だけ置き換える
+pub fn run_save(context: &MemoContext, name: &str, query: &str, force: bool) -> MemoResult<()> {
+    views::validate_name(name)?;
+    search_cmd::validate_query(query)?;
+    if !force && context.config.views.contains_key(name) {
+        return Err(MemoError::InvalidArgument(format!(
+            "view '{}' already exists (use --force to replace it)",
+            name
+        )));
+    }
+
+    config::set_view(&xdg::get_config_path()?, name, query)?;
+    println!("Saved view {}", name);
+    Ok(())
+}
+
+/// `memo view delete <name>`
+pub fn run_delete(name: &str) -> MemoResult<()> {
+    if !config::remove_view(&xdg::get_config_path()?, name)? {
+        return Err(MemoError::InvalidArgument(format!(
+            "unknown view '{}'",
+            name
+        )));
+    }
+    println!("Deleted view {}", name);
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use crate::context::MemoContext;
+use crate::encryption;
+use crate::error::{MemoError, MemoResult};
+use crate::repository::MemoRepository;
+use crate::search::SearchManager;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// 保存時に続けて届くイベントをまとめて1回の更新にする
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// `memo watch`: メモディレクトリを監視し、変更されたメモをインデックスに反映し続ける
+pub fn run(context: &MemoContext) -> MemoResult<()> {
+    let repo = MemoRepository::new(context.clone());
+    let search_manager = context.search_manager();
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(watch_error)?;
+    watcher
+        .watch(&context.memo_dir, RecursiveMode::Recursive)
+        .map_err(watch_error)?;
+
+    // 監視を始める前に変わった分を取り込む
+    update(&repo, &search_manager)?;
+    println!(
+        "Watching {} for changes (Ctrl-C to stop)",
+        context.memo_dir.display()
+    );
+
+    while let Ok(event) = rx.recv() {
+        if !is_memo_change(&context.memo_dir, &event) {
+            continue;
+        }
+        // 検索などで .index 側のイベントが続いても待ち続けないよう、最初の変更から一定時間で区切る
+        let deadline = Instant::now() + DEBOUNCE;
+        while let Some(wait) = deadline.checked_duration_since(Instant::now())
+            && rx.recv_timeout(wait).is_ok()
+        {}
+        if let Err(e) = update(&repo, &search_manager) {
+            // 書き込み途中のファイルなどは次のイベントで取り直す
+            eprintln!("Error: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn update(repo: &MemoRepository, search_manager: &SearchManager) -> MemoResult<()> {
+    let update = search_manager.update_index(&repo.list_memo_paths()?)?;
+    if update.added + update.modified + update.removed > 0 {
+        println!(
+            "Search index updated: {} added, {} modified, {} removed",
+            update.added, update.modified, update.removed
+        );
+    }
+    Ok(())
+}
+
+/// メモファイル（隠しディレクトリ以外の `.md`）の作成・変更・削除か
+fn is_memo_change(memo_dir: &Path, event: &notify::Result<Event>) -> bool {
+    let Ok(event) = event else {
+        return false;
+    };
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return false;
+    }
+    event.paths.iter().any(|path| is_memo_path(memo_dir, path))
+}
+
+fn is_memo_path(memo_dir: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(memo_dir) else {
+        return false;
+    };
+    // .index / .archive / .trash / .templates などは対象外
+    let hidden = relative
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+    !hidden && (path.extension().is_some_and(|ext| ext == "md") || encryption::is_encrypted(path))
+}
+
+fn watch_error(e: notify::Error) -> MemoError {
+    MemoError::from(std::io::Error::other(format!(
+        "failed to watch the memo directory: {}",
+        e
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, ModifyKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_is_memo_change() {
+        let memo_dir = PathBuf::from("/data/memo");
+        let event =
+            |kind: EventKind, path: &str| Ok(Event::new(kind).add_path(PathBuf::from(path)));
+        let create = EventKind::Create(CreateKind::File);
+
+        assert!(is_memo_change(
+            &memo_dir,
+            &event(create, "/data/memo/2025-01/30/20250130143022.md")
+        ));
+        assert!(is_memo_change(
+            &memo_dir,
+            &event(
+                EventKind::Modify(ModifyKind::Any),
+                "/data/memo/2025-01/30/20250130143022.md"
+            )
+        ));
+        assert!(!is_memo_change(
+            &memo_dir,
+            &event(create, "/data/memo/.index/meta.json")
+        ));
+        assert!(!is_memo_change(
+            &memo_dir,
+            &event(create, "/data/memo/.archive/2025-01/30/20250130143022.md")
+        ));
+        assert!(!is_memo_change(
+            &memo_dir,
+            &event(create, "/data/memo/2025-01/30/.20250130143022.md.swp")
+        ));
+        assert!(!is_memo_change(
+            &memo_dir,
+            &event(EventKind::Access(AccessKind::Any), "/data/memo/a.md")
+        ));
+    }
+}
@@ -0,0 +1,620 @@
+//! User configuration loaded from `$XDG_CONFIG_HOME/memo/config.toml`.
+
+use crate::display::date;
+use crate::display::theme::Theme;
+use crate::encryption::EncryptionConfig;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::git::GitConfig;
+use crate::repository::DateBasis;
+use crate::search::{DEFAULT_WRITER_HEAP_SIZE, FieldAnalyzers, FieldBoosts, TokenizerConfig};
+use crate::utils::{datetime, xdg};
+use crate::webhook::WebhookConfig;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `[notebooks]` とは別の、メモディレクトリそのものの名前
+pub const DEFAULT_NOTEBOOK: &str = "default";
+
+/// tantivy が受け付ける最小のメモリ量
+const MIN_INDEX_HEAP_SIZE: usize = 15_000_000;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// `editor = "nvim"` (takes precedence over `$EDITOR`)
+    pub editor: Option<String>,
+    /// `memo_dir = "~/notes"` (used instead of `$XDG_DATA_HOME/memo`)
+    pub memo_dir: Option<PathBuf>,
+    /// `date_format = "%Y/%m/%d %H:%M"` (`$MEMO_DATE_FORMAT` takes precedence)
+    pub date_format: Option<String>,
+    /// `[list]` limit = 50
+    pub list: ListConfig,
+    /// `[index]` heap_size = 50000000
+    pub index: IndexConfig,
+    /// `[alias]` name = "command args..."
+    pub alias: BTreeMap<String, String>,
+    /// `[[webhook]]` url = "...", events = ["create", "edit", "archive"]
+    pub webhook: Vec<WebhookConfig>,
+    /// `[status]` states = ["inbox", "active", "done"]
+    pub status: StatusConfig,
+    /// `[search.boost]` title = 2.0, tags = 1.5, content = 1.0, metadata = 1.0
+    pub search: SearchConfig,
+    /// `[notebooks]` name = "/path/to/another/memo/dir"
+    pub notebooks: BTreeMap<String, PathBuf>,
+    /// `notebook = "work"`: the notebook used when `--notebook` is not given
+    pub notebook: Option<String>,
+    /// `[git]` auto_commit = true, remote = "origin"
+    pub git: GitConfig,
+    /// `[encryption]` identity = "~/.config/memo/identity.txt", index = false
+    pub encryption: EncryptionConfig,
+    /// `[theme]` id = "yellow", date = "blue", tag = "cyan", match = "bold red"
+    pub theme: Theme,
+    /// `[archive]` older_than = "90d", by = "modified"
+    pub archive: ArchiveConfig,
+    /// `[views]` name = "query" (`memo view <name>`)
+    pub views: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArchiveConfig {
+    /// 引数なしの `memo archive --older-than` が使う期間（`90d`・`12w`）
+    pub older_than: Option<String>,
+    /// 作成日時（ID）と更新日時のどちらで古さを見るか
+    pub by: DateBasis,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SearchConfig {
+    pub boost: FieldBoosts,
+    /// `[search.analyzer]` title = "ja_en", tags = "ja"（変えたら `memo index` で作り直す）
+    pub analyzer: FieldAnalyzers,
+    /// `[search.tokenizer]` dictionary = "unidic", user_dictionary = "~/.config/memo/userdict.csv"
+    pub tokenizer: TokenizerConfig,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ListConfig {
+    /// `memo list` に `--limit` が無い時の件数
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IndexConfig {
+    /// `memo index` の書き込みに使うメモリ量（バイト）
+    pub heap_size: usize,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            heap_size: DEFAULT_WRITER_HEAP_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatusConfig {
+    /// `memo status` で使える状態（`memo board` の列順）
+    pub states: Vec<String>,
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            states: ["inbox", "active", "done"].map(String::from).to_vec(),
+        }
+    }
+}
+
+impl Config {
+    /// Load the config file; a missing file yields the defaults
+    pub fn load() -> MemoResult<Self> {
+        Self::load_from(&xdg::get_config_path()?)
+    }
+
+    pub fn load_from(path: &Path) -> MemoResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).with_path(path)?;
+        Self::parse(&content)
+            .map_err(|e| MemoError::ConfigError(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn parse(content: &str) -> MemoResult<Self> {
+        let config: Self =
+            toml::from_str(content).map_err(|e| MemoError::ConfigError(e.message().to_string()))?;
+        config.search.boost.validate()?;
+        if config.notebooks.contains_key(DEFAULT_NOTEBOOK) {
+            return Err(MemoError::ConfigError(format!(
+                "notebooks.{} is reserved for the memo directory itself",
+                DEFAULT_NOTEBOOK
+            )));
+        }
+        if let Some(name) = &config.notebook
+            && name != DEFAULT_NOTEBOOK
+            && !config.notebooks.contains_key(name)
+        {
+            return Err(MemoError::ConfigError(format!(
+                "notebook '{}' is not listed in [notebooks]",
+                name
+            )));
+        }
+        if let Some(format) = &config.date_format
+            && !date::is_valid_format(format)
+        {
+            return Err(MemoError::ConfigError(format!(
+                "date_format '{}' is not a valid strftime format",
+                format
+            )));
+        }
+        if let Some(age) = &config.archive.older_than
+            && datetime::parse_age(age).is_none()
+        {
+            return Err(MemoError::ConfigError(format!(
+                "archive.older_than '{}' is not a valid age (e.g. 90d, 12w)",
+                age
+            )));
+        }
+        if config.index.heap_size < MIN_INDEX_HEAP_SIZE {
+            return Err(MemoError::ConfigError(format!(
+                "index.heap_size must be at least {}, got {}",
+                MIN_INDEX_HEAP_SIZE, config.index.heap_size
+            )));
+        }
+        Ok(config)
+    }
+
+    /// `memo_dir` with a leading `~/` expanded to the home directory
+    pub fn memo_dir(&self) -> Option<PathBuf> {
+        self.memo_dir.as_deref().map(expand_home)
+    }
+
+    /// Directory of a `[notebooks]` entry with a leading `~/` expanded
+    pub fn notebook_dir(&self, name: &str) -> Option<PathBuf> {
+        self.notebooks.get(name).map(|dir| expand_home(dir))
+    }
+
+    /// `[search.tokenizer]` with a leading `~/` of `user_dictionary` expanded
+    pub fn tokenizer(&self) -> TokenizerConfig {
+        TokenizerConfig {
+            user_dictionary: self
+                .search
+                .tokenizer
+                .user_dictionary
+                .as_deref()
+                .map(expand_home),
+            ..self.search.tokenizer.clone()
+        }
+    }
+
+    /// `[encryption] identity` with a leading `~/` expanded
+    pub fn identity_path(&self) -> Option<PathBuf> {
+        self.encryption.identity.as_deref().map(expand_home)
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// `memo config` で表示・変更できる項目
+pub const SETTINGS: &[Setting] = &[
+    Setting::new("editor", SettingKind::String),
+    Setting::new("memo_dir", SettingKind::String),
+    Setting::new("date_format", SettingKind::String),
+    Setting::new("list.limit", SettingKind::Integer),
+    Setting::new("index.heap_size", SettingKind::Integer),
+    Setting::new("notebook", SettingKind::String),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    String,
+    Integer,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Setting {
+    /// ドット区切りのキー（`list.limit`）
+    pub key: &'static str,
+    pub kind: SettingKind,
+}
+
+impl Setting {
+    const fn new(key: &'static str, kind: SettingKind) -> Self {
+        Self { key, kind }
+    }
+
+    pub fn find(key: &str) -> MemoResult<&'static Setting> {
+        SETTINGS.iter().find(|s| s.key == key).ok_or_else(|| {
+            let keys: Vec<_> = SETTINGS.iter().map(|s| s.key).collect();
+            MemoError::ConfigError(format!(
+                "unknown setting '{}' (expected one of: {})",
+                key,
+                keys.join(", ")
+            ))
+        })
+    }
+}
+
+/// Set `key` in the config file, keeping its comments and layout
+///
+/// 書き込む前に設定全体を読み直して検証する
+pub fn set_value(path: &Path, setting: &Setting, value: &str) -> MemoResult<()> {
+    let document = read_document(path)?;
+
+    let item = match setting.kind {
+        SettingKind::String => toml_edit::value(value),
+        SettingKind::Integer => {
+            let number: i64 = value.parse().map_err(|_| {
+                MemoError::ConfigError(format!(
+                    "{} must be an integer, got '{}'",
+                    setting.key, value
+                ))
+            })?;
+            toml_edit::value(number)
+        }
+    };
+    write_item(path, document, setting.key, item)
+}
+
+/// Add `name = "dir"` to `[notebooks]` in the config file
+pub fn add_notebook(path: &Path, name: &str, dir: &Path) -> MemoResult<()> {
+    let document = read_document(path)?;
+    let key = format!("notebooks.{}", name);
+    write_item(
+        path,
+        document,
+        &key,
+        toml_edit::value(dir.to_string_lossy().to_string()),
+    )
+}
+
+/// Add or replace `name = "query"` in `[views]` of the config file
+pub fn set_view(path: &Path, name: &str, query: &str) -> MemoResult<()> {
+    let document = read_document(path)?;
+    let key = format!("views.{}", name);
+    write_item(path, document, &key, toml_edit::value(query))
+}
+
+/// Remove `name` from `[views]`; `false` if there was no such view
+pub fn remove_view(path: &Path, name: &str) -> MemoResult<bool> {
+    let mut document = read_document(path)?;
+    let removed = document
+        .get_mut("views")
+        .and_then(toml_edit::Item::as_table_like_mut)
+        .and_then(|views| views.remove(name))
+        .is_some();
+    if removed {
+        write_document(path, document)?;
+    }
+    Ok(removed)
+}
+
+fn read_document(path: &Path) -> MemoResult<toml_edit::DocumentMut> {
+    let content = if path.exists() {
+        fs::read_to_string(path).with_path(path)?
+    } else {
+        String::new()
+    };
+    content
+        .parse()
+        .map_err(|e| MemoError::ConfigError(format!("{}: {}", path.display(), e)))
+}
+
+/// Set a dotted `key` (at most one table deep) and write the file back after validating it
+fn write_item(
+    path: &Path,
+    mut document: toml_edit::DocumentMut,
+    key: &str,
+    item: toml_edit::Item,
+) -> MemoResult<()> {
+    match key.split_once('.') {
+        Some((table, key)) => {
+            let table = document
+                .entry(table)
+                .or_insert_with(toml_edit::table)
+                .as_table_mut()
+                .ok_or_else(|| {
+                    MemoError::ConfigError(format!(
+                        "{} in {} is not a table",
+                        table,
+                        path.display()
+                    ))
+                })?;
+            table[key] = item;
+        }
+        None => document[key] = item,
+    }
+    write_document(path, document)
+}
+
+/// Write the file back after validating it
+fn write_document(path: &Path, document: toml_edit::DocumentMut) -> MemoResult<()> {
+    let updated = document.to_string();
+    Config::parse(&updated)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_path(parent)?;
+    }
+    fs::write(path, updated).with_path(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::analyzer::Analyzer;
+    use crate::search::japanese_tokenizer::Dictionary;
+    use crate::webhook::WebhookEvent;
+
+    #[test]
+    fn test_parse_alias() {
+        let config = Config::parse(
+            r#"
+[alias]
+ls = "list --table"
+todo = "search tags:/@todo"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.alias["ls"], "list --table");
+        assert_eq!(config.alias["todo"], "search tags:/@todo");
+    }
+
+    #[test]
+    fn test_parse_webhooks() {
+        let config = Config::parse(
+            r#"
+[[webhook]]
+url = "https://example.com/all"
+
+[[webhook]]
+url = "https://example.com/archive"
+events = ["archive"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.webhook.len(), 2);
+        assert!(config.webhook[0].events.is_empty());
+        assert_eq!(config.webhook[1].events, vec![WebhookEvent::Archive]);
+        assert!(Config::parse("[[webhook]]\nurl = \"x\"\nevents = [\"rename\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_status_states() {
+        assert_eq!(
+            Config::default().status.states,
+            vec!["inbox", "active", "done"]
+        );
+        let config = Config::parse("[status]\nstates = [\"todo\", \"doing\"]\n").unwrap();
+        assert_eq!(config.status.states, vec!["todo", "doing"]);
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let config = Config::parse("[theme]\nid = \"bold magenta\"\nmatch = \"none\"\n").unwrap();
+        assert_eq!(config.theme.id.sgr(), "1;35");
+        assert_eq!(config.theme.matched.sgr(), "");
+        assert_eq!(config.theme.tag, Theme::default().tag);
+
+        assert!(matches!(
+            Config::parse("[theme]\ndate = \"purple\"\n"),
+            Err(MemoError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_archive_policy() {
+        let config = Config::parse("[archive]\nolder_than = \"90d\"\nby = \"modified\"\n").unwrap();
+        assert_eq!(config.archive.older_than.as_deref(), Some("90d"));
+        assert_eq!(config.archive.by, DateBasis::Modified);
+        assert_eq!(Config::default().archive.by, DateBasis::Created);
+
+        assert!(matches!(
+            Config::parse("[archive]\nolder_than = \"3 months\"\n"),
+            Err(MemoError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_search_boost() {
+        let config = Config::parse("[search.boost]\ntitle = 3.0\n").unwrap();
+        assert_eq!(config.search.boost.title, 3.0);
+        assert_eq!(config.search.boost.content, 1.0);
+
+        assert!(matches!(
+            Config::parse("[search.boost]\ncontent = 0\n"),
+            Err(MemoError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_search_analyzer() {
+        let config = Config::parse("[search.analyzer]\ntags = \"ja_en\"\n").unwrap();
+        assert_eq!(config.search.analyzer.tags, Analyzer::JapaneseEnglish);
+        assert_eq!(config.search.analyzer.content, Analyzer::JapaneseEnglish);
+        assert_eq!(Config::default().search.analyzer.tags, Analyzer::Japanese);
+        let config = Config::parse("[search.analyzer]\ncontent = \"bigram\"\n").unwrap();
+        assert_eq!(config.search.analyzer.content, Analyzer::Bigram);
+
+        assert!(matches!(
+            Config::parse("[search.analyzer]\ntitle = \"en\"\n"),
+            Err(MemoError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_search_tokenizer() {
+        let config = Config::parse(
+            "[search.tokenizer]\ndictionary = \"unidic\"\nuser_dictionary = \"/srv/userdict.csv\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.search.tokenizer.dictionary, Dictionary::Unidic);
+        assert_eq!(
+            config.tokenizer().user_dictionary,
+            Some(PathBuf::from("/srv/userdict.csv"))
+        );
+        assert_eq!(Config::default().tokenizer(), TokenizerConfig::default());
+
+        assert!(matches!(
+            Config::parse("[search.tokenizer]\ndictionary = \"jumandic\"\n"),
+            Err(MemoError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_notebooks() {
+        let config = Config::parse("[notebooks]\nwork = \"/srv/work-memo\"\n").unwrap();
+        assert_eq!(config.notebooks["work"], PathBuf::from("/srv/work-memo"));
+        assert!(matches!(
+            Config::parse("[notebooks]\ndefault = \"/tmp\"\n"),
+            Err(MemoError::ConfigError(_))
+        ));
+
+        let config =
+            Config::parse("notebook = \"work\"\n[notebooks]\nwork = \"/srv/w\"\n").unwrap();
+        assert_eq!(config.notebook.as_deref(), Some("work"));
+        assert!(Config::parse("notebook = \"default\"\n").is_ok());
+        assert!(Config::parse("notebook = \"home\"\n").is_err());
+    }
+
+    #[test]
+    fn test_add_notebook() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("memo/config.toml");
+
+        add_notebook(&path, "work", Path::new("/srv/work-memo")).unwrap();
+        set_value(&path, Setting::find("notebook").unwrap(), "work").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(
+            config.notebook_dir("work"),
+            Some(PathBuf::from("/srv/work-memo"))
+        );
+        assert_eq!(config.notebook.as_deref(), Some("work"));
+        assert!(set_value(&path, Setting::find("notebook").unwrap(), "home").is_err());
+    }
+
+    #[test]
+    fn test_load_error_has_path() {
+        // ディレクトリは読めない
+        let temp_dir = tempfile::tempdir().unwrap();
+        let error = Config::load_from(temp_dir.path()).unwrap_err();
+        assert!(
+            matches!(&error, MemoError::Io { path: Some(path), .. } if path == temp_dir.path()),
+            "{:?}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_set_view() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("memo/config.toml");
+
+        set_view(&path, "open", "tags:/@todo AND NOT tags:/@done").unwrap();
+        set_view(&path, "work", "tag:@work").unwrap();
+        set_view(&path, "work", "tag:@work -draft").unwrap();
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.views["open"], "tags:/@todo AND NOT tags:/@done");
+        assert_eq!(config.views["work"], "tag:@work -draft");
+
+        assert!(remove_view(&path, "open").unwrap());
+        assert!(!remove_view(&path, "open").unwrap());
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.views.keys().collect::<Vec<_>>(), vec!["work"]);
+    }
+
+    #[test]
+    fn test_parse_git() {
+        assert_eq!(Config::default().git.remote, "origin");
+        let config = Config::parse("[git]\nauto_commit = true\nremote = \"backup\"\n").unwrap();
+        assert!(config.git.auto_commit);
+        assert_eq!(config.git.remote, "backup");
+        assert!(Config::parse("[git]\nbranch = \"main\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_encryption() {
+        assert_eq!(Config::default().identity_path(), None);
+        let config =
+            Config::parse("[encryption]\nidentity = \"/srv/key.txt\"\nindex = true\n").unwrap();
+        assert_eq!(config.identity_path(), Some(PathBuf::from("/srv/key.txt")));
+        assert!(config.encryption.index);
+    }
+
+    #[test]
+    fn test_parse_settings() {
+        let config = Config::parse(
+            "editor = \"nvim\"\nmemo_dir = \"/srv/memo\"\ndate_format = \"%Y/%m/%d\"\n\n[list]\nlimit = 30\n\n[index]\nheap_size = 20000000\n",
+        )
+        .unwrap();
+        assert_eq!(config.editor.as_deref(), Some("nvim"));
+        assert_eq!(config.memo_dir(), Some(PathBuf::from("/srv/memo")));
+        assert_eq!(config.date_format.as_deref(), Some("%Y/%m/%d"));
+        assert_eq!(config.list.limit, Some(30));
+        assert_eq!(config.index.heap_size, 20_000_000);
+
+        assert_eq!(Config::default().index.heap_size, DEFAULT_WRITER_HEAP_SIZE);
+        assert!(matches!(
+            Config::parse("[index]\nheap_size = 1000\n"),
+            Err(MemoError::ConfigError(_))
+        ));
+        assert!(matches!(
+            Config::parse("date_format = \"%Q\"\n"),
+            Err(MemoError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_set_value() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("memo/config.toml");
+
+        set_value(&path, Setting::find("list.limit").unwrap(), "20").unwrap();
+        fs::write(
+            &path,
+            format!("# my settings\n{}", fs::read_to_string(&path).unwrap()),
+        )
+        .unwrap();
+        set_value(&path, Setting::find("editor").unwrap(), "hx").unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("# my settings"));
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.list.limit, Some(20));
+        assert_eq!(config.editor.as_deref(), Some("hx"));
+
+        // 不正な値は書き込まない
+        assert!(set_value(&path, Setting::find("list.limit").unwrap(), "many").is_err());
+        assert!(set_value(&path, Setting::find("index.heap_size").unwrap(), "1").is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+        assert!(Setting::find("nope").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config::load_from(&temp_dir.path().join("config.toml")).unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            Config::parse("[alias]\nls = 1\n"),
+            Err(MemoError::ConfigError(_))
+        ));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::error::MemoResult;
+use crate::config::{Config, DEFAULT_NOTEBOOK};
+use crate::display::theme::Theme;
+use crate::encryption::{self, EncryptionKey};
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::search::{DEFAULT_WRITER_HEAP_SIZE, FieldAnalyzers, SearchManager, TokenizerConfig};
 use crate::utils::xdg;
 use std::path::PathBuf;
 
@@ -6,20 +10,111 @@ use std::path::PathBuf;
 #[derive(Debug, Clone)]
 pub struct MemoContext {
     pub memo_dir: PathBuf,
+    /// `memo_dir` のノートブック名（`--notebook` / `notebook`、既定は `default`）
+    pub notebook: String,
     pub editor: String,
+    /// `memo list` の既定の件数（`[list] limit`）
+    pub list_limit: Option<usize>,
+    /// 日付表示の strftime 形式（`date_format`）
+    pub date_format: Option<String>,
+    /// 一覧・検索結果の色（`[theme]`）
+    pub theme: Theme,
+    /// `memo index` の書き込みメモリ量（`[index] heap_size`）
+    pub index_heap_size: usize,
+    /// 新しく作る索引のアナライザー（`[search.analyzer]`）
+    pub index_analyzers: FieldAnalyzers,
+    /// 索引と検索で使う辞書（`[search.tokenizer]`）
+    pub index_tokenizer: TokenizerConfig,
+    /// `memo add --encrypt` に使う鍵（`[encryption] identity`）
+    pub encryption_key: Option<EncryptionKey>,
+    /// 起動時に読んだ設定ファイル（コマンドの途中で読み直さない）
+    pub config: Config,
+}
+
+impl Default for MemoContext {
+    fn default() -> Self {
+        Self {
+            memo_dir: PathBuf::new(),
+            notebook: DEFAULT_NOTEBOOK.to_string(),
+            editor: "vi".to_string(),
+            list_limit: None,
+            date_format: None,
+            theme: Theme::default(),
+            index_heap_size: DEFAULT_WRITER_HEAP_SIZE,
+            index_analyzers: FieldAnalyzers::default(),
+            index_tokenizer: TokenizerConfig::default(),
+            encryption_key: None,
+            config: Config::default(),
+        }
+    }
 }
 
 impl MemoContext {
+    #[allow(dead_code)] // ライブラリとして使う側の API
     pub fn new() -> MemoResult<Self> {
-        let memo_dir = xdg::get_memo_dir()?;
-        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+        Self::from_config(&Config::load()?)
+    }
+
+    /// 設定ファイルの値を環境変数・XDG の既定より優先する
+    ///
+    /// `notebook` があれば、そのノートブックのディレクトリを使う
+    pub fn from_config(config: &Config) -> MemoResult<Self> {
+        let memo_dir = default_memo_dir(config)?;
+        let editor = match &config.editor {
+            Some(editor) => editor.clone(),
+            None => std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string()),
+        };
 
-        Ok(MemoContext { memo_dir, editor })
+        let encryption_key = match config.identity_path() {
+            Some(path) => Some(EncryptionKey::load(&path)?),
+            None => None,
+        };
+        // MemoFile が .md.age を透過的に読み書きできるように
+        encryption::install(encryption_key.clone(), config.encryption.index);
+
+        let context = MemoContext {
+            memo_dir,
+            notebook: DEFAULT_NOTEBOOK.to_string(),
+            editor,
+            list_limit: config.list.limit,
+            date_format: config.date_format.clone(),
+            theme: config.theme.clone(),
+            index_heap_size: config.index.heap_size,
+            index_analyzers: config.search.analyzer,
+            index_tokenizer: config.tokenizer(),
+            encryption_key,
+            config: config.clone(),
+        };
+        match config.notebook.as_deref() {
+            Some(name) => context.for_notebook(name),
+            None => Ok(context),
+        }
+    }
+
+    /// 同じ設定で、メモディレクトリだけ `name` のノートブックに切り替える
+    ///
+    /// `.index` や `.archive` もそのノートブックのディレクトリの下になる
+    pub fn for_notebook(&self, name: &str) -> MemoResult<Self> {
+        let memo_dir = if name == DEFAULT_NOTEBOOK {
+            default_memo_dir(&self.config)?
+        } else {
+            self.config.notebook_dir(name).ok_or_else(|| {
+                MemoError::ConfigError(format!(
+                    "unknown notebook '{}' (create it with `memo notebook create {}`)",
+                    name, name
+                ))
+            })?
+        };
+        Ok(MemoContext {
+            memo_dir,
+            notebook: name.to_string(),
+            ..self.clone()
+        })
     }
 
     pub fn ensure_memo_dir(&self) -> MemoResult<()> {
         if !self.memo_dir.exists() {
-            std::fs::create_dir_all(&self.memo_dir)?;
+            std::fs::create_dir_all(&self.memo_dir).with_path(&self.memo_dir)?;
         }
         Ok(())
     }
@@ -28,6 +123,11 @@ impl MemoContext {
         self.memo_dir.join(".archive")
     }
 
+    /// `memo delete` で消したメモの置き場
+    pub fn trash_dir(&self) -> PathBuf {
+        self.memo_dir.join(".trash")
+    }
+
     pub fn ignore_file(&self) -> PathBuf {
         self.memo_dir.join(".ignore")
     }
@@ -36,7 +136,54 @@ impl MemoContext {
         self.memo_dir.join(".index")
     }
 
+    /// 設定（`[index]`・`[search.analyzer]`・`[search.tokenizer]`）を反映した索引の書き込み口
+    pub fn search_manager(&self) -> SearchManager {
+        SearchManager::new(self.memo_dir.clone(), self.index_dir())
+            .with_heap_size(self.index_heap_size)
+            .with_analyzers(self.index_analyzers)
+            .with_tokenizer(self.index_tokenizer.clone())
+    }
+
+    /// アーカイブしたメモだけの索引（`search --include-archived` 用）
+    pub fn archive_index_dir(&self) -> PathBuf {
+        self.archive_dir().join(".index")
+    }
+
+    /// [`Self::search_manager`] のアーカイブ版
+    pub fn archive_search_manager(&self) -> SearchManager {
+        SearchManager::new(self.archive_dir(), self.archive_index_dir())
+            .with_heap_size(self.index_heap_size)
+            .with_analyzers(self.index_analyzers)
+            .with_tokenizer(self.index_tokenizer.clone())
+    }
+
     pub fn templates_dir(&self) -> PathBuf {
         self.memo_dir.join(".templates")
     }
+
+    pub fn shell_history_file(&self) -> PathBuf {
+        self.memo_dir.join(".shell_history")
+    }
+
+    /// `memo clock in` の実行中エントリ
+    pub fn clock_file(&self) -> PathBuf {
+        self.memo_dir.join(".clock")
+    }
+
+    /// `memo undo` で戻すファイル操作の記録
+    pub fn journal_file(&self) -> PathBuf {
+        self.memo_dir.join(".memo-journal")
+    }
+
+    pub fn socket_path(&self) -> PathBuf {
+        self.memo_dir.join(".daemon.sock")
+    }
+}
+
+/// `default` ノートブック、つまり `memo_dir` か XDG の既定のディレクトリ
+fn default_memo_dir(config: &Config) -> MemoResult<PathBuf> {
+    match config.memo_dir() {
+        Some(dir) => Ok(dir),
+        None => xdg::get_memo_dir(),
+    }
 }
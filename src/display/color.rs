@@ -0,0 +1,99 @@
+//! Central switch for ANSI color output.
+//!
+//! Everything that emits escape codes goes through [`paint_sgr`], so `--color`
+//! and `NO_COLOR` only need to be handled here.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// `--color` の値
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color only when stdout is a terminal and NO_COLOR is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+// 明示的に有効化されるまでは色を付けない（ライブラリ利用やテスト向け）
+static COLOR_ENABLED: AtomicBool = AtomicBool::new(false);
+
+impl ColorChoice {
+    /// Decide whether to color, given the environment
+    pub fn should_color(self, no_color: bool, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color && is_terminal,
+        }
+    }
+}
+
+/// Apply the `--color` choice for the rest of the process
+pub fn set_color_choice(choice: ColorChoice) {
+    // https://no-color.org: 空でない値が設定されていれば無効
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let enabled = choice.should_color(no_color, std::io::stdout().is_terminal());
+    COLOR_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn colors_enabled() -> bool {
+    COLOR_ENABLED.load(Ordering::Relaxed)
+}
+
+/// ANSI SGR styles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Bold,
+    Italic,
+    Underline,
+    Dim,
+    /// Inline code (cyan)
+    Code,
+}
+
+impl Style {
+    fn code(self) -> &'static str {
+        match self {
+            Style::Bold => "1",
+            Style::Italic => "3",
+            Style::Underline => "4",
+            Style::Dim => "2",
+            Style::Code => "36",
+        }
+    }
+}
+
+/// Wrap the text in escape codes if colors are enabled
+pub fn paint(text: &str, style: Style) -> String {
+    paint_all(text, &[style])
+}
+
+/// `paint` with several styles at once (e.g. bold inside italic)
+pub fn paint_all(text: &str, styles: &[Style]) -> String {
+    let codes: Vec<&str> = styles.iter().map(|style| style.code()).collect();
+    paint_sgr(text, &codes.join(";"))
+}
+
+/// Wrap the text in raw SGR parameters (e.g. `1;31`); an empty `sgr` leaves it plain
+pub fn paint_sgr(text: &str, sgr: &str) -> String {
+    if !colors_enabled() || sgr.is_empty() || text.is_empty() {
+        return text.to_string();
+    }
+    format!("\x1b[{}m{}\x1b[0m", sgr, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_color() {
+        assert!(ColorChoice::Auto.should_color(false, true));
+        assert!(!ColorChoice::Auto.should_color(true, true));
+        assert!(!ColorChoice::Auto.should_color(false, false));
+        assert!(ColorChoice::Always.should_color(true, false));
+        assert!(!ColorChoice::Never.should_color(false, true));
+    }
+}
@@ -0,0 +1,102 @@
+//! Date formatting shared by list, search and table output.
+//!
+//! `MEMO_DATE_FORMAT` (or `date_format` in config.toml) takes a strftime string;
+//! otherwise the format follows the locale (`LC_ALL` / `LC_TIME` / `LANG`),
+//! with Japanese-style dates for `ja`.
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use std::sync::OnceLock;
+
+pub const DATE_FORMAT_ENV: &str = "MEMO_DATE_FORMAT";
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+pub const JAPANESE_DATE_FORMAT: &str = "%Y年%m月%d日 %H:%M:%S";
+
+static DATE_FORMAT: OnceLock<String> = OnceLock::new();
+
+/// Use `configured` (config.toml `date_format`) when `MEMO_DATE_FORMAT` is not set
+///
+/// 最初の日付表示より前に呼ぶ。呼ばなければ環境変数とロケールだけで決まる
+pub fn init_date_format(configured: Option<&str>) {
+    date_format(configured);
+}
+
+/// Format a timestamp with the configured date format
+pub fn format_datetime(dt: &DateTime<Local>) -> String {
+    dt.format(date_format(None)).to_string()
+}
+
+fn date_format(configured: Option<&str>) -> &'static str {
+    DATE_FORMAT.get_or_init(|| {
+        let custom = std::env::var(DATE_FORMAT_ENV).ok();
+        resolve_date_format(
+            custom.as_deref().or(configured),
+            current_locale().as_deref(),
+        )
+    })
+}
+
+/// Pick the date format from a user-supplied format and the locale
+///
+/// 不正なフォーマットは chrono が表示時に panic するので、ロケールの既定に戻す
+pub fn resolve_date_format(custom: Option<&str>, locale: Option<&str>) -> String {
+    if let Some(custom) = custom.filter(|f| !f.is_empty()) {
+        if is_valid_format(custom) {
+            return custom.to_string();
+        }
+        eprintln!(
+            "Warning: invalid {} '{}', using the default",
+            DATE_FORMAT_ENV, custom
+        );
+    }
+
+    match locale {
+        Some(locale) if locale.starts_with("ja") => JAPANESE_DATE_FORMAT.to_string(),
+        _ => DEFAULT_DATE_FORMAT.to_string(),
+    }
+}
+
+pub fn is_valid_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+}
+
+/// POSIX の優先順位に従ってロケールを取得
+fn current_locale() -> Option<String> {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_resolve_date_format() {
+        assert_eq!(resolve_date_format(None, None), DEFAULT_DATE_FORMAT);
+        assert_eq!(
+            resolve_date_format(None, Some("en_US.UTF-8")),
+            DEFAULT_DATE_FORMAT
+        );
+        assert_eq!(
+            resolve_date_format(None, Some("ja_JP.UTF-8")),
+            JAPANESE_DATE_FORMAT
+        );
+        assert_eq!(
+            resolve_date_format(Some("%d/%m/%Y"), Some("ja_JP.UTF-8")),
+            "%d/%m/%Y"
+        );
+        assert_eq!(resolve_date_format(Some("%Q"), None), DEFAULT_DATE_FORMAT);
+    }
+
+    #[test]
+    fn test_japanese_date_format() {
+        let dt = Local.with_ymd_and_hms(2025, 1, 30, 14, 30, 22).unwrap();
+        assert_eq!(
+            dt.format(JAPANESE_DATE_FORMAT).to_string(),
+            "2025年01月30日 14:30:22"
+        );
+    }
+}
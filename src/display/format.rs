@@ -0,0 +1,332 @@
+//! Output formats for memo lists (`--format`).
+//!
+//! `memo list` and `memo search` hand their memos to a [`Formatter`] instead of
+//! printing them directly, so every listing supports the same set of formats.
+
+use super::theme::{self, Element};
+use super::{MemoDisplayFormatter, NO_MEMOS_MESSAGE, date};
+use crate::error::MemoResult;
+use crate::memo::MemoFile;
+use crate::projection::Projection;
+use crate::utils::text;
+use chrono::{DateTime, Local};
+use serde::{Serialize, Serializer};
+use serde_yaml::Value;
+use std::collections::HashMap;
+
+/// `--format` の値
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// ID, date, metadata and a preview for each memo
+    #[default]
+    Plain,
+    /// One line per memo: ID, date, title and tags
+    Compact,
+    /// Aligned columns of ID, date, title and tags
+    Table,
+    /// One JSON object per memo (JSON Lines)
+    Json,
+    /// A YAML sequence of memos
+    Yaml,
+}
+
+impl OutputFormat {
+    /// 端末幅に合わせた既定の設定の Formatter
+    pub fn formatter(self) -> Box<dyn Formatter> {
+        match self {
+            OutputFormat::Plain => Box::new(PlainFormatter {
+                preview_chars: MemoDisplayFormatter::preview_chars(),
+            }),
+            OutputFormat::Compact => Box::new(CompactFormatter {
+                width: MemoDisplayFormatter::output_width(),
+            }),
+            OutputFormat::Table => Box::new(TableFormatter {
+                width: MemoDisplayFormatter::output_width(),
+            }),
+            OutputFormat::Json => Box::new(JsonFormatter::default()),
+            OutputFormat::Yaml => Box::new(YamlFormatter),
+        }
+    }
+
+    /// 機械で読む形式か（見出しや「見つからない」旨を出さない）
+    pub fn is_machine_readable(self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Yaml)
+    }
+}
+
+/// メモの一覧を出力する行に変換する
+pub trait Formatter {
+    /// `title` は人が読む形式の見出しにだけ使う
+    fn format_list(&self, memos: &[MemoFile], title: &str) -> MemoResult<Vec<String>>;
+
+    fn print_list(&self, memos: &[MemoFile], title: &str) -> MemoResult<()> {
+        for line in self.format_list(memos, title)? {
+            println!("{}", line);
+        }
+        Ok(())
+    }
+}
+
+/// JSON / YAML で出力するメモ1件
+#[derive(Debug, Serialize)]
+pub struct MemoRecord {
+    pub id: String,
+    /// `MemoFile::title`（front matter に無ければ本文から推測）
+    pub title: String,
+    pub path: String,
+    /// ID の日時
+    #[serde(serialize_with = "serialize_datetime")]
+    pub created_at: DateTime<Local>,
+    #[serde(serialize_with = "serialize_datetime")]
+    pub modified: DateTime<Local>,
+    pub preview: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    pub metadata: Option<HashMap<String, Value>>,
+    pub metadata_error: Option<String>,
+    /// 添付ファイルのパス
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>,
+}
+
+impl MemoRecord {
+    /// 本文も含める
+    pub fn from_memo_file(memo: &MemoFile) -> Self {
+        MemoRecord {
+            id: memo.id.as_str(),
+            title: memo.title(),
+            path: memo.path.to_string_lossy().to_string(),
+            created_at: memo.id.get_datetime(),
+            modified: memo.modified,
+            preview: memo.preview(100),
+            content: Some(memo.content.clone()),
+            metadata: memo.metadata.clone(),
+            metadata_error: memo.metadata_error.clone(),
+            attachments: memo
+                .attachment_paths()
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+        }
+    }
+}
+
+fn serialize_datetime<S>(dt: &DateTime<Local>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&dt.to_rfc3339())
+}
+
+pub struct PlainFormatter {
+    pub preview_chars: usize,
+}
+
+impl Formatter for PlainFormatter {
+    fn format_list(&self, memos: &[MemoFile], title: &str) -> MemoResult<Vec<String>> {
+        if memos.is_empty() {
+            return Ok(vec![NO_MEMOS_MESSAGE.to_string()]);
+        }
+        let mut lines = vec![format!("{}:", title), String::new()];
+        for memo in memos {
+            lines.extend(MemoDisplayFormatter::format_memo(
+                memo,
+                &memo.preview(self.preview_chars),
+            ));
+        }
+        Ok(lines)
+    }
+}
+
+pub struct CompactFormatter {
+    pub width: usize,
+}
+
+impl Formatter for CompactFormatter {
+    fn format_list(&self, memos: &[MemoFile], _title: &str) -> MemoResult<Vec<String>> {
+        if memos.is_empty() {
+            return Ok(vec![NO_MEMOS_MESSAGE.to_string()]);
+        }
+        Ok(memos
+            .iter()
+            .map(|memo| {
+                let id = memo.id.to_string();
+                let date = date::format_datetime(&memo.modified);
+                let tags = memo.tags().join(" ");
+                let mut line = format!("{}  {}  {}", id, date, memo.title());
+                if !tags.is_empty() {
+                    line.push_str("  ");
+                    line.push_str(&tags);
+                }
+                let line = text::truncate_to_width(&line, self.width);
+                paint_compact_line(&line, &id, &date, &tags)
+            })
+            .collect())
+    }
+}
+
+/// 切り詰めた後の行の ID・日付・タグに色を付ける（途中で切れた部分はそのまま）
+fn paint_compact_line(line: &str, id: &str, date: &str, tags: &str) -> String {
+    let Some(rest) = line.strip_prefix(id) else {
+        return line.to_string();
+    };
+    let mut painted = theme::paint(id, Element::Id);
+    let date_rest = rest
+        .strip_prefix("  ")
+        .and_then(|rest| rest.strip_prefix(date));
+    let rest = match date_rest {
+        Some(rest) => {
+            painted.push_str("  ");
+            painted.push_str(&theme::paint(date, Element::Date));
+            rest
+        }
+        None => rest,
+    };
+    match rest.strip_suffix(tags) {
+        Some(title) if !tags.is_empty() => {
+            painted.push_str(title);
+            painted.push_str(&theme::paint(tags, Element::Tag));
+        }
+        _ => painted.push_str(rest),
+    }
+    painted
+}
+
+pub struct TableFormatter {
+    pub width: usize,
+}
+
+impl Formatter for TableFormatter {
+    fn format_list(&self, memos: &[MemoFile], _title: &str) -> MemoResult<Vec<String>> {
+        if memos.is_empty() {
+            return Ok(vec![NO_MEMOS_MESSAGE.to_string()]);
+        }
+        Ok(MemoDisplayFormatter::format_memo_table(memos, self.width))
+    }
+}
+
+/// 1件1行の JSON。結果が無ければ何も出さない
+#[derive(Default)]
+pub struct JsonFormatter {
+    projection: Option<Projection>,
+}
+
+impl JsonFormatter {
+    /// `--select` で項目を絞る
+    pub fn with_projection(projection: Option<Projection>) -> Self {
+        JsonFormatter { projection }
+    }
+}
+
+impl Formatter for JsonFormatter {
+    fn format_list(&self, memos: &[MemoFile], _title: &str) -> MemoResult<Vec<String>> {
+        Ok(memos
+            .iter()
+            .filter_map(|memo| {
+                let record = MemoRecord::from_memo_file(memo);
+                match &self.projection {
+                    Some(projection) => serde_json::to_value(&record)
+                        .map(|value| projection.apply(&value))
+                        .and_then(|value| serde_json::to_string(&value)),
+                    None => serde_json::to_string(&record),
+                }
+                .ok()
+            })
+            .collect())
+    }
+}
+
+/// メモの配列を1つの YAML 文書として出力
+pub struct YamlFormatter;
+
+impl Formatter for YamlFormatter {
+    fn format_list(&self, memos: &[MemoFile], _title: &str) -> MemoResult<Vec<String>> {
+        let records: Vec<MemoRecord> = memos.iter().map(MemoRecord::from_memo_file).collect();
+        Ok(serde_yaml::to_string(&records)?
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memo_id::MemoId;
+
+    fn memo(id: &str, content: &str, metadata: Option<&str>) -> MemoFile {
+        MemoFile {
+            id: MemoId::from_str(id).unwrap(),
+            path: Default::default(),
+            content: content.to_string(),
+            metadata: metadata.map(|m| serde_yaml::from_str(m).unwrap()),
+            metadata_error: None,
+            modified: Default::default(),
+        }
+    }
+
+    fn memos() -> Vec<MemoFile> {
+        vec![
+            memo(
+                "20250130143022",
+                "# Weekly sync\nnotes",
+                Some("tags: [\"@meeting\"]"),
+            ),
+            memo("20250129120000", "short", None),
+        ]
+    }
+
+    #[test]
+    fn test_compact_formatter() {
+        let lines = CompactFormatter { width: 100 }
+            .format_list(&memos(), "Recent memos")
+            .unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("20250130143022  "));
+        assert!(lines[0].ends_with("  Weekly sync  @meeting"));
+        assert!(lines[1].ends_with("  short"));
+
+        let narrow = CompactFormatter { width: 20 }
+            .format_list(&memos(), "")
+            .unwrap();
+        assert!(narrow.iter().all(|line| text::display_width(line) <= 20));
+    }
+
+    #[test]
+    fn test_json_and_yaml_formatters() {
+        let lines = JsonFormatter::default().format_list(&memos(), "").unwrap();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(first["id"], "20250130143022");
+        assert_eq!(first["content"], "# Weekly sync\nnotes");
+        assert_eq!(first["title"], "Weekly sync");
+
+        let projected = JsonFormatter::with_projection(Some(Projection::parse("id").unwrap()))
+            .format_list(&memos(), "")
+            .unwrap();
+        assert_eq!(projected[1], r#"{"id":"20250129120000"}"#);
+        assert!(
+            JsonFormatter::default()
+                .format_list(&[], "")
+                .unwrap()
+                .is_empty()
+        );
+
+        let yaml = YamlFormatter.format_list(&memos(), "").unwrap().join("\n");
+        let records: Vec<serde_yaml::Value> = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["id"].as_str(), Some("20250129120000"));
+    }
+
+    #[test]
+    fn test_human_formatters_report_empty_list() {
+        for format in [
+            OutputFormat::Plain,
+            OutputFormat::Compact,
+            OutputFormat::Table,
+        ] {
+            let lines = format.formatter().format_list(&[], "Recent memos").unwrap();
+            assert_eq!(lines, vec![NO_MEMOS_MESSAGE]);
+        }
+    }
+}
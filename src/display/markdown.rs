@@ -0,0 +1,326 @@
+//! Terminal rendering of memo bodies for `memo show --pretty`.
+//!
+//! Styles go through [`color::paint_all`], so the structure (bullets, quote bars,
+//! indentation) is kept even when colors are off.
+
+use super::color::{self, Style};
+use pulldown_cmark::{CodeBlockKind, Event, LinkType, Options, Parser, Tag, TagEnd};
+use std::sync::LazyLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::{LinesWithEndings, as_24_bit_terminal_escaped};
+use unicode_width::UnicodeWidthStr;
+
+const BULLET: &str = "• ";
+const QUOTE_BAR: &str = "│ ";
+const RULE_WIDTH: usize = 40;
+const CODE_THEME: &str = "base16-ocean.dark";
+
+// 読み込みに時間がかかるので、コードブロックを色付けする時だけ読む
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+    let mut themes = ThemeSet::load_defaults();
+    themes
+        .themes
+        .remove(CODE_THEME)
+        .expect("bundled syntect theme")
+});
+
+/// Markdown を端末向けに整形する（末尾は必ず改行）
+pub fn render(markdown: &str) -> String {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut renderer = Renderer::default();
+    for event in Parser::new_ext(markdown, options) {
+        renderer.event(event);
+    }
+    renderer.finish()
+}
+
+struct CodeBlock {
+    lang: Option<String>,
+    text: String,
+}
+
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    /// 引用の縦線やリストの字下げ（行頭ごとに付ける）
+    prefixes: Vec<String>,
+    line_start: bool,
+    /// 次のブロックの前に空行を入れる
+    needs_blank: bool,
+    /// リストの記号を書いた直後（段落の前に改行しない）
+    item_start: bool,
+    styles: Vec<Style>,
+    /// 番号付きリストなら次の番号
+    lists: Vec<Option<u64>>,
+    code: Option<CodeBlock>,
+    /// 閉じた後に表示するリンク先
+    links: Vec<Option<String>>,
+}
+
+impl Renderer {
+    fn event(&mut self, event: Event) {
+        if let Some(code) = &mut self.code {
+            match event {
+                Event::Text(text) => code.text.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => self.end_code_block(),
+                _ => {}
+            }
+            return;
+        }
+
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.write_styled(&text),
+            Event::Code(text) => {
+                self.styles.push(Style::Code);
+                self.write_styled(&text);
+                self.styles.pop();
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.write(&html),
+            Event::SoftBreak | Event::HardBreak => self.end_line(),
+            Event::Rule => {
+                self.start_block();
+                self.write(&color::paint(&"─".repeat(RULE_WIDTH), Style::Dim));
+                self.end_block();
+            }
+            Event::TaskListMarker(checked) => self.write(if checked { "[x] " } else { "[ ] " }),
+            Event::FootnoteReference(name) => self.write(&format!("[^{}]", name)),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph | Tag::HtmlBlock => self.start_block(),
+            Tag::Heading { level, .. } => {
+                self.start_block();
+                self.styles.push(Style::Bold);
+                self.write_styled(&format!("{} ", "#".repeat(level as usize)));
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block();
+                self.prefixes.push(color::paint(QUOTE_BAR, Style::Dim));
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().map(|lang| lang.to_string())
+                    }
+                    CodeBlockKind::Indented => None,
+                };
+                self.code = Some(CodeBlock {
+                    lang,
+                    text: String::new(),
+                });
+            }
+            Tag::List(start) => {
+                // 入れ子のリストは項目の中なので空行を入れない
+                if self.lists.is_empty() {
+                    self.start_block();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.end_line();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => BULLET.to_string(),
+                };
+                self.write(&marker);
+                self.prefixes.push(" ".repeat(marker.width()));
+                self.item_start = true;
+            }
+            Tag::Emphasis => self.styles.push(Style::Italic),
+            Tag::Strong => self.styles.push(Style::Bold),
+            Tag::Strikethrough => self.styles.push(Style::Dim),
+            Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }
+            | Tag::Image {
+                link_type,
+                dest_url,
+                ..
+            } => {
+                self.styles.push(Style::Underline);
+                // <https://...> は本文がそのまま URL
+                let autolink = matches!(link_type, LinkType::Autolink | LinkType::Email);
+                self.links
+                    .push((!autolink && !dest_url.is_empty()).then(|| dest_url.to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::HtmlBlock => self.end_block(),
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.end_block();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.prefixes.pop();
+                self.end_block();
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.end_block();
+                }
+            }
+            TagEnd::Item => {
+                self.end_line();
+                self.prefixes.pop();
+                self.needs_blank = false;
+                self.item_start = false;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link | TagEnd::Image => {
+                self.styles.pop();
+                if let Some(Some(url)) = self.links.pop() {
+                    self.write(&color::paint(&format!(" <{}>", url), Style::Dim));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end_code_block(&mut self) {
+        let Some(code) = self.code.take() else {
+            return;
+        };
+        let syntax = code
+            .lang
+            .as_deref()
+            .and_then(|lang| SYNTAXES.find_syntax_by_token(lang));
+        match syntax {
+            Some(syntax) if color::colors_enabled() => {
+                let mut highlighter = HighlightLines::new(syntax, &THEME);
+                for line in LinesWithEndings::from(&code.text) {
+                    let highlighted = match highlighter.highlight_line(line, &SYNTAXES) {
+                        Ok(ranges) => format!(
+                            "{}\x1b[0m",
+                            as_24_bit_terminal_escaped(&ranges, false).trim_end_matches('\n')
+                        ),
+                        Err(_) => line.trim_end_matches('\n').to_string(),
+                    };
+                    self.write_line(&highlighted);
+                }
+            }
+            _ => {
+                for line in code.text.lines() {
+                    self.write_line(&color::paint(line, Style::Code));
+                }
+            }
+        }
+        self.end_block();
+    }
+
+    /// 前のブロックとの間に空行を入れる
+    fn start_block(&mut self) {
+        if self.needs_blank && !self.item_start {
+            self.write_line("");
+        }
+        self.needs_blank = false;
+    }
+
+    fn end_block(&mut self) {
+        self.end_line();
+        self.needs_blank = true;
+    }
+
+    fn end_line(&mut self) {
+        if !self.line_start && !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.line_start = true;
+    }
+
+    /// 1 行書いて改行する（空行にも引用の縦線を付ける）
+    fn write_line(&mut self, line: &str) {
+        self.end_line();
+        if line.is_empty() {
+            let prefix = self.prefixes.concat();
+            self.out.push_str(prefix.trim_end());
+            self.out.push('\n');
+        } else {
+            self.write(line);
+            self.end_line();
+        }
+    }
+
+    fn write_styled(&mut self, text: &str) {
+        let styled = color::paint_all(text, &self.styles);
+        self.write(&styled);
+    }
+
+    fn write(&mut self, text: &str) {
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.out.push('\n');
+                self.line_start = true;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if self.line_start || self.out.is_empty() {
+                let prefix = self.prefixes.concat();
+                self.out.push_str(&prefix);
+                self.line_start = false;
+            }
+            self.out.push_str(line);
+            self.item_start = false;
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.end_line();
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // テストでは色は無効（`set_color_choice` を呼んでいない）なので構造だけを見る
+    #[test]
+    fn test_render_blocks() {
+        let markdown = "# Title\n\nSome **bold** and `code`.\n\n> quoted\n> line\n\n---\n";
+        assert_eq!(
+            render(markdown),
+            "# Title\n\nSome bold and code.\n\n│ quoted\n│ line\n\n────────────────────────────────────────\n"
+        );
+    }
+
+    #[test]
+    fn test_render_lists() {
+        let markdown = "- one\n- two\n  - nested\n- [x] done\n\n1. first\n2. second\n";
+        assert_eq!(
+            render(markdown),
+            "• one\n• two\n  • nested\n• [x] done\n\n1. first\n2. second\n"
+        );
+    }
+
+    #[test]
+    fn test_render_code_block_and_link() {
+        let markdown =
+            "See [docs](https://example.com).\n\n```rust\nfn main() {\n    let x = 1;\n}\n```\n";
+        assert_eq!(
+            render(markdown),
+            "See docs <https://example.com>.\n\nfn main() {\n    let x = 1;\n}\n"
+        );
+    }
+}
@@ -0,0 +1,327 @@
+pub mod color;
+pub mod date;
+pub mod format;
+pub mod markdown;
+pub mod theme;
+
+use crate::markdown::Heading;
+use crate::memo::MemoFile;
+use crate::utils::text;
+use color::Style;
+use serde_yaml::Value;
+use theme::Element;
+
+/// 端末幅が分からない時（パイプ出力など）の幅
+const DEFAULT_WIDTH: usize = 100;
+const NO_MEMOS_MESSAGE: &str = "No memos found. Use 'memo add' to create your first memo.";
+const PREVIEW_PREFIX: &str = "preview: ";
+const PREVIEW_MIN_CHARS: usize = 20;
+const TABLE_HEADERS: [&str; 4] = ["ID", "DATE", "TITLE", "TAGS"];
+const TABLE_GAP: &str = "  ";
+const TITLE_MIN_WIDTH: usize = 10;
+const TAGS_MAX_WIDTH: usize = 30;
+
+pub struct MemoDisplayFormatter;
+
+impl MemoDisplayFormatter {
+    pub fn display_memo(memo: &MemoFile) {
+        Self::display_memo_with_preview(memo, &memo.preview(Self::preview_chars()));
+    }
+
+    /// 先頭からのプレビューの代わりに `preview`（検索の抜粋など）を表示
+    pub fn display_memo_with_preview(memo: &MemoFile, preview: &str) {
+        for line in Self::format_memo(memo, preview) {
+            println!("{}", line);
+        }
+    }
+
+    /// `display_memo_with_preview` で表示する行
+    pub fn format_memo(memo: &MemoFile, preview: &str) -> Vec<String> {
+        let mut lines = Self::format_memo_header(memo);
+        if !preview.is_empty() {
+            lines.push(format!("{}{}", PREVIEW_PREFIX, preview));
+        }
+        lines.push("---".to_string());
+        lines
+    }
+
+    /// `format_memo` のヘッダーの後に本文の全行（`memo tui` のプレビュー）
+    pub fn format_memo_detail(memo: &MemoFile) -> Vec<String> {
+        let mut lines = Self::format_memo_header(memo);
+        lines.push("---".to_string());
+        lines.extend(memo.content.lines().map(str::to_string));
+        lines
+    }
+
+    /// プレビューの代わりに見出し構造を表示
+    pub fn display_memo_outline(memo: &MemoFile) {
+        for line in Self::format_memo_header(memo) {
+            println!("{}", line);
+        }
+
+        let outline = memo.outline();
+        if !outline.is_empty() {
+            println!("outline:");
+            for line in Self::format_outline(&outline) {
+                println!("  {}", line);
+            }
+        }
+        println!("---");
+    }
+
+    /// 出力先の端末幅（`COLUMNS` があれば優先）
+    ///
+    /// 端末でない場合は `None`
+    pub fn terminal_width() -> Option<usize> {
+        if let Some(columns) = std::env::var("COLUMNS")
+            .ok()
+            .and_then(|c| c.trim().parse::<usize>().ok())
+            .filter(|&c| c > 0)
+        {
+            return Some(columns);
+        }
+        terminal_size::terminal_size().map(|(terminal_size::Width(w), _)| w as usize)
+    }
+
+    /// `preview: ...` が1行に収まる文字数
+    pub fn preview_chars() -> usize {
+        match Self::terminal_width() {
+            Some(width) => Self::preview_chars_for(width),
+            None => DEFAULT_WIDTH,
+        }
+    }
+
+    /// 端末幅（分からなければ既定の幅）
+    pub fn output_width() -> usize {
+        Self::terminal_width().unwrap_or(DEFAULT_WIDTH)
+    }
+
+    fn preview_chars_for(width: usize) -> usize {
+        // 切り詰め時に付く "..." の分も差し引く
+        width
+            .saturating_sub(PREVIEW_PREFIX.len() + 3)
+            .max(PREVIEW_MIN_CHARS)
+    }
+
+    fn format_memo_header(memo: &MemoFile) -> Vec<String> {
+        let mut lines = vec![
+            format!("id: {}", theme::paint(&memo.id.to_string(), Element::Id)),
+            format!(
+                "modified: {}",
+                theme::paint(&date::format_datetime(&memo.modified), Element::Date)
+            ),
+        ];
+        let title = memo.title();
+        if !title.is_empty() {
+            lines.push(format!("title: {}", title));
+        }
+
+        // メタデータエラーがある場合は表示
+        if let Some(error) = &memo.metadata_error {
+            lines.push(format!("metadata error: {}", error));
+        } else if let Some(metadata) = &memo.metadata
+            && !metadata.is_empty()
+        {
+            lines.push("metadata:".to_string());
+            for (key, value) in metadata {
+                let value = match value {
+                    Value::Sequence(tags) if key == "tags" => {
+                        let tags: Vec<String> = tags
+                            .iter()
+                            .map(|tag| theme::paint(&Self::format_yaml_value(tag), Element::Tag))
+                            .collect();
+                        format!("[{}]", tags.join(", "))
+                    }
+                    value => Self::format_yaml_value(value),
+                };
+                lines.push(format!("  {}: {}", key, value));
+            }
+        }
+        lines
+    }
+
+    pub fn display_memo_outline_list(memos: &[MemoFile], title: &str) {
+        if memos.is_empty() {
+            println!("{}", NO_MEMOS_MESSAGE);
+            return;
+        }
+
+        println!("{}:", title);
+        println!();
+        for memo in memos.iter() {
+            Self::display_memo_outline(memo);
+        }
+    }
+
+    /// ID, 日付, タイトル, タグの列を揃えた行に変換
+    ///
+    /// 列幅は内容に合わせ、収まらない分はタイトル（次いでタグ）を切り詰める
+    pub fn format_memo_table(memos: &[MemoFile], width: usize) -> Vec<String> {
+        let rows: Vec<[String; 4]> = memos
+            .iter()
+            .map(|memo| {
+                [
+                    memo.id.to_string(),
+                    date::format_datetime(&memo.modified),
+                    memo.title(),
+                    memo.tags().join(" "),
+                ]
+            })
+            .collect();
+
+        let mut widths = TABLE_HEADERS.map(text::display_width);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(text::display_width(cell));
+            }
+        }
+        widths[3] = widths[3].min(TAGS_MAX_WIDTH);
+
+        let fixed = widths[0] + widths[1] + widths[3] + TABLE_GAP.len() * 3;
+        widths[2] = widths[2].min(width.saturating_sub(fixed).max(TITLE_MIN_WIDTH));
+
+        // 幅の計算が狂わないよう、色は切り詰めた後に付け、空白は外に足す
+        let columns = [
+            Some(Element::Id),
+            Some(Element::Date),
+            None,
+            Some(Element::Tag),
+        ];
+        let format_row = |row: &[String; 4], colored: bool| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .zip(columns)
+                .map(|((cell, width), element)| {
+                    let cell = text::truncate_to_width(cell, width);
+                    let padded = text::pad_to_width(&cell, width);
+                    match element {
+                        Some(element) if colored => {
+                            format!("{}{}", theme::paint(&cell, element), &padded[cell.len()..])
+                        }
+                        _ => padded,
+                    }
+                })
+                .collect();
+            cells.join(TABLE_GAP).trim_end().to_string()
+        };
+
+        let header = format_row(&TABLE_HEADERS.map(str::to_string), false);
+        std::iter::once(color::paint(&header, Style::Bold))
+            .chain(rows.iter().map(|row| format_row(row, true)))
+            .collect()
+    }
+
+    /// 見出しを階層に応じてインデントした行に変換
+    pub fn format_outline(headings: &[Heading]) -> Vec<String> {
+        let min_level = headings.iter().map(|h| h.level).min().unwrap_or(1);
+        headings
+            .iter()
+            .map(|h| format!("{}- {}", "  ".repeat(h.level - min_level), h.text))
+            .collect()
+    }
+
+    fn format_yaml_value(value: &Value) -> String {
+        // NOTE: object not supported in YAML, so we return a placeholder
+        match value {
+            Value::String(s) => s.clone(),
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Sequence(seq) => {
+                let items: Vec<String> = seq.iter().map(Self::format_yaml_value).collect();
+                format!("[{}]", items.join(", "))
+            }
+            Value::Mapping(_) => "[object]".to_string(),
+            Value::Null => "null".to_string(),
+            _ => "unknown".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memo_id::MemoId;
+
+    fn memo(id: &str, content: &str, metadata: Option<&str>) -> MemoFile {
+        MemoFile {
+            id: MemoId::from_str(id).unwrap(),
+            path: Default::default(),
+            content: content.to_string(),
+            metadata: metadata.map(|m| serde_yaml::from_str(m).unwrap()),
+            metadata_error: None,
+            modified: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_format_memo_detail() {
+        let memo = memo("20250130143022", "# Title\nbody", Some("tags: ['@a']"));
+        let lines = MemoDisplayFormatter::format_memo_detail(&memo);
+
+        assert_eq!(lines[0], "id: 20250130143022");
+        assert_eq!(
+            lines[2..],
+            ["metadata:", "  tags: [@a]", "---", "# Title", "body"]
+        );
+    }
+
+    #[test]
+    fn test_format_memo_table() {
+        let memos = vec![
+            memo(
+                "20250130143022",
+                "# Weekly sync with a rather long title",
+                Some("tags: [\"@meeting\", \"@team\"]"),
+            ),
+            memo("20250129120000", "short", None),
+        ];
+
+        let lines = MemoDisplayFormatter::format_memo_table(&memos, 66);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("ID              DATE"));
+        assert!(lines[1].starts_with("20250130143022  "));
+        assert!(lines[1].contains("Weekly sync"));
+        assert!(lines[1].contains("…"));
+        assert!(lines[1].ends_with("@meeting @team"));
+        assert!(lines[2].ends_with("short"));
+
+        // 列の開始位置が揃っている
+        let title_column = lines[0].find("TITLE").unwrap();
+        assert_eq!(lines[1].chars().nth(title_column), Some('W'));
+        assert_eq!(&lines[2][title_column..], "short");
+        assert!(lines.iter().all(|line| line.chars().count() <= 66));
+    }
+
+    #[test]
+    fn test_format_memo_table_cjk_alignment() {
+        let memos = vec![
+            memo(
+                "20250130143022",
+                "# 日本語のタイトル",
+                Some("tags: [\"@会議\"]"),
+            ),
+            memo(
+                "20250129120000",
+                "# English title",
+                Some("tags: [\"@meeting\"]"),
+            ),
+        ];
+
+        let lines = MemoDisplayFormatter::format_memo_table(&memos, 100);
+        let tags_column = text::display_width(&lines[0][..lines[0].find("TAGS").unwrap()]);
+        for line in &lines[1..] {
+            let tags_at = line.find('@').unwrap();
+            assert_eq!(text::display_width(&line[..tags_at]), tags_column);
+        }
+    }
+
+    #[test]
+    fn test_preview_chars_for() {
+        assert_eq!(MemoDisplayFormatter::preview_chars_for(80), 68);
+        assert_eq!(
+            MemoDisplayFormatter::preview_chars_for(10),
+            PREVIEW_MIN_CHARS
+        );
+    }
+}
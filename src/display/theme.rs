@@ -0,0 +1,144 @@
+//! Colors of IDs, dates, tags and matched terms in list and search output.
+//!
+//! The colors come from `[theme]` in config.toml; whether they are used at all
+//! is still decided by `--color` / `NO_COLOR` in [`color`].
+
+use super::color;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// `[theme]` id = "yellow", date = "blue", tag = "cyan", match = "bold red"
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    pub id: ThemeColor,
+    pub date: ThemeColor,
+    pub tag: ThemeColor,
+    /// 検索・grep で一致した語
+    #[serde(rename = "match")]
+    pub matched: ThemeColor,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let color = |spec| ThemeColor::parse(spec).expect("valid default theme color");
+        Self {
+            id: color("yellow"),
+            date: color("blue"),
+            tag: color("cyan"),
+            matched: color("bold red"),
+        }
+    }
+}
+
+/// 色を付ける箇所
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Element {
+    Id,
+    Date,
+    Tag,
+    Match,
+}
+
+impl Theme {
+    pub fn color(&self, element: Element) -> &ThemeColor {
+        match element {
+            Element::Id => &self.id,
+            Element::Date => &self.date,
+            Element::Tag => &self.tag,
+            Element::Match => &self.matched,
+        }
+    }
+}
+
+/// Space separated attributes and a color, e.g. `bold bright-red` (`none` for no color)
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ThemeColor {
+    /// SGR のパラメータ（`1;91`）
+    sgr: String,
+}
+
+impl ThemeColor {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut codes = Vec::new();
+        for word in spec.split_whitespace() {
+            let code = match word {
+                "none" => continue,
+                "bold" => 1,
+                "dim" => 2,
+                "italic" => 3,
+                "underline" => 4,
+                _ => match word.strip_prefix("bright-") {
+                    Some(name) => color_code(name).map(|code| code + 60),
+                    None => color_code(word),
+                }
+                .ok_or_else(|| {
+                    format!(
+                        "unknown theme color '{}' (expected e.g. \"bold red\", \"bright-cyan\" or \"none\")",
+                        word
+                    )
+                })?,
+            };
+            codes.push(code.to_string());
+        }
+        Ok(Self {
+            sgr: codes.join(";"),
+        })
+    }
+
+    pub fn sgr(&self) -> &str {
+        &self.sgr
+    }
+}
+
+impl TryFrom<String> for ThemeColor {
+    type Error = String;
+
+    fn try_from(spec: String) -> Result<Self, Self::Error> {
+        Self::parse(&spec)
+    }
+}
+
+fn color_code(name: &str) -> Option<u8> {
+    let code = match name {
+        "black" => 30,
+        "red" => 31,
+        "green" => 32,
+        "yellow" => 33,
+        "blue" => 34,
+        "magenta" => 35,
+        "cyan" => 36,
+        "white" => 37,
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Use `theme` (config.toml `[theme]`) for the rest of the process
+///
+/// 最初の表示より前に呼ぶ。呼ばなければ既定の色
+pub fn init_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// Color the text for `element` if colors are enabled
+pub fn paint(text: &str, element: Element) -> String {
+    let theme = THEME.get_or_init(Theme::default);
+    color::paint_sgr(text, theme.color(element).sgr())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_theme_color() {
+        assert_eq!(ThemeColor::parse("bold red").unwrap().sgr(), "1;31");
+        assert_eq!(ThemeColor::parse("bright-cyan").unwrap().sgr(), "96");
+        assert_eq!(ThemeColor::parse("none").unwrap().sgr(), "");
+        assert!(ThemeColor::parse("bold purple").is_err());
+    }
+}
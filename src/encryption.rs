@@ -0,0 +1,256 @@
+//! Memos encrypted with [age](https://age-encryption.org), configured in config.toml:
+//!
+//! ```toml
+//! [encryption]
+//! identity = "~/.config/memo/identity.txt"   # age-keygen で作った秘密鍵
+//! index = false                              # 復号した本文を検索インデックスに入れるか
+//! ```
+//!
+//! Encrypted memos are stored as `<id>.md.age` next to plain memos. Once the key
+//! is installed (`MemoContext::from_config` does this), `MemoFile` reads and
+//! writes them transparently through [`read_memo`] / [`write_memo`].
+
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use age::x25519;
+use serde::Deserialize;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 暗号化したメモのファイル名は `<id>.md.age`
+pub const ENCRYPTED_SUFFIX: &str = ".md.age";
+
+/// 読み書きに使う鍵（プロセスで1つ）
+static KEY: RwLock<Option<EncryptionKey>> = RwLock::new(None);
+/// 暗号化したメモも検索インデックスに入れるか
+static INDEX_ENCRYPTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EncryptionConfig {
+    /// age の秘密鍵ファイル（`AGE-SECRET-KEY-1...` の行を含む）
+    pub identity: Option<PathBuf>,
+    /// 復号した本文を索引する（インデックスには平文が残る）
+    pub index: bool,
+}
+
+/// An age x25519 identity; memos are encrypted to its own public key
+#[derive(Clone)]
+pub struct EncryptionKey {
+    identity: x25519::Identity,
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 秘密鍵はログなどに出さない
+        write!(f, "EncryptionKey({})", self.identity.to_public())
+    }
+}
+
+impl EncryptionKey {
+    /// age の鍵ファイルから読む（`#` のコメント行と空行は無視）
+    pub fn load(path: &Path) -> MemoResult<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            MemoError::Encryption(format!("cannot read identity {}: {}", path.display(), e))
+        })?;
+        let line = content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .unwrap_or("");
+        Self::from_str(line)
+            .map_err(|e| MemoError::Encryption(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> MemoResult<Vec<u8>> {
+        age::encrypt(&self.identity.to_public(), plaintext.as_bytes())
+            .map_err(|e| MemoError::Encryption(e.to_string()))
+    }
+
+    pub fn decrypt(&self, ciphertext: &[u8]) -> MemoResult<String> {
+        let plaintext = age::decrypt(&self.identity, ciphertext)
+            .map_err(|e| MemoError::Encryption(e.to_string()))?;
+        String::from_utf8(plaintext)
+            .map_err(|_| MemoError::Encryption("decrypted memo is not UTF-8".to_string()))
+    }
+}
+
+impl FromStr for EncryptionKey {
+    type Err = MemoError;
+
+    fn from_str(s: &str) -> MemoResult<Self> {
+        let identity = x25519::Identity::from_str(s)
+            .map_err(|_| MemoError::Encryption("not an age secret key".to_string()))?;
+        Ok(Self { identity })
+    }
+}
+
+/// `MemoFile` などが使う鍵と、暗号化したメモを索引するかを設定する
+pub fn install(key: Option<EncryptionKey>, index: bool) {
+    if let Ok(mut current) = KEY.write() {
+        *current = key;
+    }
+    INDEX_ENCRYPTED.store(index, Ordering::Relaxed);
+}
+
+fn installed_key() -> MemoResult<EncryptionKey> {
+    KEY.read().ok().and_then(|key| key.clone()).ok_or_else(|| {
+        MemoError::Encryption("no key configured; set [encryption] identity".to_string())
+    })
+}
+
+pub fn is_encrypted(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(ENCRYPTED_SUFFIX))
+}
+
+/// `<id>.md` を `<id>.md.age` にする
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".age");
+    PathBuf::from(name)
+}
+
+/// 検索インデックスに入れてよいメモか
+pub fn is_indexable(path: &Path) -> bool {
+    !is_encrypted(path) || INDEX_ENCRYPTED.load(Ordering::Relaxed)
+}
+
+/// メモファイルを読む（`.md.age` は復号する）
+pub fn read_memo(path: &Path) -> MemoResult<String> {
+    if !is_encrypted(path) {
+        return fs::read_to_string(path).with_path(path);
+    }
+    installed_key()?.decrypt(&fs::read(path).with_path(path)?)
+}
+
+/// メモファイルに書く（`.md.age` は暗号化する）
+pub fn write_memo(path: &Path, content: &str) -> MemoResult<()> {
+    if !is_encrypted(path) {
+        return fs::write(path, content).with_path(path);
+    }
+    let ciphertext = installed_key()?.encrypt(content)?;
+    fs::write(path, ciphertext).with_path(path)
+}
+
+/// A plaintext copy of an encrypted memo for the editor.
+///
+/// 一時ファイルは本人だけが読める権限で作り、drop で消す。
+pub struct DecryptedCopy {
+    memo_path: PathBuf,
+    temp_path: PathBuf,
+    original: String,
+}
+
+impl DecryptedCopy {
+    pub fn create(memo_path: &Path) -> MemoResult<Self> {
+        let original = read_memo(memo_path)?;
+        let name = memo_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".age"))
+            .unwrap_or("memo.md");
+        let temp_path = std::env::temp_dir().join(format!("memo-{}-{}", std::process::id(), name));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&temp_path)
+            .with_path(&temp_path)?
+            .write_all(original.as_bytes())
+            .with_path(&temp_path)?;
+
+        Ok(Self {
+            memo_path: memo_path.to_path_buf(),
+            temp_path,
+            original,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.temp_path
+    }
+
+    /// 編集された内容を暗号化して書き戻す。変更が無ければ何もしない
+    pub fn save(&self) -> MemoResult<bool> {
+        let edited = fs::read_to_string(&self.temp_path).with_path(&self.temp_path)?;
+        if edited == self.original {
+            return Ok(false);
+        }
+        write_memo(&self.memo_path, &edited)?;
+        Ok(true)
+    }
+}
+
+impl Drop for DecryptedCopy {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.temp_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn generate_key() -> EncryptionKey {
+        EncryptionKey {
+            identity: x25519::Identity::generate(),
+        }
+    }
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let key = generate_key();
+        let ciphertext = key.encrypt("# Secret\n日本語も").unwrap();
+        assert!(!String::from_utf8_lossy(&ciphertext).contains("Secret"));
+        assert_eq!(key.decrypt(&ciphertext).unwrap(), "# Secret\n日本語も");
+        assert!(generate_key().decrypt(&ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_load_identity_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("identity.txt");
+        let identity = x25519::Identity::generate();
+        fs::write(
+            &path,
+            format!(
+                "# created: 2025-01-30\n# public key: {}\n{}\n",
+                identity.to_public(),
+                age::secrecy::ExposeSecret::expose_secret(&identity.to_string())
+            ),
+        )
+        .unwrap();
+
+        let key = EncryptionKey::load(&path).unwrap();
+        assert_eq!(
+            format!("{:?}", key),
+            format!("EncryptionKey({})", identity.to_public())
+        );
+        assert!(EncryptionKey::load(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_paths() {
+        let path = Path::new("/memo/2025-01/30/20250130143022.md");
+        let encrypted = encrypted_path(path);
+        assert_eq!(
+            encrypted,
+            Path::new("/memo/2025-01/30/20250130143022.md.age")
+        );
+        assert!(is_encrypted(&encrypted));
+        assert!(!is_encrypted(path));
+        assert!(is_indexable(path));
+    }
+}
@@ -1,51 +1,161 @@
-use std::fmt;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
 
-#[derive(Debug)]
+/// `AmbiguousId` のメッセージに並べる候補の上限
+const MAX_CANDIDATES: usize = 10;
+
+#[derive(Debug, Error)]
 pub enum MemoError {
-    Io(std::io::Error),
-    YamlError(serde_yaml::Error),
+    /// `path` は分かる時だけ（[`IoResultExt::with_path`] で付ける）
+    #[error("IO error: {}{source}", path_prefix(.path))]
+    Io {
+        #[source]
+        source: std::io::Error,
+        path: Option<PathBuf>,
+    },
+    #[error("YAML error: {0}")]
+    YamlError(#[from] serde_yaml::Error),
+    #[error("Memo with ID '{0}' not found")]
     MemoNotFound(String),
+    #[error("Section '{0}' not found")]
+    SectionNotFound(String),
+    #[error("Invalid memo ID: '{0}'")]
     InvalidId(String),
+    /// 短縮ID・IDの一部に一致したメモが複数ある（候補のID、新しい順）
+    #[error("Ambiguous memo ID; candidates:{}", candidate_lines(.0))]
+    AmbiguousId(Vec<String>),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("Editor error: {0}")]
     EditorError(String),
+    #[error("Archive error: {0}")]
     ArchiveError(String),
+    #[error("Search error: {0}")]
     Search(String),
+    /// 他のプロセスが検索インデックスを使っていて、待っても空かなかった
+    #[error("Search error: {0}")]
+    LockTimeout(String),
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+    #[error("Tokenizer error: {0}")]
     Tokenizer(String),
+    #[error("Template error: {0}")]
+    TemplateError(String),
+    #[error("Config error: {0}")]
+    ConfigError(String),
+    #[error("Git error: {0}")]
+    Git(String),
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+    /// `memo undo` で戻せない（ファイルが動かされた・ジャーナルが壊れているなど）
+    #[error("Undo error: {0}")]
+    Undo(String),
+}
+
+fn path_prefix(path: &Option<PathBuf>) -> String {
+    path.as_ref()
+        .map(|path| format!("{}: ", path.display()))
+        .unwrap_or_default()
+}
+
+fn candidate_lines(candidates: &[String]) -> String {
+    let mut lines: String = candidates
+        .iter()
+        .take(MAX_CANDIDATES)
+        .map(|candidate| format!("\n  {}", candidate))
+        .collect();
+    if candidates.len() > MAX_CANDIDATES {
+        lines.push_str(&format!(
+            "\n  ... and {} more",
+            candidates.len() - MAX_CANDIDATES
+        ));
+    }
+    lines
 }
 
-impl fmt::Display for MemoError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl MemoError {
+    /// Stable identifier of the kind of error (`--error-format json`)
+    ///
+    /// メッセージと違い、文言を変えても変わらない
+    pub fn code(&self) -> &'static str {
         match self {
-            MemoError::Io(err) => write!(f, "IO error: {}", err),
-            MemoError::YamlError(err) => write!(f, "YAML error: {}", err),
-            MemoError::MemoNotFound(id) => write!(f, "Memo with ID '{}' not found", id),
-            MemoError::InvalidId(id) => write!(f, "Invalid memo ID: '{}'", id),
-            MemoError::EditorError(msg) => write!(f, "Editor error: {}", msg),
-            MemoError::ArchiveError(msg) => write!(f, "Archive error: {}", msg),
-            MemoError::Search(msg) => write!(f, "Search error: {}", msg),
-            MemoError::Tokenizer(msg) => write!(f, "Tokenizer error: {}", msg),
+            MemoError::Io { .. } => "IO_ERROR",
+            MemoError::YamlError(_) => "YAML_ERROR",
+            MemoError::MemoNotFound(_) => "MEMO_NOT_FOUND",
+            MemoError::SectionNotFound(_) => "SECTION_NOT_FOUND",
+            MemoError::InvalidId(_) => "INVALID_ID",
+            MemoError::AmbiguousId(_) => "AMBIGUOUS_ID",
+            MemoError::InvalidArgument(_) => "INVALID_ARGUMENT",
+            MemoError::EditorError(_) => "EDITOR_ERROR",
+            MemoError::ArchiveError(_) => "ARCHIVE_ERROR",
+            MemoError::Search(_) => "SEARCH_ERROR",
+            MemoError::LockTimeout(_) => "INDEX_LOCKED",
+            MemoError::InvalidQuery(_) => "INVALID_QUERY",
+            MemoError::Tokenizer(_) => "TOKENIZER_ERROR",
+            MemoError::TemplateError(_) => "TEMPLATE_ERROR",
+            MemoError::ConfigError(_) => "CONFIG_ERROR",
+            MemoError::Git(_) => "GIT_ERROR",
+            MemoError::Encryption(_) => "ENCRYPTION_ERROR",
+            MemoError::Undo(_) => "UNDO_ERROR",
         }
     }
-}
 
-impl std::error::Error for MemoError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    /// 見つからなかった ID・候補の ID など、メッセージから読み取らずに済む情報
+    pub fn context(&self) -> Value {
         match self {
-            MemoError::Io(err) => Some(err),
-            MemoError::YamlError(err) => Some(err),
-            _ => None,
+            MemoError::Io { source, path } => json!({
+                "kind": source.kind().to_string(),
+                "path": path,
+            }),
+            MemoError::MemoNotFound(id) | MemoError::InvalidId(id) => json!({ "id": id }),
+            MemoError::SectionNotFound(heading) => json!({ "section": heading }),
+            MemoError::AmbiguousId(candidates) => json!({ "candidates": candidates }),
+            _ => json!({}),
         }
     }
+
+    /// stderr に出す 1 行（`Error: ...` か `{code, message, context}` の JSON）
+    pub fn render(&self, format: ErrorFormat) -> String {
+        match format {
+            ErrorFormat::Text => format!("Error: {}", self),
+            ErrorFormat::Json => json!({
+                "code": self.code(),
+                "message": self.to_string(),
+                "context": self.context(),
+            })
+            .to_string(),
+        }
+    }
+}
+
+/// `--error-format` の値
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ErrorFormat {
+    /// `Error: <message>`
+    #[default]
+    Text,
+    /// One JSON object: `{"code", "message", "context"}`
+    Json,
 }
 
 impl From<std::io::Error> for MemoError {
-    fn from(err: std::io::Error) -> Self {
-        MemoError::Io(err)
+    fn from(source: std::io::Error) -> Self {
+        MemoError::Io { source, path: None }
     }
 }
 
-impl From<serde_yaml::Error> for MemoError {
-    fn from(err: serde_yaml::Error) -> Self {
-        MemoError::YamlError(err)
+/// IO エラーに対象のファイルを付ける（`IO error: <path>: No such file or directory`）
+pub trait IoResultExt<T> {
+    fn with_path<P: AsRef<Path>>(self, path: P) -> MemoResult<T>;
+}
+
+impl<T> IoResultExt<T> for std::io::Result<T> {
+    fn with_path<P: AsRef<Path>>(self, path: P) -> MemoResult<T> {
+        self.map_err(|source| MemoError::Io {
+            source,
+            path: Some(path.as_ref().to_path_buf()),
+        })
     }
 }
 
@@ -62,3 +172,49 @@ impl From<tantivy::query::QueryParserError> for MemoError {
 }
 
 pub type MemoResult<T> = Result<T, MemoError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_json() {
+        let error = MemoError::MemoNotFound("20250130143022".to_string());
+        let rendered: Value = serde_json::from_str(&error.render(ErrorFormat::Json)).unwrap();
+        assert_eq!(
+            rendered,
+            json!({
+                "code": "MEMO_NOT_FOUND",
+                "message": "Memo with ID '20250130143022' not found",
+                "context": { "id": "20250130143022" },
+            })
+        );
+        assert_eq!(
+            error.render(ErrorFormat::Text),
+            "Error: Memo with ID '20250130143022' not found"
+        );
+    }
+
+    #[test]
+    fn test_io_error_with_path() {
+        let result: std::io::Result<()> = Err(std::io::ErrorKind::NotFound.into());
+        let error = result
+            .with_path("/tmp/memo/2025-01/30/20250130143022.md")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "IO error: /tmp/memo/2025-01/30/20250130143022.md: entity not found"
+        );
+        assert_eq!(error.code(), "IO_ERROR");
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_ambiguous_id_message() {
+        let error = MemoError::AmbiguousId(vec!["20250130143022".into(), "20240130143022".into()]);
+        assert_eq!(
+            error.to_string(),
+            "Ambiguous memo ID; candidates:\n  20250130143022\n  20240130143022"
+        );
+    }
+}
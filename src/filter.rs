@@ -0,0 +1,439 @@
+//! Small expression language over front matter used by `memo list --where`.
+//!
+//! ```text
+//! priority >= 2 && status != 'done'
+//! !(archived == true) || tags contains '@todo'
+//! due < 2025-02-01
+//! ```
+//!
+//! Fields are front matter keys (dotted for nested mappings). Comparisons
+//! against a missing field are false, except `!=` which is true.
+
+use crate::error::{MemoError, MemoResult};
+use crate::memo::MemoFile;
+use serde_yaml::Value;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Compare {
+        field: String,
+        op: CompareOp,
+        value: Literal,
+    },
+    /// bare field: true when present and not false/null
+    Truthy(String),
+}
+
+impl FilterExpr {
+    pub fn parse(input: &str) -> MemoResult<Self> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(filter_error(format!(
+                "unexpected token '{}'",
+                parser.tokens[parser.pos]
+            )));
+        }
+        Ok(expr)
+    }
+
+    pub fn matches(&self, memo: &MemoFile) -> bool {
+        match self {
+            FilterExpr::And(a, b) => a.matches(memo) && b.matches(memo),
+            FilterExpr::Or(a, b) => a.matches(memo) || b.matches(memo),
+            FilterExpr::Not(e) => !e.matches(memo),
+            FilterExpr::Compare { field, op, value } => {
+                compare(memo.metadata_value(field), *op, value)
+            }
+            FilterExpr::Truthy(field) => match memo.metadata_value(field) {
+                None | Some(Value::Null) | Some(Value::Bool(false)) => false,
+                Some(_) => true,
+            },
+        }
+    }
+}
+
+fn filter_error(msg: String) -> MemoError {
+    MemoError::InvalidArgument(format!("invalid --where expression: {}", msg))
+}
+
+fn compare(actual: Option<&Value>, op: CompareOp, expected: &Literal) -> bool {
+    let actual = match actual {
+        Some(Value::Null) | None => {
+            return match (op, expected) {
+                (CompareOp::Eq, Literal::Null) => true,
+                (CompareOp::Ne, Literal::Null) => false,
+                (CompareOp::Ne, _) => true,
+                _ => false,
+            };
+        }
+        Some(v) => v,
+    };
+
+    if op == CompareOp::Contains {
+        return match actual {
+            Value::Sequence(items) => items
+                .iter()
+                .any(|item| literal_cmp(item, expected) == Some(Ordering::Equal)),
+            Value::String(s) => match expected {
+                Literal::String(needle) => s.contains(needle.as_str()),
+                _ => false,
+            },
+            _ => false,
+        };
+    }
+
+    let ordering = literal_cmp(actual, expected);
+    match op {
+        CompareOp::Eq => ordering == Some(Ordering::Equal),
+        CompareOp::Ne => ordering != Some(Ordering::Equal),
+        CompareOp::Lt => ordering == Some(Ordering::Less),
+        CompareOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        CompareOp::Gt => ordering == Some(Ordering::Greater),
+        CompareOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        CompareOp::Contains => unreachable!(),
+    }
+}
+
+/// Compare a front matter value with a literal; `None` when the types don't match
+fn literal_cmp(actual: &Value, expected: &Literal) -> Option<Ordering> {
+    match (actual, expected) {
+        (Value::Number(n), Literal::Number(e)) => n.as_f64()?.partial_cmp(e),
+        (Value::String(s), Literal::String(e)) => Some(s.as_str().cmp(e.as_str())),
+        (Value::Bool(b), Literal::Bool(e)) => Some(b.cmp(e)),
+        (Value::Null, Literal::Null) => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(Literal),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(s) => write!(f, "{}", s),
+            Token::Literal(Literal::String(s)) => write!(f, "'{}'", s),
+            Token::Literal(Literal::Number(n)) => write!(f, "{}", n),
+            Token::Literal(Literal::Bool(b)) => write!(f, "{}", b),
+            Token::Literal(Literal::Null) => write!(f, "null"),
+            Token::Op(op) => write!(f, "{:?}", op),
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::Not => write!(f, "!"),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> MemoResult<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '&' if next == Some('&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if next == Some('|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '=' if next == Some('=') => {
+                tokens.push(Token::Op(CompareOp::Eq));
+                i += 2;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Op(CompareOp::Ne));
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '<' | '>' => {
+                let op = match (c, next == Some('=')) {
+                    ('<', true) => CompareOp::Le,
+                    ('<', false) => CompareOp::Lt,
+                    ('>', true) => CompareOp::Ge,
+                    _ => CompareOp::Gt,
+                };
+                tokens.push(Token::Op(op));
+                i += if next == Some('=') { 2 } else { 1 };
+            }
+            '\'' | '"' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| filter_error("unterminated string".to_string()))?;
+                let s: String = chars[i + 1..i + 1 + end].iter().collect();
+                tokens.push(Token::Literal(Literal::String(s)));
+                i += end + 2;
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                // 2025-02-01 や 2025-02-01T10:00 は日付の文字列として読む
+                if chars.get(i) == Some(&'-') && chars.get(i + 1).is_some_and(char::is_ascii_digit)
+                {
+                    while i < chars.len() && (is_ident_char(chars[i]) || chars[i] == ':') {
+                        i += 1;
+                    }
+                    let s: String = chars[start..i].iter().collect();
+                    tokens.push(Token::Literal(Literal::String(s)));
+                    continue;
+                }
+                let s: String = chars[start..i].iter().collect();
+                let n = s
+                    .parse::<f64>()
+                    .map_err(|_| filter_error(format!("invalid number '{}'", s)))?;
+                tokens.push(Token::Literal(Literal::Number(n)));
+            }
+            c if is_ident_char(c) => {
+                let start = i;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(match word.as_str() {
+                    "true" => Token::Literal(Literal::Bool(true)),
+                    "false" => Token::Literal(Literal::Bool(false)),
+                    "null" => Token::Literal(Literal::Null),
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "contains" => Token::Op(CompareOp::Contains),
+                    _ => Token::Ident(word),
+                });
+            }
+            other => return Err(filter_error(format!("unexpected character '{}'", other))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '@')
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> MemoResult<FilterExpr> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = FilterExpr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> MemoResult<FilterExpr> {
+        let mut left = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            left = FilterExpr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> MemoResult<FilterExpr> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> MemoResult<FilterExpr> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(filter_error("missing ')'".to_string())),
+                }
+            }
+            Some(Token::Ident(field)) => {
+                let op = match self.peek() {
+                    Some(Token::Op(op)) => *op,
+                    _ => return Ok(FilterExpr::Truthy(field)),
+                };
+                self.pos += 1;
+                match self.next() {
+                    Some(Token::Literal(value)) => Ok(FilterExpr::Compare { field, op, value }),
+                    // allow unquoted words on the right-hand side (status == done)
+                    Some(Token::Ident(word)) => Ok(FilterExpr::Compare {
+                        field,
+                        op,
+                        value: Literal::String(word),
+                    }),
+                    Some(other) => Err(filter_error(format!(
+                        "expected a value after '{}', found '{}'",
+                        field, other
+                    ))),
+                    None => Err(filter_error(format!("expected a value after '{}'", field))),
+                }
+            }
+            Some(other) => Err(filter_error(format!("unexpected token '{}'", other))),
+            None => Err(filter_error("unexpected end of expression".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn memo_with(front_matter: &str) -> (tempfile::TempDir, MemoFile) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("2025-01/30/20250130143022.md");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, format!("---\n{}---\nbody", front_matter)).unwrap();
+        let memo = MemoFile::from_path(&path).unwrap();
+        (temp_dir, memo)
+    }
+
+    fn eval(expr: &str, memo: &MemoFile) -> bool {
+        FilterExpr::parse(expr).unwrap().matches(memo)
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        let expr = FilterExpr::parse("a || b && !c").unwrap();
+        assert_eq!(
+            expr,
+            FilterExpr::Or(
+                Box::new(FilterExpr::Truthy("a".to_string())),
+                Box::new(FilterExpr::And(
+                    Box::new(FilterExpr::Truthy("b".to_string())),
+                    Box::new(FilterExpr::Not(Box::new(FilterExpr::Truthy(
+                        "c".to_string()
+                    ))))
+                ))
+            )
+        );
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let (_dir, memo) = memo_with(
+            "priority: 2\nstatus: active\ndone: false\ntags: ['@todo', '@work']\nauthor:\n  name: John\n",
+        );
+
+        assert!(eval("priority >= 2 && status != 'done'", &memo));
+        assert!(!eval("priority > 2", &memo));
+        assert!(eval("priority == 2.0", &memo));
+        assert!(eval("status == active", &memo));
+        assert!(eval("tags contains '@todo'", &memo));
+        assert!(!eval("tags contains '@home'", &memo));
+        assert!(eval("author.name == \"John\"", &memo));
+        assert!(eval("!done && (priority < 1 || status == 'active')", &memo));
+        assert!(eval("not done and priority <= 2", &memo));
+    }
+
+    #[test]
+    fn test_unquoted_dates() {
+        let (_dir, memo) = memo_with("due: 2025-01-31\nstarted: 2025-01-30T10:00:00\n");
+
+        assert_eq!(
+            FilterExpr::parse("due < 2025-02-01").unwrap(),
+            FilterExpr::parse("due < '2025-02-01'").unwrap()
+        );
+        assert!(eval("due < 2025-02-01", &memo));
+        assert!(!eval("due >= 2025-02-01 && due != 2025-01-31", &memo));
+        assert!(eval(
+            "started > 2025-01-30T09:00 && due == 2025-01-31",
+            &memo
+        ));
+        assert!(eval("priority > -1 || due", &memo));
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        let (_dir, memo) = memo_with("priority: 2\n");
+
+        assert!(!eval("status == 'done'", &memo));
+        assert!(eval("status != 'done'", &memo));
+        assert!(eval("status == null", &memo));
+        assert!(!eval("status", &memo));
+        assert!(!eval("priority == 'high'", &memo));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(FilterExpr::parse("").is_err());
+        assert!(FilterExpr::parse("priority >=").is_err());
+        assert!(FilterExpr::parse("(priority > 1").is_err());
+        assert!(FilterExpr::parse("status == 'open").is_err());
+        assert!(FilterExpr::parse("priority > 1 )").is_err());
+        assert!(FilterExpr::parse("priority $ 1").is_err());
+    }
+}
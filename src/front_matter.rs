@@ -1,5 +1,5 @@
-use serde_yaml::Value;
-use std::collections::HashMap;
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone)]
 pub struct MemoContent {
@@ -9,8 +9,17 @@ pub struct MemoContent {
     pub front_matter_error: Option<String>,
 }
 
+/// The line ending of the first line (`\r\n` or `\n`), used for the front matter delimiters.
+fn line_ending(content: &str) -> &'static str {
+    match content.find('\n') {
+        Some(end) if content[..end].ends_with('\r') => "\r\n",
+        _ => "\n",
+    }
+}
+
 pub fn parse_memo_content(content: &str) -> MemoContent {
-    let delimiter = "---\n";
+    let delimiter = format!("---{}", line_ending(content));
+    let delimiter = delimiter.as_str();
 
     // Check if content starts with front matter delimiter
     if !content.starts_with(delimiter) {
@@ -55,6 +64,116 @@ pub fn parse_memo_content(content: &str) -> MemoContent {
     }
 }
 
+/// Set a top-level field in the front matter, leaving the other lines untouched.
+///
+/// If the content has no front matter, a new block containing only the field is prepended.
+/// The content's line ending (`\n` or `\r\n`) is kept.
+pub fn set_field(content: &str, key: &str, value: &str) -> String {
+    let newline = line_ending(content);
+    let delimiter = format!("---{}", newline);
+    let delimiter = delimiter.as_str();
+    let new_line = format!("{}: {}", key, value);
+
+    let block = content
+        .strip_prefix(delimiter)
+        .and_then(|rest| rest.find(delimiter).map(|end| (&rest[..end], &rest[end..])));
+
+    let (yaml_content, remaining) = match block {
+        Some(block) => block,
+        None => {
+            return format!(
+                "{}{}{}{}{}",
+                delimiter, new_line, newline, delimiter, content
+            );
+        }
+    };
+
+    let mut lines = Vec::new();
+    let mut replaced = false;
+    let mut skipping_value = false;
+    let key_prefix = format!("{}:", key);
+    for line in yaml_content.lines() {
+        if skipping_value {
+            // drop continuation lines of the old value (nested or sequence items)
+            if line.starts_with(' ') || line.starts_with('\t') || line.starts_with("- ") {
+                continue;
+            }
+            skipping_value = false;
+        }
+        if !replaced && line.starts_with(&key_prefix) {
+            lines.push(new_line.clone());
+            replaced = true;
+            skipping_value = true;
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+    if !replaced {
+        lines.push(new_line);
+    }
+
+    format!(
+        "{}{}{}{}",
+        delimiter,
+        lines.join(newline),
+        newline,
+        remaining
+    )
+}
+
+/// Build memo file content from a front matter map and the body.
+///
+/// Keys are written in sorted order, so the same front matter always produces the same text
+/// and `parse_memo_content` gives back the same map and body.
+pub fn serialize_memo_content(
+    front_matter: &HashMap<String, Value>,
+    content: &str,
+) -> Result<String, serde_yaml::Error> {
+    let delimiter = "---\n";
+    if front_matter.is_empty() {
+        return Ok(format!("{}{}{}", delimiter, delimiter, content));
+    }
+
+    let sorted: BTreeMap<&String, &Value> = front_matter.iter().collect();
+    let yaml = serde_yaml::to_string(&sorted)?;
+    Ok(format!("{}{}{}{}", delimiter, yaml, delimiter, content))
+}
+
+/// Set a value by dotted key (e.g. `author.name`), creating intermediate mappings.
+///
+/// A non-mapping value in the way is replaced.
+pub fn set_value(front_matter: &mut HashMap<String, Value>, key: &str, value: Value) {
+    match key.split_once('.') {
+        None => {
+            front_matter.insert(key.to_string(), value);
+        }
+        Some((head, rest)) => {
+            let child = front_matter.entry(head.to_string()).or_insert(Value::Null);
+            set_nested(child, rest, value);
+        }
+    }
+}
+
+fn set_nested(parent: &mut Value, key: &str, value: Value) {
+    if !parent.is_mapping() {
+        *parent = Value::Mapping(Mapping::new());
+    }
+    let Value::Mapping(mapping) = parent else {
+        return;
+    };
+    match key.split_once('.') {
+        None => {
+            mapping.insert(Value::String(key.to_string()), value);
+        }
+        Some((head, rest)) => {
+            let child = mapping
+                .entry(Value::String(head.to_string()))
+                .or_insert(Value::Null);
+            set_nested(child, rest, value);
+        }
+    }
+}
+
 // Function used by tests
 #[cfg(test)]
 mod tests {
@@ -101,4 +220,113 @@ Content here"#;
         assert_eq!(memo.content, "Content here");
         assert!(memo.front_matter_error.is_none());
     }
+
+    #[test]
+    fn test_set_field_replaces_existing() {
+        let content = "---\ntitle: Test\nupdated_at: old\ntags: ['@a']\n---\nbody";
+
+        assert_eq!(
+            set_field(content, "updated_at", "new"),
+            "---\ntitle: Test\nupdated_at: new\ntags: ['@a']\n---\nbody"
+        );
+    }
+
+    #[test]
+    fn test_set_field_inserts_into_existing_front_matter() {
+        let content = "---\n# comment\ntitle:   Test\n---\nbody";
+
+        assert_eq!(
+            set_field(content, "updated_at", "now"),
+            "---\n# comment\ntitle:   Test\nupdated_at: now\n---\nbody"
+        );
+        assert_eq!(
+            set_field("---\n---\nbody", "updated_at", "now"),
+            "---\nupdated_at: now\n---\nbody"
+        );
+    }
+
+    #[test]
+    fn test_set_field_creates_front_matter() {
+        assert_eq!(
+            set_field("body", "updated_at", "now"),
+            "---\nupdated_at: now\n---\nbody"
+        );
+    }
+
+    #[test]
+    fn test_set_field_replaces_multiline_value() {
+        let content = "---\nupdated_at:\n  - a\n  - b\ntitle: x\n---\n";
+
+        assert_eq!(
+            set_field(content, "updated_at", "now"),
+            "---\nupdated_at: now\ntitle: x\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_crlf_front_matter() {
+        let content = "---\r\ntitle: Test\r\nupdated_at: old\r\n---\r\nbody\r\n";
+
+        let memo = parse_memo_content(content);
+        assert_eq!(
+            memo.front_matter.unwrap().get("title").unwrap(),
+            &Value::String("Test".to_string())
+        );
+        assert_eq!(memo.content, "body\r\n");
+
+        assert_eq!(
+            set_field(content, "updated_at", "new"),
+            "---\r\ntitle: Test\r\nupdated_at: new\r\n---\r\nbody\r\n"
+        );
+        assert_eq!(
+            set_field(content, "status", "done"),
+            "---\r\ntitle: Test\r\nupdated_at: old\r\nstatus: done\r\n---\r\nbody\r\n"
+        );
+        assert_eq!(
+            set_field("body\r\n", "status", "done"),
+            "---\r\nstatus: done\r\n---\r\nbody\r\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_memo_content_round_trip() {
+        let content = "---\ntitle: Test\npriority: 2\ntags: ['@a', '@b']\nauthor:\n  name: me\n---\n# Body\n\ntext\n";
+        let parsed = parse_memo_content(content);
+        let front_matter = parsed.front_matter.unwrap();
+
+        let serialized = serialize_memo_content(&front_matter, &parsed.content).unwrap();
+        assert_eq!(
+            serialized,
+            "---\nauthor:\n  name: me\npriority: 2\ntags:\n- '@a'\n- '@b'\ntitle: Test\n---\n# Body\n\ntext\n"
+        );
+        // 何度書き直しても同じ内容になる
+        let reparsed = parse_memo_content(&serialized);
+        assert_eq!(reparsed.front_matter.unwrap(), front_matter);
+        assert_eq!(reparsed.content, parsed.content);
+        assert_eq!(
+            serialize_memo_content(&front_matter, &parsed.content).unwrap(),
+            serialized
+        );
+
+        assert_eq!(
+            serialize_memo_content(&HashMap::new(), "body").unwrap(),
+            "---\n---\nbody"
+        );
+    }
+
+    #[test]
+    fn test_set_value_dotted_key() {
+        let mut front_matter = parse_memo_content("---\nauthor: someone\n---\n")
+            .front_matter
+            .unwrap();
+
+        set_value(&mut front_matter, "priority", Value::from(3));
+        set_value(&mut front_matter, "author.name", Value::from("me"));
+        set_value(&mut front_matter, "author.team", Value::from("core"));
+
+        assert_eq!(
+            serialize_memo_content(&front_matter, "").unwrap(),
+            "---\nauthor:\n  name: me\n  team: core\npriority: 3\n---\n"
+        );
+    }
 }
@@ -0,0 +1,221 @@
+//! Optional git versioning of the memo directory, configured in config.toml:
+//!
+//! ```toml
+//! [git]
+//! auto_commit = true   # memo add / edit / archive の後にコミットする
+//! remote = "origin"    # memo sync で pull / push する先（省略時は origin）
+//! ```
+//!
+//! `memo sync` creates the repository on first use. Everything goes through the
+//! `git` command, so the user's git configuration (identity, credentials) applies.
+
+use crate::config::Config;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::memo::MemoFile;
+use chrono::Local;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// メモではないのでバージョン管理しない
+const GITIGNORE_ENTRIES: &[&str] = &[
+    ".index/",
+    ".trash/",
+    ".daemon.sock",
+    ".shell_history",
+    ".clock",
+    ".memo-journal",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitConfig {
+    /// 変更のたびにコミットする
+    pub auto_commit: bool,
+    /// `memo sync` で pull / push するリモート
+    pub remote: String,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            auto_commit: false,
+            remote: "origin".to_string(),
+        }
+    }
+}
+
+/// What `GitRepo::sync` did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncResult {
+    pub initialized: bool,
+    pub committed: bool,
+    pub pulled: bool,
+    pub pushed: bool,
+}
+
+/// The memo directory as a git working tree
+pub struct GitRepo {
+    dir: PathBuf,
+}
+
+impl GitRepo {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.dir.join(".git").exists()
+    }
+
+    /// `git init` して、インデックスなどを `.gitignore` に入れる
+    pub fn init(&self) -> MemoResult<()> {
+        self.git(&["init", "--quiet"])?;
+        self.ensure_gitignore()
+    }
+
+    /// 全ての変更をコミットする。変更が無ければ `false`
+    pub fn commit_all(&self, message: &str) -> MemoResult<bool> {
+        self.git(&["add", "--all"])?;
+        if self.git(&["status", "--porcelain"])?.trim().is_empty() {
+            return Ok(false);
+        }
+        self.git(&["commit", "--quiet", "-m", message])?;
+        Ok(true)
+    }
+
+    pub fn has_remote(&self, remote: &str) -> MemoResult<bool> {
+        Ok(self.git(&["remote"])?.lines().any(|line| line == remote))
+    }
+
+    /// 初期化・コミットの後、リモートがあれば pull --rebase と push
+    pub fn sync(&self, remote: &str) -> MemoResult<SyncResult> {
+        let mut result = SyncResult::default();
+        if !self.is_initialized() {
+            self.init()?;
+            result.initialized = true;
+        }
+        result.committed = self.commit_all(&commit_message("sync", &[]))?;
+
+        if !self.has_remote(remote)? {
+            return Ok(result);
+        }
+        let branch = self.git(&["rev-parse", "--abbrev-ref", "HEAD"])?;
+        let branch = branch.trim();
+        // リモートにまだブランチが無ければ push だけ
+        if !self
+            .git(&["ls-remote", "--heads", remote, branch])?
+            .trim()
+            .is_empty()
+        {
+            self.git(&["pull", "--quiet", "--rebase", remote, branch])?;
+            result.pulled = true;
+        }
+        if self
+            .git(&["rev-parse", "--verify", "--quiet", "HEAD"])
+            .is_ok()
+        {
+            self.git(&["push", "--quiet", remote, &format!("HEAD:{}", branch)])?;
+            result.pushed = true;
+        }
+        Ok(result)
+    }
+
+    fn ensure_gitignore(&self) -> MemoResult<()> {
+        let path = self.dir.join(".gitignore");
+        let mut content = if path.exists() {
+            fs::read_to_string(&path).with_path(&path)?
+        } else {
+            String::new()
+        };
+        let missing: Vec<&str> = GITIGNORE_ENTRIES
+            .iter()
+            .copied()
+            .filter(|entry| !content.lines().any(|line| line.trim() == *entry))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        for entry in missing {
+            content.push_str(entry);
+            content.push('\n');
+        }
+        fs::write(&path, content).with_path(&path)?;
+        Ok(())
+    }
+
+    fn git(&self, args: &[&str]) -> MemoResult<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.dir)
+            .args(args)
+            .output()
+            .map_err(|e| MemoError::Git(format!("failed to run git: {}", e)))?;
+        if !output.status.success() {
+            return Err(MemoError::Git(format!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/// `memo: edit 20250130143022 (2025-01-30 14:30:22)`
+pub fn commit_message(action: &str, memos: &[MemoFile]) -> String {
+    let mut message = format!("memo: {}", action);
+    for memo in memos {
+        message.push(' ');
+        message.push_str(&memo.id.as_str());
+    }
+    format!("{} ({})", message, Local::now().format("%Y-%m-%d %H:%M:%S"))
+}
+
+/// `[git] auto_commit` が有効で、メモディレクトリが git リポジトリならコミットする
+///
+/// webhook と同じく失敗しても警告だけでコマンドは成功させる
+pub fn auto_commit(config: &Config, memo_dir: &Path, action: &str, memos: &[MemoFile]) {
+    let repo = GitRepo::new(memo_dir);
+    if !config.git.auto_commit || !repo.is_initialized() {
+        return;
+    }
+    if let Err(e) = repo.commit_all(&commit_message(action, memos)) {
+        eprintln!("Warning: git auto commit failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_gitignore_entries_are_appended_once() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "*.tmp\n.index/").unwrap();
+
+        let repo = GitRepo::new(temp_dir.path());
+        repo.ensure_gitignore().unwrap();
+        repo.ensure_gitignore().unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap();
+        assert_eq!(
+            content,
+            "*.tmp\n.index/\n.trash/\n.daemon.sock\n.shell_history\n.clock\n.memo-journal\n"
+        );
+    }
+
+    #[test]
+    fn test_commit_message() {
+        let message = commit_message("sync", &[]);
+        assert!(message.starts_with("memo: sync ("));
+        assert!(message.ends_with(')'));
+    }
+}
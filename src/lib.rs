@@ -1,14 +1,30 @@
+pub mod alias;
+pub mod api;
 pub mod commands;
+pub mod config;
 pub mod context;
 pub mod display;
+pub mod encryption;
 pub mod error;
+pub mod filter;
 pub mod front_matter;
+pub mod git;
+pub mod links;
+pub mod markdown;
 pub mod memo;
 pub mod memo_id;
+pub mod ops;
+pub mod projection;
+pub mod render;
 pub mod repository;
 pub mod search;
+pub mod tag_index;
+pub mod template;
 pub mod utils;
+pub mod views;
+pub mod webhook;
 
+pub use api::{MemoApp, MemoAppBuilder};
 pub use commands::*;
 pub use context::*;
 pub use display::*;
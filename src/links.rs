@@ -0,0 +1,100 @@
+//! Links between memos written as `[[20250130143022]]` or `[[2025-01/30/143022]]`.
+//!
+//! A link may carry a label after `|` (`[[20250130143022|design notes]]`).
+//! Outgoing links are parsed from the body; backlinks come from the `links`
+//! field of the search index, or from reading every memo when there is no index.
+
+use crate::context::MemoContext;
+use crate::error::MemoResult;
+use crate::memo_id::MemoId;
+use crate::repository::MemoRepository;
+use regex::Regex;
+use std::sync::LazyLock;
+
+static LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[\[([^\[\]|]+)(?:\|[^\[\]]*)?\]\]").expect("valid link regex"));
+
+/// 本文中のリンク先（出てきた順、重複なし）。ID として読めないものは無視する
+pub fn parse_links(content: &str) -> Vec<MemoId> {
+    let mut targets: Vec<MemoId> = Vec::new();
+    for caps in LINK.captures_iter(content) {
+        if let Some(id) = parse_target(&caps[1])
+            && !targets.contains(&id)
+        {
+            targets.push(id);
+        }
+    }
+    targets
+}
+
+/// `20250130143022` / `2025-01/30/143022` / `2025-01/30/20250130143022.md` を ID にする
+pub fn parse_target(target: &str) -> Option<MemoId> {
+    let target = target.trim();
+    let target = target.strip_suffix(".md").unwrap_or(target);
+    if let Ok(id) = MemoId::from_str(target) {
+        return Some(id);
+    }
+
+    let mut parts = target.split('/');
+    let (Some(year_month), Some(day), Some(name), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    if name.len() == 14 {
+        return MemoId::from_str(name).ok();
+    }
+    let (year, month) = year_month.split_once('-')?;
+    MemoId::from_str(&format!("{}{}{}{}", year, month, day, name)).ok()
+}
+
+/// `id` にリンクしているメモ（ID 順）
+///
+/// 検索インデックスがあればそれを使う（`memo index` 以降の変更は反映されない）
+pub fn backlinks(context: &MemoContext, id: &MemoId) -> MemoResult<Vec<MemoId>> {
+    if let Some(ids) = context.search_manager().backlinks(&id.as_str())? {
+        return Ok(ids
+            .iter()
+            .filter_map(|id| MemoId::from_str(id).ok())
+            .collect());
+    }
+
+    let repo = MemoRepository::new(context.clone());
+    let mut sources = Vec::new();
+    for entry in repo.iter_memos()? {
+        // 読めないメモ（鍵の無い暗号化メモなど）は飛ばす
+        if let Ok(memo) = entry.load()
+            && parse_links(&memo.content).contains(id)
+        {
+            sources.push(memo.id);
+        }
+    }
+    sources.sort();
+    Ok(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_links() {
+        let content = "See [[20250130143022]] and [[2025-01/29/120000|yesterday]].\n\
+                       Again [[20250130143022]], [[2025-01/28/20250128090000.md]], [[not a memo]]";
+        let ids: Vec<String> = parse_links(content).iter().map(MemoId::as_str).collect();
+        assert_eq!(
+            ids,
+            vec!["20250130143022", "20250129120000", "20250128090000"]
+        );
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert!(parse_target("2025-01/30").is_none());
+        assert!(parse_target("2025-13/30/143022").is_none());
+        assert_eq!(
+            parse_target(" 2025-01/30/143022 ").unwrap().as_str(),
+            "20250130143022"
+        );
+    }
+}
@@ -1,26 +1,74 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 use std::process;
 
+mod alias;
 mod commands;
+mod config;
 mod context;
 mod display;
+mod encryption;
 mod error;
+mod filter;
 mod front_matter;
+mod git;
+mod links;
+mod markdown;
 mod memo;
 mod memo_id;
+mod ops;
+mod projection;
+mod render;
 mod repository;
 mod search;
+mod tag_index;
+mod template;
 mod utils;
+mod views;
+mod webhook;
 
+use commands::config as config_cmd;
+use commands::links as links_cmd;
 use commands::search as search_cmd;
-use commands::{add, archive, dir, edit, index, list, migrate, show, tags, template};
+use commands::template as template_cmd;
+use commands::{
+    add, archive, attach, cat, clock, complete, daemon, delete, dir, doctor, due, edit, export,
+    grep, help, import, index, list, meta, migrate, mv, notebook, open, recent, restore, serve,
+    shell, show, similar, stats, status, sync, tags, touch, tui, undo, view, watch,
+};
+use config::Config;
 use context::MemoContext;
-use error::MemoError;
+use display::color::{self, ColorChoice};
+use display::format::OutputFormat;
+use error::{ErrorFormat, MemoError};
+use repository::{DateBasis, SortKey};
+use search::SearchSort;
 
 #[derive(Parser)]
 #[command(name = "memo")]
 #[command(about = "A simple memo management tool")]
+#[command(disable_help_subcommand = true)]
 struct Cli {
+    /// When to use colored output (NO_COLOR is honored in auto mode)
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Same as --color never
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Output format for memo lists (list, search)
+    #[arg(long, global = true, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Use this notebook instead of the one set with `memo notebook use`
+    #[arg(long, global = true, value_name = "NAME")]
+    notebook: Option<String>,
+
+    /// How errors are printed to stderr (json: {"code", "message", "context"})
+    #[arg(long, global = true, value_enum, default_value_t)]
+    error_format: ErrorFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -32,11 +80,80 @@ enum Commands {
         /// Template name (e.g. 1on1, idea, todo, meeting)
         #[arg(long)]
         template: Option<String>,
+        /// Value for the template's {{title}} placeholder
+        #[arg(long, requires = "template")]
+        title: Option<String>,
+        /// Memo content; creates the memo without opening the editor
+        #[arg(short, long, conflicts_with = "stdin")]
+        message: Option<String>,
+        /// `-` to read the memo content from stdin instead of opening the editor
+        #[arg(value_name = "-", value_parser = ["-"])]
+        stdin: Option<String>,
+        /// Store the memo encrypted with the `[encryption] identity` key
+        #[arg(long)]
+        encrypt: bool,
+    },
+    /// Edit existing memos by ID (all are opened in a single editor)
+    Edit {
+        /// Memo IDs (`today` opens today's 00:00:00 memo, creating it if missing)
+        #[arg(required_unless_present = "last")]
+        ids: Vec<String>,
+        /// Edit the N most recently modified memos
+        #[arg(long, value_name = "N", conflicts_with = "ids")]
+        last: Option<usize>,
+        /// Create memos that do not exist yet at the path for their (full) ID
+        #[arg(long, conflicts_with = "last")]
+        create: bool,
+    },
+    /// Bump a memo's updated_at and modified time without editing
+    Touch { id: String },
+    /// Change a memo's date/time (its ID), moving the file and its attachments
+    Mv {
+        id: String,
+        /// New ID or date/time (e.g. 20250130143022, "2025-01-30 14:30")
+        #[arg(value_name = "NEW_DATETIME")]
+        to: String,
+        /// Also write the new date/time to created_at in the front matter
+        #[arg(long)]
+        created_at: bool,
+    },
+    /// Copy files into a memo's attachment directory
+    Attach {
+        id: String,
+        /// Files to attach
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
     },
-    /// Edit an existing memo by ID
-    Edit { id: String },
     /// Show memo content by ID
-    Show { id: String },
+    Show {
+        id: String,
+        /// Show heading structure instead of content
+        #[arg(long, conflicts_with = "section")]
+        outline: bool,
+        /// Show only the section under the given heading
+        #[arg(long)]
+        section: Option<String>,
+        /// Render with a Handlebars template file
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["outline", "section"])]
+        template: Option<PathBuf>,
+        /// Render markdown (headings, emphasis, lists, highlighted code) for the terminal
+        #[arg(long, conflicts_with_all = ["outline", "template"])]
+        pretty: bool,
+        /// Print id, path, dates, metadata and content as one JSON object
+        #[arg(long, conflicts_with_all = ["outline", "section", "template", "pretty"])]
+        json: bool,
+    },
+    /// Print memos one after another, oldest first, without front matter
+    Cat {
+        #[arg(required_unless_present = "date")]
+        ids: Vec<String>,
+        /// Also print every memo created on this date (e.g. 2025-01-30)
+        #[arg(long, value_name = "DATE")]
+        date: Option<String>,
+        /// Line printed between memos instead of a blank line (e.g. ---)
+        #[arg(long, value_name = "TEXT")]
+        separator: Option<String>,
+    },
     /// List all memos
     List {
         /// Output in JSONL format
@@ -45,17 +162,207 @@ enum Commands {
         /// Filter by tag (e.g. @1on1)
         #[arg(long)]
         tag: Option<String>,
+        /// Show heading structure instead of preview
+        #[arg(long)]
+        outline: bool,
+        /// Show an aligned table of ID, date, title and tags
+        #[arg(long, conflicts_with_all = ["json", "outline"])]
+        table: bool,
+        /// Sort by a front matter field (e.g. meta.priority); memos without it come last
+        #[arg(long, value_name = "meta.FIELD")]
+        sort_by: Option<String>,
+        /// Sort with a built-in ordering
+        #[arg(long, value_enum, conflicts_with = "sort_by")]
+        sort: Option<SortKey>,
+        /// Reverse the order (newest first by default)
+        #[arg(long)]
+        reverse: bool,
+        /// Only show memos whose `priority:` is at least this value
+        #[arg(long, value_name = "N")]
+        min_priority: Option<i64>,
+        /// Filter by front matter expression (e.g. "priority >= 2 && status != 'done'")
+        #[arg(long = "where", value_name = "EXPR")]
+        where_expr: Option<String>,
+        /// Only output these comma separated fields in JSON (e.g. id,metadata.priority)
+        #[arg(long, value_name = "FIELDS", requires = "json")]
+        select: Option<String>,
+        /// Render with a Handlebars template file (memos are available as `memos`)
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["json", "outline", "table"])]
+        template: Option<PathBuf>,
+        /// Only memos created on or after this date (e.g. 2025-01-01)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+        /// Only memos created on or before this date
+        #[arg(long, value_name = "DATE")]
+        until: Option<String>,
+        /// Show at most N memos
+        #[arg(long, value_name = "N", value_parser = page_parser())]
+        limit: Option<usize>,
+        /// Skip the first N memos
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        offset: usize,
+        /// Show the Nth page of --limit memos (20 if no limit is set)
+        #[arg(long, value_name = "N", conflicts_with = "offset", value_parser = page_parser())]
+        page: Option<usize>,
+    },
+    /// List memos created today, or create a new one with --add
+    Today {
+        /// Create a new memo instead of listing
+        #[arg(long)]
+        add: bool,
+    },
+    /// List memos created yesterday
+    Yesterday,
+    /// Show the most recently modified memo
+    Last,
+    /// Print the absolute path of a memo's file, or open it with the system default app
+    Open {
+        id: String,
+        /// Launch the system default app (xdg-open / open) instead of printing the path
+        #[arg(long)]
+        app: bool,
+        /// Use the directory containing the memo instead of the file
+        #[arg(long)]
+        dir: bool,
     },
     /// Show memo directory path
     Dir,
     /// Archive memos by ID, file path, or directory
-    Archive { targets: Vec<String> },
+    Archive {
+        targets: Vec<String>,
+        /// Show which files would be moved without moving them
+        #[arg(long)]
+        dry_run: bool,
+        /// List archived memos instead of archiving
+        #[arg(long, conflicts_with_all = ["targets", "dry_run"])]
+        list: bool,
+        /// Archive every memo older than AGE (e.g. 90d, 12w); without AGE, use [archive] older_than
+        #[arg(
+            long,
+            value_name = "AGE",
+            num_args = 0..=1,
+            conflicts_with_all = ["targets", "list"]
+        )]
+        older_than: Option<Option<String>>,
+        /// Judge --older-than by creation (ID) or modification time (default: [archive] by)
+        #[arg(long, value_enum, requires = "older_than")]
+        by: Option<DateBasis>,
+    },
+    /// Move archived memos back out of .archive
+    Restore {
+        #[arg(required = true)]
+        ids: Vec<String>,
+        /// Show which files would be moved without moving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Delete memos by ID (moved to .trash unless --permanent)
+    Delete {
+        #[arg(required = true)]
+        ids: Vec<String>,
+        /// Remove the files instead of moving them to .trash
+        #[arg(long)]
+        permanent: bool,
+        /// Show which files would be moved or removed without touching them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move back the files of the last archive, restore, delete or mv
+    Undo {
+        /// Show which files would be moved back without moving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export memos to JSON Lines, a Markdown bundle, a zip archive or an Obsidian vault (`--to`)
+    Export {
+        /// Archive format (not `--format`, which picks the output of memo lists)
+        #[arg(long, value_enum, default_value_t)]
+        to: export::ExportFormat,
+        /// Output file, or directory for obsidian (default: stdout)
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Only memos created on or after this date (e.g. 2025-01-01)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+        /// Only memos created on or before this date
+        #[arg(long, value_name = "DATE")]
+        until: Option<String>,
+        /// Only memos with this front matter tag (e.g. @1on1)
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Import markdown files, directories, or a `memo export --to jsonl|markdown-bundle` archive
+    Import {
+        /// Files or directories to import
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
     /// Build search index
-    Index,
+    Index {
+        /// Only re-index memos added, changed, or removed since the last run
+        #[arg(long)]
+        update: bool,
+        /// Only delete old index directories left by previous rebuilds
+        #[arg(long, conflicts_with = "update")]
+        gc: bool,
+        /// Switch back to the index built before the current one
+        #[arg(long, conflicts_with_all = ["update", "gc"])]
+        rollback: bool,
+    },
+    /// Watch the memo directory and keep the search index up to date
+    Watch,
+    /// Commit the memo directory to git and pull/push the configured remote
+    Sync,
     /// Search memos
-    Search { query: String },
-    /// List all tags with counts
-    Tags,
+    Search(SearchArgs),
+    /// Run a saved search, or list the saved searches when no name is given
+    #[command(args_conflicts_with_subcommands = true)]
+    View {
+        #[command(subcommand)]
+        command: Option<ViewCommands>,
+        /// Name of the saved search
+        name: Option<String>,
+        /// Print only the number of matching memos
+        #[arg(long)]
+        count: bool,
+        /// Output one JSON object per result (JSON Lines)
+        #[arg(long, conflicts_with = "count")]
+        json: bool,
+    },
+    /// Find memos that use similar words to the given memo (needs the search index)
+    Similar {
+        id: String,
+        /// Show at most N memos
+        #[arg(long, value_name = "N", default_value_t = similar::DEFAULT_LIMIT)]
+        limit: usize,
+        /// Output one JSON object per memo (JSON Lines), with its score
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the memos a memo links to with [[id]] and the memos linking to it
+    Links { id: String },
+    /// Search memo files with a regex without using the index
+    Grep {
+        /// Regular expression (Rust regex syntax)
+        pattern: String,
+        /// Match case-insensitively
+        #[arg(short, long)]
+        ignore_case: bool,
+        /// Show N lines before and after each match
+        #[arg(short = 'C', long, value_name = "N", default_value_t = 0)]
+        context: usize,
+    },
+    /// List all tags with counts, or rename a tag
+    Tags {
+        #[command(subcommand)]
+        command: Option<TagsCommands>,
+    },
+    /// Check the memo directory and search index for problems
+    Doctor {
+        /// Repair what can be repaired safely (index entries and locks, never memo files)
+        #[arg(long)]
+        fix: bool,
+    },
     /// Migrate old filename format (HHMMSS.md) to new format (YYYYMMDDHHmmss.md)
     Migrate {
         /// Show what would be renamed without actually renaming
@@ -67,6 +374,141 @@ enum Commands {
         #[command(subcommand)]
         command: TemplateCommands,
     },
+    /// Show help for a command or a topic (e.g. query-syntax, layout)
+    Help { topic: Option<String> },
+    /// Get or set a memo's front matter values
+    Meta {
+        #[command(subcommand)]
+        command: MetaCommands,
+    },
+    /// Show or set the workflow status of a memo
+    Status {
+        /// Memo ID
+        id: String,
+        /// New state (one of `[status] states` in config.toml)
+        state: Option<String>,
+    },
+    /// Show memos grouped by workflow status
+    Board,
+    /// Show memo counts per month and day, text totals, tags and disk usage
+    Stats {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Track time spent on a memo
+    Clock {
+        #[command(subcommand)]
+        command: ClockCommands,
+    },
+    /// Report clocked time per memo and tag
+    Timesheet {
+        /// Only count entries started at or after this date (e.g. 2025-01-01)
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// List memos whose `due:` or `remind:` time has arrived
+    Due {
+        /// Also raise a desktop notification for each memo
+        #[arg(long)]
+        notify: bool,
+    },
+    /// Start an interactive shell that keeps the search index loaded
+    Shell,
+    /// Browse, search and edit memos in an interactive terminal UI
+    Tui,
+    /// Serve a JSON protocol over a unix socket for editor plugins
+    Daemon {
+        /// Socket path (default: <memo dir>/.daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+    /// Serve a JSON REST API on 127.0.0.1 for browser extensions and editors
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = serve::DEFAULT_PORT)]
+        port: u16,
+    },
+    /// List, create or switch notebooks (separate memo directories)
+    Notebook {
+        #[command(subcommand)]
+        command: NotebookCommands,
+    },
+    /// Show the effective settings, or get/set one in config.toml
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
+    },
+    /// Print the man page (roff) to stdout
+    Man,
+    /// Print a shell completion script (bash, zsh, fish, elvish, powershell)
+    Completions {
+        /// Shell to generate the script for
+        shell: clap_complete::Shell,
+    },
+    /// Print completion candidates for the given command line words
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        words: Vec<String>,
+    },
+}
+
+#[derive(Args)]
+struct SearchArgs {
+    #[arg(required_unless_present_any = ["query_file", "help_syntax", "view"])]
+    query: Option<String>,
+    /// Read the query from a file (`-` for stdin) instead of the command line
+    #[arg(long, value_name = "PATH", conflicts_with = "query")]
+    query_file: Option<PathBuf>,
+    /// Render with a Handlebars template file (results are available as `memos`)
+    #[arg(long, value_name = "FILE")]
+    template: Option<PathBuf>,
+    /// Only show memos whose `priority:` is at least this value
+    #[arg(long, value_name = "N")]
+    min_priority: Option<i64>,
+    /// Only show memos tagged with this front matter tag (repeatable; all must match)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// Order of results
+    #[arg(long, value_enum, default_value_t = SearchSort::Relevance)]
+    sort: SearchSort,
+    /// Rank newer memos higher; the extra weight halves every DAYS (default 30)
+    #[arg(long, value_name = "DAYS", num_args = 0..=1, default_missing_value = "30")]
+    boost_recent: Option<f64>,
+    /// Show at most N results (default 100)
+    #[arg(long, value_name = "N", value_parser = page_parser())]
+    limit: Option<usize>,
+    /// Skip the first N results
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    offset: usize,
+    /// Show the Nth page of --limit results
+    #[arg(long, value_name = "N", conflicts_with = "offset", value_parser = page_parser())]
+    page: Option<usize>,
+    /// Also search every notebook listed in `[notebooks]` and label each hit
+    #[arg(long)]
+    all_notebooks: bool,
+    /// Also search archived memos (indexed separately under .archive)
+    #[arg(long, conflicts_with = "all_notebooks")]
+    include_archived: bool,
+    /// Show results under day, month or tag headings
+    #[arg(long, value_enum, conflicts_with = "template")]
+    group_by: Option<search_cmd::GroupBy>,
+    /// Print only the number of matching memos
+    #[arg(long, conflicts_with_all = ["template", "group_by", "sort", "boost_recent"])]
+    count: bool,
+    /// Output one JSON object per result (JSON Lines)
+    #[arg(long, conflicts_with_all = ["template", "group_by", "count"])]
+    json: bool,
+    /// Only output these comma separated fields in JSON (e.g. id,score)
+    #[arg(long, value_name = "FIELDS", requires = "json")]
+    select: Option<String>,
+    /// Run a saved search (`memo view save`); the query, if any, narrows it down
+    #[arg(long, value_name = "NAME")]
+    view: Option<String>,
+    /// Show the query syntax: fields, shortcuts, facets and escaping
+    #[arg(long, exclusive = true)]
+    help_syntax: bool,
 }
 
 #[derive(Subcommand)]
@@ -79,52 +521,461 @@ enum TemplateCommands {
     List,
 }
 
+#[derive(Subcommand)]
+enum NotebookCommands {
+    /// List notebooks; the one in use is marked with `*`
+    List,
+    /// Register a new notebook in `[notebooks]` and create its directory
+    Create {
+        name: String,
+        /// Directory of the notebook (default: `<memo dir>-<name>` next to the memo directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
+    },
+    /// Set the notebook used when --notebook is not given
+    Use { name: String },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the effective value of a setting (e.g. list.limit)
+    Get { key: String },
+    /// Write a setting to config.toml
+    Set { key: String, value: String },
+}
+
+#[derive(Subcommand)]
+enum MetaCommands {
+    /// Print a front matter value (dotted keys like author.name are allowed)
+    Get { id: String, key: String },
+    /// Set front matter values; each value is read as YAML (e.g. priority=2 tags=[a,b])
+    Set {
+        id: String,
+        #[arg(required = true, value_name = "KEY=VALUE")]
+        assignments: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TagsCommands {
+    /// Rename a tag in every memo (front matter and inline @tag)
+    Rename { old: String, new: String },
+}
+
+#[derive(Subcommand)]
+enum ViewCommands {
+    /// Save a search query under a name in the config file
+    Save {
+        name: String,
+        query: String,
+        /// Replace a view with the same name
+        #[arg(long)]
+        force: bool,
+    },
+    /// Delete a saved search
+    Delete { name: String },
+}
+
+#[derive(Subcommand)]
+enum ClockCommands {
+    /// Start tracking time on a memo
+    In { id: String },
+    /// Stop tracking and append the entry to the memo
+    Out,
+}
+
+/// `--page` / `--limit` は 1 以上
+fn page_parser() -> clap::builder::RangedU64ValueParser<usize> {
+    clap::builder::RangedU64ValueParser::new().range(1..)
+}
+
 fn main() {
-    let cli = Cli::parse();
+    let raw_args: Vec<String> = std::env::args().collect();
+    // 設定ファイルのエラーなどは clap の解析前に起きるので、`--error-format` だけ先に拾う
+    let early_error_format = error_format_in(&raw_args);
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => exit_with_error(&e, early_error_format),
+    };
+    // エイリアスは clap の解析前に展開する
+    let cli = match alias::expand(raw_args, &config.alias, &Cli::command()) {
+        Ok(args) => Cli::parse_from(args),
+        Err(e) => exit_with_error(&e, early_error_format),
+    };
+    let error_format = cli.error_format;
+    color::set_color_choice(if cli.no_color {
+        ColorChoice::Never
+    } else {
+        cli.color
+    });
 
-    // コンテキストを初期化
-    let memo_context = match MemoContext::new() {
-        Ok(ctx) => ctx,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            process::exit(1);
+    // ヘルプ系はメモディレクトリを必要としない
+    if let Some(result) = run_help_command(&cli.command) {
+        if let Err(e) = result {
+            exit_with_error(&e, error_format);
         }
+        return;
+    }
+
+    // コンテキストを初期化（`--notebook` は設定の `notebook` より優先）
+    let context = MemoContext::from_config(&config);
+    let context = match cli.notebook.as_deref() {
+        Some(name) => context.and_then(|context| context.for_notebook(name)),
+        None => context,
     };
+    let memo_context = match context {
+        Ok(ctx) => ctx,
+        Err(e) => exit_with_error(&e, error_format),
+    };
+
+    display::date::init_date_format(memo_context.date_format.as_deref());
+    display::theme::init_theme(memo_context.theme.clone());
 
     // メモディレクトリを確保
     if let Err(e) = memo_context.ensure_memo_dir() {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+        exit_with_error(&e, error_format);
     }
 
-    let result = match cli.command {
-        Commands::Add { template } => add::run(&memo_context, template.as_deref()),
-        Commands::Edit { id } => edit::run(&memo_context, &id),
-        Commands::Show { id } => show::run(&memo_context, &id),
-        Commands::List { json, tag } => list::run(&memo_context, json, tag.as_deref()),
-        Commands::Dir => dir::run(&memo_context),
-        Commands::Archive { targets } => archive::run(&memo_context, &targets),
-        Commands::Index => index::run_index(&memo_context),
-        Commands::Search { query } => search_cmd::run_search(&memo_context, &query),
-        Commands::Tags => tags::run(&memo_context),
-        Commands::Migrate { dry_run } => migrate::run(&memo_context, dry_run),
-        Commands::Template { command } => match command {
-            TemplateCommands::Add { name } => template::run_add(&memo_context, &name),
-            TemplateCommands::Edit { name } => template::run_edit(&memo_context, &name),
-            TemplateCommands::List => template::run_list(&memo_context),
-        },
-    };
+    let result = run_command(&memo_context, cli.command, cli.format);
 
     if let Err(e) = result {
-        match e {
-            MemoError::MemoNotFound(_) | MemoError::InvalidId(_) => {
-                eprintln!("Error: {}", e);
-                process::exit(1);
+        exit_with_error(&e, error_format);
+    }
+}
+
+fn exit_with_error(error: &MemoError, format: ErrorFormat) -> ! {
+    eprintln!("{}", error.render(format));
+    process::exit(1);
+}
+
+/// `--error-format json` / `--error-format=json`（無い・読めなければ text）
+fn error_format_in(args: &[String]) -> ErrorFormat {
+    let mut format = ErrorFormat::default();
+    for (i, arg) in args.iter().enumerate() {
+        let value = match arg.strip_prefix("--error-format") {
+            Some("") => args.get(i + 1).map(String::as_str),
+            Some(rest) => rest.strip_prefix('='),
+            None => None,
+        };
+        if let Some(parsed) = value.and_then(|value| ErrorFormat::from_str(value, true).ok()) {
+            format = parsed;
+        }
+    }
+    format
+}
+
+fn run_help_command(command: &Commands) -> Option<Result<(), MemoError>> {
+    match command {
+        Commands::Help { topic } => Some(help::run(Cli::command(), topic.as_deref())),
+        Commands::Man => Some(help::run_man(Cli::command())),
+        Commands::Completions { shell } => Some(complete::run_script(Cli::command(), *shell)),
+        _ => None,
+    }
+}
+
+/// `memo search` の引数からクエリと検索オプションを作り、`search` で実行する
+fn run_search<F>(
+    config: &Config,
+    args: SearchArgs,
+    format: Option<OutputFormat>,
+    search: F,
+) -> Result<(), MemoError>
+where
+    F: FnOnce(&str, &search_cmd::SearchOptions) -> Result<(), MemoError>,
+{
+    if args.help_syntax {
+        search_cmd::print_syntax_help();
+        return Ok(());
+    }
+    let query = search_cmd::resolve_query(
+        config,
+        args.query,
+        args.query_file.as_deref(),
+        args.view.as_deref(),
+    )?;
+    search(
+        &query,
+        &search_cmd::SearchOptions {
+            template: args.template,
+            min_priority: args.min_priority,
+            tags: args.tags,
+            sort: args.sort,
+            boost_recent: args.boost_recent,
+            limit: args.limit,
+            offset: args.offset,
+            page: args.page,
+            all_notebooks: args.all_notebooks,
+            include_archived: args.include_archived,
+            group_by: args.group_by,
+            count: args.count,
+            json: args.json,
+            select: args.select,
+            format,
+        },
+    )
+}
+
+fn run_command(
+    memo_context: &MemoContext,
+    command: Commands,
+    format: Option<OutputFormat>,
+) -> Result<(), MemoError> {
+    match command {
+        Commands::Add {
+            template,
+            title,
+            message,
+            stdin,
+            encrypt,
+        } => add::run(
+            memo_context,
+            &add::AddOptions {
+                template,
+                title,
+                body: add::resolve_body(message, stdin.is_some())?,
+                encrypt,
+            },
+        ),
+        Commands::Edit { ids, last, create } => edit::run(memo_context, &ids, last, create),
+        Commands::Touch { id } => touch::run(memo_context, &id),
+        Commands::Mv { id, to, created_at } => mv::run(memo_context, &id, &to, created_at),
+        Commands::Attach { id, files } => attach::run(memo_context, &id, &files),
+        Commands::Show {
+            id,
+            outline,
+            section,
+            template,
+            pretty,
+            json,
+        } => show::run(
+            memo_context,
+            &id,
+            &show::ShowOptions {
+                outline,
+                section,
+                template,
+                pretty,
+                json,
+            },
+        ),
+        Commands::Cat {
+            ids,
+            date,
+            separator,
+        } => cat::run(memo_context, &ids, &cat::CatOptions { date, separator }),
+        Commands::List {
+            json,
+            tag,
+            outline,
+            table,
+            sort_by,
+            sort,
+            reverse,
+            min_priority,
+            where_expr,
+            select,
+            template,
+            since,
+            until,
+            limit,
+            offset,
+            page,
+        } => list::run(
+            memo_context,
+            &list::ListOptions {
+                json,
+                tag,
+                outline,
+                table,
+                sort_by,
+                sort,
+                reverse,
+                min_priority,
+                where_expr,
+                select,
+                template,
+                since,
+                until,
+                limit,
+                offset,
+                page,
+                format,
+            },
+        ),
+        Commands::Today { add } => recent::run_today(memo_context, add, format),
+        Commands::Yesterday => recent::run_yesterday(memo_context, format),
+        Commands::Last => recent::run_last(memo_context),
+        Commands::Open { id, app, dir } => {
+            open::run(memo_context, &id, &open::OpenOptions { app, dir })
+        }
+        Commands::Dir => dir::run(memo_context),
+        Commands::Archive { list: true, .. } => archive::run_list(memo_context, format),
+        Commands::Archive {
+            older_than: Some(age),
+            by,
+            dry_run,
+            ..
+        } => archive::run_older_than(memo_context, age.as_deref(), by, dry_run),
+        Commands::Archive {
+            targets, dry_run, ..
+        } => archive::run(memo_context, &targets, dry_run),
+        Commands::Restore { ids, dry_run } => restore::run(memo_context, &ids, dry_run),
+        Commands::Undo { dry_run } => undo::run(memo_context, dry_run),
+        Commands::Delete {
+            ids,
+            permanent,
+            dry_run,
+        } => delete::run(memo_context, &ids, permanent, dry_run),
+        Commands::Export { .. } if format.is_some() => Err(MemoError::InvalidArgument(
+            "--format is the output format of memo lists; use `memo export --to <FORMAT>`"
+                .to_string(),
+        )),
+        Commands::Export {
+            to,
+            output,
+            since,
+            until,
+            tag,
+        } => export::run(
+            memo_context,
+            &export::ExportOptions {
+                format: to,
+                output,
+                since,
+                until,
+                tag,
+            },
+        ),
+        Commands::Import { paths } => import::run(memo_context, &paths),
+        Commands::Index { rollback: true, .. } => index::run_rollback(memo_context),
+        Commands::Index { gc: true, .. } => index::run_gc(memo_context),
+        Commands::Index { update: false, .. } => index::run_index(memo_context),
+        Commands::Index { update: true, .. } => index::run_update(memo_context),
+        Commands::Watch => watch::run(memo_context),
+        Commands::Sync => sync::run(memo_context),
+        Commands::Search(args) => {
+            run_search(&memo_context.config, args, format, |query, options| {
+                search_cmd::run_search(memo_context, query, options)
+            })
+        }
+        Commands::View {
+            command,
+            name,
+            count,
+            json,
+        } => match (command, name) {
+            (Some(ViewCommands::Save { name, query, force }), _) => {
+                view::run_save(memo_context, &name, &query, force)
             }
-            _ => {
-                eprintln!("Error: {}", e);
-                process::exit(1);
+            (Some(ViewCommands::Delete { name }), _) => view::run_delete(&name),
+            (None, Some(name)) => view::run(
+                memo_context,
+                &name,
+                &search_cmd::SearchOptions {
+                    count,
+                    json,
+                    format,
+                    ..Default::default()
+                },
+            ),
+            (None, None) => view::run_list(memo_context),
+        },
+        Commands::Similar { id, limit, json } => similar::run(
+            memo_context,
+            &id,
+            limit,
+            if json {
+                Some(OutputFormat::Json)
+            } else {
+                format
+            },
+        ),
+        Commands::Links { id } => links_cmd::run(memo_context, &id),
+        Commands::Grep {
+            pattern,
+            ignore_case,
+            context,
+        } => grep::run(
+            memo_context,
+            &pattern,
+            &grep::GrepOptions {
+                ignore_case,
+                context,
+            },
+        ),
+        Commands::Tags { command } => match command {
+            None => tags::run(memo_context),
+            Some(TagsCommands::Rename { old, new }) => tags::run_rename(memo_context, &old, &new),
+        },
+        Commands::Meta { command } => match command {
+            MetaCommands::Get { id, key } => meta::run_get(memo_context, &id, &key),
+            MetaCommands::Set { id, assignments } => meta::run_set(memo_context, &id, &assignments),
+        },
+        Commands::Status { id, state } => status::run(memo_context, &id, state.as_deref()),
+        Commands::Board => status::run_board(memo_context),
+        Commands::Stats { json } => stats::run(memo_context, json),
+        Commands::Clock { command } => match command {
+            ClockCommands::In { id } => clock::run_in(memo_context, &id),
+            ClockCommands::Out => clock::run_out(memo_context),
+        },
+        Commands::Timesheet { since } => clock::run_timesheet(memo_context, since.as_deref()),
+        Commands::Due { notify } => due::run(memo_context, notify),
+        Commands::Doctor { fix } => doctor::run(memo_context, fix),
+        Commands::Migrate { dry_run } => migrate::run(memo_context, dry_run),
+        Commands::Template { command } => match command {
+            TemplateCommands::Add { name } => template_cmd::run_add(memo_context, &name),
+            TemplateCommands::Edit { name } => template_cmd::run_edit(memo_context, &name),
+            TemplateCommands::List => template_cmd::run_list(memo_context),
+        },
+        Commands::Notebook { command } => match command {
+            NotebookCommands::List => notebook::run_list(memo_context),
+            NotebookCommands::Create { name, path } => {
+                notebook::run_create(memo_context, &name, path)
             }
-        }
+            NotebookCommands::Use { name } => notebook::run_use(&name),
+        },
+        Commands::Config { command } => match command {
+            None => config_cmd::run_show(memo_context),
+            Some(ConfigCommands::Get { key }) => config_cmd::run_get(memo_context, &key),
+            Some(ConfigCommands::Set { key, value }) => config_cmd::run_set(&key, &value),
+        },
+        Commands::Complete { words } => complete::run(memo_context, &Cli::command(), &words),
+        Commands::Daemon { socket } => daemon::run(memo_context, socket),
+        Commands::Serve { port } => serve::run(memo_context, port),
+        Commands::Tui => tui::run(memo_context),
+        Commands::Shell => shell::run(memo_context, &Cli::command(), |session, args| {
+            let cli = match Cli::try_parse_from(args) {
+                Ok(cli) => cli,
+                Err(e) => {
+                    // --help もここに来る
+                    let _ = e.print();
+                    return Ok(());
+                }
+            };
+            // ノートブックと出力の設定はシェル全体で共通
+            if cli.notebook.is_some()
+                || cli.color != ColorChoice::Auto
+                || cli.no_color
+                || cli.error_format != ErrorFormat::default()
+            {
+                return Err(MemoError::InvalidArgument(
+                    "--notebook, --color, --no-color and --error-format apply to the whole shell; pass them to `memo shell`".to_string(),
+                ));
+            }
+            match cli.command {
+                Commands::Search(args) => {
+                    run_search(&memo_context.config, args, cli.format, |query, options| {
+                        session.search(query, options)
+                    })
+                }
+                Commands::Shell => Err(MemoError::InvalidArgument(
+                    "already in memo shell".to_string(),
+                )),
+                command => run_command(memo_context, command, cli.format),
+            }
+        }),
+        Commands::Help { topic } => help::run(Cli::command(), topic.as_deref()),
+        Commands::Man => help::run_man(Cli::command()),
+        Commands::Completions { shell } => complete::run_script(Cli::command(), shell),
     }
 }
@@ -0,0 +1,174 @@
+//! Lightweight markdown scanner used for outlines and section extraction.
+//!
+//! Only ATX headings (`# Heading`) are recognized. Fenced code blocks are
+//! skipped so that `# comment` lines inside them are not treated as headings.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    /// heading level (1-6)
+    pub level: usize,
+    pub text: String,
+    /// 0-based line number in the content
+    pub line: usize,
+}
+
+/// Extract all ATX headings from markdown content
+pub fn extract_headings(content: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for (line_no, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+
+        // fenced code block
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+
+        if let Some((level, text)) = parse_atx_heading(line) {
+            headings.push(Heading {
+                level,
+                text,
+                line: line_no,
+            });
+        }
+    }
+
+    headings
+}
+
+/// Extract the section under the given heading, including nested subsections.
+///
+/// The heading line itself is included. Headings are matched exactly first,
+/// then case-insensitively.
+pub fn extract_section(content: &str, heading: &str) -> Option<String> {
+    let headings = extract_headings(content);
+    let wanted = heading.trim();
+
+    let index = headings.iter().position(|h| h.text == wanted).or_else(|| {
+        headings
+            .iter()
+            .position(|h| h.text.to_lowercase() == wanted.to_lowercase())
+    })?;
+    let start = &headings[index];
+
+    // 同じかより上位の見出しが現れるまでをセクションとする
+    let end_line = headings[index + 1..]
+        .iter()
+        .find(|h| h.level <= start.level)
+        .map(|h| h.line);
+
+    let lines: Vec<&str> = content.lines().collect();
+    let end_line = end_line.unwrap_or(lines.len());
+    let section = lines[start.line..end_line].join("\n");
+
+    Some(format!("{}\n", section.trim_end()))
+}
+
+/// Parse a single line as an ATX heading, returning its level and text
+fn parse_atx_heading(line: &str) -> Option<(usize, String)> {
+    // up to 3 spaces of indentation are allowed
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let line = &line[indent..];
+
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') && !rest.starts_with('\t') {
+        return None;
+    }
+
+    // remove optional closing sequence (e.g. "## Title ##")
+    let text = rest.trim();
+    let without_closing = text.trim_end_matches('#');
+    let text = if without_closing.is_empty() || without_closing.ends_with(' ') {
+        without_closing.trim_end()
+    } else {
+        text
+    };
+
+    Some((level, text.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_headings() {
+        let content = "# Title\n\ntext\n\n## Section 1\nbody\n### Sub ###\n## Section 2\n";
+        let headings = extract_headings(content);
+
+        assert_eq!(
+            headings,
+            vec![
+                Heading {
+                    level: 1,
+                    text: "Title".to_string(),
+                    line: 0
+                },
+                Heading {
+                    level: 2,
+                    text: "Section 1".to_string(),
+                    line: 4
+                },
+                Heading {
+                    level: 3,
+                    text: "Sub".to_string(),
+                    line: 6
+                },
+                Heading {
+                    level: 2,
+                    text: "Section 2".to_string(),
+                    line: 7
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_headings_ignores_code_blocks_and_tags() {
+        let content =
+            "# 見出し\n```sh\n# comment\n```\n#hashtag\n####### too deep\n    # indented code\n";
+        let headings = extract_headings(content);
+
+        assert_eq!(headings.len(), 1);
+        assert_eq!(headings[0].text, "見出し");
+    }
+
+    #[test]
+    fn test_extract_section() {
+        let content = "# Title\nintro\n## Section 1\none\n### Sub\nnested\n## Section 2\ntwo\n";
+
+        assert_eq!(
+            extract_section(content, "Section 1").unwrap(),
+            "## Section 1\none\n### Sub\nnested\n"
+        );
+        assert_eq!(
+            extract_section(content, "section 2").unwrap(),
+            "## Section 2\ntwo\n"
+        );
+        assert_eq!(
+            extract_section(content, "Sub").unwrap(),
+            "### Sub\nnested\n"
+        );
+        assert!(extract_section(content, "Missing").is_none());
+    }
+}
@@ -1,41 +1,61 @@
-use crate::error::MemoResult;
+use crate::encryption;
+use crate::error::{IoResultExt, MemoError, MemoResult};
 use crate::front_matter;
+use crate::markdown::{self, Heading};
 use crate::memo_id::MemoId;
+use crate::utils::text;
 
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
 
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// front matter key maintained by `edit`
+pub const UPDATED_AT_KEY: &str = "updated_at";
+/// front matter key maintained by `attach`
+pub const ATTACHMENTS_KEY: &str = "attachments";
+/// front matter key rewritten by `mv --created-at`
+pub const CREATED_AT_KEY: &str = "created_at";
+
 /// MemoDocument is used for search functionality and represents a memo document with its content,
 /// path, creation date, and front matter.
 #[derive(Debug, Clone)]
 pub struct MemoDocument {
     pub id: MemoId,
+    /// `MemoFile::title`, inferred from the content when the front matter has none
+    pub title: String,
     pub content: String,
     pub path: String,
     pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
     pub metadata: Option<serde_json::Value>,
 }
 
 impl MemoDocument {
     pub fn from_memo_file(memo_file: &MemoFile) -> Self {
         let created_at = memo_file.id.get_datetime().to_utc();
+        let updated_at = memo_file.updated_at().map(|dt| dt.to_utc());
         let metadata = memo_file.metadata.as_ref().map(yaml_to_json_value);
 
         Self {
             id: memo_file.id.clone(),
+            title: memo_file.title(),
             content: memo_file.content.clone(),
             path: memo_file.path.to_string_lossy().to_string(),
             created_at,
+            updated_at,
             metadata,
         }
     }
+
+    pub fn priority(&self) -> Option<i64> {
+        self.metadata.as_ref()?.get("priority")?.as_i64()
+    }
 }
 
 /// Convert serde_yaml::Value to serde_json::Value
-fn yaml_to_json_value(fm: &HashMap<String, serde_yaml::Value>) -> serde_json::Value {
+pub(crate) fn yaml_to_json_value(fm: &HashMap<String, serde_yaml::Value>) -> serde_json::Value {
     let mut map = serde_json::Map::new();
     for (k, v) in fm {
         map.insert(k.clone(), yaml_value_to_json(v));
@@ -96,7 +116,7 @@ impl MemoFile {
 
         let id = MemoId::from_path(&path)?;
 
-        let content = fs::read_to_string(&path)?;
+        let content = encryption::read_memo(&path)?;
         let parsed = front_matter::parse_memo_content(&content);
 
         let modified = Self::get_modified_time(&path)?;
@@ -115,9 +135,9 @@ impl MemoFile {
         let id = MemoId::from_path(&path)?;
 
         if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
+            fs::create_dir_all(parent).with_path(parent)?;
         }
-        fs::write(&path, &content)?;
+        encryption::write_memo(&path, &content)?;
 
         let parsed = front_matter::parse_memo_content(&content);
         let modified = Self::get_modified_time(&path)?;
@@ -133,13 +153,25 @@ impl MemoFile {
     }
 
     pub fn move_to<P: AsRef<Path>>(&self, new_path: P) -> MemoResult<MemoFile> {
+        self.move_to_id(&self.id, new_path)
+    }
+
+    /// Move the memo to `new_path` as `id`; the attachment directory is renamed to match
+    pub fn move_to_id<P: AsRef<Path>>(&self, id: &MemoId, new_path: P) -> MemoResult<MemoFile> {
         let new_path = new_path.as_ref().to_path_buf();
         if let Some(parent) = new_path.parent() {
-            fs::create_dir_all(parent)?;
+            fs::create_dir_all(parent).with_path(parent)?;
+        }
+        fs::rename(&self.path, &new_path).with_path(&self.path)?;
+        // 添付ファイルもメモと一緒に移す
+        let assets_dir = self.assets_dir();
+        if assets_dir.is_dir()
+            && let Some(parent) = new_path.parent()
+        {
+            fs::rename(&assets_dir, parent.join(id.assets_dir_name())).with_path(&assets_dir)?;
         }
-        fs::rename(&self.path, &new_path)?;
 
-        let id = self.id.clone();
+        let id = id.clone();
         let modified = Self::get_modified_time(&new_path)?;
         Ok(MemoFile {
             id,
@@ -151,26 +183,179 @@ impl MemoFile {
         })
     }
 
+    /// Path relative to the memo (or archive) directory, keeping `.md.age` for encrypted memos
+    pub fn relative_path(&self) -> PathBuf {
+        self.relative_path_as(&self.id)
+    }
+
+    /// [`Self::relative_path`] if the memo had the ID `id`
+    pub fn relative_path_as(&self, id: &MemoId) -> PathBuf {
+        let relative = id.to_relative_path();
+        if encryption::is_encrypted(&self.path) {
+            encryption::encrypted_path(&relative)
+        } else {
+            relative
+        }
+    }
+
+    /// Look up a front matter value by dotted key (e.g. `author.name`)
+    pub fn metadata_value(&self, key: &str) -> Option<&serde_yaml::Value> {
+        let mut parts = key.split('.');
+        let mut value = self.metadata.as_ref()?.get(parts.next()?)?;
+        for part in parts {
+            value = value.as_mapping()?.get(part)?;
+        }
+        Some(value)
+    }
+
+    /// Set a front matter value by dotted key, rewriting only the front matter block, and reload
+    /// the memo
+    ///
+    /// Refuses to touch a memo whose front matter does not parse, so nothing in it is lost.
+    pub fn set_metadata_value(&self, key: &str, value: serde_yaml::Value) -> MemoResult<MemoFile> {
+        let raw = encryption::read_memo(&self.path)?;
+        let parsed = front_matter::parse_memo_content(&raw);
+        if let Some(error) = parsed.front_matter_error {
+            return Err(MemoError::InvalidArgument(format!(
+                "cannot rewrite the front matter of {}: {}",
+                self.id, error
+            )));
+        }
+
+        let mut metadata = parsed.front_matter.unwrap_or_default();
+        front_matter::set_value(&mut metadata, key, value);
+        encryption::write_memo(
+            &self.path,
+            &front_matter::serialize_memo_content(&metadata, &parsed.content)?,
+        )?;
+
+        MemoFile::from_path(&self.path)
+    }
+
+    /// `updated_at` in the front matter, if present and valid RFC 3339
+    pub fn updated_at(&self) -> Option<DateTime<Local>> {
+        let value = self.metadata.as_ref()?.get(UPDATED_AT_KEY)?.as_str()?;
+        DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|dt| dt.with_timezone(&Local))
+    }
+
+    /// Write `updated_at` into the front matter and reload the memo
+    pub fn set_updated_at(&self, at: DateTime<Local>) -> MemoResult<MemoFile> {
+        let raw = encryption::read_memo(&self.path)?;
+        let timestamp = at.to_rfc3339_opts(SecondsFormat::Secs, false);
+        encryption::write_memo(
+            &self.path,
+            &front_matter::set_field(&raw, UPDATED_AT_KEY, &timestamp),
+        )?;
+
+        MemoFile::from_path(&self.path)
+    }
+
+    /// Write `created_at` into the front matter and reload the memo
+    pub fn set_created_at(&self, at: DateTime<Local>) -> MemoResult<MemoFile> {
+        let raw = encryption::read_memo(&self.path)?;
+        let timestamp = at.to_rfc3339_opts(SecondsFormat::Secs, false);
+        encryption::write_memo(
+            &self.path,
+            &front_matter::set_field(&raw, CREATED_AT_KEY, &timestamp),
+        )?;
+
+        MemoFile::from_path(&self.path)
+    }
+
+    /// Append `text` on its own line(s) at the end of the file and reload the memo
+    pub fn append(&self, text: &str) -> MemoResult<MemoFile> {
+        let mut raw = encryption::read_memo(&self.path)?;
+        if !raw.is_empty() && !raw.ends_with('\n') {
+            raw.push('\n');
+        }
+        raw.push_str(text);
+        if !text.ends_with('\n') {
+            raw.push('\n');
+        }
+        encryption::write_memo(&self.path, &raw)?;
+
+        MemoFile::from_path(&self.path)
+    }
+
     fn get_modified_time(path: &Path) -> MemoResult<DateTime<Local>> {
-        let metadata = fs::metadata(path)?;
-        let modified = metadata.modified()?;
+        let metadata = fs::metadata(path).with_path(path)?;
+        let modified = metadata.modified().with_path(path)?;
         Ok(DateTime::from(modified))
     }
 
+    /// First `max_chars` grapheme clusters of the content
     pub fn preview(&self, max_chars: usize) -> String {
         let content = self.content.trim();
-        if content.chars().count() <= max_chars {
-            return content.to_string();
+        match text::truncate_graphemes(content, max_chars) {
+            Some(truncated) => format!("{}...", truncated),
+            None => content.to_string(),
+        }
+    }
+
+    /// markdown heading structure of the content
+    pub fn outline(&self) -> Vec<Heading> {
+        markdown::extract_headings(&self.content)
+    }
+
+    /// `title` in the front matter, else the first heading, else the first non-empty line
+    pub fn title(&self) -> String {
+        if let Some(title) = self.metadata_value("title").and_then(|v| v.as_str()) {
+            return title.to_string();
         }
+        if let Some(heading) = self.outline().into_iter().next() {
+            return heading.text;
+        }
+        self.content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// String entries of the front matter `tags` list
+    pub fn tags(&self) -> Vec<&str> {
+        self.metadata_value("tags")
+            .and_then(|v| v.as_sequence())
+            .map(|tags| tags.iter().filter_map(|t| t.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Directory holding this memo's attachments, next to the memo file
+    pub fn assets_dir(&self) -> PathBuf {
+        let parent = self.path.parent().unwrap_or(Path::new(""));
+        parent.join(self.id.assets_dir_name())
+    }
+
+    /// File names listed in the front matter `attachments`
+    pub fn attachments(&self) -> Vec<&str> {
+        self.metadata_value(ATTACHMENTS_KEY)
+            .and_then(|v| v.as_sequence())
+            .map(|names| names.iter().filter_map(|n| n.as_str()).collect())
+            .unwrap_or_default()
+    }
 
-        let truncated: String = content.chars().take(max_chars).collect();
-        format!("{}...", truncated)
+    /// Full paths of the attachments
+    pub fn attachment_paths(&self) -> Vec<PathBuf> {
+        let assets_dir = self.assets_dir();
+        self.attachments()
+            .into_iter()
+            .map(|name| assets_dir.join(name))
+            .collect()
+    }
+
+    /// Integer front matter `priority` (higher is more urgent)
+    pub fn priority(&self) -> Option<i64> {
+        self.metadata_value("priority")?.as_i64()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
 
     #[test]
     fn test_memo_document_from_memo_file() {
@@ -248,6 +433,95 @@ Test content"#,
         assert!(new_path.exists());
     }
 
+    #[test]
+    fn test_memo_file_move_to_carries_attachments() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let old_path = temp_dir.path().join("2025-01/30/20250130143022.md");
+        let new_path = temp_dir
+            .path()
+            .join(".archive/2025-01/30/20250130143022.md");
+        let memo_file = MemoFile::create(
+            &old_path,
+            "---\nattachments: [\"photo.png\"]\n---\nbody".to_string(),
+        )
+        .unwrap();
+        fs::create_dir_all(memo_file.assets_dir()).unwrap();
+        fs::write(memo_file.assets_dir().join("photo.png"), "png").unwrap();
+        assert_eq!(memo_file.attachments(), vec!["photo.png"]);
+
+        let moved = memo_file.move_to(&new_path).unwrap();
+        assert!(!temp_dir.path().join("2025-01/30/143022").exists());
+        assert_eq!(
+            moved.attachment_paths(),
+            vec![temp_dir.path().join(".archive/2025-01/30/143022/photo.png")]
+        );
+        assert!(moved.attachment_paths()[0].exists());
+    }
+
+    #[test]
+    fn test_memo_file_append() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let memo_path = temp_dir.path().join("2025-01/30/20250130143022.md");
+        let memo_file = MemoFile::create(&memo_path, "# Memo".to_string()).unwrap();
+
+        let appended = memo_file
+            .append("- one")
+            .unwrap()
+            .append("- two\n")
+            .unwrap();
+
+        assert_eq!(appended.content, "# Memo\n- one\n- two\n");
+    }
+
+    #[test]
+    fn test_memo_file_set_updated_at() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let memo_path = temp_dir.path().join("2025-01/30/20250130143022.md");
+        let memo_file = MemoFile::create(
+            &memo_path,
+            "---\ntitle: Test\n---\nTest content".to_string(),
+        )
+        .unwrap();
+        assert!(memo_file.updated_at().is_none());
+
+        let at = Local.with_ymd_and_hms(2025, 2, 1, 10, 0, 0).unwrap();
+        let updated = memo_file.set_updated_at(at).unwrap();
+
+        assert_eq!(updated.updated_at(), Some(at));
+        assert_eq!(updated.content, "Test content");
+        assert!(
+            fs::read_to_string(&memo_path)
+                .unwrap()
+                .starts_with("---\ntitle: Test\nupdated_at: 2025-02-01T10:00:00")
+        );
+        assert_eq!(
+            MemoDocument::from_memo_file(&updated).updated_at,
+            Some(at.to_utc())
+        );
+    }
+
+    #[test]
+    fn test_memo_file_metadata_value() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let memo_path = temp_dir.path().join("2025-01/30/20250130143022.md");
+        let memo_file = MemoFile::create(
+            &memo_path,
+            "---\npriority: 2\nauthor:\n  name: John\n---\nTest content".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            memo_file.metadata_value("priority"),
+            Some(&serde_yaml::Value::from(2))
+        );
+        assert_eq!(
+            memo_file.metadata_value("author.name"),
+            Some(&serde_yaml::Value::from("John"))
+        );
+        assert!(memo_file.metadata_value("author.email").is_none());
+        assert!(memo_file.metadata_value("missing").is_none());
+    }
+
     #[test]
     fn test_memo_file_preview() {
         let memo = MemoFile {
@@ -269,5 +543,46 @@ Test content"#,
             modified: Default::default(),
         };
         assert_eq!(memo.preview(100).chars().count(), 103);
+
+        let memo = MemoFile {
+            id: MemoId::from_str("20250130143022").unwrap(),
+            path: Default::default(),
+            content: "👨‍👩‍👧".repeat(3),
+            metadata: None,
+            metadata_error: None,
+            modified: Default::default(),
+        };
+        assert_eq!(memo.preview(2), format!("{}...", "👨‍👩‍👧".repeat(2)));
+    }
+
+    #[test]
+    fn test_memo_file_title_and_tags() {
+        let memo = |content: &str, metadata: Option<&str>| MemoFile {
+            id: MemoId::from_str("20250130143022").unwrap(),
+            path: Default::default(),
+            content: content.to_string(),
+            metadata: metadata.map(|m| serde_yaml::from_str(m).unwrap()),
+            metadata_error: None,
+            modified: Default::default(),
+        };
+
+        let with_front_matter = memo(
+            "# Heading",
+            Some("title: Weekly\ntags: [\"@a\", 1, \"@b\"]"),
+        );
+        assert_eq!(with_front_matter.title(), "Weekly");
+        assert_eq!(with_front_matter.tags(), vec!["@a", "@b"]);
+
+        assert_eq!(memo("\ntext\n# Heading", None).title(), "Heading");
+        assert_eq!(memo("\n  first line\nsecond", None).title(), "first line");
+        assert!(memo("", None).tags().is_empty());
+
+        let prioritized = memo("", Some("priority: 3"));
+        assert_eq!(prioritized.priority(), Some(3));
+        assert_eq!(
+            MemoDocument::from_memo_file(&prioritized).priority(),
+            Some(3)
+        );
+        assert_eq!(memo("", Some("priority: high")).priority(), None);
     }
 }
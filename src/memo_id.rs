@@ -1,5 +1,5 @@
 use crate::error::{MemoError, MemoResult};
-use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta, TimeZone, Timelike};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -7,6 +7,12 @@ pub struct MemoId {
     datetime: DateTime<Local>,
 }
 
+impl Default for MemoId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MemoId {
     pub fn new() -> Self {
         Self {
@@ -14,6 +20,14 @@ impl MemoId {
         }
     }
 
+    /// 秒未満は切り捨てる（ID は秒単位）
+    pub fn from_datetime(datetime: DateTime<Local>) -> Self {
+        Self {
+            datetime: datetime.with_nanosecond(0).unwrap_or(datetime),
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(id: &str) -> MemoResult<Self> {
         if id.len() != 14 {
             return Err(MemoError::InvalidId(id.to_string()));
@@ -34,8 +48,10 @@ impl MemoId {
             .file_stem()
             .ok_or_else(|| MemoError::InvalidId(path.to_string_lossy().to_string()))?
             .to_string_lossy();
+        // 暗号化したメモは `<id>.md.age`
+        let filename = filename.strip_suffix(".md").unwrap_or(&filename);
 
-        Self::from_str(&filename)
+        Self::from_str(filename)
     }
 
     pub fn as_str(&self) -> String {
@@ -46,6 +62,11 @@ impl MemoId {
         base_dir.join(self.to_relative_path())
     }
 
+    /// 添付ファイルの置き場（`2025-01/30/143022`）。メモファイルと同じディレクトリに作る
+    pub fn assets_dir_name(&self) -> String {
+        self.datetime.format("%H%M%S").to_string()
+    }
+
     pub fn to_relative_path(&self) -> PathBuf {
         let year_month = self.datetime.format("%Y-%m").to_string();
         let day = self.datetime.format("%d").to_string();
@@ -59,6 +80,13 @@ impl MemoId {
     pub fn get_datetime(&self) -> DateTime<Local> {
         self.datetime
     }
+
+    /// 1秒後の ID（ID が重なった時に使う）
+    pub fn next(&self) -> Self {
+        Self {
+            datetime: self.datetime + TimeDelta::seconds(1),
+        }
+    }
 }
 
 impl std::fmt::Display for MemoId {
@@ -82,6 +110,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_memo_id_from_datetime_and_next() {
+        let datetime =
+            Local.with_ymd_and_hms(2025, 1, 30, 23, 59, 59).unwrap() + TimeDelta::milliseconds(500);
+        let id = MemoId::from_datetime(datetime);
+        assert_eq!(id.as_str(), "20250130235959");
+        assert_eq!(id.next().as_str(), "20250131000000");
+        assert_eq!(id.next(), MemoId::from_str("20250131000000").unwrap());
+    }
+
     #[test]
     fn test_memo_id_from_str_invalid() {
         assert!(MemoId::from_str("invalid").is_err());
@@ -95,6 +133,9 @@ mod tests {
         let path = PathBuf::from("/tmp/memo/2025-01/30/20250130143022.md");
         let id = MemoId::from_path(&path).unwrap();
         assert_eq!(id.as_str(), "20250130143022");
+
+        let encrypted = PathBuf::from("/tmp/memo/2025-01/30/20250130143022.md.age");
+        assert_eq!(MemoId::from_path(&encrypted).unwrap(), id);
     }
 
     #[test]
@@ -106,6 +147,12 @@ mod tests {
         assert_eq!(path, PathBuf::from("/tmp/memo/2025-01/30/20250130143022.md"));
     }
 
+    #[test]
+    fn test_memo_id_assets_dir_name() {
+        let id = MemoId::from_str("20250130143022").unwrap();
+        assert_eq!(id.assets_dir_name(), "143022");
+    }
+
     #[test]
     fn test_memo_id_to_relative_path() {
         let id = MemoId::from_str("20250130143022").unwrap();
@@ -0,0 +1,368 @@
+//! File operations that `memo undo` can reverse: archive, restore, delete and mv.
+//!
+//! Commands move memos through a [`Batch`], which records every file and
+//! attachment directory it moved. On [`Batch::commit`] the moves are appended to
+//! `.memo-journal` in the memo directory as one JSON line per command, with paths
+//! relative to the memo directory. `memo undo` moves the files of the last line
+//! back. Permanent deletes are not recorded since there is nothing to move back.
+//!
+//! [`create_memo`] is the shared "new memo" path of `memo add`, `memo serve` and
+//! `memo daemon`: it writes the memo, indexes it and runs the webhooks and git auto commit.
+
+use crate::commands::edit::update_search_index;
+use crate::context::MemoContext;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::git;
+use crate::memo::MemoFile;
+use crate::memo_id::MemoId;
+use crate::repository::{MemoRepository, PlannedMove};
+use crate::webhook::{self, WebhookEvent};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 古い操作から捨て、ジャーナルに残す操作の数
+const JOURNAL_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Archive,
+    Restore,
+    Delete,
+    Mv,
+}
+
+impl Action {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Action::Archive => "archive",
+            Action::Restore => "restore",
+            Action::Delete => "delete",
+            Action::Mv => "mv",
+        }
+    }
+}
+
+/// ジャーナルの 1 行（1 回のコマンドで動かしたファイル）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub action: Action,
+    /// RFC 3339
+    pub at: String,
+    pub moves: Vec<JournalMove>,
+}
+
+/// メモディレクトリからの相対パス（外にあれば絶対パス）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalMove {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+impl JournalEntry {
+    /// 戻す移動（記録と逆向き・逆順）を絶対パスで
+    pub fn reverse_moves(&self, memo_dir: &Path) -> Vec<PlannedMove> {
+        self.moves
+            .iter()
+            .rev()
+            .map(|m| PlannedMove {
+                from: memo_dir.join(&m.to),
+                to: Some(memo_dir.join(&m.from)),
+            })
+            .collect()
+    }
+
+    /// 動かしたメモの数（添付ディレクトリは数えない）
+    pub fn memo_count(&self) -> usize {
+        self.moves
+            .iter()
+            .filter(|m| MemoId::from_path(&m.from).is_ok() && m.from.extension().is_some())
+            .count()
+    }
+
+    /// `2025-01-30 14:30`（読めなければ記録のまま）
+    pub fn display_time(&self) -> String {
+        DateTime::parse_from_rfc3339(&self.at)
+            .map(|at| {
+                at.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|_| self.at.clone())
+    }
+}
+
+/// 1 回のコマンドで行うファイル操作。移動は [`MemoRepository`] に任せ、動かした分を記録する
+pub struct Batch {
+    repo: MemoRepository,
+    context: MemoContext,
+    action: Action,
+    moves: Vec<PlannedMove>,
+}
+
+impl Batch {
+    pub fn new(context: &MemoContext, action: Action) -> Self {
+        Self {
+            repo: MemoRepository::new(context.clone()),
+            context: context.clone(),
+            action,
+            moves: Vec::new(),
+        }
+    }
+
+    pub fn archive(&mut self, memo: &MemoFile) -> MemoResult<MemoFile> {
+        let planned = self.repo.plan_archive(std::slice::from_ref(memo));
+        let archived = self.repo.archive_memo(memo)?;
+        self.moves.extend(planned);
+        Ok(archived)
+    }
+
+    pub fn restore(&mut self, memo: &MemoFile) -> MemoResult<MemoFile> {
+        let planned = self.repo.plan_restore(std::slice::from_ref(memo))?;
+        let restored = self.repo.restore_memo(memo)?;
+        self.moves.extend(planned);
+        Ok(restored)
+    }
+
+    /// `permanent` なら消したファイルは戻せないので記録しない
+    pub fn delete(&mut self, memo: &MemoFile, permanent: bool) -> MemoResult<Option<MemoFile>> {
+        let planned = self.repo.plan_delete(std::slice::from_ref(memo), permanent);
+        let trashed = self.repo.delete_memo(memo, permanent)?;
+        if !permanent {
+            self.moves.extend(planned);
+        }
+        Ok(trashed)
+    }
+
+    pub fn move_memo(&mut self, memo: &MemoFile, id: &MemoId) -> MemoResult<MemoFile> {
+        let planned = self.repo.plan_move(memo, id);
+        let moved = self.repo.move_memo(memo, id)?;
+        self.moves.extend(planned);
+        Ok(moved)
+    }
+
+    /// 動かしたファイルを 1 つの操作としてジャーナルに書く（何も動かしていなければ書かない）
+    pub fn commit(self) -> MemoResult<()> {
+        if self.moves.is_empty() {
+            return Ok(());
+        }
+        let memo_dir = &self.context.memo_dir;
+        let relative = |path: &Path| path.strip_prefix(memo_dir).unwrap_or(path).to_path_buf();
+        let entry = JournalEntry {
+            action: self.action,
+            at: Local::now().to_rfc3339(),
+            moves: self
+                .moves
+                .iter()
+                .filter_map(|m| {
+                    Some(JournalMove {
+                        from: relative(&m.from),
+                        to: relative(m.to.as_deref()?),
+                    })
+                })
+                .collect(),
+        };
+
+        let mut entries = read_journal(&self.context)?;
+        entries.push(entry);
+        let skip = entries.len().saturating_sub(JOURNAL_LIMIT);
+        write_journal(&self.context, &entries[skip..])
+    }
+}
+
+/// 新しいメモを作って検索インデックスに加え、webhook と git の自動コミットに知らせる
+///
+/// `content` は決まった ID から本文を作る。`edit` は書き込んだ後・インデックスに加える前に
+/// 呼ばれる（`memo add` はここでエディタを開く）
+pub fn create_memo<C, E>(
+    context: &MemoContext,
+    encrypt: bool,
+    content: C,
+    edit: E,
+) -> MemoResult<MemoFile>
+where
+    C: FnOnce(&MemoId) -> String,
+    E: FnOnce(&Path) -> MemoResult<()>,
+{
+    context.ensure_memo_dir()?;
+    let memo = MemoRepository::new(context.clone()).create_new_memo(encrypt, content)?;
+    edit(&memo.path)?;
+    update_search_index(context, &memo.path)?;
+    // エディタで書き換えた内容を読み直す
+    let created = [MemoFile::from_path(&memo.path)?];
+    webhook::notify(&context.config, WebhookEvent::Create, &created);
+    git::auto_commit(&context.config, &context.memo_dir, "add", &created);
+    let [memo] = created;
+    Ok(memo)
+}
+
+/// `memo undo` の結果
+#[derive(Debug)]
+pub struct Undone {
+    pub entry: JournalEntry,
+    /// 戻す前の場所にあったメモ（検索インデックスから外す）
+    pub before: Vec<MemoFile>,
+    /// 元の場所に戻ったメモ
+    pub after: Vec<MemoFile>,
+}
+
+/// 最後に記録した操作（無ければ `None`）
+pub fn last_entry(context: &MemoContext) -> MemoResult<Option<JournalEntry>> {
+    Ok(read_journal(context)?.pop())
+}
+
+/// 最後に記録した操作のファイルを元の場所に戻し、ジャーナルから消す
+///
+/// 戻す先が埋まっている・戻すファイルが無いなど 1 つでも動かせなければ、何も動かさない
+pub fn undo(context: &MemoContext) -> MemoResult<Option<Undone>> {
+    let mut entries = read_journal(context)?;
+    let Some(entry) = entries.pop() else {
+        return Ok(None);
+    };
+
+    let moves = entry.reverse_moves(&context.memo_dir);
+    for planned in &moves {
+        if !planned.from.exists() {
+            return Err(MemoError::Undo(format!(
+                "cannot undo {}: {} no longer exists",
+                entry.action.as_str(),
+                planned.from.display()
+            )));
+        }
+        if let Some(to) = &planned.to
+            && to.exists()
+        {
+            return Err(MemoError::Undo(format!(
+                "cannot undo {}: {} already exists",
+                entry.action.as_str(),
+                to.display()
+            )));
+        }
+    }
+
+    // 読めないメモ（鍵の無い暗号化メモなど）は戻すだけでインデックスには触れない
+    let before = moves
+        .iter()
+        .filter(|m| m.from.is_file())
+        .filter_map(|m| MemoFile::from_path(&m.from).ok())
+        .collect();
+    let mut after = Vec::new();
+    for planned in &moves {
+        let Some(to) = &planned.to else { continue };
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).with_path(parent)?;
+        }
+        fs::rename(&planned.from, to).with_path(&planned.from)?;
+        if to.is_file()
+            && let Ok(memo) = MemoFile::from_path(to)
+        {
+            after.push(memo);
+        }
+    }
+
+    write_journal(context, &entries)?;
+    Ok(Some(Undone {
+        entry,
+        before,
+        after,
+    }))
+}
+
+fn read_journal(context: &MemoContext) -> MemoResult<Vec<JournalEntry>> {
+    let path = context.journal_file();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    fs::read_to_string(&path)
+        .with_path(&path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .map_err(|e| MemoError::Undo(format!("{} is corrupted: {}", path.display(), e)))
+        })
+        .collect()
+}
+
+fn write_journal(context: &MemoContext, entries: &[JournalEntry]) -> MemoResult<()> {
+    let mut content = String::new();
+    for entry in entries {
+        let line = serde_json::to_string(entry).map_err(|e| MemoError::Undo(e.to_string()))?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+    let path = context.journal_file();
+    fs::write(&path, content).with_path(&path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> (TempDir, MemoContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let memo_dir = temp_dir.path().join("memo");
+        fs::create_dir_all(&memo_dir).unwrap();
+
+        let context = MemoContext {
+            memo_dir,
+            ..Default::default()
+        };
+        (temp_dir, context)
+    }
+
+    #[test]
+    fn test_undo_archive() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        let memo = repo
+            .create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+
+        let mut batch = Batch::new(&context, Action::Archive);
+        batch.archive(&memo).unwrap();
+        batch.commit().unwrap();
+
+        let entry = last_entry(&context).unwrap().unwrap();
+        assert_eq!(entry.action, Action::Archive);
+        assert_eq!(
+            entry.moves,
+            vec![JournalMove {
+                from: PathBuf::from("2025-01/30/20250130143022.md"),
+                to: PathBuf::from(".archive/2025-01/30/20250130143022.md"),
+            }]
+        );
+
+        let undone = undo(&context).unwrap().unwrap();
+        assert_eq!(undone.entry.memo_count(), 1);
+        assert_eq!(undone.after.len(), 1);
+        assert!(memo.path.exists());
+        assert!(last_entry(&context).unwrap().is_none());
+        assert!(undo(&context).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_undo_refuses_when_target_is_taken() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+        let memo = repo
+            .create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+
+        let mut batch = Batch::new(&context, Action::Mv);
+        let moved = batch
+            .move_memo(&memo, &MemoId::from_str("20250131090000").unwrap())
+            .unwrap();
+        batch.commit().unwrap();
+        repo.create_memo("2025-01/30/20250130143022.md", "New memo".to_string())
+            .unwrap();
+
+        assert!(matches!(undo(&context), Err(MemoError::Undo(_))));
+        assert!(moved.path.exists());
+        assert!(last_entry(&context).unwrap().is_some());
+    }
+}
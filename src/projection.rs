@@ -0,0 +1,111 @@
+//! Field projection for JSON output (`--select id,metadata.priority`).
+//!
+//! Selected paths keep their nesting, so `metadata.priority` is emitted as
+//! `{"metadata": {"priority": ...}}`. Paths that don't exist yield `null`.
+
+use crate::error::{MemoError, MemoResult};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Projection {
+    paths: Vec<Vec<String>>,
+}
+
+impl Projection {
+    /// Parse a comma separated list of dotted paths
+    pub fn parse(spec: &str) -> MemoResult<Self> {
+        let mut paths = Vec::new();
+        for field in spec.split(',').map(str::trim) {
+            if field.is_empty() || field.split('.').any(str::is_empty) {
+                return Err(MemoError::InvalidArgument(format!(
+                    "invalid --select field '{}'",
+                    field
+                )));
+            }
+            paths.push(field.split('.').map(str::to_string).collect());
+        }
+        Ok(Self { paths })
+    }
+
+    pub fn apply(&self, value: &Value) -> Value {
+        let mut result = Value::Object(Map::new());
+        for path in &self.paths {
+            let selected = lookup(value, path).cloned().unwrap_or(Value::Null);
+            insert(&mut result, path, selected);
+        }
+        result
+    }
+}
+
+fn lookup<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(value, |current, key| current.as_object()?.get(key))
+}
+
+fn insert(target: &mut Value, path: &[String], value: Value) {
+    let (last, parents) = match path.split_last() {
+        Some(split) => split,
+        None => return,
+    };
+
+    let mut current = target;
+    for key in parents {
+        let map = match current {
+            Value::Object(map) => map,
+            _ => return,
+        };
+        current = map
+            .entry(key.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        // 既にスカラ値が選択されていれば上書きしない
+        if !current.is_object() {
+            return;
+        }
+    }
+
+    if let Value::Object(map) = current {
+        map.insert(last.clone(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_projection_apply() {
+        let value = json!({
+            "id": "20250130143022",
+            "preview": "text",
+            "metadata": {"priority": 2, "title": "Memo", "tags": ["@a", "@b"]},
+        });
+
+        let projection = Projection::parse("id, metadata.priority,metadata.tags").unwrap();
+        assert_eq!(
+            projection.apply(&value),
+            json!({
+                "id": "20250130143022",
+                "metadata": {"priority": 2, "tags": ["@a", "@b"]},
+            })
+        );
+    }
+
+    #[test]
+    fn test_projection_missing_fields_are_null() {
+        let value = json!({"id": "20250130143022", "metadata": null});
+
+        let projection = Projection::parse("title,metadata.priority").unwrap();
+        assert_eq!(
+            projection.apply(&value),
+            json!({"title": null, "metadata": {"priority": null}})
+        );
+    }
+
+    #[test]
+    fn test_projection_parse_errors() {
+        assert!(Projection::parse("").is_err());
+        assert!(Projection::parse("id,,title").is_err());
+        assert!(Projection::parse("metadata.").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! User-provided Handlebars templates for `list`, `show` and `search` output.
+//!
+//! `show` renders a single memo with its fields at the template root.
+//! `list` and `search` render once with all memos under `memos`
+//! (`search` additionally gets `query`, and each memo a `score`).
+
+use crate::error::{MemoError, MemoResult};
+use crate::memo::{MemoFile, yaml_to_json_value};
+use handlebars::Handlebars;
+use serde::Serialize;
+use std::path::Path;
+
+const TEMPLATE_NAME: &str = "output";
+
+/// Data exposed to templates for each memo
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoTemplateData {
+    pub id: String,
+    pub path: String,
+    pub created_at: String,
+    pub modified: String,
+    pub preview: String,
+    pub content: String,
+    pub metadata: serde_json::Value,
+    pub metadata_error: Option<String>,
+    pub outline: Vec<OutlineData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notebook: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutlineData {
+    pub level: usize,
+    pub text: String,
+}
+
+impl MemoTemplateData {
+    pub fn from_memo_file(memo: &MemoFile) -> Self {
+        Self {
+            id: memo.id.as_str(),
+            path: memo.path.to_string_lossy().to_string(),
+            created_at: memo.id.get_datetime().to_rfc3339(),
+            modified: memo.modified.to_rfc3339(),
+            preview: memo.preview(100),
+            content: memo.content.clone(),
+            metadata: memo
+                .metadata
+                .as_ref()
+                .map(yaml_to_json_value)
+                .unwrap_or(serde_json::Value::Null),
+            metadata_error: memo.metadata_error.clone(),
+            outline: memo
+                .outline()
+                .into_iter()
+                .map(|h| OutlineData {
+                    level: h.level,
+                    text: h.text,
+                })
+                .collect(),
+            score: None,
+            notebook: None,
+        }
+    }
+
+    pub fn with_score(mut self, score: f32) -> Self {
+        self.score = Some(score);
+        self
+    }
+
+    pub fn with_notebook(mut self, notebook: &str) -> Self {
+        self.notebook = Some(notebook.to_string());
+        self
+    }
+}
+
+/// A compiled output template
+pub struct OutputTemplate {
+    registry: Handlebars<'static>,
+}
+
+impl OutputTemplate {
+    pub fn new(template: &str) -> MemoResult<Self> {
+        let mut registry = Handlebars::new();
+        // 端末出力なのでHTMLエスケープはしない
+        registry.register_escape_fn(handlebars::no_escape);
+        registry
+            .register_template_string(TEMPLATE_NAME, template)
+            .map_err(|e| MemoError::TemplateError(e.to_string()))?;
+        Ok(Self { registry })
+    }
+
+    pub fn from_file(path: &Path) -> MemoResult<Self> {
+        let template = std::fs::read_to_string(path).map_err(|e| {
+            MemoError::TemplateError(format!("Failed to read '{}': {}", path.display(), e))
+        })?;
+        Self::new(&template)
+    }
+
+    pub fn render<T: Serialize>(&self, data: &T) -> MemoResult<String> {
+        self.registry
+            .render(TEMPLATE_NAME, data)
+            .map_err(|e| MemoError::TemplateError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_render_memo() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let memo_path = temp_dir.path().join("2025-01/30/20250130143022.md");
+        fs::create_dir_all(memo_path.parent().unwrap()).unwrap();
+        fs::write(
+            &memo_path,
+            "---\ntitle: Weekly <sync>\n---\n# Agenda\n## Topics\n",
+        )
+        .unwrap();
+        let memo = MemoFile::from_path(&memo_path).unwrap();
+
+        let template = OutputTemplate::new(
+            "{{id}} {{metadata.title}}\n{{#each outline}}{{level}}:{{text}}\n{{/each}}",
+        )
+        .unwrap();
+        let rendered = template
+            .render(&MemoTemplateData::from_memo_file(&memo))
+            .unwrap();
+
+        assert_eq!(
+            rendered,
+            "20250130143022 Weekly <sync>\n1:Agenda\n2:Topics\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        assert!(matches!(
+            OutputTemplate::new("{{#each memos}}"),
+            Err(MemoError::TemplateError(_))
+        ));
+    }
+}
@@ -1,9 +1,15 @@
 use crate::context::MemoContext;
-use crate::error::{MemoError, MemoResult};
-use crate::memo::{MemoDocument, MemoFile};
+use crate::encryption;
+use crate::error::{IoResultExt, MemoError, MemoResult};
+use crate::memo::MemoFile;
+use crate::memo_id::MemoId;
 use crate::utils::id_resolver::resolve_memo_id;
+use chrono::{DateTime, Local, NaiveDate};
+use serde_yaml::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// MemoRepository is responsible for managing memo files.
 pub struct MemoRepository {
@@ -19,27 +25,81 @@ impl MemoRepository {
         &self.context.memo_dir
     }
 
+    /// memo files newest first (by ID), without reading their content
+    ///
+    /// Files whose name is not a memo ID are skipped. Use `MemoEntry::load` to read a memo.
+    pub fn iter_memos(&self) -> MemoResult<impl Iterator<Item = MemoEntry> + use<>> {
+        let mut paths = self.list_memo_paths()?;
+        paths.sort_by(|a, b| b.cmp(a));
+        Ok(paths
+            .into_iter()
+            .filter_map(|path| MemoEntry::new(path).ok()))
+    }
+
+    /// all memos, newest first (reads every memo; prefer `iter_memos` for large repositories)
     pub fn list_all_memos(&self) -> MemoResult<Vec<MemoFile>> {
-        let mut memos = Vec::new();
-        self.collect_memos_recursive(&self.context.memo_dir, &mut memos)?;
+        Ok(self
+            .iter_memos()?
+            .filter_map(|entry| entry.load().ok())
+            .collect())
+    }
 
-        // sort by modified time in descending order
-        memos.sort_by(|a, b| b.path.cmp(&a.path));
+    /// memos created within `range`, newest first, skipping `offset` and at most `limit`
+    ///
+    /// Only the memos that are returned are read.
+    pub fn list_memos_filtered(
+        &self,
+        range: &DateRange,
+        limit: Option<usize>,
+        offset: usize,
+    ) -> MemoResult<Vec<MemoFile>> {
+        let limit = limit.unwrap_or(usize::MAX);
+        Ok(self
+            .iter_memos()?
+            .filter(|entry| range.contains(&entry.id.get_datetime()))
+            .filter_map(|entry| entry.load().ok())
+            .skip(offset)
+            .take(limit)
+            .collect())
+    }
 
-        Ok(memos)
+    /// memos whose creation (ID) or modification time is within `range`, newest first (by ID)
+    pub fn select_memos(&self, range: &DateRange, by: DateBasis) -> MemoResult<Vec<MemoFile>> {
+        Ok(self
+            .iter_memos()?
+            .filter(|entry| match by {
+                DateBasis::Created => range.contains(&entry.id.get_datetime()),
+                DateBasis::Modified => range.contains(&entry.modified),
+            })
+            .filter_map(|entry| entry.load().ok())
+            .collect())
     }
 
-    pub fn list_all_memo_documents(&self) -> MemoResult<Vec<MemoDocument>> {
-        let memo_files = self.list_all_memos()?;
-        Ok(memo_files
-            .iter()
-            .map(MemoDocument::from_memo_file)
+    /// memos created on `date` (local time), newest first
+    pub fn list_memos_for_date(&self, date: NaiveDate) -> MemoResult<Vec<MemoFile>> {
+        Ok(self
+            .iter_memos()?
+            .filter(|entry| entry.id.get_datetime().date_naive() == date)
+            .filter_map(|entry| entry.load().ok())
             .collect())
     }
 
+    /// the memo whose file was modified most recently, if any
+    pub fn latest_modified_memo(&self) -> MemoResult<Option<MemoFile>> {
+        self.iter_memos()?
+            .max_by_key(|entry| entry.modified)
+            .map(|entry| entry.load())
+            .transpose()
+    }
+
+    /// 完全ID、短縮ID、IDの先頭・末尾の一部（4桁以上）でメモを探す
+    ///
+    /// 一部が複数のメモに一致した場合は `MemoError::AmbiguousId` を返す。
     pub fn find_memo_by_id(&self, id: &str) -> MemoResult<MemoFile> {
-        let resolved_path = resolve_memo_id(&self.context.memo_dir, id)
-            .map_err(|_| MemoError::MemoNotFound(id.to_string()))?;
+        let resolved_path = resolve_memo_id(&self.context.memo_dir, id).map_err(|e| match e {
+            MemoError::AmbiguousId(_) => e,
+            _ => MemoError::MemoNotFound(id.to_string()),
+        })?;
 
         MemoFile::from_path(resolved_path)
     }
@@ -53,20 +113,125 @@ impl MemoRepository {
         MemoFile::create(full_path, content)
     }
 
+    /// create a memo with a new ID for the current time
+    ///
+    /// When the ID is taken (another memo created in the same second), the next free second is
+    /// used. `content` gets the final ID, e.g. for templates.
+    pub fn create_new_memo(
+        &self,
+        encrypt: bool,
+        content: impl FnOnce(&MemoId) -> String,
+    ) -> MemoResult<MemoFile> {
+        self.create_memo_from(MemoId::new(), encrypt, content)
+    }
+
+    fn create_memo_from(
+        &self,
+        mut id: MemoId,
+        encrypt: bool,
+        content: impl FnOnce(&MemoId) -> String,
+    ) -> MemoResult<MemoFile> {
+        loop {
+            let plain_path = id.to_file_path(&self.context.memo_dir);
+            let encrypted_path = encryption::encrypted_path(&plain_path);
+            let path = if encrypt {
+                &encrypted_path
+            } else {
+                &plain_path
+            };
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).with_path(parent)?;
+            }
+            // 同じ秒に別のプロセスが作っても上書きしないよう、空のファイルを先に作って確保する
+            let taken = plain_path.exists() || encrypted_path.exists();
+            if !taken {
+                match fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(path)
+                {
+                    Ok(_) => return MemoFile::create(path, content(&id)),
+                    Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+                    Err(e) => return Err(e).with_path(path),
+                }
+            }
+            id = id.next();
+        }
+    }
+
     // archive a single memo file
     pub fn archive_memo(&self, memo: &MemoFile) -> MemoResult<MemoFile> {
-        let archive_dir = self.context.archive_dir();
-        let relative_path = memo.id.to_relative_path();
-        let archive_path = archive_dir.join(relative_path);
+        let archive_path = self.archive_path(memo);
 
         if let Some(parent) = archive_path.parent() {
-            fs::create_dir_all(parent)?;
+            fs::create_dir_all(parent).with_path(parent)?;
         }
-        self.ensure_archive_ignored()?;
+        self.ensure_ignored(".archive")?;
 
         memo.move_to(archive_path)
     }
 
+    /// find an archived memo by ID
+    pub fn find_archived_memo(&self, id: &str) -> MemoResult<MemoFile> {
+        let resolved_path =
+            resolve_memo_id(self.context.archive_dir(), id).map_err(|e| match e {
+                MemoError::AmbiguousId(_) => e,
+                _ => MemoError::MemoNotFound(id.to_string()),
+            })?;
+
+        MemoFile::from_path(resolved_path)
+    }
+
+    /// move an archived memo back to its original place
+    pub fn restore_memo(&self, memo: &MemoFile) -> MemoResult<MemoFile> {
+        let restore_path = self.restore_path(memo)?;
+
+        if let Some(parent) = restore_path.parent() {
+            fs::create_dir_all(parent).with_path(parent)?;
+        }
+        memo.move_to(restore_path)
+    }
+
+    /// give a memo a new ID, moving it (and its attachments) to the path for `id`
+    pub fn move_memo(&self, memo: &MemoFile, id: &MemoId) -> MemoResult<MemoFile> {
+        if &memo.id == id {
+            return Err(MemoError::InvalidArgument(format!(
+                "memo {} already has that ID",
+                id
+            )));
+        }
+        // 暗号化の有無に関わらず、同じ ID のメモがあれば移さない
+        if resolve_memo_id(&self.context.memo_dir, &id.as_str()).is_ok() {
+            return Err(MemoError::InvalidArgument(format!(
+                "cannot move '{}': memo {} already exists",
+                memo.id, id
+            )));
+        }
+        memo.move_to_id(id, self.context.memo_dir.join(memo.relative_path_as(id)))
+    }
+
+    /// delete a memo: move it to `.trash`, or remove the file when `permanent`
+    ///
+    /// Returns the trashed memo (`None` when permanently removed).
+    pub fn delete_memo(&self, memo: &MemoFile, permanent: bool) -> MemoResult<Option<MemoFile>> {
+        if permanent {
+            fs::remove_file(&memo.path).with_path(&memo.path)?;
+            let assets_dir = memo.assets_dir();
+            if assets_dir.is_dir() {
+                fs::remove_dir_all(&assets_dir).with_path(&assets_dir)?;
+            }
+            return Ok(None);
+        }
+
+        let trash_path = self.trash_path(memo);
+        if let Some(parent) = trash_path.parent() {
+            fs::create_dir_all(parent).with_path(parent)?;
+        }
+        self.ensure_ignored(".trash")?;
+
+        memo.move_to(trash_path).map(Some)
+    }
+
     // archive multiple memo files
     pub fn archive_memos(&self, memos: Vec<MemoFile>) -> MemoResult<Vec<MemoFile>> {
         let mut archived = Vec::new();
@@ -80,7 +245,14 @@ impl MemoRepository {
     }
 
     /// archive all memo files in a directory
+    #[allow(dead_code)] // ライブラリとして使う側の API
     pub fn archive_directory(&self, dir_path: &str) -> MemoResult<Vec<MemoFile>> {
+        let memos = self.list_memos_in_directory(dir_path)?;
+        self.archive_memos(memos)
+    }
+
+    /// all memo files under a directory relative to the memo directory
+    pub fn list_memos_in_directory(&self, dir_path: &str) -> MemoResult<Vec<MemoFile>> {
         let full_dir_path = self.context.memo_dir.join(dir_path);
 
         if !full_dir_path.exists() || !full_dir_path.is_dir() {
@@ -89,51 +261,231 @@ impl MemoRepository {
 
         let mut memos = Vec::new();
         self.collect_memos_recursive(&full_dir_path, &mut memos)?;
+        Ok(memos)
+    }
 
-        self.archive_memos(memos)
+    /// moves `archive_memo` would make, without touching the filesystem
+    pub fn plan_archive(&self, memos: &[MemoFile]) -> Vec<PlannedMove> {
+        memos
+            .iter()
+            .flat_map(|memo| Self::planned_moves(memo, Some(self.archive_path(memo)), &memo.id))
+            .collect()
+    }
+
+    /// moves `restore_memo` would make; fails like `restore_memo` when a memo is in the way
+    pub fn plan_restore(&self, memos: &[MemoFile]) -> MemoResult<Vec<PlannedMove>> {
+        let mut moves = Vec::new();
+        for memo in memos {
+            moves.extend(Self::planned_moves(
+                memo,
+                Some(self.restore_path(memo)?),
+                &memo.id,
+            ));
+        }
+        Ok(moves)
+    }
+
+    /// moves (or removals when `permanent`) `delete_memo` would make
+    pub fn plan_delete(&self, memos: &[MemoFile], permanent: bool) -> Vec<PlannedMove> {
+        memos
+            .iter()
+            .flat_map(|memo| {
+                if permanent {
+                    // 完全削除はメモのファイルと添付ファイルのディレクトリを消す
+                    Self::planned_moves(memo, None, &memo.id)
+                } else {
+                    Self::planned_moves(memo, Some(self.trash_path(memo)), &memo.id)
+                }
+            })
+            .collect()
+    }
+
+    /// moves `move_memo` would make
+    pub fn plan_move(&self, memo: &MemoFile, id: &MemoId) -> Vec<PlannedMove> {
+        let to = self.context.memo_dir.join(memo.relative_path_as(id));
+        Self::planned_moves(memo, Some(to), id)
+    }
+
+    /// the memo file and, if present, its attachment directory (`MemoFile::move_to_id`);
+    /// `to: None` removes both
+    fn planned_moves(memo: &MemoFile, to: Option<PathBuf>, id: &MemoId) -> Vec<PlannedMove> {
+        let assets_dir = memo.assets_dir();
+        let assets_to = to
+            .as_ref()
+            .and_then(|to| to.parent())
+            .map(|parent| parent.join(id.assets_dir_name()));
+
+        let mut moves = vec![PlannedMove {
+            from: memo.path.clone(),
+            to,
+        }];
+        if assets_dir.is_dir() {
+            moves.push(PlannedMove {
+                from: assets_dir,
+                to: assets_to,
+            });
+        }
+        moves
+    }
+
+    fn archive_path(&self, memo: &MemoFile) -> PathBuf {
+        self.context.archive_dir().join(memo.relative_path())
+    }
+
+    fn restore_path(&self, memo: &MemoFile) -> MemoResult<PathBuf> {
+        let restore_path = self.context.memo_dir.join(memo.relative_path());
+        if restore_path.exists() {
+            return Err(MemoError::ArchiveError(format!(
+                "cannot restore '{}': {} already exists",
+                memo.id,
+                restore_path.display()
+            )));
+        }
+        Ok(restore_path)
+    }
+
+    fn trash_path(&self, memo: &MemoFile) -> PathBuf {
+        self.context.trash_dir().join(memo.id.to_relative_path())
+    }
+
+    /// number of memos per creation month (`2025-01`) or day (`2025-01-30`)
+    ///
+    /// Counted from the file names, so the memos are not read.
+    pub fn count_memos_by(&self, period: Period) -> MemoResult<BTreeMap<String, usize>> {
+        let mut counts = BTreeMap::new();
+        for path in self.list_memo_paths()? {
+            if let Ok(id) = MemoId::from_path(&path) {
+                let key = id.get_datetime().format(period.format()).to_string();
+                *counts.entry(key).or_insert(0) += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+    /// total words (separated by whitespace) and characters of all memos
+    #[allow(dead_code)] // ライブラリとして使う側の API
+    pub fn text_totals(&self) -> MemoResult<TextTotals> {
+        let mut totals = TextTotals::default();
+        for memo in self.iter_memos()?.filter_map(|entry| entry.load().ok()) {
+            totals.add(&memo);
+        }
+        Ok(totals)
+    }
+
+    /// archived memos and their size on disk
+    pub fn archive_usage(&self) -> MemoResult<DiskUsage> {
+        let mut paths = Vec::new();
+        Self::collect_memo_paths(&self.context.archive_dir(), &mut paths)?;
+        let mut usage = DiskUsage::default();
+        for path in paths {
+            usage.files += 1;
+            usage.bytes += fs::metadata(&path).with_path(&path)?.len();
+        }
+        Ok(usage)
     }
 
-    /// add ".archive" to .ignore file
-    fn ensure_archive_ignored(&self) -> MemoResult<()> {
+    /// files under the search index directory and their size on disk
+    pub fn index_usage(&self) -> MemoResult<DiskUsage> {
+        let mut usage = DiskUsage::default();
+        Self::add_dir_usage(&self.context.index_dir(), &mut usage)?;
+        Ok(usage)
+    }
+
+    fn add_dir_usage(dir: &Path, usage: &mut DiskUsage) -> MemoResult<()> {
+        if !dir.exists() {
+            return Ok(());
+        }
+        for entry in fs::read_dir(dir).with_path(dir)? {
+            let entry = entry.with_path(dir)?;
+            let metadata = entry.metadata().with_path(entry.path())?;
+            if metadata.is_dir() {
+                Self::add_dir_usage(&entry.path(), usage)?;
+            } else {
+                usage.files += 1;
+                usage.bytes += metadata.len();
+            }
+        }
+        Ok(())
+    }
+
+    /// add an entry such as ".archive" to .ignore file
+    fn ensure_ignored(&self, entry: &str) -> MemoResult<()> {
         let ignore_file = self.context.ignore_file();
 
         let mut content = if ignore_file.exists() {
-            fs::read_to_string(&ignore_file)?
+            fs::read_to_string(&ignore_file).with_path(&ignore_file)?
         } else {
             String::new()
         };
 
-        if !content.contains(".archive") {
+        if !content.lines().any(|line| line.trim() == entry) {
             if !content.is_empty() && !content.ends_with('\n') {
                 content.push('\n');
             }
-            content.push_str(".archive\n");
-            fs::write(&ignore_file, content)?;
+            content.push_str(entry);
+            content.push('\n');
+            fs::write(&ignore_file, content).with_path(&ignore_file)?;
         }
 
         Ok(())
     }
 
+    /// paths of all memo files, without reading them
+    pub fn list_memo_paths(&self) -> MemoResult<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        Self::collect_memo_paths(&self.context.memo_dir, &mut paths)?;
+        Ok(paths)
+    }
+
+    /// archived memos, newest first (by ID)
+    pub fn list_archived_memos(&self) -> MemoResult<Vec<MemoFile>> {
+        let mut paths = self.list_archived_memo_paths()?;
+        paths.sort_by(|a, b| b.cmp(a));
+        Ok(paths
+            .iter()
+            .filter_map(|path| MemoFile::from_path(path).ok())
+            .collect())
+    }
+
+    /// paths of all memo files under `.archive`, without reading them
+    pub fn list_archived_memo_paths(&self) -> MemoResult<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        Self::collect_memo_paths(&self.context.archive_dir(), &mut paths)?;
+        Ok(paths)
+    }
+
     /// connects to the memo directory and recursively collects all memo files
     fn collect_memos_recursive(&self, dir: &Path, memos: &mut Vec<MemoFile>) -> MemoResult<()> {
+        let mut paths = Vec::new();
+        Self::collect_memo_paths(dir, &mut paths)?;
+        memos.extend(
+            paths
+                .iter()
+                .filter_map(|path| MemoFile::from_path(path).ok()),
+        );
+        Ok(())
+    }
+
+    fn collect_memo_paths(dir: &Path, paths: &mut Vec<PathBuf>) -> MemoResult<()> {
         if !dir.exists() {
             return Ok(());
         }
 
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
+        for entry in fs::read_dir(dir).with_path(dir)? {
+            let entry = entry.with_path(dir)?;
             let path = entry.path();
 
             if path.is_dir() {
-                if path.file_name().and_then(|n| n.to_str()) == Some(".archive") {
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                // 添付ファイルのディレクトリ（`143022`）の中はメモではない
+                if matches!(name, ".archive" | ".trash" | ".index") || is_assets_dir_name(name) {
                     continue;
                 }
-                self.collect_memos_recursive(&path, memos)?;
-            } else if path.extension().and_then(|s| s.to_str()) == Some("md") {
-                match MemoFile::from_path(&path) {
-                    Ok(memo) => memos.push(memo),
-                    Err(_) => continue,
-                }
+                Self::collect_memo_paths(&path, paths)?;
+            } else if path.extension().and_then(|s| s.to_str()) == Some("md")
+                || encryption::is_encrypted(&path)
+            {
+                paths.push(path);
             }
         }
 
@@ -141,9 +493,180 @@ impl MemoRepository {
     }
 }
 
+/// `HHMMSS` の6桁（月 `2025-01`・日 `30` のディレクトリとは重ならない）
+fn is_assets_dir_name(name: &str) -> bool {
+    name.len() == 6 && name.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Creation time range for `list_memos_filtered` (`since` inclusive, `until` exclusive)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DateRange {
+    pub since: Option<DateTime<Local>>,
+    pub until: Option<DateTime<Local>>,
+}
+
+impl DateRange {
+    pub fn contains(&self, at: &DateTime<Local>) -> bool {
+        self.since.is_none_or(|since| *at >= since) && self.until.is_none_or(|until| *at < until)
+    }
+}
+
+/// Which time of a memo `select_memos` looks at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateBasis {
+    /// When the memo was created (its ID)
+    #[default]
+    Created,
+    /// When the memo file was last modified
+    Modified,
+}
+
+/// Granularity for `count_memos_by`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Month,
+    Day,
+}
+
+impl Period {
+    fn format(self) -> &'static str {
+        match self {
+            Period::Month => "%Y-%m",
+            Period::Day => "%Y-%m-%d",
+        }
+    }
+}
+
+/// Result of `text_totals`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextTotals {
+    pub memos: usize,
+    pub words: usize,
+    pub chars: usize,
+}
+
+impl TextTotals {
+    pub fn add(&mut self, memo: &MemoFile) {
+        self.memos += 1;
+        self.words += memo.content.split_whitespace().count();
+        self.chars += memo.content.chars().count();
+    }
+}
+
+/// A memo file found by `iter_memos`; the file is read only by `load`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoEntry {
+    pub path: PathBuf,
+    pub id: MemoId,
+    /// Last modification time of the file
+    pub modified: DateTime<Local>,
+}
+
+impl MemoEntry {
+    fn new(path: PathBuf) -> MemoResult<Self> {
+        let id = MemoId::from_path(&path)?;
+        let metadata = fs::metadata(&path).with_path(&path)?;
+        let modified = DateTime::from(metadata.modified().with_path(&path)?);
+        Ok(Self { path, id, modified })
+    }
+
+    pub fn load(&self) -> MemoResult<MemoFile> {
+        MemoFile::from_path(&self.path)
+    }
+}
+
+/// A file or directory `archive` / `restore` / `delete` would move (`--dry-run`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedMove {
+    pub from: PathBuf,
+    /// `None` when the file is removed (`delete --permanent`)
+    pub to: Option<PathBuf>,
+}
+
+/// Number of files and their total size
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Built-in orderings for memo lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SortKey {
+    /// Most recently modified first
+    Modified,
+    /// Newest first (by memo ID)
+    Created,
+    /// Title in alphabetical order, ignoring case
+    Title,
+    /// Longest body first
+    Size,
+    /// Highest `priority:` first; memos without it come last
+    Priority,
+}
+
+impl SortKey {
+    /// Compare two memos in this ordering
+    pub fn compare(self, a: &MemoFile, b: &MemoFile) -> Ordering {
+        match self {
+            SortKey::Modified => b.modified.cmp(&a.modified),
+            SortKey::Created => b.id.cmp(&a.id),
+            SortKey::Title => a.title().to_lowercase().cmp(&b.title().to_lowercase()),
+            SortKey::Size => b.content.len().cmp(&a.content.len()),
+            SortKey::Priority => {
+                let priority = |memo: &MemoFile| memo.priority().unwrap_or(i64::MIN);
+                priority(b).cmp(&priority(a))
+            }
+        }
+    }
+}
+
+/// Sort memos by `key`. The sort is stable, so ties keep their current order.
+pub fn sort_memos(memos: &mut [MemoFile], key: SortKey) {
+    match key {
+        // タイトルは比較のたびに作らず、一度だけ求める
+        SortKey::Title => memos.sort_by_cached_key(|memo| memo.title().to_lowercase()),
+        key => memos.sort_by(|a, b| key.compare(a, b)),
+    }
+}
+
+/// Sort memos by a front matter field in ascending order.
+///
+/// Numbers compare numerically and sort before strings; memos without the field come last.
+/// The sort is stable, so memos with equal values keep their current order.
+pub fn sort_memos_by_metadata(memos: &mut [MemoFile], key: &str) {
+    memos.sort_by(
+        |a, b| match (a.metadata_value(key), b.metadata_value(key)) {
+            (Some(a), Some(b)) => compare_yaml_values(a, b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
+    );
+}
+
+fn compare_yaml_values(a: &Value, b: &Value) -> Ordering {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => yaml_sort_string(a).cmp(&yaml_sort_string(b)),
+    }
+}
+
+fn yaml_sort_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        other => serde_yaml::to_string(other).unwrap_or_default(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
     use std::fs;
     use tempfile::TempDir;
 
@@ -155,6 +678,7 @@ mod tests {
         let context = MemoContext {
             memo_dir,
             editor: "echo".to_string(),
+            ..Default::default()
         };
 
         (temp_dir, context)
@@ -199,6 +723,204 @@ mod tests {
         assert_eq!(memos[1].content, "Memo 1");
     }
 
+    #[test]
+    fn test_sort_memos_by_metadata() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+
+        repo.create_memo(
+            "2025-01/30/20250130100000.md",
+            "---\npriority: 10\n---\nA".to_string(),
+        )
+        .unwrap();
+        repo.create_memo("2025-01/30/20250130110000.md", "B".to_string())
+            .unwrap();
+        repo.create_memo(
+            "2025-01/30/20250130120000.md",
+            "---\npriority: 2\n---\nC".to_string(),
+        )
+        .unwrap();
+        repo.create_memo(
+            "2025-01/30/20250130130000.md",
+            "---\npriority: high\n---\nD".to_string(),
+        )
+        .unwrap();
+
+        let mut memos = repo.list_all_memos().unwrap();
+        sort_memos_by_metadata(&mut memos, "priority");
+
+        let contents: Vec<_> = memos.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["C", "A", "D", "B"]);
+
+        // list_all_memos は新しい順なので、priority の無いメモ同士は D, B の順
+        let mut memos = repo.list_all_memos().unwrap();
+        sort_memos(&mut memos, SortKey::Priority);
+
+        let contents: Vec<_> = memos.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["A", "C", "D", "B"]);
+    }
+
+    #[test]
+    fn test_sort_memos_builtin_keys() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+
+        let older = repo
+            .create_memo(
+                "2025-01/30/20250130100000.md",
+                "# beta\nlong body".to_string(),
+            )
+            .unwrap();
+        repo.create_memo("2025-01/30/20250130110000.md", "# Alpha".to_string())
+            .unwrap();
+        repo.create_memo("2025-01/30/20250130120000.md", "# gamma\nbody".to_string())
+            .unwrap();
+        // 一番古いメモを最後に更新する
+        let file = fs::File::options().write(true).open(&older.path).unwrap();
+        file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+
+        let titles = |key| {
+            let mut memos = repo.list_all_memos().unwrap();
+            sort_memos(&mut memos, key);
+            memos.iter().map(|m| m.title()).collect::<Vec<_>>()
+        };
+        assert_eq!(titles(SortKey::Created), vec!["gamma", "Alpha", "beta"]);
+        assert_eq!(titles(SortKey::Modified), vec!["beta", "gamma", "Alpha"]);
+        assert_eq!(titles(SortKey::Title), vec!["Alpha", "beta", "gamma"]);
+        assert_eq!(titles(SortKey::Size), vec!["beta", "gamma", "Alpha"]);
+    }
+
+    #[test]
+    fn test_list_memos_filtered() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+        for id in [
+            "20241231230000",
+            "20250101000000",
+            "20250115120000",
+            "20250201090000",
+        ] {
+            let path = MemoId::from_str(id).unwrap().to_relative_path();
+            repo.create_memo(path, id.to_string()).unwrap();
+        }
+        let at = |y, m, d| Local.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap();
+
+        let ids = |memos: Vec<MemoFile>| memos.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+        let range = DateRange {
+            since: Some(at(2025, 1, 1)),
+            until: Some(at(2025, 2, 1)),
+        };
+        assert_eq!(
+            ids(repo.list_memos_filtered(&range, None, 0).unwrap()),
+            vec!["20250115120000", "20250101000000"]
+        );
+        let latest = repo
+            .list_memos_filtered(&DateRange::default(), Some(2), 0)
+            .unwrap();
+        assert_eq!(ids(latest), vec!["20250201090000", "20250115120000"]);
+        let next = repo
+            .list_memos_filtered(&DateRange::default(), Some(2), 2)
+            .unwrap();
+        assert_eq!(ids(next), vec!["20250101000000", "20241231230000"]);
+    }
+
+    #[test]
+    fn test_select_memos() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+        for id in ["20240101090000", "20250130143022"] {
+            let path = MemoId::from_str(id).unwrap().to_relative_path();
+            repo.create_memo(path, id.to_string()).unwrap();
+        }
+        // 古いメモを最近書き換えた
+        let old = repo.find_memo_by_id("20240101090000").unwrap();
+        let recent = repo.find_memo_by_id("20250130143022").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&recent.path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap();
+
+        let range = DateRange {
+            since: None,
+            until: Some(Local.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+        };
+        let ids = |by| -> Vec<String> {
+            repo.select_memos(&range, by)
+                .unwrap()
+                .iter()
+                .map(|m| m.id.as_str())
+                .collect()
+        };
+        assert_eq!(ids(DateBasis::Created), vec![old.id.as_str()]);
+        assert_eq!(ids(DateBasis::Modified), vec![recent.id.as_str()]);
+    }
+
+    #[test]
+    fn test_list_memos_for_date() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+        for id in ["20250129235959", "20250130000000", "20250130143022"] {
+            let path = MemoId::from_str(id).unwrap().to_relative_path();
+            repo.create_memo(path, id.to_string()).unwrap();
+        }
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 30).unwrap();
+        let ids: Vec<String> = repo
+            .list_memos_for_date(date)
+            .unwrap()
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["20250130143022", "20250130000000"]);
+        assert!(
+            repo.list_memos_for_date(date.succ_opt().unwrap())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_aggregations() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+        repo.create_memo("2025-01/30/20250130100000.md", "hello world".to_string())
+            .unwrap();
+        repo.create_memo("2025-01/30/20250130110000.md", "週報 です".to_string())
+            .unwrap();
+        let old = repo
+            .create_memo("2024-12/31/20241231100000.md", "old".to_string())
+            .unwrap();
+        repo.archive_memo(&old).unwrap();
+        repo.create_memo("2025-02/01/20250201100000.md", "a b c".to_string())
+            .unwrap();
+
+        let by_month = repo.count_memos_by(Period::Month).unwrap();
+        assert_eq!(
+            by_month.into_iter().collect::<Vec<_>>(),
+            vec![("2025-01".to_string(), 2), ("2025-02".to_string(), 1)]
+        );
+        let by_day = repo.count_memos_by(Period::Day).unwrap();
+        assert_eq!(by_day.get("2025-01-30"), Some(&2));
+        assert_eq!(by_day.len(), 2);
+
+        assert_eq!(
+            repo.text_totals().unwrap(),
+            TextTotals {
+                memos: 3,
+                words: 7,
+                chars: 21,
+            }
+        );
+        assert_eq!(
+            repo.archive_usage().unwrap(),
+            DiskUsage { files: 1, bytes: 3 }
+        );
+        assert_eq!(repo.index_usage().unwrap(), DiskUsage::default());
+    }
+
     #[test]
     fn test_find_memo_by_id() {
         let (_temp_dir, context) = create_test_context();
@@ -215,6 +937,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_memo_by_partial_id() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+
+        repo.create_memo("2025-01/30/20250130143022.md", "A".to_string())
+            .unwrap();
+        repo.create_memo("2025-01/31/20250131143022.md", "B".to_string())
+            .unwrap();
+
+        assert_eq!(repo.find_memo_by_id("0131143022").unwrap().content, "B");
+        assert_eq!(repo.find_memo_by_id("202501301").unwrap().content, "A");
+        match repo.find_memo_by_id("143022") {
+            Err(MemoError::AmbiguousId(candidates)) => {
+                assert_eq!(candidates, vec!["20250131143022", "20250130143022"])
+            }
+            other => panic!("Expected AmbiguousId, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_archive_memo() {
         let (_temp_dir, context) = create_test_context();
@@ -230,6 +972,142 @@ mod tests {
         assert!(archived.path.to_string_lossy().contains(".archive"));
     }
 
+    #[test]
+    fn test_list_archived_memos() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+
+        assert!(repo.list_archived_memos().unwrap().is_empty());
+        for path in [
+            "2025-01/30/20250130143022.md",
+            "2025-02/01/20250201090000.md",
+            "2025-02/01/20250201100000.md",
+        ] {
+            let memo = repo.create_memo(path, "Test memo".to_string()).unwrap();
+            if !path.ends_with("100000.md") {
+                repo.archive_memo(&memo).unwrap();
+            }
+        }
+
+        let ids: Vec<String> = repo
+            .list_archived_memos()
+            .unwrap()
+            .iter()
+            .map(|memo| memo.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["20250201090000", "20250130143022"]);
+        assert_eq!(repo.list_all_memos().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_restore_memo() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+
+        let memo = repo
+            .create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+        repo.archive_memo(&memo).unwrap();
+
+        let archived = repo.find_archived_memo("20250130143022").unwrap();
+        let restored = repo.restore_memo(&archived).unwrap();
+        assert_eq!(restored.path, memo.path);
+        assert!(!archived.path.exists());
+        assert!(repo.find_archived_memo("20250130143022").is_err());
+
+        // 同じ場所に別のメモがあれば上書きしない
+        repo.archive_memo(&restored).unwrap();
+        repo.create_memo("2025-01/30/20250130143022.md", "New memo".to_string())
+            .unwrap();
+        let archived = repo.find_archived_memo("20250130143022").unwrap();
+        assert!(matches!(
+            repo.restore_memo(&archived),
+            Err(MemoError::ArchiveError(_))
+        ));
+        assert!(archived.path.exists());
+    }
+
+    #[test]
+    fn test_create_memo_from_taken_id() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+
+        repo.create_memo("2025-01/30/20250130235959.md", "First".to_string())
+            .unwrap();
+        let id = MemoId::from_str("20250130235959").unwrap();
+        let memo = repo
+            .create_memo_from(id.clone(), false, |id| format!("Second {}", id))
+            .unwrap();
+        assert_eq!(memo.id.as_str(), "20250131000000");
+        assert_eq!(memo.content, "Second 20250131000000");
+        assert_eq!(
+            repo.find_memo_by_id("20250130235959").unwrap().content,
+            "First"
+        );
+
+        // 暗号化したメモとも重ならない
+        fs::write(
+            repo.memo_dir().join("2025-01/31/20250131000001.md.age"),
+            "ciphertext",
+        )
+        .unwrap();
+        let memo = repo
+            .create_memo_from(id, false, |_| "Third".to_string())
+            .unwrap();
+        assert_eq!(memo.id.as_str(), "20250131000002");
+    }
+
+    #[test]
+    fn test_move_memo() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+
+        let memo = repo
+            .create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+        fs::create_dir_all(memo.assets_dir()).unwrap();
+        fs::write(memo.assets_dir().join("photo.png"), "png").unwrap();
+        repo.create_memo("2025-02/01/20250201090000.md", "Other".to_string())
+            .unwrap();
+
+        let id = MemoId::from_str("20250131080000").unwrap();
+        let moved = repo.move_memo(&memo, &id).unwrap();
+        assert_eq!(moved.id, id);
+        assert_eq!(
+            moved.path,
+            context.memo_dir.join("2025-01/31/20250131080000.md")
+        );
+        assert_eq!(moved.content, "Test memo");
+        assert!(!memo.path.exists());
+        assert!(moved.assets_dir().join("photo.png").is_file());
+
+        // 既にあるIDや同じIDには移さない
+        let taken = MemoId::from_str("20250201090000").unwrap();
+        assert!(repo.move_memo(&moved, &taken).is_err());
+        assert!(repo.move_memo(&moved, &id).is_err());
+        assert!(moved.path.exists());
+    }
+
+    #[test]
+    fn test_delete_memo() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+
+        let memo = repo
+            .create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+        let trashed = repo.delete_memo(&memo, false).unwrap().unwrap();
+        assert!(!memo.path.exists());
+        assert!(trashed.path.to_string_lossy().contains(".trash"));
+        assert!(repo.list_all_memos().unwrap().is_empty());
+
+        let memo = repo
+            .create_memo("2025-01/30/20250130151545.md", "Test memo".to_string())
+            .unwrap();
+        assert!(repo.delete_memo(&memo, true).unwrap().is_none());
+        assert!(!memo.path.exists());
+    }
+
     #[test]
     fn test_archive_memos() {
         let (_temp_dir, context) = create_test_context();
@@ -275,4 +1153,79 @@ mod tests {
         assert!(repo.memo_dir().join("2025-02/01/20250201151545.md").exists());
         assert!(repo.memo_dir().join("2025-02/01").exists());
     }
+
+    #[test]
+    fn test_plan_moves_do_not_touch_files() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context.clone());
+
+        let memo = repo
+            .create_memo("2025-01/30/20250130143022.md", "Test memo".to_string())
+            .unwrap();
+        fs::create_dir_all(memo.assets_dir()).unwrap();
+
+        let moves = repo.plan_archive(std::slice::from_ref(&memo));
+        assert_eq!(
+            moves,
+            vec![
+                PlannedMove {
+                    from: memo.path.clone(),
+                    to: Some(context.archive_dir().join("2025-01/30/20250130143022.md")),
+                },
+                PlannedMove {
+                    from: memo.assets_dir(),
+                    to: Some(context.archive_dir().join("2025-01/30/143022")),
+                },
+            ]
+        );
+        assert_eq!(
+            repo.plan_delete(std::slice::from_ref(&memo), true),
+            vec![
+                PlannedMove {
+                    from: memo.path.clone(),
+                    to: None,
+                },
+                PlannedMove {
+                    from: memo.assets_dir(),
+                    to: None,
+                },
+            ]
+        );
+        assert_eq!(
+            repo.plan_delete(std::slice::from_ref(&memo), false)[0].to,
+            Some(context.trash_dir().join("2025-01/30/20250130143022.md"))
+        );
+        assert!(memo.path.exists());
+        assert!(memo.assets_dir().exists());
+        assert!(!context.archive_dir().exists());
+        assert!(!context.trash_dir().exists());
+
+        // 戻し先にメモがあれば計画の段階でエラーになる
+        let archived = repo.archive_memo(&memo).unwrap();
+        repo.create_memo("2025-01/30/20250130143022.md", "New memo".to_string())
+            .unwrap();
+        assert!(repo.plan_restore(&[archived]).is_err());
+    }
+
+    #[test]
+    fn test_iter_memos_reads_lazily() {
+        let (_temp_dir, context) = create_test_context();
+        let repo = MemoRepository::new(context);
+
+        repo.create_memo("2025-01/29/20250129120000.md", "Older".to_string())
+            .unwrap();
+        repo.create_memo("2025-01/30/20250130143022.md", "Newer".to_string())
+            .unwrap();
+        repo.create_memo("2025-01/30/notes.md", "Not a memo".to_string())
+            .unwrap();
+
+        let entries: Vec<MemoEntry> = repo.iter_memos().unwrap().collect();
+        let ids: Vec<String> = entries.iter().map(|entry| entry.id.to_string()).collect();
+        assert_eq!(ids, vec!["20250130143022", "20250129120000"]);
+
+        // 一覧を取った後に消えたファイルは読む時にエラーになる
+        fs::remove_file(&entries[1].path).unwrap();
+        assert_eq!(entries[0].load().unwrap().content, "Newer");
+        assert!(entries[1].load().is_err());
+    }
 }
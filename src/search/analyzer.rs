@@ -0,0 +1,166 @@
+//! Analyzer chains used by the text fields of the index.
+//!
+//! - `ja`: Lindera + [`normalize`](crate::search::normalize::normalize)
+//! - `ja_en`: `ja` に加えて、英字だけのトークンを小文字化して英語の語幹にそろえる
+//!   （"running" → "run"）。日本語のトークンはそのまま
+//! - `bigram`: 辞書を使わず、かな・漢字を 2 文字ずつ（bi-gram）に分ける。
+//!   辞書が読めない時の `ja` と同じ分け方で、辞書に無い語も部分一致で引ける
+//!
+//! どのアナライザーを使うかはフィールドごとに `[search.analyzer]` で選ぶ。
+//! 索引を作る時にスキーマへ記録されるので、変えたら `memo index` で作り直す。
+//! Lindera の辞書（`[search.tokenizer]`）は両方のアナライザーで共通。
+
+use crate::search::japanese_tokenizer::{JapaneseTokenizer, TokenizerConfig};
+use rust_stemmers::{Algorithm, Stemmer};
+use tantivy::tokenizer::{
+    TextAnalyzer, Token, TokenFilter, TokenStream, Tokenizer, TokenizerManager,
+};
+
+/// テキストフィールドのアナライザー
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum Analyzer {
+    /// 日本語のみ（語幹処理なし）
+    #[serde(rename = "ja")]
+    Japanese,
+    /// 日本語 + 英語の語幹処理
+    #[serde(rename = "ja_en")]
+    JapaneseEnglish,
+    /// 辞書を使わない bi-gram
+    #[serde(rename = "bigram")]
+    Bigram,
+}
+
+impl Analyzer {
+    /// tantivy に登録するトークナイザー名
+    pub fn tokenizer_name(self) -> &'static str {
+        match self {
+            Analyzer::Japanese => "lang_ja",
+            Analyzer::JapaneseEnglish => "lang_ja_en",
+            Analyzer::Bigram => "lang_bigram",
+        }
+    }
+}
+
+/// フィールドごとのアナライザー（config.toml の `[search.analyzer]`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FieldAnalyzers {
+    pub title: Analyzer,
+    pub tags: Analyzer,
+    pub content: Analyzer,
+    pub metadata: Analyzer,
+}
+
+impl Default for FieldAnalyzers {
+    /// タグは書いたとおりに一致させたいので語幹処理しない
+    fn default() -> Self {
+        Self {
+            title: Analyzer::JapaneseEnglish,
+            tags: Analyzer::Japanese,
+            content: Analyzer::JapaneseEnglish,
+            metadata: Analyzer::JapaneseEnglish,
+        }
+    }
+}
+
+/// 全アナライザーを登録する（古い索引が使う `lang_ja` も含む）
+pub fn register(tokenizers: &TokenizerManager, config: &TokenizerConfig) {
+    let japanese = JapaneseTokenizer::new(config);
+    if !japanese.is_available() {
+        eprintln!(
+            "Warning: Japanese tokenizer is not available. Falling back to bi-gram tokenization."
+        );
+    }
+    tokenizers.register(Analyzer::Japanese.tokenizer_name(), japanese.clone());
+    tokenizers.register(
+        Analyzer::JapaneseEnglish.tokenizer_name(),
+        TextAnalyzer::builder(japanese).filter(LatinStemmer).build(),
+    );
+    tokenizers.register(
+        Analyzer::Bigram.tokenizer_name(),
+        JapaneseTokenizer::bigram(),
+    );
+}
+
+/// 英字だけのトークンを小文字化して英語の語幹にそろえるフィルター
+#[derive(Clone, Copy)]
+pub struct LatinStemmer;
+
+impl TokenFilter for LatinStemmer {
+    type Tokenizer<T: Tokenizer> = LatinStemmerFilter<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> LatinStemmerFilter<T> {
+        LatinStemmerFilter { inner: tokenizer }
+    }
+}
+
+#[derive(Clone)]
+pub struct LatinStemmerFilter<T> {
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for LatinStemmerFilter<T> {
+    type TokenStream<'a> = LatinStemmerStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        LatinStemmerStream {
+            tail: self.inner.token_stream(text),
+            stemmer: Stemmer::create(Algorithm::English),
+        }
+    }
+}
+
+pub struct LatinStemmerStream<T> {
+    tail: T,
+    stemmer: Stemmer,
+}
+
+impl<T: TokenStream> TokenStream for LatinStemmerStream<T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        let token = self.tail.token_mut();
+        if is_latin_word(&token.text) {
+            let lower = token.text.to_ascii_lowercase();
+            token.text = self.stemmer.stem(&lower).into_owned();
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+fn is_latin_word(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tantivy::tokenizer::SimpleTokenizer;
+
+    fn tokens(text: &str) -> Vec<String> {
+        let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LatinStemmer)
+            .build();
+        let mut stream = analyzer.token_stream(text);
+        let mut tokens = Vec::new();
+        stream.process(&mut |token| tokens.push(token.text.clone()));
+        tokens
+    }
+
+    #[test]
+    fn test_latin_stemmer() {
+        assert_eq!(tokens("Running runs run"), vec!["run", "run", "run"]);
+        assert_eq!(tokens("Memos indexing"), vec!["memo", "index"]);
+        // 英字以外を含むトークンと日本語はそのまま
+        assert_eq!(tokens("v2 会議"), vec!["v2", "会議"]);
+    }
+}
@@ -0,0 +1,154 @@
+//! Extra index fields for programs embedding memo as a library.
+//!
+//! ```ignore
+//! let extension = SchemaExtension::new()
+//!     .with_field("project", ExtraFieldKind::Keyword)
+//!     .with_enricher(|memo| vec![("project".to_string(), ExtraValue::Text(project_of(&memo.path)))]);
+//! let manager = SearchManager::new(data_dir, index_dir).with_extension(extension);
+//! ```
+//!
+//! The field list is saved next to the index so that opening it with a different
+//! extension is reported instead of silently returning wrong results.
+
+// ライブラリとして使う側の API なので、CLI からは使わないものがある
+#![allow(dead_code)]
+
+use crate::error::{IoResultExt, MemoError};
+use crate::memo::MemoDocument;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+const EXTENSION_FILE: &str = "schema_extension.json";
+
+/// 追加フィールドの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtraFieldKind {
+    /// Tokenized like the memo body
+    Text,
+    /// Matched as a whole value (ids, project names)
+    Keyword,
+    /// Integer, usable in range queries (`field:>=3`)
+    I64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtraField {
+    pub name: String,
+    pub kind: ExtraFieldKind,
+}
+
+/// 追加フィールドに入れる値
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtraValue {
+    Text(String),
+    I64(i64),
+}
+
+pub type Enricher = Arc<dyn Fn(&MemoDocument) -> Vec<(String, ExtraValue)> + Send + Sync>;
+
+/// 索引に足すフィールドと、その値をメモから作るコールバック
+#[derive(Clone, Default)]
+pub struct SchemaExtension {
+    fields: Vec<ExtraField>,
+    enricher: Option<Enricher>,
+}
+
+impl fmt::Debug for SchemaExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchemaExtension")
+            .field("fields", &self.fields)
+            .field("enricher", &self.enricher.is_some())
+            .finish()
+    }
+}
+
+impl SchemaExtension {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_field(mut self, name: &str, kind: ExtraFieldKind) -> Self {
+        self.fields.push(ExtraField {
+            name: name.to_string(),
+            kind,
+        });
+        self
+    }
+
+    /// メモを索引に追加するたびに呼ばれる
+    pub fn with_enricher<F>(mut self, enricher: F) -> Self
+    where
+        F: Fn(&MemoDocument) -> Vec<(String, ExtraValue)> + Send + Sync + 'static,
+    {
+        self.enricher = Some(Arc::new(enricher));
+        self
+    }
+
+    pub fn fields(&self) -> &[ExtraField] {
+        &self.fields
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub(crate) fn enrich(&self, memo: &MemoDocument) -> Vec<(String, ExtraValue)> {
+        self.enricher
+            .as_ref()
+            .map(|enricher| enricher(memo))
+            .unwrap_or_default()
+    }
+
+    /// 組み込みのフィールドと重なる名前や重複は作成前に弾く
+    pub(crate) fn validate(&self, reserved: &[&str]) -> Result<(), MemoError> {
+        for (i, field) in self.fields.iter().enumerate() {
+            if reserved.contains(&field.name.as_str())
+                || self.fields[..i].iter().any(|f| f.name == field.name)
+            {
+                return Err(MemoError::Search(format!(
+                    "extra field '{}' clashes with another field",
+                    field.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn save(&self, index_dir: &Path) -> Result<(), MemoError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let json = serde_json::to_string_pretty(&self.fields)
+            .map_err(|e| MemoError::Search(e.to_string()))?;
+        let path = index_dir.join(EXTENSION_FILE);
+        std::fs::write(&path, json).with_path(&path)?;
+        Ok(())
+    }
+
+    /// 索引作成時に記録したフィールド（無ければ空）
+    pub fn load_recorded(index_dir: &Path) -> Result<Vec<ExtraField>, MemoError> {
+        let path = index_dir.join(EXTENSION_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let json = std::fs::read_to_string(&path).with_path(&path)?;
+        serde_json::from_str(&json)
+            .map_err(|e| MemoError::Search(format!("{}: {}", path.display(), e)))
+    }
+
+    /// 要求するフィールドが全て同じ種類で記録されていること（記録側に余分があるのは構わない）
+    pub(crate) fn check_compatible(&self, recorded: &[ExtraField]) -> Result<(), MemoError> {
+        for field in &self.fields {
+            if !recorded.contains(field) {
+                return Err(MemoError::Search(format!(
+                    "the search index has no {:?} field '{}'; rebuild it with this extension",
+                    field.kind, field.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
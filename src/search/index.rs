@@ -1,26 +1,45 @@
 use std::collections::BTreeMap;
 
+use crate::encryption;
 use crate::error::MemoError;
+use crate::links;
 use crate::memo::{MemoDocument, MemoFile};
-use crate::search::{SearchResult, japanese_tokenizer::JapaneseTokenizer};
+use crate::search::analyzer::{self, Analyzer, FieldAnalyzers};
+use crate::search::extension::{ExtraFieldKind, ExtraValue, SchemaExtension};
+use crate::search::japanese_tokenizer::TokenizerConfig;
+use crate::search::{SearchParams, SearchResult, SearchSnippet, SearchSort, query};
 
 use tantivy::TantivyDocument;
-use tantivy::collector::TopDocs;
+use tantivy::collector::{Count, DocSetCollector, TopDocs};
 use tantivy::doc;
-use tantivy::query::QueryParser;
+use tantivy::query::{
+    AllQuery, BooleanQuery, BoostQuery, MoreLikeThisQuery, Occur, Query, QueryParser,
+    QueryParserError, RangeQuery, RegexQuery, TermQuery,
+};
+use tantivy::query_grammar::{self, UserInputAst, UserInputLeaf};
 use tantivy::schema::Value;
 use tantivy::schema::*;
+use tantivy::snippet::SnippetGenerator;
 use tantivy::*;
 
+use std::ops::Bound;
 use std::path::{Path, PathBuf};
 
+/// 書き込み時のメモリ量の既定値（バイト、`[index] heap_size` で変更可）
+pub const DEFAULT_WRITER_HEAP_SIZE: usize = 50_000_000;
+
+/// `similar` で使う特徴語の数
+const SIMILAR_QUERY_TERMS: usize = 25;
+
 /// Tantivy-based search index for memo documents
 pub struct SearchIndex {
     #[allow(dead_code)]
     pub data_dir: PathBuf,
     pub index_dir: PathBuf,
     index: Index,
-    writer: IndexWriter,
+    // 書き込みロックを握り続けないよう、書き込む時だけ作る
+    writer: Option<IndexWriter>,
+    heap_size: usize,
     reader: IndexReader,
 
     // fields
@@ -32,62 +51,134 @@ pub struct SearchIndex {
     tags_field: Field,
     tags_facet_field: Field,
     created_at_field: Field,
+    // indexes built before `updated_at` was introduced don't have this field
+    updated_at_field: Option<Field>,
+    // 同じく `priority` 導入前のインデックスには無い
+    priority_field: Option<Field>,
+    // 本文中の `[[id]]` のリンク先（`links` 導入前のインデックスには無い）
+    links_field: Option<Field>,
 
     metadata_field: Field,
+
+    // 埋め込み側が足したフィールド
+    extension: SchemaExtension,
+    extra_fields: Vec<(String, Field, ExtraFieldKind)>,
+}
+
+/// 組み込みのフィールド名（追加フィールドには使えない）
+const BUILTIN_FIELDS: [&str; 11] = [
+    "id",
+    "path",
+    "content",
+    "title",
+    "tags",
+    "tags.facet",
+    "created_at",
+    "updated_at",
+    "priority",
+    "links",
+    "metadata",
+];
+
+/// クエリに書ける組み込みのフィールド名（`path` は保存するだけで索引しない）
+pub fn query_fields() -> impl Iterator<Item = &'static str> {
+    BUILTIN_FIELDS.into_iter().filter(|field| *field != "path")
 }
 
 impl SearchIndex {
+    #[allow(dead_code)]
     pub fn create<P: AsRef<Path>>(
         data_dir: P,
         index_dir: P,
     ) -> std::result::Result<Self, MemoError> {
+        Self::create_with(
+            data_dir,
+            index_dir,
+            SchemaExtension::new(),
+            FieldAnalyzers::default(),
+            &TokenizerConfig::default(),
+        )
+    }
+
+    /// 追加フィールド付きで作成し、フィールド一覧を索引ディレクトリに記録する
+    ///
+    /// テキストフィールドは `analyzers` で指定したアナライザーで、`tokenizer` の辞書を使って索引する
+    pub fn create_with<P: AsRef<Path>>(
+        data_dir: P,
+        index_dir: P,
+        extension: SchemaExtension,
+        analyzers: FieldAnalyzers,
+        tokenizer: &TokenizerConfig,
+    ) -> std::result::Result<Self, MemoError> {
+        extension.validate(&BUILTIN_FIELDS)?;
         let data_dir = data_dir.as_ref().to_path_buf();
         let index_dir = index_dir.as_ref().to_path_buf();
 
-        // japanese text options
-        let ja_fi = TextFieldIndexing::default()
-            .set_tokenizer("lang_ja") // 日本語トークナイザーを指定
-            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
-        let text_options = TextOptions::default()
-            .set_indexing_options(ja_fi.clone())
-            .set_stored();
+        let indexing = |analyzer: Analyzer| {
+            TextFieldIndexing::default()
+                .set_tokenizer(analyzer.tokenizer_name())
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions)
+        };
+        let text_options = |analyzer: Analyzer| {
+            TextOptions::default()
+                .set_indexing_options(indexing(analyzer))
+                .set_stored()
+        };
 
         // schema building
         let mut schema_builder = Schema::builder();
         // required fields
         let id_field = schema_builder.add_text_field("id", TEXT | STORED);
         let path_field = schema_builder.add_text_field("path", STORED);
-        let content_field = schema_builder.add_text_field("content", text_options.clone());
+        let content_field =
+            schema_builder.add_text_field("content", text_options(analyzers.content));
 
         // optional fields
-        let title_field = schema_builder.add_text_field("title", text_options.clone());
-        let tags_field = schema_builder.add_text_field("tags", text_options.clone());
+        let title_field = schema_builder.add_text_field("title", text_options(analyzers.title));
+        let tags_field = schema_builder.add_text_field("tags", text_options(analyzers.tags));
         let tags_facet_field = schema_builder.add_facet_field("tags.facet", INDEXED);
-        let created_at_field = schema_builder.add_date_field("created_at", INDEXED | STORED);
+        let created_at_field = schema_builder.add_date_field("created_at", INDEXED | STORED | FAST);
+        let updated_at_field = schema_builder.add_date_field("updated_at", INDEXED | STORED | FAST);
+        let priority_field = schema_builder.add_i64_field("priority", INDEXED | STORED | FAST);
+        let links_field = schema_builder.add_text_field("links", STRING | STORED);
 
         let json_options = JsonObjectOptions::default()
             .set_stored()
             .set_expand_dots_enabled()
-            .set_indexing_options(ja_fi.clone());
+            .set_indexing_options(indexing(analyzers.metadata));
         let metadata_field = schema_builder.add_json_field("metadata", json_options);
+
+        let extra_fields = extension
+            .fields()
+            .iter()
+            .map(|field| {
+                let handle = match field.kind {
+                    ExtraFieldKind::Text => {
+                        schema_builder.add_text_field(&field.name, text_options(analyzers.content))
+                    }
+                    ExtraFieldKind::Keyword => {
+                        schema_builder.add_text_field(&field.name, STRING | STORED)
+                    }
+                    ExtraFieldKind::I64 => {
+                        schema_builder.add_i64_field(&field.name, INDEXED | STORED | FAST)
+                    }
+                };
+                (field.name.clone(), handle, field.kind)
+            })
+            .collect();
         let schema = schema_builder.build();
 
         let index = Index::create_in_dir(&index_dir, schema)?;
-        let japanese_tokenizer = JapaneseTokenizer::new();
-        if !japanese_tokenizer.is_available() {
-            eprintln!(
-                "Warning: Japanese tokenizer is not available. Falling back to simple tokenization."
-            );
-        }
-        index.tokenizers().register("lang_ja", japanese_tokenizer);
+        extension.save(&index_dir)?;
+        analyzer::register(index.tokenizers(), tokenizer);
 
-        let writer = index.writer(50_000_000)?;
         let reader = index.reader()?;
         Ok(Self {
             data_dir,
             index_dir,
             index,
-            writer,
+            writer: None,
+            heap_size: DEFAULT_WRITER_HEAP_SIZE,
             reader,
             id_field,
             path_field,
@@ -96,22 +187,40 @@ impl SearchIndex {
             tags_field,
             tags_facet_field,
             created_at_field,
+            updated_at_field: Some(updated_at_field),
+            priority_field: Some(priority_field),
+            links_field: Some(links_field),
             metadata_field,
+            extension,
+            extra_fields,
         })
     }
 
+    #[allow(dead_code)]
     pub fn open<P: AsRef<Path>>(data_dir: P, index_dir: P) -> std::result::Result<Self, MemoError> {
+        Self::open_with(
+            data_dir,
+            index_dir,
+            SchemaExtension::new(),
+            &TokenizerConfig::default(),
+        )
+    }
+
+    /// 作成時に記録された追加フィールドが `extension` と合うか確かめてから開く
+    ///
+    /// 検索語は `tokenizer` の辞書で分割する（作成時と同じ辞書を渡す）
+    pub fn open_with<P: AsRef<Path>>(
+        data_dir: P,
+        index_dir: P,
+        extension: SchemaExtension,
+        tokenizer: &TokenizerConfig,
+    ) -> std::result::Result<Self, MemoError> {
         let data_dir = data_dir.as_ref().to_path_buf();
         let index_dir = index_dir.as_ref().to_path_buf();
+        extension.check_compatible(&SchemaExtension::load_recorded(&index_dir)?)?;
 
         let index = Index::open_in_dir(&index_dir)?;
-        let japanese_tokenizer = JapaneseTokenizer::new();
-        if !japanese_tokenizer.is_available() {
-            eprintln!(
-                "Warning: Japanese tokenizer is not available. Falling back to simple tokenization."
-            );
-        }
-        index.tokenizers().register("lang_ja", japanese_tokenizer);
+        analyzer::register(index.tokenizers(), tokenizer);
 
         let schema = index.schema();
         let id_field = schema.get_field("id")?;
@@ -121,16 +230,30 @@ impl SearchIndex {
         let tags_field = schema.get_field("tags")?;
         let tags_facet_field = schema.get_field("tags.facet")?;
         let created_at_field = schema.get_field("created_at")?;
+        let updated_at_field = schema.get_field("updated_at").ok();
+        let priority_field = schema.get_field("priority").ok();
+        let links_field = schema.get_field("links").ok();
         let metadata_field = schema.get_field("metadata")?;
+        let extra_fields = extension
+            .fields()
+            .iter()
+            .map(|field| {
+                Ok((
+                    field.name.clone(),
+                    schema.get_field(&field.name)?,
+                    field.kind,
+                ))
+            })
+            .collect::<std::result::Result<Vec<_>, MemoError>>()?;
 
-        let writer = index.writer(50_000_000)?;
         let reader = index.reader()?;
 
         Ok(Self {
             data_dir,
             index_dir,
             index,
-            writer,
+            writer: None,
+            heap_size: DEFAULT_WRITER_HEAP_SIZE,
             reader,
             id_field,
             path_field,
@@ -139,11 +262,20 @@ impl SearchIndex {
             tags_field,
             tags_facet_field,
             created_at_field,
+            updated_at_field,
+            priority_field,
+            links_field,
             metadata_field,
+            extension,
+            extra_fields,
         })
     }
 
     pub fn add_memo(&mut self, memo: &MemoDocument) -> std::result::Result<(), MemoError> {
+        // 暗号化したメモの平文は、設定で許可しない限りインデックスに残さない
+        if !encryption::is_indexable(Path::new(&memo.path)) {
+            return Ok(());
+        }
         let mut doc = doc!(
             self.id_field => memo.id.to_string(),
             self.path_field => memo.path.clone(),
@@ -152,12 +284,24 @@ impl SearchIndex {
         );
 
         // optional fields
-        if let Some(front_matter) = &memo.metadata {
-            // title
-            if let Some(title) = front_matter.get("title").and_then(|v| v.as_str()) {
-                doc.add_text(self.title_field, title);
+        if let (Some(field), Some(updated_at)) = (self.updated_at_field, memo.updated_at) {
+            doc.add_date(field, DateTime::from_timestamp_secs(updated_at.timestamp()));
+        }
+        if let (Some(field), Some(priority)) = (self.priority_field, memo.priority()) {
+            doc.add_i64(field, priority);
+        }
+        if let Some(field) = self.links_field {
+            for target in links::parse_links(&memo.content) {
+                doc.add_text(field, target.as_str());
             }
+        }
 
+        // front matter に無ければ見出し・最初の行から推測したタイトル
+        if !memo.title.is_empty() {
+            doc.add_text(self.title_field, &memo.title);
+        }
+
+        if let Some(front_matter) = &memo.metadata {
             // tags
             if let Some(tags) = front_matter.get("tags").and_then(|v| v.as_array()) {
                 for tag in tags {
@@ -171,87 +315,496 @@ impl SearchIndex {
             doc.add_object(self.metadata_field, convert_map(front_matter.clone()));
         }
 
-        self.writer.add_document(doc)?;
+        for (name, value) in self.extension.enrich(memo) {
+            let field = self.extra_fields.iter().find(|(n, _, _)| *n == name);
+            match (field, value) {
+                (
+                    Some((_, field, ExtraFieldKind::Text | ExtraFieldKind::Keyword)),
+                    ExtraValue::Text(text),
+                ) => doc.add_text(*field, text),
+                (Some((_, field, ExtraFieldKind::I64)), ExtraValue::I64(value)) => {
+                    doc.add_i64(*field, value)
+                }
+                (_, value) => {
+                    return Err(MemoError::Search(format!(
+                        "enricher returned {:?} for field '{}', which is not declared with that type",
+                        value, name
+                    )));
+                }
+            }
+        }
+
+        self.writer()?.add_document(doc)?;
         Ok(())
     }
 
     pub fn remove_memo(&mut self, memo: &MemoDocument) -> std::result::Result<(), MemoError> {
-        let term = Term::from_field_text(self.id_field, &memo.id.as_str());
-        self.writer.delete_term(term);
+        self.remove_id(&memo.id.as_str())
+    }
+
+    pub fn remove_id(&mut self, id: &str) -> std::result::Result<(), MemoError> {
+        let term = Term::from_field_text(self.id_field, id);
+        self.writer()?.delete_term(term);
         Ok(())
     }
 
+    /// Commit pending changes and release the writer lock
     pub fn commit(&mut self) -> std::result::Result<(), MemoError> {
-        self.writer.commit()?;
+        if let Some(mut writer) = self.writer.take() {
+            writer.commit()?;
+        }
         self.reader.reload()?;
         Ok(())
     }
 
-    pub fn list_tags(&self) -> std::result::Result<Vec<(String, u64)>, MemoError> {
+    /// 索引にある全てのメモの ID とパス（ID 順）
+    pub fn indexed_entries(&self) -> std::result::Result<Vec<(String, PathBuf)>, MemoError> {
         let searcher = self.reader.searcher();
-        let mut merged = std::collections::HashMap::new();
-        for segment_reader in searcher.segment_readers() {
-            let facet_reader = segment_reader.facet_reader("tags.facet")?;
-            let alive_bitset = segment_reader.alive_bitset();
-            for doc_id in 0..segment_reader.max_doc() {
-                if alive_bitset.map_or(true, |bs| bs.is_alive(doc_id)) {
-                    let mut facet_ords = facet_reader.facet_ords(doc_id);
-                    while let Some(ord) = facet_ords.next() {
-                        let mut facet = tantivy::schema::Facet::root();
-                        facet_reader.facet_from_ord(ord, &mut facet)?;
-                        let facet_str = facet.to_string();
-                        if facet_str != "/" {
-                            let tag = facet_str.strip_prefix('/').unwrap_or(&facet_str).to_string();
-                            *merged.entry(tag).or_insert(0u64) += 1;
-                        }
-                    }
-                }
+        let mut entries = Vec::new();
+        for address in searcher.search(&AllQuery, &DocSetCollector)? {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            let text = |field: Field| {
+                doc.get_first(field)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string()
+            };
+            entries.push((text(self.id_field), PathBuf::from(text(self.path_field))));
+        }
+        entries.sort();
+        Ok(entries)
+    }
+
+    /// 本文で `[[id]]` とリンクしているメモの ID（ID 順）
+    pub fn backlinks(&self, id: &str) -> std::result::Result<Vec<String>, MemoError> {
+        let field = self.links_field.ok_or_else(|| {
+            MemoError::Search(
+                "the search index has no links field; run `memo index` to rebuild it".to_string(),
+            )
+        })?;
+        let query = TermQuery::new(Term::from_field_text(field, id), IndexRecordOption::Basic);
+        let searcher = self.reader.searcher();
+        let mut ids = Vec::new();
+        for address in searcher.search(&query, &DocSetCollector)? {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            if let Some(source) = doc.get_first(self.id_field).and_then(|v| v.as_str()) {
+                ids.push(source.to_string());
             }
         }
-        let mut result: Vec<_> = merged.into_iter().collect();
-        result.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
-        Ok(result)
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// 書き込みに使うメモリ量（次に書き込む時から有効）
+    pub fn with_heap_size(mut self, bytes: usize) -> Self {
+        self.heap_size = bytes;
+        self
+    }
+
+    fn writer(&mut self) -> std::result::Result<&mut IndexWriter, MemoError> {
+        let writer = match self.writer.take() {
+            Some(writer) => writer,
+            None => self.index.writer(self.heap_size)?,
+        };
+        Ok(self.writer.insert(writer))
+    }
+
+    /// `tags.facet` で絞り込んで検索する（`params.tags` に `tags` を足したもの全てが必要）
+    #[allow(dead_code)] // ライブラリとして使う側の API
+    pub fn search_with_facets(
+        &self,
+        query_str: &str,
+        tags: &[String],
+        params: &SearchParams,
+    ) -> std::result::Result<Vec<SearchResult>, MemoError> {
+        let mut params = params.clone();
+        params.tags.extend(tags.iter().cloned());
+        self.search(query_str, &params)
+    }
+
+    pub fn search(
+        &self,
+        query_str: &str,
+        params: &SearchParams,
+    ) -> std::result::Result<Vec<SearchResult>, MemoError> {
+        self.search_inner(query_str, params, None)
+    }
+
+    /// 検索して、本文中の一致箇所を `SnippetGenerator` で切り出す（最大 `max_chars` 文字）
+    ///
+    /// 本文に一致しなかったメモ（タイトル・タグだけの一致など）は空の抜粋になる
+    pub fn search_with_snippets(
+        &self,
+        query_str: &str,
+        params: &SearchParams,
+        max_chars: usize,
+    ) -> std::result::Result<Vec<SearchResult>, MemoError> {
+        self.search_inner(query_str, params, Some(max_chars))
     }
 
-    pub fn search(&self, query_str: &str) -> std::result::Result<Vec<SearchResult>, MemoError> {
+    fn search_inner(
+        &self,
+        query_str: &str,
+        params: &SearchParams,
+        snippet_chars: Option<usize>,
+    ) -> std::result::Result<Vec<SearchResult>, MemoError> {
         let searcher = self.reader.searcher();
+        let query = self.build_query(query_str, params)?;
+
+        let half_life = match params.recency_half_life {
+            Some(days) if !days.is_finite() || days <= 0.0 => {
+                return Err(MemoError::InvalidArgument(format!(
+                    "the recency half-life must be a positive number of days, got {}",
+                    days
+                )));
+            }
+            Some(days) => {
+                self.check_created_at_fast()?;
+                Some(days)
+            }
+            None => None,
+        };
+        let now = chrono::Utc::now().timestamp();
+        // 作成日時の列（`--boost-recent` と `--sort date` の時だけ読む）
+        let created_at = move |segment_reader: &SegmentReader| {
+            segment_reader
+                .fast_fields()
+                .date("created_at")
+                .ok()
+                .map(|column| {
+                    move |doc: DocId| column.first(doc).map(|dt| dt.into_timestamp_secs())
+                })
+        };
+        let recency = move |score: Score, created: Option<i64>| match (half_life, created) {
+            (Some(days), Some(created)) => score * recency_factor(now - created, days),
+            _ => score,
+        };
 
-        let query_parser =
-            QueryParser::for_index(&self.index, vec![self.content_field, self.title_field]);
-        let query = query_parser.parse_query(query_str)?;
-        let top_docs = searcher.search(&query, &TopDocs::with_limit(100))?;
+        let top_docs = TopDocs::with_limit(params.page_size()).and_offset(params.offset);
+        let doc_addresses: Vec<(f32, DocAddress)> = match params.sort {
+            SearchSort::Relevance if half_life.is_none() => searcher.search(&query, &top_docs)?,
+            SearchSort::Relevance => {
+                let collector = top_docs.tweak_score(move |segment_reader: &SegmentReader| {
+                    let created_at = created_at(segment_reader);
+                    move |doc: DocId, score: Score| {
+                        recency(score, created_at.as_ref().and_then(|column| column(doc)))
+                    }
+                });
+                searcher.search(&query, &collector)?
+            }
+            SearchSort::Date => {
+                self.check_created_at_fast()?;
+                // (作成日時, score) の順で比較
+                let collector = top_docs.tweak_score(move |segment_reader: &SegmentReader| {
+                    let created_at = created_at(segment_reader);
+                    move |doc: DocId, score: Score| {
+                        let created = created_at.as_ref().and_then(|column| column(doc));
+                        (created.unwrap_or(i64::MIN), recency(score, created))
+                    }
+                });
+                searcher
+                    .search(&query, &collector)?
+                    .into_iter()
+                    .map(|((_, score), address)| (score, address))
+                    .collect()
+            }
+            SearchSort::Priority => {
+                self.priority_field()?;
+                // (priority, score) の順で比較。priority の無いメモは最後
+                let collector = top_docs.tweak_score(move |segment_reader: &SegmentReader| {
+                    let priority = segment_reader.fast_fields().i64("priority").ok();
+                    let created_at = created_at(segment_reader);
+                    move |doc: DocId, score: Score| {
+                        let value = priority.as_ref().and_then(|column| column.first(doc));
+                        let created = created_at.as_ref().and_then(|column| column(doc));
+                        (value.unwrap_or(i64::MIN), recency(score, created))
+                    }
+                });
+                searcher
+                    .search(&query, &collector)?
+                    .into_iter()
+                    .map(|((_, score), address)| (score, address))
+                    .collect()
+            }
+        };
+
+        let snippet_generator = match snippet_chars {
+            Some(max_chars) => {
+                let mut generator =
+                    SnippetGenerator::create(&searcher, &*query, self.content_field)?;
+                generator.set_max_num_chars(max_chars);
+                Some(generator)
+            }
+            None => None,
+        };
 
         let mut results = Vec::new();
-        for (score, doc_address) in top_docs {
+        for (score, doc_address) in doc_addresses {
             let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
+            let memo = self.load_memo(&retrieved_doc)?;
+            let snippet = snippet_generator.as_ref().map(|generator| {
+                let snippet = generator.snippet_from_doc(&retrieved_doc);
+                SearchSnippet {
+                    fragment: snippet.fragment().to_string(),
+                    highlighted: snippet.highlighted().to_vec(),
+                }
+            });
+            results.push(SearchResult {
+                memo,
+                score,
+                snippet,
+            });
+        }
 
-            let path = retrieved_doc
-                .get_first(self.path_field)
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
+        Ok(results)
+    }
 
-            // get real data from the path
-            let memo = MemoFile::from_path(&path)?;
-            let memo = MemoDocument::from_memo_file(&memo);
-            results.push(SearchResult { memo, score });
-        }
+    /// `id` のメモとタイトル・タグ・本文の語が似ているメモ（自分自身は除く）
+    ///
+    /// tantivy の `MoreLikeThisQuery` で、そのメモに特徴的な語（TF-IDF の上位）を集めて検索する
+    pub fn similar(
+        &self,
+        id: &str,
+        limit: usize,
+    ) -> std::result::Result<Vec<SearchResult>, MemoError> {
+        let searcher = self.reader.searcher();
+        let id_query = TermQuery::new(
+            Term::from_field_text(self.id_field, id),
+            IndexRecordOption::Basic,
+        );
+        let Some((_, address)) = searcher
+            .search(&id_query, &TopDocs::with_limit(1))?
+            .into_iter()
+            .next()
+        else {
+            return Err(MemoError::Search(format!(
+                "memo {} is not in the search index; run `memo index` to add it",
+                id
+            )));
+        };
+        let doc: TantivyDocument = searcher.doc(address)?;
+        let fields = [self.title_field, self.tags_field, self.content_field]
+            .into_iter()
+            .map(|field| {
+                let values = doc
+                    .get_all(field)
+                    .filter_map(|value| value.as_str())
+                    .map(|text| OwnedValue::Str(text.to_string()))
+                    .collect();
+                (field, values)
+            })
+            .collect();
 
+        // メモの数が少なくても使えるよう、1 回しか出ない語も拾う。
+        // 他のメモに無い語（doc freq 1）では何も見つからないので除く
+        let more_like_this = MoreLikeThisQuery::builder()
+            .with_min_doc_frequency(2)
+            .with_min_term_frequency(1)
+            .with_max_query_terms(SIMILAR_QUERY_TERMS)
+            .with_document_fields(fields);
+        let query = BooleanQuery::new(vec![
+            (Occur::Must, Box::new(more_like_this) as Box<dyn Query>),
+            (Occur::MustNot, Box::new(id_query)),
+        ]);
+
+        let mut results = Vec::new();
+        for (score, address) in searcher.search(&query, &TopDocs::with_limit(limit))? {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            results.push(SearchResult {
+                memo: self.load_memo(&doc)?,
+                score,
+                snippet: None,
+            });
+        }
         Ok(results)
     }
+
+    /// 索引に記録したパスからメモを読み直す
+    fn load_memo(&self, doc: &TantivyDocument) -> std::result::Result<MemoDocument, MemoError> {
+        let path = doc
+            .get_first(self.path_field)
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let memo = MemoFile::from_path(path)?;
+        Ok(MemoDocument::from_memo_file(&memo))
+    }
+
+    /// 件数だけ数える（結果の中身は読まない）
+    pub fn count(
+        &self,
+        query_str: &str,
+        params: &SearchParams,
+    ) -> std::result::Result<usize, MemoError> {
+        let query = self.build_query(query_str, params)?;
+        Ok(self.reader.searcher().search(&query, &Count)?)
+    }
+
+    fn build_query(
+        &self,
+        query_str: &str,
+        params: &SearchParams,
+    ) -> std::result::Result<Box<dyn Query>, MemoError> {
+        let mut query_parser = QueryParser::for_index(
+            &self.index,
+            vec![
+                self.content_field,
+                self.title_field,
+                self.tags_field,
+                self.metadata_field,
+            ],
+        );
+        query_parser.set_field_boost(self.title_field, params.boost.title);
+        query_parser.set_field_boost(self.tags_field, params.boost.tags);
+        query_parser.set_field_boost(self.content_field, params.boost.content);
+        query_parser.set_field_boost(self.metadata_field, params.boost.metadata);
+        let extracted = query::extract_regexes(&query::expand_aliases(query_str)?)?;
+        let parsed = if extracted.patterns.is_empty() {
+            query_parser.parse_query(&extracted.query)
+        } else {
+            let regexes = extracted
+                .patterns
+                .iter()
+                .map(|pattern| self.regex_query(pattern, params))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            query_grammar::parse_query(&extracted.query)
+                .map_err(|_| QueryParserError::SyntaxError(extracted.query.clone()))
+                .and_then(|ast| build_with_regexes(&query_parser, ast, &extracted, &regexes))
+        };
+        let mut query = parsed.map_err(|e| query::parse_error(query_str, &e))?;
+
+        if let Some(min_priority) = params.min_priority {
+            let range = RangeQuery::new(
+                Bound::Included(Term::from_field_i64(self.priority_field()?, min_priority)),
+                Bound::Unbounded,
+            );
+            query = Box::new(BooleanQuery::new(vec![
+                (Occur::Must, query),
+                (Occur::Must, Box::new(range) as Box<dyn Query>),
+            ]));
+        }
+
+        if !params.tags.is_empty() {
+            let mut clauses = vec![(Occur::Must, query)];
+            for tag in &params.tags {
+                clauses.push((Occur::Must, self.facet_query(tag)?));
+            }
+            query = Box::new(BooleanQuery::new(clauses));
+        }
+
+        Ok(query)
+    }
+
+    /// タグ（`@proj` なら `/@proj/...` の子タグも含む）を持つメモ
+    fn facet_query(&self, tag: &str) -> std::result::Result<Box<dyn Query>, MemoError> {
+        let facet = Facet::from_text(&format!("/{}", tag.trim_start_matches('/')))
+            .map_err(|e| MemoError::InvalidArgument(format!("invalid tag '{}': {}", tag, e)))?;
+        Ok(Box::new(TermQuery::new(
+            Term::from_facet(self.tags_facet_field, &facet),
+            IndexRecordOption::Basic,
+        )))
+    }
+
+    /// タイトルか本文の語にマッチする正規表現クエリ（語は小文字で索引されているので大小無視）
+    fn regex_query(
+        &self,
+        pattern: &str,
+        params: &SearchParams,
+    ) -> std::result::Result<Box<dyn Query>, MemoError> {
+        let per_field = |field: Field, boost: f32| {
+            let regex =
+                RegexQuery::from_pattern(&format!("(?i){}", pattern), field).map_err(|e| {
+                    MemoError::InvalidArgument(format!("invalid regex /{}/: {}", pattern, e))
+                })?;
+            Ok::<Box<dyn Query>, MemoError>(Box::new(BoostQuery::new(Box::new(regex), boost)))
+        };
+        Ok(Box::new(BooleanQuery::union(vec![
+            per_field(self.title_field, params.boost.title)?,
+            per_field(self.content_field, params.boost.content)?,
+        ])))
+    }
+
+    /// `created_at` で並べ替え・減衰できるか（古いインデックスは fast field が無い）
+    fn check_created_at_fast(&self) -> std::result::Result<(), MemoError> {
+        if self
+            .index
+            .schema()
+            .get_field_entry(self.created_at_field)
+            .is_fast()
+        {
+            Ok(())
+        } else {
+            Err(MemoError::Search(
+                "the search index cannot sort by date; run `memo index` to rebuild it".to_string(),
+            ))
+        }
+    }
+
+    fn priority_field(&self) -> std::result::Result<Field, MemoError> {
+        self.priority_field.ok_or_else(|| {
+            MemoError::Search(
+                "the search index has no priority field; run `memo index` to rebuild it"
+                    .to_string(),
+            )
+        })
+    }
+}
+
+/// 経過秒数に応じたスコアの倍率（作成直後は 2、`half_life_days` ごとに上乗せ分が半分）
+fn recency_factor(age_secs: i64, half_life_days: f64) -> f32 {
+    let age_days = age_secs.max(0) as f64 / 86_400.0;
+    (1.0 + 0.5_f64.powf(age_days / half_life_days)) as f32
+}
+
+/// `__memo_regex_N__` の語を正規表現クエリに差し替えながら AST をクエリにする
+fn build_with_regexes(
+    query_parser: &QueryParser,
+    ast: UserInputAst,
+    extracted: &query::Extracted,
+    regexes: &[Box<dyn Query>],
+) -> std::result::Result<Box<dyn Query>, QueryParserError> {
+    match ast {
+        UserInputAst::Clause(clauses) => {
+            let mut subqueries = Vec::new();
+            for (occur, ast) in clauses {
+                subqueries.push((
+                    occur.unwrap_or(Occur::Should),
+                    build_with_regexes(query_parser, ast, extracted, regexes)?,
+                ));
+            }
+            // `-/re/` だけの場合も全件から除外する
+            if !subqueries.is_empty()
+                && subqueries.iter().all(|(occur, _)| *occur == Occur::MustNot)
+            {
+                subqueries.push((Occur::Must, Box::new(AllQuery)));
+            }
+            Ok(Box::new(BooleanQuery::new(subqueries)))
+        }
+        UserInputAst::Boost(ast, boost) => Ok(Box::new(BoostQuery::new(
+            build_with_regexes(query_parser, *ast, extracted, regexes)?,
+            boost as Score,
+        ))),
+        UserInputAst::Leaf(leaf) => match &*leaf {
+            UserInputLeaf::Literal(literal)
+                if literal.field_name.is_none()
+                    && let Some(regex) = extracted
+                        .placeholder(&literal.phrase)
+                        .and_then(|index| regexes.get(index)) =>
+            {
+                Ok(regex.box_clone())
+            }
+            _ => query_parser.build_query_from_user_input_ast(UserInputAst::Leaf(leaf)),
+        },
+    }
 }
 
 fn convert_map(value: serde_json::Value) -> BTreeMap<String, OwnedValue> {
     use serde_json::Value;
 
     let mut tmp = BTreeMap::new();
-    match value {
-        Value::Object(t) => {
-            for (k, v) in t.into_iter() {
-                tmp.insert(k.clone(), convert_value(v));
-            }
+    if let Value::Object(t) = value {
+        for (k, v) in t.into_iter() {
+            tmp.insert(k.clone(), convert_value(v));
         }
-        _ => {}
     }
     tmp
 }
@@ -279,6 +832,7 @@ fn convert_value(value: serde_json::Value) -> OwnedValue {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memo_id::MemoId;
     use serde_json::json;
 
     macro_rules! btree_map {
@@ -310,7 +864,7 @@ mod tests {
         assert_eq!(convert_value(json!(42u64)), OwnedValue::U64(42));
         assert_eq!(convert_value(json!(-42i64)), OwnedValue::I64(-42));
         assert!(
-            matches!(convert_value(json!(3.14f64)), OwnedValue::F64(f) if (f - 3.14).abs() < f64::EPSILON)
+            matches!(convert_value(json!(2.5f64)), OwnedValue::F64(f) if (f - 2.5).abs() < f64::EPSILON)
         );
 
         // large numbers
@@ -459,4 +1013,495 @@ mod tests {
         let converted = convert_map(array_value);
         assert!(converted.is_empty());
     }
+
+    fn index_with_priorities(dir: &Path) -> SearchIndex {
+        let mut index = SearchIndex::create(dir, dir).unwrap();
+        for (id, front_matter) in [
+            ("20250130100000", "priority: 1"),
+            ("20250130110000", "priority: 5\ntags: [\"@todo\"]"),
+            ("20250130120000", "title: none"),
+            (
+                "20241231130000",
+                "priority: 3\ntags: [\"@todo\", \"@team\"]",
+            ),
+        ] {
+            let memo_id = MemoId::from_str(id).unwrap();
+            let path = memo_id.to_file_path(dir);
+            let memo =
+                MemoFile::create(&path, format!("---\n{}\n---\nbody", front_matter)).unwrap();
+            index
+                .add_memo(&MemoDocument::from_memo_file(&memo))
+                .unwrap();
+        }
+        index.commit().unwrap();
+        index
+    }
+
+    fn result_ids(results: &[SearchResult]) -> Vec<String> {
+        results.iter().map(|r| r.memo.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_search_priority() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+
+        let filtered = index
+            .search(
+                "*",
+                &SearchParams {
+                    min_priority: Some(3),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let mut ids = result_ids(&filtered);
+        ids.sort();
+        assert_eq!(ids, vec!["20241231130000", "20250130110000"]);
+
+        let sorted = index
+            .search(
+                "*",
+                &SearchParams {
+                    sort: SearchSort::Priority,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            result_ids(&sorted),
+            vec![
+                "20250130110000",
+                "20241231130000",
+                "20250130100000",
+                "20250130120000"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_inferred_title() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let mut index = SearchIndex::create(dir, dir).unwrap();
+        for (id, content) in [
+            ("20250130100000", "# Quarterly planning\nbody"),
+            ("20250130110000", "body about quarterly numbers"),
+        ] {
+            let path = MemoId::from_str(id).unwrap().to_file_path(dir);
+            let memo = MemoFile::create(&path, content.to_string()).unwrap();
+            index
+                .add_memo(&MemoDocument::from_memo_file(&memo))
+                .unwrap();
+        }
+        index.commit().unwrap();
+
+        let results = index
+            .search("title:quarterly", &SearchParams::default())
+            .unwrap();
+        assert_eq!(result_ids(&results), vec!["20250130100000"]);
+    }
+
+    #[test]
+    fn test_similar() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let mut index = SearchIndex::create(dir, dir).unwrap();
+        for (id, content) in [
+            (
+                "20250130100000",
+                "# Rust async runtime\ntokio executor tasks futures",
+            ),
+            ("20250130110000", "tokio runtime spawns async tasks"),
+            ("20250130120000", "grocery list: milk eggs bread"),
+            ("20250130130000", "bread, milk again"),
+        ] {
+            let path = MemoId::from_str(id).unwrap().to_file_path(dir);
+            let memo = MemoFile::create(&path, content.to_string()).unwrap();
+            index
+                .add_memo(&MemoDocument::from_memo_file(&memo))
+                .unwrap();
+        }
+        index.commit().unwrap();
+
+        let results = index.similar("20250130100000", 10).unwrap();
+        assert_eq!(result_ids(&results), vec!["20250130110000"]);
+        assert!(results[0].score > 0.0);
+        assert_eq!(
+            result_ids(&index.similar("20250130120000", 10).unwrap()),
+            vec!["20250130130000"]
+        );
+        assert!(index.similar("20250101000000", 10).is_err());
+    }
+
+    #[test]
+    fn test_backlinks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let mut index = SearchIndex::create(dir, dir).unwrap();
+        for (id, content) in [
+            ("20250130100000", "target"),
+            ("20250130110000", "see [[20250130100000|the target]]"),
+            (
+                "20250130120000",
+                "[[2025-01/30/100000]] and [[20250130110000]]",
+            ),
+        ] {
+            let path = MemoId::from_str(id).unwrap().to_file_path(dir);
+            let memo = MemoFile::create(&path, content.to_string()).unwrap();
+            index
+                .add_memo(&MemoDocument::from_memo_file(&memo))
+                .unwrap();
+        }
+        index.commit().unwrap();
+
+        assert_eq!(
+            index.backlinks("20250130100000").unwrap(),
+            vec!["20250130110000", "20250130120000"]
+        );
+        assert_eq!(
+            index.backlinks("20250130110000").unwrap(),
+            vec!["20250130120000"]
+        );
+        assert!(index.backlinks("20250130120000").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_sort_date() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+        let params = SearchParams {
+            sort: SearchSort::Date,
+            ..Default::default()
+        };
+        assert_eq!(
+            result_ids(&index.search("*", &params).unwrap()),
+            vec![
+                "20250130120000",
+                "20250130110000",
+                "20250130100000",
+                "20241231130000"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_limit_offset() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+        let page = |limit, offset| {
+            let params = SearchParams {
+                sort: SearchSort::Date,
+                limit: Some(limit),
+                offset,
+                ..Default::default()
+            };
+            result_ids(&index.search("*", &params).unwrap())
+        };
+        assert_eq!(page(2, 0), vec!["20250130120000", "20250130110000"]);
+        assert_eq!(page(2, 2), vec!["20250130100000", "20241231130000"]);
+        assert!(page(2, 4).is_empty());
+    }
+
+    #[test]
+    fn test_search_recency() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+        let search = |half_life: Option<f64>| {
+            index.search(
+                "*",
+                &SearchParams {
+                    recency_half_life: half_life,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let results = search(Some(365.0)).unwrap();
+        assert_eq!(results[0].memo.id.as_str(), "20250130120000");
+        assert_eq!(results[3].memo.id.as_str(), "20241231130000");
+        assert!(results[0].score > results[3].score);
+        assert!(search(Some(0.0)).is_err());
+
+        assert_eq!(recency_factor(0, 30.0), 2.0);
+        assert_eq!(recency_factor(30 * 86_400, 30.0), 1.5);
+        // 未来の日付は作成直後と同じ扱い
+        assert_eq!(recency_factor(-86_400, 30.0), 2.0);
+    }
+
+    #[test]
+    fn test_search_date_ranges() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+        let search = |query: &str| {
+            let mut ids = result_ids(&index.search(query, &SearchParams::default()).unwrap());
+            ids.sort();
+            ids
+        };
+        assert_eq!(
+            search("created_at:[2025-01-01 TO 2025-01-30] AND tag:@todo"),
+            vec!["20250130110000"]
+        );
+        assert_eq!(search("created_at:<2025-01"), vec!["20241231130000"]);
+        assert_eq!(search("created_at:{2024-12 TO *]").len(), 3);
+        assert!(search("created_at:>2025-01-30").is_empty());
+        assert!(
+            index
+                .search("created_at:[2025-02 TO 2025-01]", &SearchParams::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_search_regex() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+        let search = |query: &str| {
+            let mut ids = result_ids(&index.search(query, &SearchParams::default()).unwrap());
+            ids.sort();
+            ids
+        };
+
+        // 本文・タイトルに zzz で始まる語は無い
+        assert_eq!(search("-/zzz.*/").len(), 4);
+        assert_eq!(search("+tag:@team -/zzz.*/"), vec!["20241231130000"]);
+        assert!(search("+/zzz.*/ tag:@team").is_empty());
+        assert_eq!(
+            search("(/zzz.*/ OR tag:@todo)^2"),
+            vec!["20241231130000", "20250130110000"]
+        );
+        // 置き換え用の語をそのまま書いても普通の語として扱う
+        assert_eq!(search("/bod./ __memo_regex_5__").len(), 4);
+        assert_eq!(search("/bod./ -__memo_regex_0__").len(), 4);
+        assert!(matches!(
+            index.search("/[a-/", &SearchParams::default()),
+            Err(MemoError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_search_wildcard() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+        let search = |query: &str| {
+            let mut ids = result_ids(&index.search(query, &SearchParams::default()).unwrap());
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(search("-zzz*").len(), 4);
+        assert_eq!(
+            search("+tag:@todo -zz?"),
+            vec!["20241231130000", "20250130110000"]
+        );
+        assert!(search("+zzz* +tag:@todo").is_empty());
+        // `*` だけなら全件
+        assert_eq!(search("*").len(), 4);
+    }
+
+    #[test]
+    fn test_count() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+        let params = SearchParams {
+            min_priority: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(index.count("*", &SearchParams::default()).unwrap(), 4);
+        assert_eq!(index.count("*", &params).unwrap(), 2);
+        assert_eq!(index.count("tag:@team", &params).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_search_with_facets() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+
+        let team = index
+            .search_with_facets("*", &["@team".to_string()], &SearchParams::default())
+            .unwrap();
+        assert_eq!(result_ids(&team), vec!["20241231130000"]);
+
+        let params = SearchParams {
+            min_priority: Some(3),
+            ..Default::default()
+        };
+        let narrowed = index
+            .search_with_facets("*", &["@team".to_string()], &params)
+            .unwrap();
+        assert_eq!(narrowed.len(), 1);
+        assert!(
+            index
+                .search_with_facets("*", &["@none".to_string()], &params)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_search_with_snippets() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+        let params = SearchParams::default();
+
+        assert!(
+            index
+                .search("*", &params)
+                .unwrap()
+                .iter()
+                .all(|r| r.snippet.is_none())
+        );
+        // 本文の語に一致しないクエリでは抜粋は空（表示側は先頭のプレビューに戻す）
+        let results = index.search_with_snippets("*", &params, 80).unwrap();
+        assert!(!results.is_empty());
+        assert!(
+            results
+                .iter()
+                .all(|r| r.snippet.as_ref().is_some_and(|s| s.is_empty()))
+        );
+    }
+
+    #[test]
+    fn test_snippet_render() {
+        let snippet = SearchSnippet {
+            fragment: "週次の\nデプロイ手順とデプロイ後の確認".to_string(),
+            highlighted: vec![10..22, 31..43],
+        };
+        assert_eq!(
+            snippet.render(|text| format!("[{}]", text)),
+            "週次の [デプロイ]手順と[デプロイ]後の確認"
+        );
+        assert!(!snippet.is_empty());
+        assert!(SearchSnippet::default().is_empty());
+    }
+
+    #[test]
+    fn test_field_analyzers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let analyzers = FieldAnalyzers {
+            metadata: Analyzer::Japanese,
+            ..Default::default()
+        };
+        let index = SearchIndex::create_with(
+            dir,
+            dir,
+            SchemaExtension::new(),
+            analyzers,
+            &TokenizerConfig::default(),
+        )
+        .unwrap();
+        let schema = index.index.schema();
+        let tokenizer = |field: Field| match schema.get_field_entry(field).field_type() {
+            FieldType::Str(options) => options.get_indexing_options().unwrap().tokenizer(),
+            FieldType::JsonObject(options) => {
+                options.get_text_indexing_options().unwrap().tokenizer()
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(tokenizer(index.content_field), "lang_ja_en");
+        assert_eq!(tokenizer(index.title_field), "lang_ja_en");
+        assert_eq!(tokenizer(index.tags_field), "lang_ja");
+        assert_eq!(tokenizer(index.metadata_field), "lang_ja");
+
+        // 作成時の設定はスキーマに残るので、開き直しても同じトークナイザーで検索する
+        let reopened = SearchIndex::open(dir, dir).unwrap();
+        for name in ["lang_ja", "lang_ja_en", "lang_bigram"] {
+            assert!(reopened.index.tokenizers().get(name).is_some());
+        }
+    }
+
+    #[test]
+    fn test_schema_extension() {
+        use crate::search::extension::{ExtraFieldKind, ExtraValue, SchemaExtension};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let extension = || {
+            SchemaExtension::new()
+                .with_field("project", ExtraFieldKind::Keyword)
+                .with_field("words", ExtraFieldKind::I64)
+                .with_enricher(|memo| {
+                    let project = if memo.id.as_str().starts_with("2024") {
+                        "archive"
+                    } else {
+                        "current"
+                    };
+                    vec![
+                        ("project".to_string(), ExtraValue::Text(project.to_string())),
+                        (
+                            "words".to_string(),
+                            ExtraValue::I64(memo.content.len() as i64),
+                        ),
+                    ]
+                })
+        };
+
+        let mut index = SearchIndex::create_with(
+            dir,
+            dir,
+            extension(),
+            FieldAnalyzers::default(),
+            &TokenizerConfig::default(),
+        )
+        .unwrap();
+        for id in ["20241231130000", "20250130100000", "20250130110000"] {
+            let path = MemoId::from_str(id).unwrap().to_file_path(dir);
+            let memo = MemoFile::create(&path, "body".to_string()).unwrap();
+            index
+                .add_memo(&MemoDocument::from_memo_file(&memo))
+                .unwrap();
+        }
+        index.commit().unwrap();
+        let params = SearchParams::default();
+        assert_eq!(index.count("project:current", &params).unwrap(), 2);
+        assert_eq!(index.count("words:>=4", &params).unwrap(), 3);
+
+        // 記録されたフィールドと合わない拡張では開けない
+        let tokenizer = TokenizerConfig::default();
+        assert!(SearchIndex::open_with(dir, dir, extension(), &tokenizer).is_ok());
+        assert!(SearchIndex::open(dir, dir).is_ok());
+        let other = SchemaExtension::new().with_field("project", ExtraFieldKind::Text);
+        assert!(matches!(
+            SearchIndex::open_with(dir, dir, other, &tokenizer),
+            Err(MemoError::Search(_))
+        ));
+
+        let clash = SchemaExtension::new().with_field("title", ExtraFieldKind::Text);
+        let other_dir = tempfile::tempdir().unwrap();
+        assert!(
+            SearchIndex::create_with(
+                other_dir.path(),
+                other_dir.path(),
+                clash,
+                FieldAnalyzers::default(),
+                &tokenizer
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_search_query_aliases() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = index_with_priorities(temp_dir.path());
+        let search = |query: &str| {
+            let mut ids = result_ids(&index.search(query, &SearchParams::default()).unwrap());
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(
+            search("tag:@todo"),
+            vec!["20241231130000", "20250130110000"]
+        );
+        assert_eq!(search("tag:@team"), vec!["20241231130000"]);
+        assert_eq!(search("prio:>=3"), vec!["20241231130000", "20250130110000"]);
+        assert_eq!(
+            search("in:2025-01"),
+            vec!["20250130100000", "20250130110000", "20250130120000"]
+        );
+        assert_eq!(search("+in:2025 +tag:@todo"), vec!["20250130110000"]);
+    }
 }
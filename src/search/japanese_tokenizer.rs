@@ -1,37 +1,111 @@
 use crate::error::MemoError;
-use lindera::dictionary::{DictionaryKind, load_embedded_dictionary};
+use crate::search::normalize::normalize;
+use lindera::dictionary::{DictionaryKind, load_embedded_dictionary, load_user_dictionary};
 use lindera::mode::Mode;
 use lindera::segmenter::Segmenter;
 use lindera::tokenizer::Tokenizer as LinderaTokenizer;
+use serde::Deserialize;
+use std::path::PathBuf;
 use tantivy::tokenizer::{Token, TokenStream, Tokenizer as TantivyTokenizer};
 
+/// Lindera のシステム辞書
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dictionary {
+    #[default]
+    Ipadic,
+    Unidic,
+}
+
+impl Dictionary {
+    fn kind(self) -> DictionaryKind {
+        match self {
+            Dictionary::Ipadic => DictionaryKind::IPADIC,
+            Dictionary::Unidic => DictionaryKind::UniDic,
+        }
+    }
+
+    /// 品詞情報のうち基本形（UniDic は語彙素）の位置
+    fn base_form_index(self) -> usize {
+        match self {
+            Dictionary::Ipadic => 6,
+            Dictionary::Unidic => 7,
+        }
+    }
+}
+
+/// 辞書の設定（config.toml の `[search.tokenizer]`）
+///
+/// 索引と検索で同じ分割になるよう、変えたら `memo index` で作り直す
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TokenizerConfig {
+    pub dictionary: Dictionary,
+    /// 製品名・社内用語などを 1 語として扱わせるユーザー辞書（CSV）
+    pub user_dictionary: Option<PathBuf>,
+}
+
 /// Linderaを使った日本語トークナイザー
+///
+/// 辞書が読めない時は、かな・漢字を bi-gram に分けて索引する
 #[derive(Clone)]
 pub struct JapaneseTokenizer {
     tokenizer: Option<LinderaTokenizer>,
+    base_form_index: usize,
 }
 
 impl Default for JapaneseTokenizer {
     fn default() -> Self {
-        Self::new()
+        Self::new(&TokenizerConfig::default())
     }
 }
 
 impl JapaneseTokenizer {
-    pub fn new() -> Self {
-        let tokenizer = Self::create_tokenizer().ok();
-        Self { tokenizer }
+    /// 辞書が読めなければ警告を出し、空白区切りにフォールバックする
+    pub fn new(config: &TokenizerConfig) -> Self {
+        let tokenizer = match Self::create_tokenizer(config) {
+            Ok(tokenizer) => Some(tokenizer),
+            Err(e) => {
+                eprintln!("Warning: {}", e);
+                None
+            }
+        };
+        Self {
+            tokenizer,
+            base_form_index: config.dictionary.base_form_index(),
+        }
     }
 
     /// トークナイザーを作成（エラーハンドリング付き）
-    fn create_tokenizer() -> Result<LinderaTokenizer, MemoError> {
-        let dict = load_embedded_dictionary(DictionaryKind::IPADIC)
+    fn create_tokenizer(config: &TokenizerConfig) -> Result<LinderaTokenizer, MemoError> {
+        let dict = load_embedded_dictionary(config.dictionary.kind())
             .map_err(|e| MemoError::Tokenizer(format!("Failed to load dictionary: {}", e)))?;
 
-        let segmenter = Segmenter::new(Mode::Normal, dict, None);
+        let user_dict = match &config.user_dictionary {
+            Some(path) => Some(
+                load_user_dictionary(&path.to_string_lossy(), &dict.metadata).map_err(|e| {
+                    MemoError::Tokenizer(format!(
+                        "Failed to load user dictionary {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?,
+            ),
+            None => None,
+        };
+
+        let segmenter = Segmenter::new(Mode::Normal, dict, user_dict);
         Ok(LinderaTokenizer::new(segmenter))
     }
 
+    /// 辞書を使わず、常に bi-gram で分けるトークナイザー（アナライザー `bigram`）
+    pub fn bigram() -> Self {
+        Self {
+            tokenizer: None,
+            base_form_index: Dictionary::default().base_form_index(),
+        }
+    }
+
     /// トークナイザーが利用可能かチェック
     pub fn is_available(&self) -> bool {
         self.tokenizer.is_some()
@@ -42,7 +116,7 @@ impl TantivyTokenizer for JapaneseTokenizer {
     type TokenStream<'a> = JapaneseTokenStream<'a>;
 
     fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
-        JapaneseTokenStream::new(text, self.tokenizer.as_mut())
+        JapaneseTokenStream::new(text, self.tokenizer.as_mut(), self.base_form_index)
     }
 }
 
@@ -54,7 +128,11 @@ pub struct JapaneseTokenStream<'a> {
 }
 
 impl<'a> JapaneseTokenStream<'a> {
-    fn new(text: &'a str, tokenizer: Option<&mut LinderaTokenizer>) -> Self {
+    fn new(
+        text: &'a str,
+        tokenizer: Option<&mut LinderaTokenizer>,
+        base_form_index: usize,
+    ) -> Self {
         let mut tokens = Vec::new();
         let mut position = 0;
 
@@ -75,39 +153,40 @@ impl<'a> JapaneseTokenStream<'a> {
 
                         // 意味のあるトークンのみを追加
                         if should_include_token(&surface, features.as_ref()) {
-                            let mut token = Token::default();
-
                             // 基本形があれば基本形を、なければ表層形を使用
-                            token.text = if let Some(features_vec) = &features {
-                                if features_vec.len() > 6
-                                    && !features_vec[6].is_empty()
-                                    && features_vec[6] != "*"
+                            // 全角・半角、ひらがな・カタカナ、語末の長音をそろえる
+                            let text = if let Some(features_vec) = &features {
+                                if features_vec.len() > base_form_index
+                                    && !features_vec[base_form_index].is_empty()
+                                    && features_vec[base_form_index] != "*"
                                 {
-                                    features_vec[6].to_lowercase()
+                                    normalize(&features_vec[base_form_index])
                                 } else {
-                                    surface.to_lowercase()
+                                    normalize(&surface)
                                 }
                             } else {
-                                surface.to_lowercase()
+                                normalize(&surface)
                             };
 
                             // オフセット情報を設定
-                            token.offset_from = lindera_token.byte_start;
-                            token.offset_to = lindera_token.byte_end;
-                            token.position = position;
-
-                            tokens.push(token);
+                            tokens.push(Token {
+                                text,
+                                offset_from: lindera_token.byte_start,
+                                offset_to: lindera_token.byte_end,
+                                position,
+                                ..Default::default()
+                            });
                             position += 1;
                         }
                     }
                 }
                 Err(_) => {
-                    // トークン化に失敗した場合は、単純な空白分割にフォールバック
+                    // トークン化に失敗した場合は、bi-gram にフォールバック
                     Self::fallback_tokenize(text, &mut tokens, &mut position);
                 }
             }
         } else {
-            // トークナイザーが利用できない場合は、単純な空白分割にフォールバック
+            // トークナイザーが利用できない場合は、bi-gram にフォールバック
             Self::fallback_tokenize(text, &mut tokens, &mut position);
         }
 
@@ -118,30 +197,86 @@ impl<'a> JapaneseTokenStream<'a> {
         }
     }
 
-    /// フォールバック用の単純なトークン化
+    /// フォールバック用のトークン化
+    ///
+    /// 空白で区切り、かな・漢字の並びは 1 文字ずつずらした 2 文字（bi-gram）にする。
+    /// 位置が連続するので、検索語も bi-gram のフレーズとして部分一致で引ける
     fn fallback_tokenize(text: &str, tokens: &mut Vec<Token>, position: &mut usize) {
-        let mut byte_offset = 0;
-
-        for word in text.split_whitespace() {
-            // 空白をスキップして実際の単語の開始位置を見つける
-            while byte_offset < text.len() && text.as_bytes()[byte_offset].is_ascii_whitespace() {
-                byte_offset += 1;
+        let mut push = |from: usize, to: usize| {
+            let word = &text[from..to];
+            if should_include_simple_token(word) {
+                tokens.push(Token {
+                    text: normalize(word),
+                    offset_from: from,
+                    offset_to: to,
+                    position: *position,
+                    ..Default::default()
+                });
+                *position += 1;
             }
+        };
+
+        for (start, run, class) in char_runs(text) {
+            match class {
+                CharClass::Separator => {}
+                CharClass::Other => push(start, start + run.len()),
+                CharClass::Cjk => {
+                    let chars: Vec<(usize, char)> = run.char_indices().collect();
+                    if chars.len() == 1 {
+                        push(start, start + run.len());
+                    }
+                    for pair in chars.windows(2) {
+                        let (from, _) = pair[0];
+                        let (last, c) = pair[1];
+                        push(start + from, start + last + c.len_utf8());
+                    }
+                }
+            }
+        }
+    }
+}
 
-            if !word.is_empty() && should_include_simple_token(word) {
-                let mut token = Token::default();
-                token.text = word.to_lowercase();
-                token.offset_from = byte_offset;
-                token.offset_to = byte_offset + word.len();
-                token.position = *position;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    /// 空白と全角の句読点・括弧
+    Separator,
+    /// ひらがな・カタカナ・漢字
+    Cjk,
+    Other,
+}
 
-                tokens.push(token);
-                *position += 1;
-            }
+fn char_class(c: char) -> CharClass {
+    match c {
+        c if c.is_whitespace() => CharClass::Separator,
+        '\u{3000}'..='\u{303F}' | '！' | '？' | '（' | '）' | '・' => CharClass::Separator,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF66}'..='\u{FF9F}' => CharClass::Cjk,
+        _ => CharClass::Other,
+    }
+}
 
-            byte_offset += word.len();
+/// 同じ文字種が続く範囲（開始位置・文字列・文字種）
+fn char_runs(text: &str) -> Vec<(usize, &str, CharClass)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut current = None;
+    for (i, c) in text.char_indices() {
+        let class = char_class(c);
+        if let Some(prev) = current
+            && prev != class
+        {
+            runs.push((start, &text[start..i], prev));
+            start = i;
         }
+        current = Some(class);
     }
+    if let Some(class) = current {
+        runs.push((start, &text[start..], class));
+    }
+    runs
 }
 
 impl<'a> TokenStream for JapaneseTokenStream<'a> {
@@ -170,20 +305,23 @@ fn should_include_token(surface: &str, features: Option<&Vec<String>>) -> bool {
     }
 
     // 品詞情報を確認（features[0]が品詞）
-    if let Some(features_vec) = features {
-        if !features_vec.is_empty() {
-            let pos = &features_vec[0];
-
-            // 記号、助詞、助動詞は除外
-            if pos.starts_with("記号") || pos.starts_with("助詞") || pos.starts_with("助動詞")
-            {
-                return false;
-            }
+    if let Some(features_vec) = features
+        && !features_vec.is_empty()
+    {
+        let pos = &features_vec[0];
+
+        // 記号（UniDic では補助記号）、助詞、助動詞は除外
+        if pos.starts_with("記号")
+            || pos.starts_with("補助記号")
+            || pos.starts_with("助詞")
+            || pos.starts_with("助動詞")
+        {
+            return false;
+        }
 
-            // 一文字の名詞（「の」「が」など）は除外
-            if pos.starts_with("名詞") && surface.chars().count() == 1 {
-                return false;
-            }
+        // 一文字の名詞（「の」「が」など）は除外
+        if pos.starts_with("名詞") && surface.chars().count() == 1 {
+            return false;
         }
     }
 
@@ -200,9 +338,9 @@ fn should_include_token(surface: &str, features: Option<&Vec<String>>) -> bool {
 
     // 日本語文字を含む場合は含める
     if surface.chars().any(|c| {
-        (c >= 'あ' && c <= 'ん') ||  // ひらがな
-        (c >= 'ア' && c <= 'ン') ||  // カタカナ
-        (c >= '一' && c <= '龯') // 漢字
+        ('あ'..='ん').contains(&c) ||  // ひらがな
+        ('ア'..='ン').contains(&c) ||  // カタカナ
+        ('一'..='龯').contains(&c) // 漢字
     }) {
         return true;
     }
@@ -226,3 +364,58 @@ fn should_include_simple_token(word: &str) -> bool {
     // 1文字以上なら含める（フォールバック時は緩い条件）
     !word.is_empty()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(tokenizer: &mut JapaneseTokenizer, text: &str) -> Vec<String> {
+        let mut stream = tokenizer.token_stream(text);
+        let mut tokens = Vec::new();
+        stream.process(&mut |token| tokens.push(token.text.clone()));
+        tokens
+    }
+
+    #[test]
+    fn test_user_dictionary() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("userdict.csv");
+        std::fs::write(&path, "メモ検索基盤,カスタム名詞,メモケンサクキバン\n").unwrap();
+
+        let mut plain = JapaneseTokenizer::default();
+        assert!(!tokens(&mut plain, "メモ検索基盤を直す").contains(&normalize("メモ検索基盤")));
+
+        let mut tokenizer = JapaneseTokenizer::new(&TokenizerConfig {
+            user_dictionary: Some(path),
+            ..Default::default()
+        });
+        assert!(tokenizer.is_available());
+        assert!(tokens(&mut tokenizer, "メモ検索基盤を直す").contains(&normalize("メモ検索基盤")));
+    }
+
+    #[test]
+    fn test_bigram() {
+        let mut tokenizer = JapaneseTokenizer::bigram();
+        assert_eq!(
+            tokens(&mut tokenizer, "東京都の memo、都"),
+            vec!["東京", "京都", "都の", "memo", "都"]
+                .into_iter()
+                .map(normalize)
+                .collect::<Vec<_>>()
+        );
+
+        let mut stream = tokenizer.token_stream("v2会議");
+        let mut offsets = Vec::new();
+        stream.process(&mut |token| offsets.push((token.offset_from, token.offset_to)));
+        assert_eq!(offsets, vec![(0, 2), (2, 8)]);
+    }
+
+    #[test]
+    fn test_missing_user_dictionary_falls_back() {
+        let tokenizer = JapaneseTokenizer::new(&TokenizerConfig {
+            user_dictionary: Some(PathBuf::from("/nonexistent/userdict.csv")),
+            ..Default::default()
+        });
+        assert!(!tokenizer.is_available());
+    }
+}
@@ -1,30 +1,169 @@
-use crate::error::MemoError;
+use crate::error::{IoResultExt, MemoError};
 use fs2::FileExt;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long `acquire` / `acquire_shared` wait for another process before giving up
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
 
 /// IndexLock manages a file-based lock for the search index directory.
+///
+/// Writers take the lock exclusively and record their process ID in the lock file while
+/// they hold it; readers share it. The lock file itself is kept, so a non-empty lock file
+/// that nobody holds means a writer died before finishing.
 pub struct IndexLock {
-    _file: File,
-    lock_path: PathBuf,
+    file: File,
+    exclusive: bool,
 }
 
 impl IndexLock {
+    /// exclusive lock for writing, waiting up to `LOCK_TIMEOUT`
     pub fn acquire<P: AsRef<Path>>(index_dir: P) -> Result<Self, MemoError> {
-        let lock_path = index_dir.as_ref().join("lock");
-        let file = File::create(&lock_path).map_err(|e| MemoError::Io(e))?;
-        file.lock_exclusive().map_err(|e| MemoError::Io(e))?;
+        Self::acquire_with(index_dir.as_ref(), true, LOCK_TIMEOUT)
+    }
+
+    /// shared lock for reading, waiting up to `LOCK_TIMEOUT` for a writer to finish
+    pub fn acquire_shared<P: AsRef<Path>>(index_dir: P) -> Result<Self, MemoError> {
+        Self::acquire_with(index_dir.as_ref(), false, LOCK_TIMEOUT)
+    }
+
+    pub fn acquire_with(
+        index_dir: &Path,
+        exclusive: bool,
+        timeout: Duration,
+    ) -> Result<Self, MemoError> {
+        let lock_path = index_dir.join("lock");
+        // 他のプロセスが書いた PID を消さないよう、開く時には切り詰めない
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_path(&lock_path)?;
+
+        let started = Instant::now();
+        loop {
+            // std の `File::try_lock_shared` と名前が重なるので fs2 のものを明示する
+            let locked = if exclusive {
+                FileExt::try_lock_exclusive(&file)
+            } else {
+                FileExt::try_lock_shared(&file)
+            };
+            match locked {
+                Ok(()) => break,
+                Err(_) if started.elapsed() < timeout => std::thread::sleep(RETRY_INTERVAL),
+                Err(_) => {
+                    return Err(MemoError::LockTimeout(format!(
+                        "search index is locked by another process (gave up after {}s): {}",
+                        timeout.as_secs(),
+                        lock_path.display()
+                    )));
+                }
+            }
+        }
+
+        if exclusive {
+            file.set_len(0).with_path(&lock_path)?;
+            write!(&file, "{}", std::process::id()).with_path(&lock_path)?;
+        }
+        Ok(Self { file, exclusive })
+    }
+
+    /// lock files under `index_base_dir` left by a writer that did not finish
+    ///
+    /// These are left behind when a process is killed before the lock is dropped.
+    pub fn stale_locks<P: AsRef<Path>>(index_base_dir: P) -> Result<Vec<PathBuf>, MemoError> {
+        let index_base_dir = index_base_dir.as_ref();
+        if !index_base_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut dirs = vec![index_base_dir.to_path_buf()];
+        for entry in std::fs::read_dir(index_base_dir).with_path(index_base_dir)? {
+            let path = entry.with_path(index_base_dir)?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            }
+        }
 
-        Ok(Self {
-            _file: file,
-            lock_path,
-        })
+        let mut stale = Vec::new();
+        for index_dir in dirs {
+            let lock_path = index_dir.join("lock");
+            let written = std::fs::metadata(&lock_path).is_ok_and(|m| m.is_file() && m.len() > 0);
+            if written && !Self::is_held(&index_dir)? {
+                stale.push(lock_path);
+            }
+        }
+        stale.sort();
+        Ok(stale)
+    }
+
+    /// whether some process is holding the lock of `index_dir` right now
+    pub fn is_held<P: AsRef<Path>>(index_dir: P) -> Result<bool, MemoError> {
+        let lock_path = index_dir.as_ref().join("lock");
+        if !lock_path.is_file() {
+            return Ok(false);
+        }
+        let file = File::open(&lock_path).with_path(&lock_path)?;
+        // 閉じればロックも外れる
+        Ok(FileExt::try_lock_exclusive(&file).is_err())
     }
 }
 
 impl Drop for IndexLock {
     fn drop(&mut self) {
-        // delete the lock file when the lock is dropped
-        let _ = std::fs::remove_file(&self.lock_path);
+        // 書き終えたら PID を消す（ロックはファイルを閉じた時に外れる）
+        if self.exclusive {
+            let _ = self.file.set_len(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_stale_locks() {
+        let temp_dir = TempDir::new().unwrap();
+        let held_dir = temp_dir.path().join("20250130_000000");
+        let stale_dir = temp_dir.path().join("20250131_000000");
+        let finished_dir = temp_dir.path().join("20250201_000000");
+        std::fs::create_dir_all(&held_dir).unwrap();
+        std::fs::create_dir_all(&stale_dir).unwrap();
+        std::fs::create_dir_all(&finished_dir).unwrap();
+        std::fs::write(stale_dir.join("lock"), "12345").unwrap();
+        drop(IndexLock::acquire(&finished_dir).unwrap());
+
+        let _lock = IndexLock::acquire(&held_dir).unwrap();
+        assert_eq!(
+            IndexLock::stale_locks(temp_dir.path()).unwrap(),
+            vec![stale_dir.join("lock")]
+        );
+    }
+
+    #[test]
+    fn test_shared_and_exclusive() {
+        let temp_dir = TempDir::new().unwrap();
+        let timeout = Duration::from_millis(100);
+
+        let reader = IndexLock::acquire_shared(temp_dir.path()).unwrap();
+        // 読み手どうしは同時に持てる
+        let other_reader = IndexLock::acquire_with(temp_dir.path(), false, timeout).unwrap();
+        assert!(IndexLock::acquire_with(temp_dir.path(), true, timeout).is_err());
+        drop(reader);
+        drop(other_reader);
+
+        let writer = IndexLock::acquire(temp_dir.path()).unwrap();
+        assert!(IndexLock::acquire_with(temp_dir.path(), false, timeout).is_err());
+        assert!(IndexLock::is_held(temp_dir.path()).unwrap());
+        drop(writer);
+        assert!(!IndexLock::is_held(temp_dir.path()).unwrap());
+        assert!(IndexLock::stale_locks(temp_dir.path()).unwrap().is_empty());
     }
 }
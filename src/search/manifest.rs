@@ -0,0 +1,176 @@
+//! Modification times of the memo files at indexing time, kept next to the
+//! index so that `memo index --update` only re-reads files that changed.
+
+use crate::error::{IoResultExt, MemoError};
+use crate::memo_id::MemoId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// mtime (ns since the Unix epoch)
+    pub modified: i64,
+    // 今回スキャンしたファイルの場所（保存はしない）
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// メモID → ファイルの mtime
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexManifest {
+    memos: BTreeMap<String, ManifestEntry>,
+}
+
+/// `memo index --update` で変わった件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexUpdate {
+    pub added: usize,
+    pub modified: usize,
+    pub removed: usize,
+}
+
+impl IndexManifest {
+    /// ファイルの stat だけを取る（中身は読まない）
+    pub fn scan(paths: &[PathBuf]) -> Result<Self, MemoError> {
+        let mut memos = BTreeMap::new();
+        for path in paths {
+            let Ok(id) = MemoId::from_path(path) else {
+                continue;
+            };
+            let modified = fs::metadata(path)
+                .with_path(path)?
+                .modified()
+                .with_path(path)?
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as i64)
+                .unwrap_or_default();
+            memos.insert(
+                id.as_str(),
+                ManifestEntry {
+                    modified,
+                    path: path.clone(),
+                },
+            );
+        }
+        Ok(Self { memos })
+    }
+
+    /// 記録が無ければ `None`（マニフェスト導入前の索引）
+    pub fn load(index_dir: &Path) -> Result<Option<Self>, MemoError> {
+        let path = index_dir.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(&path).with_path(&path)?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| MemoError::Search(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn save(&self, index_dir: &Path) -> Result<(), MemoError> {
+        let json = serde_json::to_string(self).map_err(|e| MemoError::Search(e.to_string()))?;
+        let path = index_dir.join(MANIFEST_FILE);
+        fs::write(&path, json).with_path(&path)?;
+        Ok(())
+    }
+
+    /// `previous` に無い・mtime が違うメモ
+    pub fn changed_since<'a>(
+        &'a self,
+        previous: &'a IndexManifest,
+    ) -> impl Iterator<Item = (&'a String, &'a ManifestEntry)> {
+        self.memos.iter().filter(|(id, entry)| {
+            previous
+                .memos
+                .get(*id)
+                .is_none_or(|old| old.modified != entry.modified)
+        })
+    }
+
+    /// `previous` にあって今は無いメモ
+    pub fn removed_since<'a>(
+        &'a self,
+        previous: &'a IndexManifest,
+    ) -> impl Iterator<Item = &'a String> {
+        previous
+            .memos
+            .keys()
+            .filter(|id| !self.memos.contains_key(*id))
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.memos.contains_key(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(modified: i64) -> ManifestEntry {
+        ManifestEntry {
+            modified,
+            path: PathBuf::new(),
+        }
+    }
+
+    fn manifest(entries: &[(&str, i64)]) -> IndexManifest {
+        IndexManifest {
+            memos: entries
+                .iter()
+                .map(|(id, modified)| (id.to_string(), entry(*modified)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_manifest_diff() {
+        let previous = manifest(&[
+            ("20250130100000", 1),
+            ("20250130110000", 2),
+            ("20250130120000", 3),
+        ]);
+        let current = manifest(&[
+            ("20250130100000", 1),
+            ("20250130110000", 5),
+            ("20250131090000", 4),
+        ]);
+
+        let changed: Vec<_> = current
+            .changed_since(&previous)
+            .map(|(id, _)| id.as_str())
+            .collect();
+        assert_eq!(changed, vec!["20250130110000", "20250131090000"]);
+        let removed: Vec<_> = current
+            .removed_since(&previous)
+            .map(String::as_str)
+            .collect();
+        assert_eq!(removed, vec!["20250130120000"]);
+    }
+
+    #[test]
+    fn test_manifest_scan_save_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let memo = temp_dir.path().join("20250130143022.md");
+        fs::write(&memo, "memo").unwrap();
+        fs::write(temp_dir.path().join("notes.md"), "not a memo").unwrap();
+
+        let scanned =
+            IndexManifest::scan(&[memo.clone(), temp_dir.path().join("notes.md")]).unwrap();
+        assert!(scanned.contains("20250130143022"));
+        assert_eq!(scanned.changed_since(&IndexManifest::default()).count(), 1);
+
+        assert_eq!(IndexManifest::load(temp_dir.path()).unwrap(), None);
+        scanned.save(temp_dir.path()).unwrap();
+        let loaded = IndexManifest::load(temp_dir.path()).unwrap().unwrap();
+        // path は保存しない
+        assert_eq!(loaded.changed_since(&scanned).count(), 0);
+        assert_eq!(loaded.removed_since(&scanned).count(), 0);
+    }
+}
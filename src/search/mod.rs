@@ -1,27 +1,206 @@
+pub mod analyzer;
+pub mod extension;
 pub mod index;
 pub mod japanese_tokenizer;
 pub mod lock;
+pub mod manifest;
+pub mod normalize;
+pub mod query;
 
-pub use index::SearchIndex;
+pub use analyzer::FieldAnalyzers;
+pub use extension::SchemaExtension;
+pub use index::{DEFAULT_WRITER_HEAP_SIZE, SearchIndex};
+pub use japanese_tokenizer::TokenizerConfig;
 pub use lock::IndexLock;
+pub use manifest::{IndexManifest, IndexUpdate};
 
-use crate::error::MemoError;
-use crate::memo::MemoDocument;
+use crate::encryption;
+use crate::error::{IoResultExt, MemoError};
+use crate::memo::{MemoDocument, MemoFile};
+use std::ops::Range;
 use std::path::PathBuf;
 
+/// `limit` を指定しない時に 1 回の検索で返す件数
+pub const MAX_RESULTS: usize = 100;
+
 /// 検索結果
 #[derive(Debug, Clone)]
 pub struct SearchResult {
     pub memo: MemoDocument,
-
-    #[allow(dead_code)]
     pub score: f32,
+    /// 本文中の一致箇所（`search_with_snippets` の時だけ）
+    pub snippet: Option<SearchSnippet>,
+}
+
+/// 本文から切り出した一致箇所
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchSnippet {
+    pub fragment: String,
+    /// `fragment` 内で一致した語のバイト範囲
+    pub highlighted: Vec<Range<usize>>,
+}
+
+impl SearchSnippet {
+    pub fn is_empty(&self) -> bool {
+        self.fragment.trim().is_empty()
+    }
+
+    /// 一致した語を `mark` で囲み、1行にまとめる
+    pub fn render(&self, mark: impl Fn(&str) -> String) -> String {
+        let mut rendered = String::with_capacity(self.fragment.len());
+        let mut start = 0;
+        for range in &self.highlighted {
+            rendered.push_str(&self.fragment[start..range.start]);
+            rendered.push_str(&mark(&self.fragment[range.clone()]));
+            start = range.end;
+        }
+        rendered.push_str(&self.fragment[start..]);
+        rendered.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// ノートブックを横断した検索結果
+#[derive(Debug, Clone)]
+pub struct NotebookResult {
+    pub notebook: String,
+    pub result: SearchResult,
+}
+
+/// 各ノートブックのインデックスで検索し、1 つの順位にまとめる
+///
+/// インデックスごとにスコアの尺度が違うので、各ノートブックの最高スコアを 1.0 に
+/// そろえてから並べ直す
+pub fn search_notebooks(
+    notebooks: &[(String, SearchManager)],
+    query: &str,
+    params: &SearchParams,
+) -> Result<Vec<NotebookResult>, MemoError> {
+    // 各ノートブックの上位 offset + limit 件を集めてから、まとめてページを切り出す
+    let per_notebook = SearchParams {
+        limit: Some(params.offset + params.page_size()),
+        offset: 0,
+        ..params.clone()
+    };
+    let mut merged = Vec::new();
+    for (notebook, manager) in notebooks {
+        let results = manager.search(query, &per_notebook)?;
+        let top = results.iter().map(|r| r.score).fold(0.0_f32, f32::max);
+        merged.extend(results.into_iter().map(|mut result| {
+            if top > 0.0 {
+                result.score /= top;
+            }
+            NotebookResult {
+                notebook: notebook.clone(),
+                result,
+            }
+        }));
+    }
+
+    let by_score =
+        |a: &NotebookResult, b: &NotebookResult| b.result.score.total_cmp(&a.result.score);
+    match params.sort {
+        SearchSort::Relevance => merged.sort_by(by_score),
+        SearchSort::Priority => merged.sort_by(|a, b| {
+            let priority = |r: &NotebookResult| r.result.memo.priority().unwrap_or(i64::MIN);
+            priority(b).cmp(&priority(a)).then_with(|| by_score(a, b))
+        }),
+        SearchSort::Date => merged.sort_by(|a, b| {
+            (b.result.memo.created_at.cmp(&a.result.memo.created_at)).then_with(|| by_score(a, b))
+        }),
+    }
+    Ok(merged
+        .into_iter()
+        .skip(params.offset)
+        .take(params.page_size())
+        .collect())
+}
+
+/// 検索結果の並び順
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SearchSort {
+    /// Best match first
+    #[default]
+    Relevance,
+    /// Highest `priority:` first, then best match
+    Priority,
+    /// Newest first, then best match
+    Date,
+}
+
+/// フィールドごとのスコア倍率（config.toml の `[search.boost]`）
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FieldBoosts {
+    pub title: f32,
+    pub tags: f32,
+    pub content: f32,
+    pub metadata: f32,
+}
+
+impl Default for FieldBoosts {
+    /// タイトル > タグ > 本文・front matter
+    fn default() -> Self {
+        Self {
+            title: 2.0,
+            tags: 1.5,
+            content: 1.0,
+            metadata: 1.0,
+        }
+    }
+}
+
+impl FieldBoosts {
+    pub fn validate(&self) -> Result<(), MemoError> {
+        let boosts = [
+            ("title", self.title),
+            ("tags", self.tags),
+            ("content", self.content),
+            ("metadata", self.metadata),
+        ];
+        for (name, boost) in boosts {
+            if !boost.is_finite() || boost <= 0.0 {
+                return Err(MemoError::ConfigError(format!(
+                    "search.boost.{} must be a positive number, got {}",
+                    name, boost
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 検索クエリ以外の条件
+#[derive(Debug, Clone, Default)]
+pub struct SearchParams {
+    /// Only memos with `priority:` >= this value
+    pub min_priority: Option<i64>,
+    /// Only memos whose front matter `tags` contain every one of these
+    pub tags: Vec<String>,
+    pub sort: SearchSort,
+    pub boost: FieldBoosts,
+    /// 新しいメモのスコアを上げる半減期（日）。作成直後は 2 倍、半減期ごとに上乗せ分が半分になる
+    pub recency_half_life: Option<f64>,
+    /// 返す件数（`None` なら `MAX_RESULTS`）
+    pub limit: Option<usize>,
+    /// 上位から読み飛ばす件数
+    pub offset: usize,
+}
+
+impl SearchParams {
+    /// 1 ページの件数
+    pub fn page_size(&self) -> usize {
+        self.limit.unwrap_or(MAX_RESULTS)
+    }
 }
 
 /// 検索機能の統合 interface
 pub struct SearchManager {
     data_dir: PathBuf,
     index_base_dir: PathBuf,
+    extension: SchemaExtension,
+    analyzers: FieldAnalyzers,
+    tokenizer: TokenizerConfig,
+    heap_size: usize,
 }
 
 impl SearchManager {
@@ -29,21 +208,51 @@ impl SearchManager {
         Self {
             data_dir,
             index_base_dir,
+            extension: SchemaExtension::new(),
+            analyzers: FieldAnalyzers::default(),
+            tokenizer: TokenizerConfig::default(),
+            heap_size: DEFAULT_WRITER_HEAP_SIZE,
         }
     }
 
+    /// 新しく作る索引のフィールドごとのアナライザー
+    pub fn with_analyzers(mut self, analyzers: FieldAnalyzers) -> Self {
+        self.analyzers = analyzers;
+        self
+    }
+
+    /// 索引と検索で使う Lindera の辞書
+    pub fn with_tokenizer(mut self, tokenizer: TokenizerConfig) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    /// 索引への書き込みに使うメモリ量（バイト）
+    pub fn with_heap_size(mut self, bytes: usize) -> Self {
+        self.heap_size = bytes;
+        self
+    }
+
+    /// 追加フィールド付きの索引を作る・開く
+    #[allow(dead_code)] // ライブラリとして使う側の API
+    pub fn with_extension(mut self, extension: SchemaExtension) -> Self {
+        self.extension = extension;
+        self
+    }
+
     fn get_version_file(&self) -> PathBuf {
         self.index_base_dir.join("version")
     }
 
-    fn get_version(&self) -> Result<Option<String>, MemoError> {
+    /// 現在のインデックスのバージョン（`.index/version` の内容）
+    pub fn get_version(&self) -> Result<Option<String>, MemoError> {
         let version_file = self.get_version_file();
         if !version_file.exists() {
             return Ok(None);
         }
 
         let version = std::fs::read_to_string(&version_file)
-            .map_err(|e| MemoError::Io(e))
+            .with_path(&version_file)
             .map(|s| s.trim().to_string())?;
         Ok(Some(version))
     }
@@ -57,32 +266,158 @@ impl SearchManager {
         if !index_dir.exists() {
             return Ok(None);
         }
-        Ok(Some(SearchIndex::open(&self.data_dir, &index_dir)?))
+        Ok(Some(
+            SearchIndex::open_with(
+                &self.data_dir,
+                &index_dir,
+                self.extension.clone(),
+                &self.tokenizer,
+            )?
+            .with_heap_size(self.heap_size),
+        ))
     }
 
-    pub fn create_new_index(&self) -> Result<SearchIndex, MemoError> {
-        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
-        let index_dir = self.index_base_dir.join(&timestamp);
+    /// `.index/version` に書かれているのに、そのディレクトリが無いバージョン
+    pub fn missing_version(&self) -> Result<Option<String>, MemoError> {
+        Ok(self
+            .get_version()?
+            .filter(|version| !self.index_base_dir.join(version).exists()))
+    }
+
+    /// 新しい索引を一時ディレクトリに作り、`build` とコミットが成功した時だけ切り替える
+    ///
+    /// 失敗したら作りかけの索引を消し、`.index/version` は書き換えない（今の索引のまま）。
+    /// 切り替えた索引のディレクトリを返す
+    pub fn create_new_index<F>(&self, build: F) -> Result<PathBuf, MemoError>
+    where
+        F: FnOnce(&mut SearchIndex) -> Result<(), MemoError>,
+    {
+        let _write = self.lock_for_write()?;
+        self.build_new_index(build)
+    }
 
-        std::fs::create_dir_all(&index_dir).map_err(|e| MemoError::Io(e))?;
-        let index = SearchIndex::create(self.data_dir.clone(), index_dir)?;
+    /// `create_new_index` の本体（書き込みロックは呼び出し側が持つ）
+    fn build_new_index<F>(&self, build: F) -> Result<PathBuf, MemoError>
+    where
+        F: FnOnce(&mut SearchIndex) -> Result<(), MemoError>,
+    {
+        let version = self.new_version_name();
+        let index_dir = self.index_base_dir.join(&version);
+        let temp_dir = self.index_base_dir.join(format!("{}.tmp", version));
+
+        std::fs::create_dir_all(&temp_dir).with_path(&temp_dir)?;
+        // 索引は閉じてからディレクトリを移す
+        let built = SearchIndex::create_with(
+            self.data_dir.clone(),
+            temp_dir.clone(),
+            self.extension.clone(),
+            self.analyzers,
+            &self.tokenizer,
+        )
+        .and_then(|index| {
+            let mut index = index.with_heap_size(self.heap_size);
+            build(&mut index)?;
+            index.commit()
+        });
+        if let Err(e) = built {
+            let _ = std::fs::remove_dir_all(&temp_dir);
+            return Err(e);
+        }
+
+        std::fs::rename(&temp_dir, &index_dir).with_path(&index_dir)?;
+        self.set_version(&version)?;
+        Ok(index_dir)
+    }
 
-        // update version file
+    /// 索引を書き換える間、他の書き手（作り直しを含む）を待たせるロック（`.index/lock`）
+    ///
+    /// 読み手はこれを待たず、各バージョンのディレクトリの共有ロックだけを取る
+    pub fn lock_for_write(&self) -> Result<IndexLock, MemoError> {
+        std::fs::create_dir_all(&self.index_base_dir).with_path(&self.index_base_dir)?;
+        IndexLock::acquire(&self.index_base_dir)
+    }
+
+    /// 1 つ前の索引に戻す（戻したバージョンを返す。前の索引が残っていなければ `None`）
+    pub fn rollback(&self) -> Result<Option<String>, MemoError> {
+        let _write = self.lock_for_write()?;
+        let current = self.get_version()?;
+        let previous = self
+            .list_versions()?
+            .into_iter()
+            .find(|version| current.as_ref().is_none_or(|current| version < current));
+        if let Some(previous) = &previous {
+            self.set_version(previous)?;
+        }
+        Ok(previous)
+    }
+
+    /// `.index/version` を書き換える（途中で止まっても壊れた内容が残らないよう rename で置き換える）
+    fn set_version(&self, version: &str) -> Result<(), MemoError> {
         let version_file = self.get_version_file();
-        std::fs::write(&version_file, &timestamp).map_err(|e| MemoError::Io(e))?;
+        let temp_file = self.index_base_dir.join("version.tmp");
+        std::fs::write(&temp_file, version).with_path(&temp_file)?;
+        std::fs::rename(&temp_file, &version_file).with_path(&version_file)
+    }
 
-        Ok(index)
+    /// 作り直すたびに新しくなるディレクトリ名（同じ秒に作り直したら `-1` などを付ける）
+    fn new_version_name(&self) -> String {
+        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
+        let mut version = timestamp.clone();
+        let mut n = 0;
+        while self.index_base_dir.join(&version).exists()
+            || self
+                .index_base_dir
+                .join(format!("{}.tmp", version))
+                .exists()
+        {
+            n += 1;
+            version = format!("{}-{}", timestamp, n);
+        }
+        version
     }
 
-    pub fn add_memo(&self, memo: &MemoDocument) -> Result<(), MemoError> {
-        let mut index = {
-            match self.get_current_index()? {
-                Some(index) => index,
-                None => {
-                    // If no index exists, create a new one
-                    self.create_new_index()?
-                }
+    /// 索引ディレクトリのバージョン（新しい順）
+    fn list_versions(&self) -> Result<Vec<String>, MemoError> {
+        if !self.index_base_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut versions = Vec::new();
+        for entry in std::fs::read_dir(&self.index_base_dir).with_path(&self.index_base_dir)? {
+            let entry = entry.with_path(&self.index_base_dir)?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() && is_version_name(&name) {
+                versions.push(name);
+            }
+        }
+        versions.sort_by(|a, b| b.cmp(a));
+        Ok(versions)
+    }
+
+    /// 古い索引ディレクトリを消し、新しい順に `keep_n` 個だけ残す（消したバージョンを返す）
+    ///
+    /// 現在のバージョンと、書き込み中（ロックが握られている）のものは消さない
+    pub fn prune_old_indexes(&self, keep_n: usize) -> Result<Vec<String>, MemoError> {
+        let versions = self.list_versions()?;
+        let current = self.get_version()?;
+        let mut removed = Vec::new();
+        for version in versions.into_iter().skip(keep_n) {
+            let dir = self.index_base_dir.join(&version);
+            if current.as_ref() == Some(&version) || IndexLock::is_held(&dir)? {
+                continue;
             }
+            std::fs::remove_dir_all(&dir).with_path(&dir)?;
+            removed.push(version);
+        }
+        Ok(removed)
+    }
+
+    pub fn add_memo(&self, memo: &MemoDocument) -> Result<(), MemoError> {
+        let _write = self.lock_for_write()?;
+        let Some(mut index) = self.get_current_index()? else {
+            // If no index exists, create a new one
+            self.build_new_index(|index| index.add_memo(memo))?;
+            return Ok(());
         };
 
         let _lock = IndexLock::acquire(&index.index_dir)?;
@@ -92,6 +427,7 @@ impl SearchManager {
     }
 
     pub fn remove_memo(&self, memo: &MemoDocument) -> Result<(), MemoError> {
+        let _write = self.lock_for_write()?;
         if let Some(mut index) = self.get_current_index()? {
             let _lock = IndexLock::acquire(&index.index_dir)?;
             index.remove_memo(memo)?;
@@ -100,21 +436,208 @@ impl SearchManager {
         Ok(())
     }
 
-    /// タグ一覧取得
-    pub fn list_tags(&self) -> Result<Vec<(String, u64)>, MemoError> {
-        if let Some(index) = self.get_current_index()? {
-            index.list_tags()
-        } else {
-            Ok(vec![])
+    /// まとめて索引から外す（コミットは 1 回）
+    pub fn remove_memos(&self, memos: &[MemoDocument]) -> Result<(), MemoError> {
+        let _write = self.lock_for_write()?;
+        if let Some(mut index) = self.get_current_index()? {
+            let _lock = IndexLock::acquire(&index.index_dir)?;
+            for memo in memos {
+                index.remove_memo(memo)?;
+            }
+            index.commit()?;
         }
+        Ok(())
+    }
+
+    /// 前回の索引作成・更新から追加・変更・削除されたメモだけを索引し直す
+    ///
+    /// `paths` は今あるメモファイル全て。索引かマニフェストが無ければ作り直す。
+    pub fn update_index(&self, paths: &[PathBuf]) -> Result<IndexUpdate, MemoError> {
+        let _write = self.lock_for_write()?;
+        let current = IndexManifest::scan(paths)?;
+        let existing = match self.get_current_index()? {
+            Some(index) => IndexManifest::load(&index.index_dir)?.map(|previous| (index, previous)),
+            None => None,
+        };
+        match existing {
+            Some((mut index, previous)) => Self::apply_update(&mut index, &current, &previous),
+            None => {
+                let mut update = IndexUpdate::default();
+                self.build_new_index(|index| {
+                    update = Self::apply_update(index, &current, &IndexManifest::default())?;
+                    Ok(())
+                })?;
+                Ok(update)
+            }
+        }
+    }
+
+    fn apply_update(
+        index: &mut SearchIndex,
+        current: &IndexManifest,
+        previous: &IndexManifest,
+    ) -> Result<IndexUpdate, MemoError> {
+        let _lock = IndexLock::acquire(&index.index_dir)?;
+        let mut update = IndexUpdate::default();
+        for (id, entry) in current.changed_since(previous) {
+            index.remove_id(id)?;
+            // 暗号化したメモは設定で許可した時だけ索引する
+            if !encryption::is_indexable(&entry.path) {
+                continue;
+            }
+            let memo = MemoFile::from_path(&entry.path)?;
+            index.add_memo(&MemoDocument::from_memo_file(&memo))?;
+            if previous.contains(id) {
+                update.modified += 1;
+            } else {
+                update.added += 1;
+            }
+        }
+        for id in current.removed_since(previous) {
+            index.remove_id(id)?;
+            update.removed += 1;
+        }
+        index.commit()?;
+        current.save(&index.index_dir)?;
+
+        Ok(update)
     }
 
     /// 検索実行
-    pub fn search(&self, query: &str) -> Result<Vec<SearchResult>, MemoError> {
+    pub fn search(
+        &self,
+        query: &str,
+        params: &SearchParams,
+    ) -> Result<Vec<SearchResult>, MemoError> {
         if let Some(index) = self.get_current_index()? {
-            index.search(query)
+            let _lock = IndexLock::acquire_shared(&index.index_dir)?;
+            index.search(query, params)
         } else {
             Ok(vec![])
         }
     }
+
+    /// 検索して、本文の一致箇所（最大 `max_chars` 文字）も返す
+    pub fn search_with_snippets(
+        &self,
+        query: &str,
+        params: &SearchParams,
+        max_chars: usize,
+    ) -> Result<Vec<SearchResult>, MemoError> {
+        match self.get_current_index()? {
+            Some(index) => {
+                let _lock = IndexLock::acquire_shared(&index.index_dir)?;
+                index.search_with_snippets(query, params, max_chars)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    /// `id` のメモに似ているメモ（インデックスが無ければ空）
+    pub fn similar(&self, id: &str, limit: usize) -> Result<Vec<SearchResult>, MemoError> {
+        match self.get_current_index()? {
+            Some(index) => {
+                let _lock = IndexLock::acquire_shared(&index.index_dir)?;
+                index.similar(id, limit)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    /// `id` にリンクしているメモの ID（インデックスが無ければ `None`）
+    pub fn backlinks(&self, id: &str) -> Result<Option<Vec<String>>, MemoError> {
+        match self.get_current_index()? {
+            Some(index) => {
+                let _lock = IndexLock::acquire_shared(&index.index_dir)?;
+                index.backlinks(id).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// ヒット件数
+    pub fn count(&self, query: &str, params: &SearchParams) -> Result<usize, MemoError> {
+        match self.get_current_index()? {
+            Some(index) => {
+                let _lock = IndexLock::acquire_shared(&index.index_dir)?;
+                index.count(query, params)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+/// `create_new_index` が作るディレクトリ名（`20250130_143022`・`20250130_143022-1`）か
+fn is_version_name(name: &str) -> bool {
+    let (timestamp, suffix) = name.split_once('-').unwrap_or((name, "0"));
+    chrono::NaiveDateTime::parse_from_str(timestamp, "%Y%m%d_%H%M%S").is_ok()
+        && suffix.parse::<u32>().is_ok()
+}
+
+/// 開いたままのインデックスで検索する（`memo shell` / `memo daemon` 用）
+///
+/// `memo index` で作り直されてバージョンが変わった時だけ開き直す
+pub struct CachedSearchIndex {
+    manager: SearchManager,
+    index: Option<(String, SearchIndex)>,
+}
+
+impl CachedSearchIndex {
+    pub fn new(manager: SearchManager) -> Self {
+        Self {
+            manager,
+            index: None,
+        }
+    }
+
+    pub fn search(
+        &mut self,
+        query: &str,
+        params: &SearchParams,
+    ) -> Result<Vec<SearchResult>, MemoError> {
+        match self.current()? {
+            Some(index) => {
+                let _lock = IndexLock::acquire_shared(&index.index_dir)?;
+                index.search(query, params)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    pub fn search_with_snippets(
+        &mut self,
+        query: &str,
+        params: &SearchParams,
+        max_chars: usize,
+    ) -> Result<Vec<SearchResult>, MemoError> {
+        match self.current()? {
+            Some(index) => {
+                let _lock = IndexLock::acquire_shared(&index.index_dir)?;
+                index.search_with_snippets(query, params, max_chars)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    pub fn count(&mut self, query: &str, params: &SearchParams) -> Result<usize, MemoError> {
+        match self.current()? {
+            Some(index) => {
+                let _lock = IndexLock::acquire_shared(&index.index_dir)?;
+                index.count(query, params)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn current(&mut self) -> Result<Option<&SearchIndex>, MemoError> {
+        let version = self.manager.get_version()?;
+        let cached = self.index.as_ref().map(|(v, _)| v);
+        if cached != version.as_ref() {
+            self.index = match (version, self.manager.get_current_index()?) {
+                (Some(version), Some(index)) => Some((version, index)),
+                _ => None,
+            };
+        }
+        Ok(self.index.as_ref().map(|(_, index)| index))
+    }
 }
@@ -0,0 +1,94 @@
+//! Token normalization shared by indexing and querying.
+//!
+//! - full-width ASCII (`ＡＢＣ１２３`) → half-width, then lower case
+//! - half-width katakana (`ｻｰﾊﾞ`) → full-width katakana
+//! - hiragana → katakana
+//! - a trailing long vowel mark is dropped (`サーバー` → `サーバ`)
+
+const HALF_WIDTH_KATAKANA: &str = "ｦｧｨｩｪｫｬｭｮｯｰｱｲｳｴｵｶｷｸｹｺｻｼｽｾｿﾀﾁﾂﾃﾄﾅﾆﾇﾈﾉﾊﾋﾌﾍﾎﾏﾐﾑﾒﾓﾔﾕﾖﾗﾘﾙﾚﾛﾜﾝ";
+const FULL_WIDTH_KATAKANA: &str = "ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+
+/// 索引・検索の両方で同じ形にそろえる
+pub fn normalize(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            // 全角英数記号・全角空白
+            '！'..='～' => out.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)),
+            '　' => out.push(' '),
+            // 半角カナの濁点・半濁点は直前の文字と合成
+            'ﾞ' | 'ﾟ' => match out
+                .chars()
+                .last()
+                .and_then(|prev| combine_sound_mark(prev, c))
+            {
+                Some(combined) => {
+                    out.pop();
+                    out.push(combined);
+                }
+                None => out.push(if c == 'ﾞ' { '゛' } else { '゜' }),
+            },
+            'ｦ'..='ﾝ' => out.push(half_to_full_katakana(c)),
+            // ひらがな → カタカナ
+            'ぁ'..='ゖ' => out.push(char::from_u32(c as u32 + 0x60).unwrap_or(c)),
+            c => out.push(c),
+        }
+    }
+
+    // 長音記号は語末だけ落とす（語末以外は読みの区別に必要）
+    let trimmed = out.trim_end_matches('ー');
+    if trimmed.is_empty() {
+        return out.to_lowercase();
+    }
+    trimmed.to_lowercase()
+}
+
+fn half_to_full_katakana(c: char) -> char {
+    HALF_WIDTH_KATAKANA
+        .chars()
+        .position(|half| half == c)
+        .and_then(|i| FULL_WIDTH_KATAKANA.chars().nth(i))
+        .unwrap_or(c)
+}
+
+/// カ→ガ、ハ→バ/パ、ウ→ヴ
+fn combine_sound_mark(prev: char, mark: char) -> Option<char> {
+    let voiced = mark == 'ﾞ';
+    match prev {
+        'ウ' if voiced => Some('ヴ'),
+        'カ' | 'キ' | 'ク' | 'ケ' | 'コ' | 'サ' | 'シ' | 'ス' | 'セ' | 'ソ' | 'タ' | 'チ'
+        | 'ツ' | 'テ' | 'ト'
+            if voiced =>
+        {
+            char::from_u32(prev as u32 + 1)
+        }
+        'ハ' | 'ヒ' | 'フ' | 'ヘ' | 'ホ' => {
+            char::from_u32(prev as u32 + if voiced { 1 } else { 2 })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_width() {
+        assert_eq!(normalize("ＡＢＣ１２３"), "abc123");
+        assert_eq!(normalize("ABC"), normalize("ＡＢＣ"));
+        assert_eq!(normalize("ｻｰﾊﾞｰ"), "サーバ");
+        assert_eq!(normalize("ﾊﾟｽﾜｰﾄﾞ"), "パスワード");
+        assert_eq!(normalize("ｳﾞｨ"), "ヴィ");
+        assert_eq!(normalize("aﾞ"), "a゛");
+    }
+
+    #[test]
+    fn test_normalize_kana_and_long_vowel() {
+        assert_eq!(normalize("サーバー"), normalize("サーバ"));
+        assert_eq!(normalize("さーばー"), "サーバ");
+        assert_eq!(normalize("コーヒー"), "コーヒ");
+        assert_eq!(normalize("ー"), "ー");
+        assert_eq!(normalize("漢字"), "漢字");
+    }
+}
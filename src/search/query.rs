@@ -0,0 +1,589 @@
+//! User-friendly query prefixes rewritten into tantivy syntax before parsing.
+//!
+//! | alias          | rewritten to                                        |
+//! |----------------|-----------------------------------------------------|
+//! | `tag:@todo`    | `tags.facet:"/@todo"`                               |
+//! | `prio:3`       | `priority:3` (also `prio:>=3`)                      |
+//! | `in:2025-01`   | `created_at:[2025-01-01T.. TO 2025-02-01T..}`       |
+//!
+//! `in:` accepts a year (`2025`), a month (`2025-01`) or a day (`2025-01-30`) in local time.
+//!
+//! `created_at:` / `updated_at:` take the same dates, as ranges or comparisons, each date
+//! standing for its whole period:
+//!
+//! | query                                      | matches                            |
+//! |--------------------------------------------|------------------------------------|
+//! | `created_at:2025-01`                       | January 2025 (same as `in:`)       |
+//! | `created_at:[2025-01-01 TO 2025-01-31]`    | Jan 1 through the end of Jan 31    |
+//! | `created_at:{2025-01 TO *]`                | after January 2025                 |
+//! | `updated_at:>=2025-01-15`, `<2025`         | comparisons (`>`, `>=`, `<`, `<=`) |
+//!
+//! RFC 3339 timestamps are left for tantivy to parse as they are.
+//!
+//! `/pattern/` and wildcard (`deploy*`, `ver?ion`) terms are not understood by tantivy's
+//! parser, so [`extract_regexes`] swaps them for placeholder words which the index turns
+//! into `RegexQuery`s.
+
+use crate::error::MemoError;
+use crate::search::normalize::normalize;
+use chrono::{DateTime, Local, Months, NaiveDate, SecondsFormat, TimeZone, Utc};
+use tantivy::query::QueryParserError;
+use tantivy::query_grammar;
+use unicode_width::UnicodeWidthStr;
+
+const SYNTAX_HELP_HINT: &str = "run `memo search --help-syntax` for the supported syntax";
+const QUOTE_HINT: &str = "hint: quote the text (\"...\") or escape special characters with \\ to search for them literally";
+
+/// クエリ中のエイリアスを展開する（引用符の中はそのまま）
+pub fn expand_aliases(query: &str) -> Result<String, MemoError> {
+    let mut out = String::with_capacity(query.len());
+    let mut rest = query;
+    let mut in_quote = false;
+    let mut at_token_start = true;
+
+    while let Some(c) = rest.chars().next() {
+        if !in_quote
+            && at_token_start
+            && let Some((expanded, consumed)) = expand_term(rest)?
+        {
+            out.push_str(&expanded);
+            rest = &rest[consumed..];
+            at_token_start = false;
+            continue;
+        }
+
+        if c == '"' {
+            in_quote = !in_quote;
+        }
+        // 語頭の +/- の直後も語の先頭
+        at_token_start =
+            c.is_whitespace() || c == '(' || (at_token_start && matches!(c, '+' | '-'));
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    Ok(out)
+}
+
+const REGEX_PLACEHOLDER_PREFIX: &str = "__memo_regex_";
+
+/// [`extract_regexes`] の結果
+#[derive(Debug)]
+pub struct Extracted {
+    /// パターンを置き換えたクエリ
+    pub query: String,
+    pub patterns: Vec<String>,
+    /// 置き換えた語の頭。元のクエリに現れないものを選ぶ
+    prefix: String,
+}
+
+impl Extracted {
+    /// [`extract_regexes`] が入れた語なら、そのパターンの番号
+    pub fn placeholder(&self, word: &str) -> Option<usize> {
+        word.strip_prefix(&self.prefix)?
+            .strip_suffix("__")?
+            .parse()
+            .ok()
+    }
+}
+
+/// `/pattern/` 項とワイルドカード語を `__memo_regex_N__` に置き換え、パターンを順に返す
+///
+/// 置き換えた語は tantivy の文法でそのまま解析できるので、`+`/`-` や括弧も効く。
+/// 語の先頭の `/` だけを対象にするので `tags.facet:"/@todo"` などはそのまま。
+/// パターン中の `/` は `\/` と書く。
+/// クエリに `__memo_regex_` が書かれていれば、`_` を足して重ならない語を使う。
+pub fn extract_regexes(query: &str) -> Result<Extracted, MemoError> {
+    // `\` を外した形でも重ならないようにする（tantivy はエスケープを解いて語にする）
+    let unescaped = query.replace('\\', "");
+    let mut prefix = REGEX_PLACEHOLDER_PREFIX.to_string();
+    while unescaped.contains(&prefix) {
+        prefix.push('_');
+    }
+
+    let mut out = String::with_capacity(query.len());
+    let mut patterns = Vec::new();
+    let mut rest = query;
+    let mut in_quote = false;
+    let mut at_token_start = true;
+
+    while let Some(c) = rest.chars().next() {
+        if !in_quote && at_token_start && c == '/' {
+            let (pattern, consumed) = read_regex(&rest[1..])
+                .filter(|(pattern, _)| !pattern.is_empty())
+                .ok_or_else(|| MemoError::InvalidArgument(format!("invalid regex: {}", rest)))?;
+            out.push_str(&format!("{}{}__", prefix, patterns.len()));
+            patterns.push(pattern);
+            rest = &rest[1 + consumed..];
+            at_token_start = false;
+            continue;
+        }
+        if !in_quote
+            && at_token_start
+            && let Some(word) = wildcard_word(rest)
+        {
+            out.push_str(&format!("{}{}__", prefix, patterns.len()));
+            patterns.push(wildcard_to_regex(word));
+            rest = &rest[word.len()..];
+            at_token_start = false;
+            continue;
+        }
+
+        if c == '"' {
+            in_quote = !in_quote;
+        }
+        at_token_start =
+            c.is_whitespace() || c == '(' || (at_token_start && matches!(c, '+' | '-'));
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    Ok(Extracted {
+        query: out,
+        patterns,
+        prefix,
+    })
+}
+
+/// `rest` の先頭が `*` か `?` を含む語（フィールド指定なし）ならその語
+fn wildcard_word(rest: &str) -> Option<&str> {
+    if rest.starts_with(['+', '-', '(', '/']) {
+        return None;
+    }
+    let end = rest
+        .find(|c: char| c.is_whitespace() || matches!(c, ')' | '^'))
+        .unwrap_or(rest.len());
+    let word = &rest[..end];
+    let has_wildcard = word.contains(['*', '?']);
+    // `*` だけは全件検索（範囲の `TO *]` も）、`field:value*` は tantivy に任せる
+    let has_text = word.chars().any(|c| !matches!(c, '*' | '?' | ']' | '}'));
+    (has_wildcard && has_text && !word.contains([':', '"'])).then_some(word)
+}
+
+/// `*` は任意の文字列、`?` は任意の 1 文字。それ以外は索引と同じく正規化する
+fn wildcard_to_regex(word: &str) -> String {
+    let mut pattern = String::with_capacity(word.len() * 2);
+    let mut literal = String::new();
+    for c in word.chars().chain(std::iter::once('\0')) {
+        if !matches!(c, '*' | '?' | '\0') {
+            literal.push(c);
+            continue;
+        }
+        for c in normalize(&literal).chars() {
+            if c.is_ascii_punctuation() {
+                pattern.push('\\');
+            }
+            pattern.push(c);
+        }
+        literal.clear();
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            _ => {}
+        }
+    }
+    pattern
+}
+
+/// 開きの `/` の後ろから閉じの `/` までを読み、パターンと消費したバイト数（閉じを含む）
+fn read_regex(s: &str) -> Option<(String, usize)> {
+    let mut pattern = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '/' => return Some((pattern, i + 1)),
+            '\\' => match chars.next() {
+                Some((_, '/')) => pattern.push('/'),
+                Some((_, escaped)) => {
+                    pattern.push('\\');
+                    pattern.push(escaped);
+                }
+                None => return None,
+            },
+            _ => pattern.push(c),
+        }
+    }
+    None
+}
+
+/// tantivy のパースエラーを、問題の位置を示すメッセージにする
+///
+/// ```text
+/// expected ')'
+///   foo (bar
+///           ^
+/// hint: quote the text ("...") or escape special characters with \ to search for them literally
+/// run `memo search --help-syntax` for the supported syntax
+/// ```
+pub fn parse_error(query: &str, err: &QueryParserError) -> MemoError {
+    let (message, position, hint) = match err {
+        QueryParserError::SyntaxError(_) => {
+            // 位置は緩いパーサーに聞く
+            let (_, errors) = query_grammar::parse_query_lenient(query);
+            match errors.into_iter().next() {
+                Some(error) => (error.message, Some(error.pos), QUOTE_HINT.to_string()),
+                None => ("syntax error".to_string(), None, QUOTE_HINT.to_string()),
+            }
+        }
+        QueryParserError::FieldDoesNotExist(field) => (
+            format!("unknown field '{}'", field),
+            query.find(&format!("{}:", field)),
+            "hint: searchable fields are content, title, tags and metadata.<key> \
+             (or tag:, prio:, in:, created_at:, updated_at:); quote the text to search for a literal ':'"
+                .to_string(),
+        ),
+        QueryParserError::AllButQueryForbidden => (
+            "a query cannot consist only of excluded terms".to_string(),
+            None,
+            "hint: add something to search for, e.g. `* -draft`".to_string(),
+        ),
+        err => (err.to_string(), None, QUOTE_HINT.to_string()),
+    };
+    query_error(query, message, position, &hint)
+}
+
+/// `message`、`position`（バイト位置）を指す `^`、`hint` と `--help-syntax` の案内を並べたエラー
+pub fn query_error(query: &str, message: String, position: Option<usize>, hint: &str) -> MemoError {
+    let mut lines = vec![message];
+    if let Some(position) = position {
+        let mut position = position.min(query.len());
+        while !query.is_char_boundary(position) {
+            position -= 1;
+        }
+        lines.push(format!("  {}", query));
+        lines.push(format!("  {}^", " ".repeat(query[..position].width())));
+    }
+    lines.push(hint.to_string());
+    lines.push(SYNTAX_HELP_HINT.to_string());
+    MemoError::InvalidQuery(lines.join("\n"))
+}
+
+/// [`expand_aliases`] が展開するフィールド名
+pub const ALIASES: [&str; 3] = ["tag", "prio", "in"];
+
+/// `rest` の先頭がエイリアスなら、展開後の文字列と消費したバイト数
+fn expand_term(rest: &str) -> Result<Option<(String, usize)>, MemoError> {
+    if let Some(expanded) = expand_date_field(rest)? {
+        return Ok(Some(expanded));
+    }
+    let Some((alias, value_start)) = ALIASES.iter().find_map(|alias| {
+        rest.strip_prefix(alias)?
+            .strip_prefix(':')
+            .map(|value| (*alias, value))
+    }) else {
+        return Ok(None);
+    };
+    let (value, value_len) = read_value(value_start);
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let consumed = alias.len() + 1 + value_len;
+
+    let expanded = match alias {
+        "tag" => format!("tags.facet:\"/{}\"", value.trim_start_matches('/')),
+        "prio" => format!("priority:{}", value),
+        _ => {
+            let (start, end) = date_range(value)?;
+            format!("created_at:[{} TO {}}}", rfc3339(start), rfc3339(end))
+        }
+    };
+    Ok(Some((expanded, consumed)))
+}
+
+/// 日付で絞り込めるフィールド
+const DATE_FIELDS: [&str; 2] = ["created_at:", "updated_at:"];
+
+/// `created_at:` / `updated_at:` の日付・範囲・比較を RFC 3339 の範囲にする
+fn expand_date_field(rest: &str) -> Result<Option<(String, usize)>, MemoError> {
+    let Some(field) = DATE_FIELDS.iter().find(|field| rest.starts_with(*field)) else {
+        return Ok(None);
+    };
+    let value_start = &rest[field.len()..];
+
+    if value_start.starts_with(['[', '{']) {
+        // 閉じ括弧が無い・TO が無いなどの誤りは tantivy のエラーにまかせる
+        let Some(close) = value_start.find([']', '}']) else {
+            return Ok(None);
+        };
+        let Some((lower, upper)) = value_start[1..close].split_once(" TO ") else {
+            return Ok(None);
+        };
+        let (lower, upper) = (lower.trim(), upper.trim());
+        if is_timestamp(lower) || is_timestamp(upper) {
+            return Ok(None);
+        }
+        // 日付はその期間全体。`[` は期間の始め、`{` は期間の後から、`]` は期間の終わりまで
+        let inclusive_lower = value_start.starts_with('[');
+        let inclusive_upper = value_start[close..].starts_with(']');
+        let bound = |value: &str, use_end: bool| -> Result<String, MemoError> {
+            if value == "*" {
+                return Ok(value.to_string());
+            }
+            let (start, end) = date_range(value)?;
+            Ok(rfc3339(if use_end { end } else { start }))
+        };
+        let expanded = format!(
+            "{}[{} TO {}}}",
+            field,
+            bound(lower, !inclusive_lower)?,
+            bound(upper, inclusive_upper)?
+        );
+        return Ok(Some((expanded, field.len() + close + 1)));
+    }
+
+    let (value, value_len) = read_value(value_start);
+    let (op, date) = [">=", "<=", ">", "<"]
+        .iter()
+        .find_map(|op| value.strip_prefix(op).map(|date| (*op, date)))
+        .unwrap_or(("", value));
+    if date.is_empty() || is_timestamp(date) {
+        return Ok(None);
+    }
+    let (start, end) = date_range(date)?;
+    let expanded = match op {
+        ">=" => format!(">={}", rfc3339(start)),
+        ">" => format!(">={}", rfc3339(end)),
+        "<" => format!("<{}", rfc3339(start)),
+        "<=" => format!("<{}", rfc3339(end)),
+        _ => format!("[{} TO {}}}", rfc3339(start), rfc3339(end)),
+    };
+    Ok(Some((
+        format!("{}{}", field, expanded),
+        field.len() + value_len,
+    )))
+}
+
+fn is_timestamp(value: &str) -> bool {
+    DateTime::parse_from_rfc3339(value).is_ok()
+}
+
+/// 値（`"..."` なら引用符の中）と、引用符を含めた長さ
+fn read_value(s: &str) -> (&str, usize) {
+    if let Some(quoted) = s.strip_prefix('"') {
+        return match quoted.find('"') {
+            Some(end) => (&quoted[..end], end + 2),
+            None => (quoted, s.len()),
+        };
+    }
+    let end = s
+        .find(|c: char| c.is_whitespace() || c == ')')
+        .unwrap_or(s.len());
+    (&s[..end], end)
+}
+
+/// `in:` などの日付を [start, end) のローカル時刻の範囲に
+fn date_range(value: &str) -> Result<(DateTime<Local>, DateTime<Local>), MemoError> {
+    let invalid = || {
+        MemoError::InvalidArgument(format!(
+            "invalid date '{}' (expected YYYY, YYYY-MM or YYYY-MM-DD)",
+            value
+        ))
+    };
+
+    let parts: Vec<&str> = value.split('-').collect();
+    let (start, months) = match parts.as_slice() {
+        [year] => (format!("{}-01-01", year), 12),
+        [year, month] => (format!("{}-{}-01", year, month), 1),
+        [_, _, _] => (value.to_string(), 0),
+        _ => return Err(invalid()),
+    };
+    let start = NaiveDate::parse_from_str(&start, "%Y-%m-%d").map_err(|_| invalid())?;
+    let end = if months == 0 {
+        start.succ_opt()
+    } else {
+        start.checked_add_months(Months::new(months))
+    }
+    .ok_or_else(invalid)?;
+
+    let local = |date: NaiveDate| {
+        Local
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+            .earliest()
+    };
+    Ok((
+        local(start).ok_or_else(invalid)?,
+        local(end).ok_or_else(invalid)?,
+    ))
+}
+
+fn rfc3339(at: DateTime<Local>) -> String {
+    at.with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_tag_and_prio() {
+        assert_eq!(
+            expand_aliases("meeting tag:@todo").unwrap(),
+            "meeting tags.facet:\"/@todo\""
+        );
+        assert_eq!(
+            expand_aliases("+tag:\"@team sync\" -prio:1").unwrap(),
+            "+tags.facet:\"/@team sync\" -priority:1"
+        );
+        assert_eq!(
+            expand_aliases("(prio:>=3 OR tag:/@x)").unwrap(),
+            "(priority:>=3 OR tags.facet:\"/@x\")"
+        );
+    }
+
+    #[test]
+    fn test_expand_leaves_other_text() {
+        assert_eq!(
+            expand_aliases("\"tag:@todo\" title:foo metag:x").unwrap(),
+            "\"tag:@todo\" title:foo metag:x"
+        );
+        assert_eq!(expand_aliases("foo-tag:x").unwrap(), "foo-tag:x");
+        assert_eq!(expand_aliases("tag: x").unwrap(), "tag: x");
+    }
+
+    #[test]
+    fn test_extract_regexes() {
+        let extracted = extract_regexes("meeting /inc-[0-9]+/ -/10\\.0\\/8/").unwrap();
+        assert_eq!(
+            extracted.query,
+            "meeting __memo_regex_0__ -__memo_regex_1__"
+        );
+        assert_eq!(extracted.patterns, vec!["inc-[0-9]+", "10\\.0/8"]);
+        assert_eq!(extracted.placeholder("__memo_regex_1__"), Some(1));
+        assert_eq!(extracted.placeholder("meeting"), None);
+
+        let extracted = extract_regexes("+(/a.c/ OR b)").unwrap();
+        assert_eq!(extracted.query, "+(__memo_regex_0__ OR b)");
+        assert_eq!(extracted.patterns, vec!["a.c"]);
+
+        let extracted = extract_regexes("tags.facet:\"/@todo\" \"/x/\" a/b/").unwrap();
+        assert_eq!(extracted.query, "tags.facet:\"/@todo\" \"/x/\" a/b/");
+        assert!(extracted.patterns.is_empty());
+
+        assert!(matches!(
+            extract_regexes("/open"),
+            Err(MemoError::InvalidArgument(_))
+        ));
+        assert!(extract_regexes("//").is_err());
+    }
+
+    #[test]
+    fn test_extract_regexes_with_literal_placeholder() {
+        let extracted = extract_regexes("/foo/ __memo_regex_0__ __memo_regex_5__").unwrap();
+        assert_eq!(
+            extracted.query,
+            "__memo_regex__0__ __memo_regex_0__ __memo_regex_5__"
+        );
+        assert_eq!(extracted.patterns, vec!["foo"]);
+        assert_eq!(extracted.placeholder("__memo_regex__0__"), Some(0));
+        assert_eq!(extracted.placeholder("__memo_regex_0__"), None);
+        assert_eq!(extracted.placeholder("__memo_regex_5__"), None);
+
+        // エスケープを解いた語とも重ならない
+        let extracted = extract_regexes("/foo/ __memo\\_regex__0__").unwrap();
+        assert_eq!(extracted.placeholder("__memo_regex__0__"), None);
+        assert_eq!(extracted.placeholder("__memo_regex___0__"), Some(0));
+    }
+
+    #[test]
+    fn test_extract_wildcards() {
+        let extracted = extract_regexes("+deploy* -v1.?^2 (デプロイ*)").unwrap();
+        assert_eq!(
+            extracted.query,
+            "+__memo_regex_0__ -__memo_regex_1__^2 (__memo_regex_2__)"
+        );
+        assert_eq!(extracted.patterns, vec!["deploy.*", "v1\\..", "デプロイ.*"]);
+
+        let extracted = extract_regexes("ＳＥＲＶＥＲ* さーばー*").unwrap();
+        assert_eq!(extracted.patterns, vec!["server.*", "サーバ.*"]);
+
+        let extracted = extract_regexes("* title:dep* \"a*\" -? x:[1 TO *]").unwrap();
+        assert_eq!(extracted.query, "* title:dep* \"a*\" -? x:[1 TO *]");
+        assert!(extracted.patterns.is_empty());
+    }
+
+    #[test]
+    fn test_parse_error_points_at_token() {
+        let err = parse_error("foo (bar", &QueryParserError::SyntaxError(String::new()));
+        let MemoError::InvalidQuery(message) = err else {
+            panic!("unexpected error: {:?}", err);
+        };
+        let lines: Vec<&str> = message.lines().collect();
+        assert_eq!(lines[0], "expected ')'");
+        assert_eq!(lines[1], "  foo (bar");
+        assert_eq!(lines[2], "          ^");
+        assert!(lines[3].starts_with("hint: quote the text"));
+
+        let err = parse_error(
+            "会議 http://example.com",
+            &QueryParserError::FieldDoesNotExist("http".to_string()),
+        );
+        let MemoError::InvalidQuery(message) = err else {
+            panic!("unexpected error: {:?}", err);
+        };
+        assert!(message.starts_with("unknown field 'http'\n  会議 http://example.com\n       ^\n"));
+    }
+
+    #[test]
+    fn test_expand_in() {
+        let expected_start = rfc3339(Local.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+        let expected_end = rfc3339(Local.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            expand_aliases("in:2025-01").unwrap(),
+            format!("created_at:[{} TO {}}}", expected_start, expected_end)
+        );
+
+        let (start, end) = date_range("2024-12-31").unwrap();
+        assert_eq!(end - start, chrono::Duration::days(1));
+        let (start, end) = date_range("2025").unwrap();
+        assert_eq!(end - start, chrono::Duration::days(365));
+
+        assert!(matches!(
+            expand_aliases("in:January"),
+            Err(MemoError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_expand_date_fields() {
+        let day = |y, m, d| rfc3339(Local.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap());
+        assert_eq!(
+            expand_aliases("created_at:[2025-01-01 TO 2025-01-31] AND tag:@work").unwrap(),
+            format!(
+                "created_at:[{} TO {}}} AND tags.facet:\"/@work\"",
+                day(2025, 1, 1),
+                day(2025, 2, 1)
+            )
+        );
+        assert_eq!(
+            expand_aliases("created_at:{2025-01 TO *]").unwrap(),
+            format!("created_at:[{} TO *}}", day(2025, 2, 1))
+        );
+        assert_eq!(
+            expand_aliases("updated_at:2025").unwrap(),
+            format!("updated_at:[{} TO {}}}", day(2025, 1, 1), day(2026, 1, 1))
+        );
+        assert_eq!(
+            expand_aliases("-updated_at:>=2025-01-15 created_at:<=2024-12").unwrap(),
+            format!(
+                "-updated_at:>={} created_at:<{}",
+                day(2025, 1, 15),
+                day(2025, 1, 1)
+            )
+        );
+        assert_eq!(
+            expand_aliases("created_at:>2025-01-30").unwrap(),
+            format!("created_at:>={}", day(2025, 1, 31))
+        );
+
+        // RFC 3339 はそのまま、閉じていない範囲は tantivy がエラーにする
+        let raw = "created_at:[2025-01-01T00:00:00Z TO 2025-02-01T00:00:00Z]";
+        assert_eq!(expand_aliases(raw).unwrap(), raw);
+        assert_eq!(
+            expand_aliases("created_at:[2025 TO").unwrap(),
+            "created_at:[2025 TO"
+        );
+        assert!(matches!(
+            expand_aliases("created_at:[2025-13 TO *]"),
+            Err(MemoError::InvalidArgument(_))
+        ));
+    }
+}
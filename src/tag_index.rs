@@ -0,0 +1,248 @@
+//! Tags used across memos: front matter `tags` entries plus inline `@tag` tokens.
+
+use crate::encryption;
+use crate::error::MemoResult;
+use crate::front_matter;
+use crate::memo::MemoFile;
+use std::collections::{BTreeSet, HashMap};
+
+const TAGS_KEY: &str = "tags";
+
+/// タグごとの使用メモ数
+#[derive(Debug, Default)]
+pub struct TagIndex {
+    counts: HashMap<String, usize>,
+}
+
+impl TagIndex {
+    pub fn build(memos: &[MemoFile]) -> Self {
+        let mut index = Self::default();
+        for memo in memos {
+            index.add(memo);
+        }
+        index
+    }
+
+    /// メモ 1 つ分のタグを数える
+    pub fn add(&mut self, memo: &MemoFile) {
+        for tag in memo_tags(memo) {
+            *self.counts.entry(tag).or_default() += 1;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// 使用数の多い順（同数ならタグ名順）
+    pub fn sorted(&self) -> Vec<(String, usize)> {
+        let mut tags: Vec<(String, usize)> = self
+            .counts
+            .iter()
+            .map(|(tag, count)| (tag.clone(), *count))
+            .collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        tags
+    }
+}
+
+/// Front matter tags and inline `@tag` tokens of a memo, without duplicates
+pub fn memo_tags(memo: &MemoFile) -> BTreeSet<String> {
+    memo.tags()
+        .into_iter()
+        .chain(inline_tags(&memo.content))
+        .map(str::to_string)
+        .collect()
+}
+
+/// 本文中の `@tag`（行頭か空白の直後の `@` から始まるもの）
+pub fn inline_tags(content: &str) -> Vec<&str> {
+    inline_tag_spans(content)
+        .into_iter()
+        .map(|(start, end)| &content[start..end])
+        .collect()
+}
+
+/// Rename `old` to `new` in the front matter `tags` list and, for `@` tags, in the body.
+///
+/// Returns `None` (and leaves the file alone) when the memo does not use `old`.
+pub fn rename_tag(memo: &MemoFile, old: &str, new: &str) -> MemoResult<Option<MemoFile>> {
+    let raw = encryption::read_memo(&memo.path)?;
+    let mut updated = raw.clone();
+    let mut changed = false;
+
+    if old.starts_with('@') {
+        let replacement = if new.starts_with('@') {
+            new.to_string()
+        } else {
+            format!("@{}", new)
+        };
+        updated = replace_inline_tags(&raw, |tag| {
+            if tag == old {
+                replacement.clone()
+            } else {
+                tag.to_string()
+            }
+        });
+        changed = updated != raw;
+    }
+
+    let tags = memo.tags();
+    if tags.contains(&old) {
+        let mut renamed: Vec<&str> = Vec::new();
+        for tag in tags {
+            let tag = if tag == old { new } else { tag };
+            if !renamed.contains(&tag) {
+                renamed.push(tag);
+            }
+        }
+        updated = front_matter::set_field(&updated, TAGS_KEY, &tags_value(&renamed));
+        changed = true;
+    }
+
+    if !changed {
+        return Ok(None);
+    }
+    encryption::write_memo(&memo.path, &updated)?;
+    MemoFile::from_path(&memo.path).map(Some)
+}
+
+/// Rewrite every inline `@tag` in `content` with `replace` (which receives the tag with its `@`)
+pub fn replace_inline_tags(content: &str, replace: impl Fn(&str) -> String) -> String {
+    let mut rewritten = String::with_capacity(content.len());
+    let mut last = 0;
+    for (start, end) in inline_tag_spans(content) {
+        rewritten.push_str(&content[last..start]);
+        rewritten.push_str(&replace(&content[start..end]));
+        last = end;
+    }
+    rewritten.push_str(&content[last..]);
+    rewritten
+}
+
+/// Append `tag` to the front matter `tags` list.
+///
+/// Returns `None` (and leaves the file alone) when the memo already has it.
+pub fn add_tag(memo: &MemoFile, tag: &str) -> MemoResult<Option<MemoFile>> {
+    let mut tags = memo.tags();
+    if tags.contains(&tag) {
+        return Ok(None);
+    }
+    tags.push(tag);
+    let raw = encryption::read_memo(&memo.path)?;
+    encryption::write_memo(
+        &memo.path,
+        &front_matter::set_field(&raw, TAGS_KEY, &tags_value(&tags)),
+    )?;
+    MemoFile::from_path(&memo.path).map(Some)
+}
+
+/// `["@a", "@b"]`
+fn tags_value(tags: &[&str]) -> String {
+    let value = tags
+        .iter()
+        .map(|tag| serde_json::to_string(tag).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("[{}]", value)
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '/')
+}
+
+/// Byte ranges of the inline tags in `content`, `@` included
+fn inline_tag_spans(content: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = content.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c == '@' && previous.is_none_or(char::is_whitespace) {
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, next)) = chars.peek()
+                && is_tag_char(next)
+            {
+                end = i + next.len_utf8();
+                chars.next();
+            }
+            if end > start + 1 {
+                spans.push((start, end));
+            }
+            previous = content[..end].chars().next_back();
+            continue;
+        }
+        previous = Some(c);
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn memo(content: &str) -> (tempfile::TempDir, MemoFile) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("2025-01/30/20250130143022.md");
+        let memo = MemoFile::create(&path, content.to_string()).unwrap();
+        (temp_dir, memo)
+    }
+
+    #[test]
+    fn test_inline_tags() {
+        assert_eq!(
+            inline_tags("@todo first\nmail me@example.com (@x) @会議 @a-b/c."),
+            vec!["@todo", "@会議", "@a-b/c"]
+        );
+        assert!(inline_tags("@ alone").is_empty());
+        assert_eq!(
+            replace_inline_tags("@todo and me@example.com @a", |tag| tag.to_uppercase()),
+            "@TODO and me@example.com @A"
+        );
+    }
+
+    #[test]
+    fn test_tag_index() {
+        let (_d1, a) = memo("---\ntags: [\"@work\"]\n---\n@work @idea");
+        let (_d2, b) = memo("@idea\n@idea");
+        let index = TagIndex::build(&[a, b]);
+
+        assert_eq!(
+            index.sorted(),
+            vec![("@idea".to_string(), 2), ("@work".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_rename_tag() {
+        let raw = "---\ntitle: T\ntags: [\"@old\", \"@new\"]\n---\nsee @old and @older\n";
+        let (_dir, memo) = memo(raw);
+
+        let renamed = rename_tag(&memo, "@old", "@new").unwrap().unwrap();
+        assert_eq!(renamed.tags(), vec!["@new"]);
+        assert_eq!(
+            fs::read_to_string(&memo.path).unwrap(),
+            "---\ntitle: T\ntags: [\"@new\"]\n---\nsee @new and @older\n"
+        );
+        assert!(rename_tag(&renamed, "@none", "@new").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_add_tag() {
+        let (_dir, original) = memo("---\ntitle: T\ntags: [\"@a\"]\n---\nbody\n");
+
+        let tagged = add_tag(&original, "@b").unwrap().unwrap();
+        assert_eq!(tagged.tags(), vec!["@a", "@b"]);
+        assert_eq!(
+            fs::read_to_string(&original.path).unwrap(),
+            "---\ntitle: T\ntags: [\"@a\", \"@b\"]\n---\nbody\n"
+        );
+        assert!(add_tag(&tagged, "@a").unwrap().is_none());
+
+        let (_dir, plain) = memo("# Plain\n");
+        assert_eq!(
+            add_tag(&plain, "@new").unwrap().unwrap().tags(),
+            vec!["@new"]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Templates for new memos.
+//!
+//! `memo add --template <name>` starts from `.templates/<name>.md` in the memo
+//! directory (or a builtin) and expands `{{id}}`, `{{date}}`, `{{time}}` and
+//! `{{title}}` before the editor opens. Unknown placeholders are left as they are.
+
+use crate::context::MemoContext;
+use crate::memo_id::MemoId;
+
+pub const BUILTIN_TEMPLATES: &[&str] = &["1on1", "idea", "meeting", "todo"];
+
+/// `.templates/{name}.md` があればそれを、無ければビルトインを返す（展開前）
+pub fn load(context: &MemoContext, name: &str) -> String {
+    let template_path = context.templates_dir().join(format!("{}.md", name));
+    if template_path.exists()
+        && let Ok(content) = std::fs::read_to_string(&template_path)
+    {
+        return content;
+    }
+    builtin(name)
+}
+
+pub fn builtin(name: &str) -> String {
+    match name {
+        "1on1" => "---\ntitle: \"{{title}}\"\ntags: ['@1on1']\n---\n\n## 話したこと\n\n\n## ネクストアクション\n\n".to_string(),
+        "idea" => "---\ntitle: \"{{title}}\"\ntags: ['@idea']\n---\n\n## アイデア\n\n\n## 背景・動機\n\n".to_string(),
+        "todo" => "---\ntitle: \"{{title}}\"\ntags: ['@todo']\n---\n\n## やること\n\n- [ ] \n".to_string(),
+        "meeting" => "---\ntitle: \"{{title}}\"\ntags: ['@meeting']\n---\n\n## 日時\n{{date}} {{time}}\n\n## 参加者\n\n\n## 議題\n\n\n## 決定事項\n\n\n## ネクストアクション\n\n".to_string(),
+        other => format!("---\ntitle: \"{{{{title}}}}\"\ntags: ['@{}']\n---\n\n", other),
+    }
+}
+
+/// Values substituted into a template
+#[derive(Debug, Clone)]
+pub struct TemplateVars {
+    pub id: String,
+    /// `YYYY-MM-DD` of the memo's creation
+    pub date: String,
+    /// `HH:MM` of the memo's creation
+    pub time: String,
+    pub title: String,
+}
+
+impl TemplateVars {
+    pub fn new(id: &MemoId, title: Option<&str>) -> Self {
+        let created_at = id.get_datetime();
+        Self {
+            id: id.as_str(),
+            date: created_at.format("%Y-%m-%d").to_string(),
+            time: created_at.format("%H:%M").to_string(),
+            title: title.unwrap_or_default().to_string(),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "id" => Some(&self.id),
+            "date" => Some(&self.date),
+            "time" => Some(&self.time),
+            "title" => Some(&self.title),
+            _ => None,
+        }
+    }
+}
+
+/// `{{name}}`（前後の空白は無視）を置き換える
+pub fn expand(template: &str, vars: &TemplateVars) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let placeholder = &rest[start..start + 2 + len + 2];
+        expanded.push_str(&rest[..start]);
+        match vars.get(placeholder[2..2 + len].trim()) {
+            Some(value) => expanded.push_str(value),
+            None => expanded.push_str(placeholder),
+        }
+        rest = &rest[start + placeholder.len()..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> TemplateVars {
+        TemplateVars::new(
+            &MemoId::from_str("20250130143022").unwrap(),
+            Some("Weekly sync"),
+        )
+    }
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(
+            expand("# {{title}}\n{{ date }} {{time}} ({{id}})", &vars()),
+            "# Weekly sync\n2025-01-30 14:30 (20250130143022)"
+        );
+        assert_eq!(
+            expand("{{unknown}} {{title", &vars()),
+            "{{unknown}} {{title"
+        );
+    }
+
+    #[test]
+    fn test_builtin_placeholders() {
+        let content = expand(&builtin("meeting"), &vars());
+        assert!(content.starts_with("---\ntitle: \"Weekly sync\"\ntags: ['@meeting']"));
+        assert!(content.contains("2025-01-30 14:30"));
+        assert_eq!(
+            expand(&builtin("retro"), &vars()),
+            "---\ntitle: \"Weekly sync\"\ntags: ['@retro']\n---\n\n"
+        );
+    }
+}
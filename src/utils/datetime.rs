@@ -0,0 +1,90 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeDelta, TimeZone};
+
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+];
+
+/// RFC 3339、`YYYY-MM-DD HH:MM[:SS]`、`YYYY-MM-DD`（その日の0時）をローカル時刻として解釈
+pub fn parse_datetime(value: &str) -> Option<DateTime<Local>> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Local));
+    }
+    let naive = NAIVE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+        })?;
+    Local.from_local_datetime(&naive).earliest()
+}
+
+/// 範囲の終わり（この時刻を含まない）として解釈する
+///
+/// 日付だけなら翌日の0時、つまりその日を含む。
+pub fn parse_end_datetime(value: &str) -> Option<DateTime<Local>> {
+    match NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d") {
+        Ok(date) => {
+            let next_day = date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+            Local.from_local_datetime(&next_day).earliest()
+        }
+        Err(_) => parse_datetime(value),
+    }
+}
+
+/// `90d`（日）・`12w`（週）の長さ
+pub fn parse_age(value: &str) -> Option<TimeDelta> {
+    let value = value.trim();
+    let (number, days_per_unit) = if let Some(days) = value.strip_suffix('d') {
+        (days, 1)
+    } else {
+        (value.strip_suffix('w')?, 7)
+    };
+    let number: i64 = number.parse().ok().filter(|n| *n > 0)?;
+    TimeDelta::try_days(number.checked_mul(days_per_unit)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_datetime() {
+        let expected = Local.with_ymd_and_hms(2025, 1, 30, 15, 0, 0).unwrap();
+        assert_eq!(parse_datetime("2025-01-30 15:00"), Some(expected));
+        assert_eq!(parse_datetime("2025-01-30T15:00:00"), Some(expected));
+        assert_eq!(
+            parse_datetime("2025-01-30"),
+            Some(Local.with_ymd_and_hms(2025, 1, 30, 0, 0, 0).unwrap())
+        );
+        assert!(parse_datetime("2025-01-30T15:00:00+09:00").is_some());
+        assert_eq!(parse_datetime("tomorrow"), None);
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("90d"), Some(TimeDelta::days(90)));
+        assert_eq!(parse_age("2w"), Some(TimeDelta::days(14)));
+        assert_eq!(parse_age("0d"), None);
+        assert_eq!(parse_age("3m"), None);
+        assert_eq!(parse_age("d"), None);
+    }
+
+    #[test]
+    fn test_parse_end_datetime() {
+        assert_eq!(
+            parse_end_datetime("2025-01-31"),
+            Some(Local.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse_end_datetime("2025-01-31 12:00"),
+            Some(Local.with_ymd_and_hms(2025, 1, 31, 12, 0, 0).unwrap())
+        );
+        assert_eq!(parse_end_datetime("someday"), None);
+    }
+}
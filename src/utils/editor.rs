@@ -1,10 +1,34 @@
 use crate::context::MemoContext;
+use crate::encryption::{self, DecryptedCopy};
 use crate::error::{MemoError, MemoResult};
+use std::path::Path;
 use std::process::Command;
 
-pub fn open_editor(context: &MemoContext, file_path: &std::path::Path) -> MemoResult<()> {
+pub fn open_editor(context: &MemoContext, file_path: &Path) -> MemoResult<()> {
+    open_editor_with_files(context, &[file_path])
+}
+
+/// 1回のエディタ起動で全てのファイルを開く
+///
+/// 暗号化したメモは復号した一時ファイルを開き、保存されたら暗号化し直す
+pub fn open_editor_with_files<P: AsRef<Path>>(
+    context: &MemoContext,
+    file_paths: &[P],
+) -> MemoResult<()> {
+    let mut copies = Vec::new();
+    let mut editor_paths = Vec::new();
+    for path in file_paths.iter().map(AsRef::as_ref) {
+        if encryption::is_encrypted(path) {
+            let copy = DecryptedCopy::create(path)?;
+            editor_paths.push(copy.path().to_path_buf());
+            copies.push(copy);
+        } else {
+            editor_paths.push(path.to_path_buf());
+        }
+    }
+
     let status = Command::new(&context.editor)
-        .arg(file_path)
+        .args(&editor_paths)
         .status()
         .map_err(|e| {
             MemoError::EditorError(format!(
@@ -18,6 +42,9 @@ pub fn open_editor(context: &MemoContext, file_path: &std::path::Path) -> MemoRe
             "Editor exited with non-zero status".to_string(),
         ));
     }
+    for copy in &copies {
+        copy.save()?;
+    }
 
     Ok(())
 }
@@ -1,26 +1,106 @@
-use crate::error::{MemoError, MemoResult};
+use crate::encryption;
+use crate::error::{IoResultExt, MemoError, MemoResult};
 use crate::memo_id::MemoId;
+use chrono::{DateTime, Local};
+use std::fs;
 use std::path::{Path, PathBuf};
 
-/// MemoIDを使ってファイルパスを解決（14桁の完全IDのみサポート）
+/// MemoIDを使ってファイルパスを解決
+///
+/// - 14桁の完全ID
+/// - 短縮ID: `hhmmss`（今日）、`DDhhmmss`（今月）、`MMDDhhmmss`（今年）
+///
+/// 短縮IDのメモが今日・今月・今年に無い場合や、それ以外の桁数（4桁以上）の数字は、
+/// IDの先頭か末尾が一致するメモを探す。
+/// 複数見つかった場合は候補を `MemoError::AmbiguousId` で返す。
 pub fn resolve_memo_id<P: AsRef<Path>>(memo_dir: P, id: &str) -> MemoResult<PathBuf> {
-    let memo_dir = memo_dir.as_ref();
+    resolve_memo_id_at(memo_dir.as_ref(), id, Local::now())
+}
+
+fn resolve_memo_id_at(memo_dir: &Path, id: &str, now: DateTime<Local>) -> MemoResult<PathBuf> {
+    if !is_partial_id(id) {
+        let memo_id = MemoId::from_str(id)?;
+        return existing_memo_path(&memo_id.to_file_path(memo_dir))
+            .ok_or_else(|| MemoError::MemoNotFound(id.to_string()));
+    }
+
+    // 足りない上位の桁を現在日時で補う
+    if matches!(id.len(), 6 | 8 | 10) {
+        let now = now.format("%Y%m%d%H%M%S").to_string();
+        let full_id = format!("{}{}", &now[..now.len() - id.len()], id);
+        if let Ok(memo_id) = MemoId::from_str(&full_id)
+            && let Some(file_path) = existing_memo_path(&memo_id.to_file_path(memo_dir))
+        {
+            return Ok(file_path);
+        }
+    }
 
-    // 14桁の完全IDのみサポート
-    let memo_id = MemoId::from_str(id)?;
-    let file_path = memo_id.to_file_path(memo_dir);
+    let mut matches = Vec::new();
+    collect_matching_ids(memo_dir, id, &mut matches)?;
+    // 新しい順
+    matches.sort_by(|a, b| b.0.cmp(&a.0));
+    match matches.len() {
+        0 => Err(MemoError::MemoNotFound(id.to_string())),
+        1 => Ok(matches.remove(0).1),
+        _ => Err(MemoError::AmbiguousId(
+            matches.into_iter().map(|(id, _)| id).collect(),
+        )),
+    }
+}
 
-    if file_path.exists() {
-        Ok(file_path)
-    } else {
-        Err(MemoError::MemoNotFound(id.to_string()))
+/// `<id>.md` か、暗号化した `<id>.md.age` のある方
+fn existing_memo_path(path: &Path) -> Option<PathBuf> {
+    if path.exists() {
+        return Some(path.to_path_buf());
     }
+    let encrypted = encryption::encrypted_path(path);
+    encrypted.exists().then_some(encrypted)
+}
+
+/// 完全IDではない4桁以上の数字
+fn is_partial_id(id: &str) -> bool {
+    (4..14).contains(&id.len()) && id.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// 先頭か末尾が `part` と一致するID（`.archive` などの隠しディレクトリは見ない）
+fn collect_matching_ids(
+    dir: &Path,
+    part: &str,
+    matches: &mut Vec<(String, PathBuf)>,
+) -> MemoResult<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir).with_path(dir)? {
+        let path = entry.with_path(dir)?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_matching_ids(&path, part, matches)?;
+        } else if let Some(stem) = name
+            .strip_suffix(".md")
+            .or_else(|| name.strip_suffix(encryption::ENCRYPTED_SUFFIX))
+            && stem.len() == 14
+            && (stem.starts_with(part) || stem.ends_with(part))
+            && MemoId::from_str(stem).is_ok()
+        {
+            matches.push((stem.to_string(), path));
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use chrono::TimeZone;
     use tempfile::TempDir;
 
     fn setup_test_memo_structure() -> (TempDir, PathBuf) {
@@ -73,12 +153,85 @@ mod tests {
     fn test_resolve_invalid_id_format() {
         let (_temp_dir, memo_dir) = setup_test_memo_structure();
 
-        // 短縮IDは無効
-        let result = resolve_memo_id(&memo_dir, "143022");
-        assert!(result.is_err());
+        // 3桁以下は探さない
+        let result = resolve_memo_id(&memo_dir, "022");
+        assert!(matches!(result, Err(MemoError::InvalidId(_))));
 
         // 無効な形式
         let result = resolve_memo_id(&memo_dir, "invalid");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_resolve_short_id_relative_to_today() {
+        let (_temp_dir, memo_dir) = setup_test_memo_structure();
+        // 前の年にも同じ日時のメモがある
+        let other = memo_dir.join("2024-01/30");
+        fs::create_dir_all(&other).unwrap();
+        fs::write(other.join("20240130143022.md"), "Last year").unwrap();
+
+        let now = Local.with_ymd_and_hms(2025, 1, 30, 18, 0, 0).unwrap();
+        for id in ["143022", "30143022", "0130143022"] {
+            let path = resolve_memo_id_at(&memo_dir, id, now).unwrap();
+            assert!(path.ends_with("2025-01/30/20250130143022.md"), "{}", id);
+        }
+    }
+
+    #[test]
+    fn test_resolve_short_id_other_day() {
+        let (_temp_dir, memo_dir) = setup_test_memo_structure();
+
+        let now = Local.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let path = resolve_memo_id_at(&memo_dir, "151545", now).unwrap();
+        assert!(path.ends_with("2025-01/30/20250130151545.md"));
+
+        assert!(matches!(
+            resolve_memo_id_at(&memo_dir, "999999", now),
+            Err(MemoError::MemoNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_prefix_and_suffix() {
+        let (_temp_dir, memo_dir) = setup_test_memo_structure();
+
+        let path = resolve_memo_id(&memo_dir, "2025013015").unwrap();
+        assert!(path.ends_with("20250130151545.md"));
+        let path = resolve_memo_id(&memo_dir, "3022").unwrap();
+        assert!(path.ends_with("20250130143022.md"));
+        let path = resolve_memo_id(&memo_dir, "30151545").unwrap();
+        assert!(path.ends_with("20250130151545.md"));
+
+        match resolve_memo_id(&memo_dir, "20250130") {
+            Err(MemoError::AmbiguousId(candidates)) => {
+                assert_eq!(candidates, vec!["20250130151545", "20250130143022"]);
+            }
+            other => panic!("Expected AmbiguousId, got {:?}", other),
+        }
+        assert!(matches!(
+            resolve_memo_id(&memo_dir, "20240130"),
+            Err(MemoError::MemoNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_short_id_ambiguous() {
+        let (_temp_dir, memo_dir) = setup_test_memo_structure();
+        let other = memo_dir.join("2025-02/15");
+        fs::create_dir_all(&other).unwrap();
+        fs::write(other.join("20250215143022.md"), "Another day").unwrap();
+
+        let now = Local.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        match resolve_memo_id_at(&memo_dir, "143022", now) {
+            Err(MemoError::AmbiguousId(candidates)) => {
+                assert_eq!(candidates, vec!["20250215143022", "20250130143022"]);
+            }
+            other => panic!("Expected AmbiguousId, got {:?}", other),
+        }
+
+        // 今日のメモがあればそれを優先
+        let now = Local.with_ymd_and_hms(2025, 2, 15, 9, 0, 0).unwrap();
+        let path = resolve_memo_id_at(&memo_dir, "143022", now).unwrap();
+        assert!(path.ends_with("2025-02/15/20250215143022.md"));
+    }
 }
@@ -1,3 +1,5 @@
+pub mod datetime;
 pub mod editor;
 pub mod id_resolver;
+pub mod text;
 pub mod xdg;
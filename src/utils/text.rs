@@ -0,0 +1,71 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ELLIPSIS: &str = "…";
+
+/// Take at most `max` grapheme clusters, returning `None` if nothing was cut
+///
+/// 絵文字のZWJシーケンスや結合文字を途中で切らない
+pub fn truncate_graphemes(text: &str, max: usize) -> Option<&str> {
+    text.grapheme_indices(true)
+        .nth(max)
+        .map(|(index, _)| &text[..index])
+}
+
+/// Columns the text occupies in a terminal (CJK and emoji count as 2)
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+/// Fit the text into `width` columns, ending with `…` if it was cut
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+
+    let budget = width.saturating_sub(display_width(ELLIPSIS));
+    let mut used = 0;
+    let mut truncated = String::new();
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = display_width(grapheme);
+        if used + grapheme_width > budget {
+            break;
+        }
+        used += grapheme_width;
+        truncated.push_str(grapheme);
+    }
+    if width > 0 {
+        truncated.push_str(ELLIPSIS);
+    }
+    truncated
+}
+
+/// Pad the text with spaces up to `width` columns
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_graphemes_keeps_clusters() {
+        // 家族の絵文字（ZWJシーケンス）と結合文字
+        let text = "a👨‍👩‍👧b\u{0065}\u{0301}c";
+        assert_eq!(truncate_graphemes(text, 2), Some("a👨‍👩‍👧"));
+        assert_eq!(truncate_graphemes(text, 4), Some("a👨‍👩‍👧be\u{0301}"));
+        assert_eq!(truncate_graphemes(text, 5), None);
+    }
+
+    #[test]
+    fn test_truncate_to_width_cjk() {
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(truncate_to_width("日本語のメモ", 7), "日本語…");
+        assert_eq!(truncate_to_width("日本語のメモ", 12), "日本語のメモ");
+        // 全角文字の途中では切らない
+        assert_eq!(truncate_to_width("日本語", 4), "日…");
+        assert_eq!(pad_to_width("日本", 6), "日本  ");
+    }
+}
@@ -21,7 +21,7 @@ pub fn get_memo_dir_with_override(override_dir: Option<PathBuf>) -> MemoResult<P
     }
 
     let home_dir = dirs::home_dir().ok_or_else(|| {
-        MemoError::Io(std::io::Error::new(
+        MemoError::from(std::io::Error::new(
             std::io::ErrorKind::NotFound,
             "Could not determine home directory",
         ))
@@ -30,6 +30,25 @@ pub fn get_memo_dir_with_override(override_dir: Option<PathBuf>) -> MemoResult<P
     Ok(home_dir.join(".local").join("share").join("memo"))
 }
 
+/// Get the memo config file path following XDG Base Directory specification
+/// `XDG_CONFIG_HOME/memo/config.toml` or `~/.config/memo/config.toml` if not set.
+pub fn get_config_path() -> MemoResult<PathBuf> {
+    if let Ok(xdg_config_home) = env::var("XDG_CONFIG_HOME") {
+        return Ok(PathBuf::from(xdg_config_home)
+            .join("memo")
+            .join("config.toml"));
+    }
+
+    let home_dir = dirs::home_dir().ok_or_else(|| {
+        MemoError::from(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Could not determine home directory",
+        ))
+    })?;
+
+    Ok(home_dir.join(".config").join("memo").join("config.toml"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,88 @@
+//! Saved searches ("views") kept in the `[views]` table of the config file.
+//!
+//! ```toml
+//! [views]
+//! open = "tags:/@todo AND NOT tags:/@done"
+//! ```
+//!
+//! `memo view open` runs the query like `memo search`, and `memo search --view open deploy`
+//! narrows it down with more terms.
+
+use crate::config::Config;
+use crate::error::{MemoError, MemoResult};
+
+/// `memo view` のサブコマンドと重なるので付けられない名前
+const RESERVED: [&str; 2] = ["save", "delete"];
+
+/// 設定ファイルのキーに使える名前か
+pub fn validate_name(name: &str) -> MemoResult<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(MemoError::InvalidArgument(format!(
+            "invalid view name '{}' (use letters, digits, '-' and '_')",
+            name
+        )));
+    }
+    if RESERVED.contains(&name) {
+        return Err(MemoError::InvalidArgument(format!(
+            "'{}' is a `memo view` subcommand and cannot be a view name",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// 保存したクエリ
+pub fn query<'a>(config: &'a Config, name: &str) -> MemoResult<&'a str> {
+    config.views.get(name).map(String::as_str).ok_or_else(|| {
+        MemoError::InvalidArgument(format!(
+            "unknown view '{}' (save it with `memo view save {} \"<query>\"`)",
+            name, name
+        ))
+    })
+}
+
+/// ビューのクエリに `extra` の条件を加える（空なら元のまま）
+pub fn combine(view_query: &str, extra: &str) -> String {
+    if extra.trim().is_empty() {
+        view_query.to_string()
+    } else {
+        format!("({}) AND ({})", view_query, extra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let config =
+            Config::parse("[views]\nopen = \"tags:/@todo AND NOT tags:/@done\"\n").unwrap();
+        assert_eq!(
+            query(&config, "open").unwrap(),
+            "tags:/@todo AND NOT tags:/@done"
+        );
+        assert!(matches!(
+            query(&config, "closed"),
+            Err(MemoError::InvalidArgument(_))
+        ));
+
+        assert_eq!(combine("tag:@work", " "), "tag:@work");
+        assert_eq!(
+            combine("tag:@work", "deploy -draft"),
+            "(tag:@work) AND (deploy -draft)"
+        );
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("open-work_2").is_ok());
+        for name in ["", "open work", "a.b", "save", "delete"] {
+            assert!(validate_name(name).is_err(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Webhooks fired on memo lifecycle events, configured in config.toml:
+//!
+//! ```toml
+//! [[webhook]]
+//! url = "https://hooks.slack.com/services/..."
+//! events = ["create", "archive"]   # 省略時はすべてのイベント
+//! ```
+//!
+//! Each hook receives a `POST` with `{"event": "...", "memo": {...}}`.
+
+use crate::config::Config;
+use crate::memo::{MemoFile, yaml_to_json_value};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 送信が詰まってもコマンドを長く止めない
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookEvent {
+    Create,
+    Edit,
+    Archive,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// 空ならすべてのイベント
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+}
+
+impl WebhookConfig {
+    pub fn accepts(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    pub memo: WebhookMemo,
+}
+
+/// Memo metadata sent to webhooks (the body is not included)
+#[derive(Debug, Serialize)]
+pub struct WebhookMemo {
+    pub id: String,
+    pub path: String,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub created_at: String,
+    pub modified: String,
+    pub metadata: serde_json::Value,
+}
+
+impl WebhookPayload {
+    pub fn new(event: WebhookEvent, memo: &MemoFile) -> Self {
+        Self {
+            event,
+            memo: WebhookMemo {
+                id: memo.id.as_str(),
+                path: memo.path.to_string_lossy().to_string(),
+                title: memo.title(),
+                tags: memo.tags().into_iter().map(str::to_string).collect(),
+                created_at: memo.id.get_datetime().to_rfc3339(),
+                modified: memo.modified.to_rfc3339(),
+                metadata: memo
+                    .metadata
+                    .as_ref()
+                    .map(yaml_to_json_value)
+                    .unwrap_or(serde_json::Value::Null),
+            },
+        }
+    }
+}
+
+/// Fire the configured webhooks for `event`
+///
+/// Webhooks are best-effort: failures are reported as warnings and never fail the command.
+pub fn notify(config: &Config, event: WebhookEvent, memos: &[MemoFile]) {
+    if memos.is_empty() {
+        return;
+    }
+    send(&config.webhook, event, memos);
+}
+
+pub fn send(hooks: &[WebhookConfig], event: WebhookEvent, memos: &[MemoFile]) {
+    let hooks: Vec<&WebhookConfig> = hooks.iter().filter(|h| h.accepts(event)).collect();
+    if hooks.is_empty() {
+        return;
+    }
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(TIMEOUT))
+        .build()
+        .into();
+    for memo in memos {
+        let body = match serde_json::to_string(&WebhookPayload::new(event, memo)) {
+            Ok(body) => body,
+            Err(e) => {
+                eprintln!("Warning: failed to encode webhook payload: {}", e);
+                continue;
+            }
+        };
+        for hook in &hooks {
+            if let Err(e) = agent
+                .post(&hook.url)
+                .header("Content-Type", "application/json")
+                .send(&body)
+            {
+                eprintln!("Warning: webhook {} failed: {}", hook.url, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// 1リクエストだけ受け取ってボディを返すHTTPサーバ
+    fn receive_one(listener: TcpListener) -> std::thread::JoinHandle<String> {
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            String::from_utf8(body).unwrap()
+        })
+    }
+
+    fn create_memo(dir: &std::path::Path) -> MemoFile {
+        let path = dir.join("2025-01/30/20250130143022.md");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "---\ntitle: Weekly sync\ntags: [\"@team\"]\n---\nbody",
+        )
+        .unwrap();
+        MemoFile::from_path(&path).unwrap()
+    }
+
+    #[test]
+    fn test_accepts() {
+        let all = WebhookConfig {
+            url: String::new(),
+            events: vec![],
+        };
+        let archive_only = WebhookConfig {
+            url: String::new(),
+            events: vec![WebhookEvent::Archive],
+        };
+        assert!(all.accepts(WebhookEvent::Edit));
+        assert!(archive_only.accepts(WebhookEvent::Archive));
+        assert!(!archive_only.accepts(WebhookEvent::Create));
+    }
+
+    #[test]
+    fn test_send_payload() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let memo = create_memo(temp_dir.path());
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = receive_one(listener);
+
+        let hooks = vec![
+            WebhookConfig {
+                url: "http://127.0.0.1:1/skipped".to_string(),
+                events: vec![WebhookEvent::Archive],
+            },
+            WebhookConfig {
+                url,
+                events: vec![WebhookEvent::Create],
+            },
+        ];
+        send(&hooks, WebhookEvent::Create, &[memo]);
+
+        let payload: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+        assert_eq!(payload["event"], "create");
+        assert_eq!(payload["memo"]["id"], "20250130143022");
+        assert_eq!(payload["memo"]["title"], "Weekly sync");
+        assert_eq!(payload["memo"]["tags"][0], "@team");
+        assert!(payload["memo"].get("content").is_none());
+    }
+}
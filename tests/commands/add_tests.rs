@@ -100,3 +100,100 @@ mod add_integration_tests {
         assert_output_contains(&search_output, "Content for search test");
     }
 }
+
+#[test]
+fn test_add_template_placeholders() {
+    let context = TestContext::new();
+    let templates_dir = context.memo_dir().join(".templates");
+    fs::create_dir_all(&templates_dir).unwrap();
+    fs::write(
+        templates_dir.join("daily.md"),
+        "# {{title}} {{date}}\nid: {{id}} {{unknown}}\n",
+    )
+    .unwrap();
+
+    let output = context.run_command(&["add", "--template", "daily", "--title", "Daily"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let id = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Memo created: "))
+        .unwrap();
+
+    let repo = memo::repository::MemoRepository::new(context.memo_context.clone());
+    let memo = repo.find_memo_by_id(id).unwrap();
+    let date = format!("{}-{}-{}", &id[..4], &id[4..6], &id[6..8]);
+    assert_eq!(
+        fs::read_to_string(&memo.path).unwrap(),
+        format!("# Daily {}\nid: {} {{{{unknown}}}}\n", date, id)
+    );
+
+    let output = context.run_command(&["add", "--title", "No template"]);
+    assert_command_failure(&output);
+}
+
+#[test]
+fn test_add_message_and_stdin_skip_editor() {
+    // エディタが起動すれば失敗する
+    let context = TestContext::with_editor(&mock_editor_fail());
+
+    let output = context.run_command(&["add", "-m", "# From flag\n@cron"]);
+    assert_command_success(&output);
+    // 同じ秒に作っても上書きしない
+    let output = context.run_command_with_input(&["add", "-"], "# From stdin\npiped\n");
+    assert_command_success(&output);
+
+    let repo = memo::repository::MemoRepository::new(context.memo_context.clone());
+    let mut contents: Vec<String> = repo
+        .list_all_memos()
+        .unwrap()
+        .iter()
+        .map(|memo| fs::read_to_string(&memo.path).unwrap())
+        .collect();
+    contents.sort();
+    assert_eq!(
+        contents,
+        vec!["# From flag\n@cron\n", "# From stdin\npiped\n"]
+    );
+
+    // 検索インデックスにも入る
+    let output = context.run_command(&["search", "*", "--count"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2");
+
+    let output = context.run_command_with_input(&["add", "-"], "  \n");
+    assert_command_failure(&output);
+    assert_command_error(&output, "empty");
+}
+
+#[test]
+fn test_add_in_same_second_uses_next_id() {
+    let context = TestContext::new();
+    let templates_dir = context.memo_dir().join(".templates");
+    fs::create_dir_all(&templates_dir).unwrap();
+    fs::write(templates_dir.join("id.md"), "id: {{id}}\n").unwrap();
+
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let output = context.run_command(&["add", "--template", "id", "-m", "body"]);
+        assert_command_success(&output);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let id = stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("Memo created: "))
+            .unwrap()
+            .to_string();
+        ids.push(id);
+    }
+
+    let repo = memo::repository::MemoRepository::new(context.memo_context.clone());
+    for id in &ids {
+        // テンプレートの {{id}} もずらした ID になる
+        let memo = repo.find_memo_by_id(id).unwrap();
+        assert_eq!(
+            fs::read_to_string(&memo.path).unwrap(),
+            format!("id: {}\nbody\n", id)
+        );
+    }
+    ids.dedup();
+    assert_eq!(ids.len(), 3);
+}
@@ -0,0 +1,46 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+
+#[test]
+fn test_alias_expansion() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+    context.write_config(
+        r#"
+[alias]
+ls = "list --table"
+"#,
+    );
+
+    let output = context.run_command(&["ls"]);
+
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("ID"));
+    assert!(stdout.contains("20250130143022"));
+}
+
+#[test]
+fn test_alias_with_extra_args() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+    context.write_config("[alias]\nj = \"list\"\n");
+
+    let output = context.run_command(&["j", "--json"]);
+
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_valid_json(stdout.trim());
+}
+
+#[test]
+fn test_invalid_config() {
+    let context = TestContext::new();
+
+    context.write_config("[alias\n");
+
+    let output = context.run_command(&["list"]);
+
+    assert_command_error(&output, "Config error");
+}
@@ -0,0 +1,97 @@
+use crate::utils::{TestContext, assertions::*};
+use memo::MemoApp;
+use memo::api::{self, MemoFilter};
+
+#[test]
+fn test_api_create_get_and_search() {
+    let context = TestContext::new();
+    let ctx = &context.memo_context;
+
+    let memo = api::create_memo(ctx, "---\ntags: [\"@todo\"]\n---\n# Library memo\n").unwrap();
+    assert!(memo.path.starts_with(context.memo_dir()));
+
+    let found = api::get_memo(ctx, &memo.id.to_string()).unwrap();
+    assert_eq!(found.path, memo.path);
+    assert_eq!(found.tags(), vec!["@todo"]);
+
+    let results = api::search(ctx, "*").unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].memo.id, memo.id);
+}
+
+#[test]
+fn test_api_list_memos_filter() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130100000.md",
+        "---\ntags: [\"@todo\"]\npriority: 3\n---\n# A\n",
+    );
+    context.create_memo(
+        "2025-01/30/20250130110000.md",
+        "---\ntags: [\"@todo\"]\npriority: 1\n---\n# B\n",
+    );
+    context.create_memo("2025-01/30/20250130120000.md", "# C\n");
+    let ctx = &context.memo_context;
+
+    assert_eq!(
+        api::list_memos(ctx, &MemoFilter::default()).unwrap().len(),
+        3
+    );
+
+    let todo = api::list_memos(ctx, &MemoFilter::default().with_tag("@todo")).unwrap();
+    assert_eq!(todo.len(), 2);
+
+    let urgent = MemoFilter::default().with_tag("@todo").with_min_priority(2);
+    let urgent = api::list_memos(ctx, &urgent).unwrap();
+    assert_eq!(urgent.len(), 1);
+    assert_eq!(urgent[0].id.to_string(), "20250130100000");
+}
+
+#[test]
+fn test_api_archive() {
+    let context = TestContext::new();
+    let ctx = &context.memo_context;
+    let memo = api::create_memo(ctx, "# To archive\n").unwrap();
+    let relative = memo.id.to_relative_path();
+
+    let archived = api::archive(ctx, &memo.id.to_string()).unwrap();
+    assert!(archived.path.starts_with(context.archive_dir()));
+    assert_memo_not_exists(&context, relative.to_str().unwrap());
+    assert_memo_archived(&context, relative.to_str().unwrap());
+
+    assert!(api::search(ctx, "*").unwrap().is_empty());
+    assert!(api::get_memo(ctx, &memo.id.to_string()).is_err());
+}
+
+#[test]
+fn test_memo_app_facade() {
+    let context = TestContext::new();
+    let app = MemoApp::builder()
+        .memo_dir(context.memo_dir())
+        .build()
+        .unwrap();
+    assert_eq!(app.context().memo_dir, context.memo_dir());
+
+    let memo = app
+        .create("---\ntags: [\"@todo\"]\n---\n# Facade memo\n")
+        .unwrap();
+    assert_eq!(app.get(&memo.id.to_string()).unwrap().path, memo.path);
+    assert_eq!(
+        app.list(&MemoFilter::default().with_tag("@todo"))
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(app.search("Facade").unwrap().len(), 1);
+
+    app.archive(&memo.id.to_string()).unwrap();
+    assert!(app.list(&MemoFilter::default()).unwrap().is_empty());
+
+    assert!(
+        MemoApp::builder()
+            .memo_dir(context.memo_dir())
+            .notebook("work")
+            .build()
+            .is_err()
+    );
+}
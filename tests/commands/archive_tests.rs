@@ -150,6 +150,54 @@ fn test_archive_empty_directory() {
     assert_command_success(&output);
 }
 
+#[test]
+fn test_restore() {
+    let context = TestContext::new();
+    setup_test_memos(&context);
+    context.build_search_index().unwrap();
+
+    let output = context.run_command(&["archive", "20250130143022", "20250130151545"]);
+    assert_command_success(&output);
+
+    let output = context.run_command(&["restore", "20250130143022"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Restored 1 memo(s)");
+
+    assert_memo_exists(&context, "2025-01/30/20250130143022.md");
+    assert!(!context.archive_dir().join("2025-01/30/20250130143022.md").exists());
+    assert_memo_archived(&context, "2025-01/30/20250130151545.md");
+
+    // 検索インデックスにも戻る
+    let output = context.run_command(&["search", "*"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("20250130143022"));
+    assert!(!stdout.contains("20250130151545"));
+}
+
+#[test]
+fn test_restore_destination_exists() {
+    let context = TestContext::new();
+    setup_test_memos(&context);
+
+    let output = context.run_command(&["archive", "20250130143022"]);
+    assert_command_success(&output);
+    context.create_memo("2025-01/30/20250130143022.md", "# Newer memo");
+
+    let output = context.run_command(&["restore", "20250130143022"]);
+    assert_command_failure(&output);
+    assert_command_error(&output, "already exists");
+    assert!(context.archive_dir().join("2025-01/30/20250130143022.md").exists());
+}
+
+#[test]
+fn test_restore_not_archived() {
+    let context = TestContext::new();
+    setup_test_memos(&context);
+
+    let output = context.run_command(&["restore", "20250130143022"]);
+    assert_command_failure(&output);
+}
+
 #[cfg(test)]
 mod archive_integration_tests {
     use super::*;
@@ -173,6 +221,42 @@ mod archive_integration_tests {
         assert!(!stdout.contains("143022"));
     }
 
+    #[test]
+    fn test_archive_then_search_workflow() {
+        let context = TestContext::new();
+        setup_test_memos(&context);
+        context.build_search_index().unwrap();
+
+        let count = |context: &TestContext| {
+            let output = context.run_command(&["search", "*", "--count"]);
+            assert_command_success(&output);
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        assert_eq!(count(&context), "4");
+
+        // ID・ファイルパス・ディレクトリのどれで指定してもインデックスから外れる
+        let output = context.run_command(&["archive", "20250130143022"]);
+        assert_command_success(&output);
+        assert_eq!(count(&context), "3");
+
+        let output = context.run_command(&["archive", "2025-01/30/20250130151545.md"]);
+        assert_command_success(&output);
+        assert_eq!(count(&context), "2");
+
+        let output = context.run_command(&["archive", "2025-01/29/"]);
+        assert_command_success(&output);
+        assert_eq!(count(&context), "1");
+
+        let output = context.run_command(&["search", "*"]);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("20250130090000"));
+        assert!(!stdout.contains("20250129120000"));
+
+        let output = context.run_command(&["restore", "20250129120000"]);
+        assert_command_success(&output);
+        assert_eq!(count(&context), "2");
+    }
+
     #[test]
     fn test_archive_multiple_workflows() {
         let context = TestContext::new();
@@ -193,3 +277,117 @@ mod archive_integration_tests {
         assert_memo_archived(&context, "2025-01/29/20250129120000.md");
     }
 }
+
+#[test]
+fn test_archive_dry_run() {
+    let context = TestContext::new();
+    setup_test_memos(&context);
+    fs::create_dir_all(context.memo_dir().join("2025-01/30/143022")).unwrap();
+
+    let output = context.run_command(&["archive", "2025-01/30", "--dry-run"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let before = context.memo_dir().join("2025-01/30/20250130143022.md");
+    let after = context.archive_dir().join("2025-01/30/20250130143022.md");
+    assert!(stdout.contains(&format!("{} -> {}", before.display(), after.display())));
+    assert!(stdout.contains(&format!(
+        "{} -> {}",
+        context.memo_dir().join("2025-01/30/143022").display(),
+        context.archive_dir().join("2025-01/30/143022").display()
+    )));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("3 memo(s) would be archived"));
+
+    // 何も動かさない
+    assert_memo_exists(&context, "2025-01/30/20250130143022.md");
+    assert!(!context.archive_dir().exists());
+
+    let output = context.run_command(&["delete", "20250129120000", "--permanent", "--dry-run"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "(removed)");
+    assert_memo_exists(&context, "2025-01/29/20250129120000.md");
+
+    context.run_command(&["archive", "20250129120000"]);
+    let output = context.run_command(&["restore", "20250129120000", "--dry-run"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "2025-01/29/20250129120000.md -> ");
+    assert_memo_archived(&context, "2025-01/29/20250129120000.md");
+}
+
+#[test]
+fn test_archive_list() {
+    let context = TestContext::new();
+    setup_test_memos(&context);
+
+    let output = context.run_command(&["archive", "--list", "--format", "json"]);
+    assert_command_success(&output);
+    assert!(output.stdout.is_empty());
+
+    assert_command_success(&context.run_command(&["archive", "20250129120000", "20250130143022"]));
+    let output = context.run_command(&["archive", "--list", "--format", "json"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let ids: Vec<String> = stdout
+        .lines()
+        .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(ids, vec!["20250130143022", "20250129120000"]);
+
+    let output = context.run_command(&["archive", "--list"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Archived memos");
+
+    assert_command_failure(&context.run_command(&["archive", "--list", "20250130151545"]));
+}
+
+#[test]
+fn test_archive_older_than() {
+    let context = TestContext::new();
+    context.create_memo("2024-01/05/20240105090000.md", "# Old 1");
+    context.create_memo("2024-01/20/20240120090000.md", "# Old 2");
+    context.create_memo("2024-03/01/20240301090000.md", "# Old 3");
+    assert_command_success(&context.run_command(&["add", "-m", "# Recent"]));
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["archive", "--older-than", "30d", "--dry-run"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "  2024-01: 2\n  2024-03: 1\n");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("3 memo(s) would be archived"));
+    assert_memo_exists(&context, "2024-01/05/20240105090000.md");
+
+    let output = context.run_command(&["archive", "--older-than", "30d"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Archived 3 memo(s) created before ");
+    assert_memo_archived(&context, "2024-01/05/20240105090000.md");
+    assert_memo_archived(&context, "2024-03/01/20240301090000.md");
+
+    // 索引からも外れる
+    let output = context.run_command(&["search", "*", "--count"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1");
+
+    let output = context.run_command(&["archive", "--older-than", "30d"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "No memos created before ");
+}
+
+#[test]
+fn test_archive_older_than_config_policy() {
+    let context = TestContext::new();
+    context.create_memo("2024-01/05/20240105090000.md", "# Old");
+
+    let output = context.run_command(&["archive", "--older-than"]);
+    assert_command_error(&output, "[archive] older_than");
+    assert_command_error(
+        &context.run_command(&["archive", "--older-than", "3 months"]),
+        "invalid age",
+    );
+
+    context.write_config("[archive]\nolder_than = \"30d\"\nby = \"modified\"\n");
+    // 作成は古くても、更新したばかりのメモは残る
+    let output = context.run_command(&["archive", "--older-than"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "No memos modified before ");
+
+    let output = context.run_command(&["archive", "--older-than", "--by", "created"]);
+    assert_command_success(&output);
+    assert_memo_archived(&context, "2024-01/05/20240105090000.md");
+}
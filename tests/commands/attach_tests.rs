@@ -0,0 +1,87 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+use std::fs;
+
+const MEMO: &str = "2025-01/30/20250130143022.md";
+
+fn attach_file(context: &TestContext, name: &str) -> String {
+    let file = context.memo_dir().parent().unwrap().join(name);
+    fs::write(&file, format!("contents of {}", name)).unwrap();
+    file.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_attach_copies_file_and_records_it() {
+    let context = TestContext::new();
+    context.create_memo(MEMO, TestMemoTemplates::BASIC);
+    let file = attach_file(&context, "photo.png");
+
+    let output = context.run_command(&["attach", "20250130143022", &file, &file]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Attached photo.png to 20250130143022");
+    assert_output_contains(&output, "Attached photo-1.png to 20250130143022");
+
+    let assets_dir = context.memo_dir().join("2025-01/30/143022");
+    assert_eq!(
+        fs::read_to_string(assets_dir.join("photo.png")).unwrap(),
+        "contents of photo.png"
+    );
+    assert!(assets_dir.join("photo-1.png").exists());
+    let content = fs::read_to_string(context.memo_dir().join(MEMO)).unwrap();
+    assert!(content.contains("attachments: [\"photo.png\", \"photo-1.png\"]"));
+
+    let output = context.run_command(&["show", "20250130143022"]);
+    assert_output_contains(&output, "Attachments:");
+    assert_output_contains(&output, &assets_dir.join("photo.png").to_string_lossy());
+
+    let output = context.run_command(&["list", "--json"]);
+    let record = assert_valid_json(String::from_utf8_lossy(&output.stdout).trim());
+    assert_eq!(record["attachments"].as_array().unwrap().len(), 2);
+
+    // 添付ディレクトリの中はメモとして数えない
+    assert_eq!(
+        String::from_utf8_lossy(&context.run_command(&["list", "--json"]).stdout)
+            .lines()
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_attach_errors() {
+    let context = TestContext::new();
+    context.create_memo(MEMO, TestMemoTemplates::BASIC);
+
+    let output = context.run_command(&["attach", "20250130143022", "/nonexistent/file.png"]);
+    assert_command_failure(&output);
+    assert!(!context.memo_dir().join("2025-01/30/143022").exists());
+
+    let file = attach_file(&context, "photo.png");
+    let output = context.run_command(&["attach", "20250101000000", &file]);
+    assert_command_failure(&output);
+}
+
+#[test]
+fn test_archive_and_restore_carry_attachments() {
+    let context = TestContext::new();
+    context.create_memo(MEMO, TestMemoTemplates::BASIC);
+    let file = attach_file(&context, "notes.md");
+    assert_command_success(&context.run_command(&["attach", "20250130143022", &file]));
+
+    assert_command_success(&context.run_command(&["archive", "20250130143022"]));
+    assert!(!context.memo_dir().join("2025-01/30/143022").exists());
+    assert!(
+        context
+            .archive_dir()
+            .join("2025-01/30/143022/notes.md")
+            .exists()
+    );
+
+    assert_command_success(&context.run_command(&["restore", "20250130143022"]));
+    assert!(
+        context
+            .memo_dir()
+            .join("2025-01/30/143022/notes.md")
+            .exists()
+    );
+    assert!(!context.archive_dir().join("2025-01/30/143022").exists());
+}
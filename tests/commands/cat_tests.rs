@@ -0,0 +1,42 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_cat_memos_in_order() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130150000.md",
+        "---\ntitle: Afternoon\n---\n# Afternoon\nreview",
+    );
+    context.create_memo("2025-01/30/20250130090000.md", "# Morning\nstandup\n");
+    context.create_memo("2025-01/31/20250131090000.md", "# Next day");
+
+    let output = context.run_command(&["cat", "20250130150000", "20250130090000"]);
+    assert_command_success(&output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "# Morning\nstandup\n\n# Afternoon\nreview\n"
+    );
+
+    let output = context.run_command(&["cat", "--date", "2025-01-30", "--separator", "---"]);
+    assert_command_success(&output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "# Morning\nstandup\n---\n# Afternoon\nreview\n"
+    );
+}
+
+#[test]
+fn test_cat_errors() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130090000.md", "memo");
+
+    assert_command_failure(&context.run_command(&["cat"]));
+    assert_command_error(
+        &context.run_command(&["cat", "--date", "2025-02-01"]),
+        "no memos on 2025-02-01",
+    );
+    assert_command_error(
+        &context.run_command(&["cat", "20250130090000", "--date", "someday"]),
+        "invalid date",
+    );
+}
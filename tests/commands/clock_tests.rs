@@ -0,0 +1,61 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_clock_in_out_and_timesheet() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntitle: Write report\ntags: [\"@work\"]\n---\nbody",
+    );
+
+    let output = context.run_command(&["clock", "in", "20250130143022"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Clocked in: 20250130143022");
+
+    let output = context.run_command(&["clock", "in", "20250130143022"]);
+    assert_command_error(&output, "already clocked in to 20250130143022");
+
+    let output = context.run_command(&["clock", "out"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Clocked out: 20250130143022 (0m)");
+
+    let content =
+        std::fs::read_to_string(context.memo_dir().join("2025-01/30/20250130143022.md")).unwrap();
+    assert!(content.contains("body\n- clock: "));
+
+    let output = context.run_command(&["timesheet"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "No clock entries found.");
+}
+
+#[test]
+fn test_timesheet_report() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntitle: Write report\ntags: [\"@work\"]\n---\n\
+         - clock: 2025-01-30T09:00:00+09:00 -- 2025-01-30T10:30:00+09:00 (1h 30m)\n\
+         - clock: 2025-02-03T09:00:00+09:00 -- 2025-02-03T09:20:00+09:00 (20m)\n",
+    );
+
+    let output = context.run_command(&["timesheet"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(" 1h 50m  20250130143022  Write report"));
+    assert!(stdout.contains(" 1h 50m  @work"));
+    assert!(stdout.contains("Total: 1h 50m"));
+
+    let output = context.run_command(&["timesheet", "--since", "2025-02-01"]);
+    assert_output_contains(&output, "Total: 20m");
+
+    let output = context.run_command(&["timesheet", "--since", "last week"]);
+    assert_command_error(&output, "invalid date: last week");
+}
+
+#[test]
+fn test_clock_out_without_clock_in() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["clock", "out"]);
+    assert_command_error(&output, "not clocked in");
+}
@@ -0,0 +1,48 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+
+#[test]
+fn test_complete_memo_ids() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+    context.create_memo(
+        "2025-01/30/20250130151545.md",
+        TestMemoTemplates::WITH_FRONT_MATTER,
+    );
+
+    let output = context.run_command(&["__complete", "show", "202501301"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut ids: Vec<&str> = stdout.lines().collect();
+    ids.sort();
+    assert_eq!(ids, vec!["20250130143022", "20250130151545"]);
+
+    let output = context.run_command(&["__complete", "list", "--tag", "@f"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "@frontmatter\n");
+
+    // 隠しコマンドはヘルプに出さない
+    let output = context.run_command(&["help"]);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("__complete"));
+}
+
+#[test]
+fn test_completions_script() {
+    let context = TestContext::new();
+
+    for (shell, expected) in [
+        ("bash", "complete -F _memo_dynamic"),
+        ("zsh", "compdef _memo_dynamic memo"),
+        ("fish", "memo __complete $words[2..-1]"),
+    ] {
+        let output = context.run_command(&["completions", shell]);
+        assert_command_success(&output);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(expected), "{}: {}", shell, stdout);
+        // サブコマンドは clap_complete の生成部分で補完する
+        assert!(stdout.contains("archive"));
+    }
+
+    let output = context.run_command(&["completions", "tcsh"]);
+    assert_command_failure(&output);
+}
@@ -0,0 +1,76 @@
+use crate::utils::{TestContext, assertions::*};
+use std::fs;
+
+#[test]
+fn test_config_set_and_get() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+
+    let output = context.run_command(&["config", "set", "list.limit", "2"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Set list.limit = 2");
+    let config = fs::read_to_string(context.config_home().join("memo/config.toml")).unwrap();
+    assert!(config.contains("[list]\nlimit = 2"));
+
+    let output = context.run_command(&["config", "get", "list.limit"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2");
+
+    // --limit が無ければ設定の件数になる
+    let output = context.run_command(&["list", "--json"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 2);
+    let output = context.run_command(&["list", "--json", "--limit", "3"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 3);
+
+    let output = context.run_command(&["config"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "list.limit = 2");
+    assert_output_contains(&output, "editor = echo");
+}
+
+#[test]
+fn test_config_overrides_environment() {
+    let context = TestContext::new();
+    let other_dir = context.temp_dir.path().join("notes");
+    context.write_config(&format!(
+        "editor = \"nano\"\nmemo_dir = \"{}\"\ndate_format = \"%Y|%m\"\n",
+        other_dir.display()
+    ));
+
+    let output = context.run_command(&["config", "get", "editor"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "nano");
+
+    let output = context.run_command(&["dir"]);
+    assert_command_success(&output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        other_dir.display().to_string()
+    );
+
+    fs::create_dir_all(other_dir.join("2025-01/30")).unwrap();
+    fs::write(
+        other_dir.join("2025-01/30/20250130143022.md"),
+        "# Elsewhere",
+    )
+    .unwrap();
+    let year = chrono::Local::now().format("%Y").to_string();
+    let output = context.run_command(&["list"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Elsewhere");
+    assert_output_contains(&output, &format!("modified: {}|", year));
+}
+
+#[test]
+fn test_config_set_rejects_invalid_values() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["config", "set", "colour", "red"]);
+    assert_command_failure(&output);
+    assert_command_error(&output, "unknown setting 'colour'");
+
+    let output = context.run_command(&["config", "set", "index.heap_size", "1000"]);
+    assert_command_failure(&output);
+    assert_command_error(&output, "index.heap_size must be at least");
+    assert!(!context.config_home().join("memo/config.toml").exists());
+}
@@ -0,0 +1,59 @@
+use crate::utils::{TestContext, TestMemoTemplates};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::time::{Duration, Instant};
+
+fn connect(socket: &std::path::Path) -> UnixStream {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        match UnixStream::connect(socket) {
+            Ok(stream) => return stream,
+            Err(e) if Instant::now() > deadline => panic!("daemon did not start: {}", e),
+            Err(_) => std::thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+#[test]
+fn test_daemon_protocol() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+
+    let socket = context.memo_dir().join("test.sock");
+    let mut child = context.spawn_command(&["daemon", "--socket", socket.to_str().unwrap()]);
+
+    let stream = connect(&socket);
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request = |line: &str| -> serde_json::Value {
+        writeln!(&stream, "{}", line).unwrap();
+        let mut response = String::new();
+        reader.read_line(&mut response).unwrap();
+        serde_json::from_str(&response).unwrap()
+    };
+
+    let got = request(r#"{"method": "get", "id": "20250130143022"}"#);
+    assert_eq!(got["ok"], true);
+    assert_eq!(got["result"]["id"], "20250130143022");
+
+    let created = request(r##"{"method": "create", "content": "# From plugin\n"}"##);
+    assert_eq!(created["ok"], true);
+    let id = created["result"]["id"].as_str().unwrap().to_string();
+
+    let appended = request(&format!(
+        r#"{{"method": "append", "id": "{}", "text": "- appended"}}"#,
+        id
+    ));
+    assert_eq!(appended["ok"], true);
+    let content = std::fs::read_to_string(created["result"]["path"].as_str().unwrap()).unwrap();
+    assert!(content.contains("# From plugin\n- appended\n"));
+
+    let queried = request(r#"{"method": "query", "query": "plugin", "limit": 5}"#);
+    assert_eq!(queried["ok"], true);
+    assert!(queried["result"].is_array());
+
+    let invalid = request("not json");
+    assert_eq!(invalid["ok"], false);
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
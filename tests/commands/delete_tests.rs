@@ -0,0 +1,78 @@
+use crate::utils::{TestContext, assertions::*};
+use std::fs;
+
+#[test]
+fn test_delete_moves_to_trash() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    context.build_search_index().unwrap();
+
+    let output = context.run_command(&["delete", "20250130143022"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Moved 1 memo(s) to trash");
+
+    assert_memo_not_exists(&context, "2025-01/30/20250130143022.md");
+    let trashed = context
+        .memo_dir()
+        .join(".trash/2025-01/30/20250130143022.md");
+    assert!(trashed.exists());
+
+    let ignore_content = fs::read_to_string(context.memo_dir().join(".ignore")).unwrap();
+    assert!(ignore_content.lines().any(|line| line == ".trash"));
+
+    // 一覧・検索から消える
+    let output = context.run_command(&["list"]);
+    assert_command_success(&output);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("20250130143022"));
+
+    let output = context.run_command(&["search", "*"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("20250130151545"));
+    assert!(!stdout.contains("20250130143022"));
+}
+
+#[test]
+fn test_delete_permanent() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+
+    let output =
+        context.run_command(&["delete", "20250130143022", "20250129120000", "--permanent"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Permanently deleted 2 memo(s)");
+
+    assert_memo_not_exists(&context, "2025-01/30/20250130143022.md");
+    assert_memo_not_exists(&context, "2025-01/29/20250129120000.md");
+    assert!(!context.memo_dir().join(".trash").exists());
+    assert_memo_exists(&context, "2025-01/30/20250130151545.md");
+}
+
+#[test]
+fn test_delete_nonexistent() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+
+    let output = context.run_command(&["delete", "20250130143022", "20991231235959"]);
+    assert_command_failure(&output);
+    assert_memo_exists(&context, "2025-01/30/20250130143022.md");
+}
+
+#[test]
+fn test_delete_permanent_removes_attachments() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    let assets_dir = context.memo_dir().join("2025-01/30/143022");
+    fs::create_dir_all(&assets_dir).unwrap();
+    fs::write(assets_dir.join("photo.png"), "image").unwrap();
+
+    let output = context.run_command(&["delete", "20250130143022", "--permanent", "--dry-run"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, &format!("{} -> (removed)", assets_dir.display()));
+    assert!(assets_dir.join("photo.png").exists());
+
+    let output = context.run_command(&["delete", "20250130143022", "--permanent"]);
+    assert_command_success(&output);
+    assert_memo_not_exists(&context, "2025-01/30/20250130143022.md");
+    assert!(!assets_dir.exists());
+}
@@ -92,7 +92,7 @@ mod dir_integration_tests {
         // シェルコマンドでファイル数をカウント
         let ls_output = std::process::Command::new("sh")
             .arg("-c")
-            .arg(&format!("find '{}' -name '*.md' | wc -l", memo_dir_path))
+            .arg(format!("find '{}' -name '*.md' | wc -l", memo_dir_path))
             .output()
             .expect("Failed to execute shell command");
 
@@ -0,0 +1,31 @@
+use crate::utils::{TestContext, assertions::*};
+use std::fs;
+
+#[test]
+fn test_doctor_reports_and_fixes() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    context.build_search_index().unwrap();
+
+    let output = context.run_command(&["doctor"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "No problems found");
+
+    // 索引を残したままファイルを消す
+    fs::remove_file(context.memo_dir().join("2025-01/30/20250130143022.md")).unwrap();
+    context.create_memo("2025-01/30/draft.md", "# Draft");
+
+    let output = context.run_command(&["doctor"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "[fixable] index entry 20250130143022");
+    assert_output_contains(&output, "[manual]");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Run with --fix"));
+
+    let output = context.run_command(&["doctor", "--fix"]);
+    assert_command_success(&output);
+
+    let output = context.run_command(&["doctor"]);
+    assert_command_success(&output);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("[fixable]"));
+    assert_output_contains(&output, "draft.md");
+}
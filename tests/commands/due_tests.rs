@@ -0,0 +1,34 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_due_lists_arrived_memos() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntitle: Send report\ndue: 2025-01-31 09:00\n---\nbody",
+    );
+    context.create_memo(
+        "2025-01/29/20250129120000.md",
+        "---\ntitle: Far future\nremind: 2999-01-01\n---\nbody",
+    );
+    context.create_memo("2025-01/28/20250128090000.md", "no front matter");
+
+    let output = context.run_command(&["due"]);
+
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("20250130143022  due    2025-01-31 09:00:00  Send report"));
+    assert!(!stdout.contains("Far future"));
+    assert!(!stdout.contains("20250128090000"));
+}
+
+#[test]
+fn test_due_nothing() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", "plain");
+
+    let output = context.run_command(&["due"]);
+
+    assert_command_success(&output);
+    assert_output_contains(&output, "No memos are due.");
+}
@@ -10,7 +10,14 @@ fn test_edit_existing_memo() {
 
     let output = context.run_command(&["edit", "20250130143022"]);
     assert_command_success(&output);
-    assert_output_contains(&output, "Memo edited: 20250130143022");
+
+    // エディタで変更していないので何も更新されない
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Memo edited"));
+
+    let memo_path = context.memo_dir().join("2025-01/30/20250130143022.md");
+    assert_eq!(fs::read_to_string(&memo_path).unwrap(), "Original content");
+    assert!(!context.index_dir().exists());
 }
 
 #[test]
@@ -23,6 +30,7 @@ fn test_edit_with_content_modification() {
 
     let output = context.run_command(&["edit", "20250130143022"]);
     assert_command_success(&output);
+    assert_output_contains(&output, "Memo edited: 20250130143022");
 
     // ファイル内容が更新されていることを確認
     let memo_path = context.memo_dir().join("2025-01/30/20250130143022.md");
@@ -30,6 +38,25 @@ fn test_edit_with_content_modification() {
     assert!(content.contains("Modified content by editor"));
 }
 
+#[test]
+fn test_edit_sets_updated_at() {
+    let script_path = create_mock_editor_script("---\ntitle: Edited\n---\nBody");
+    let context = TestContext::with_editor(script_path.to_str().unwrap());
+
+    context.create_memo("2025-01/30/20250130143022.md", "Original content");
+
+    let output = context.run_command(&["edit", "20250130143022"]);
+    assert_command_success(&output);
+
+    let memo_path = context.memo_dir().join("2025-01/30/20250130143022.md");
+    let content = fs::read_to_string(&memo_path).unwrap();
+    assert!(content.starts_with("---\ntitle: Edited\nupdated_at: "));
+    assert!(content.ends_with("---\nBody\n"));
+
+    let memo = memo::MemoFile::from_path(&memo_path).unwrap();
+    assert!(memo.updated_at().is_some());
+}
+
 #[test]
 fn test_edit_nonexistent_memo() {
     let context = TestContext::new();
@@ -73,9 +100,62 @@ fn test_edit_editor_exits_with_error() {
     assert_command_error(&output, "Editor exited with non-zero status");
 }
 
+#[test]
+fn test_edit_multiple_ids() {
+    let script_path = create_mock_append_editor_script("appended");
+    let context = TestContext::with_editor(script_path.to_str().unwrap());
+
+    context.create_memo("2025-01/30/20250130143022.md", "First");
+    context.create_memo("2025-01/29/20250129120000.md", "Second");
+    context.create_memo("2025-01/28/20250128090000.md", "Untouched");
+    context.build_search_index().unwrap();
+
+    let output = context.run_command(&["edit", "20250130143022", "20250129120000"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Memo edited: 20250130143022");
+    assert_output_contains(&output, "Memo edited: 20250129120000");
+
+    for path in [
+        "2025-01/30/20250130143022.md",
+        "2025-01/29/20250129120000.md",
+    ] {
+        let content = fs::read_to_string(context.memo_dir().join(path)).unwrap();
+        assert!(content.contains("updated_at: "));
+        assert!(content.ends_with("appended\n"));
+    }
+    let untouched =
+        fs::read_to_string(context.memo_dir().join("2025-01/28/20250128090000.md")).unwrap();
+    assert_eq!(untouched, "Untouched");
+
+    // 見つからない ID があればエディタを開かない
+    let output = context.run_command(&["edit", "20250130143022", "999999"]);
+    assert_command_failure(&output);
+    let first =
+        fs::read_to_string(context.memo_dir().join("2025-01/30/20250130143022.md")).unwrap();
+    assert_eq!(first.matches("appended").count(), 1);
+}
+
+#[test]
+fn test_edit_last() {
+    let script_path = create_mock_append_editor_script("appended");
+    let context = TestContext::with_editor(script_path.to_str().unwrap());
+
+    context.create_memo("2025-01/30/20250130143022.md", "Old");
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    context.create_memo("2025-01/28/20250128090000.md", "Recently modified");
+
+    let output = context.run_command(&["edit", "--last", "1"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Memo edited: 20250128090000");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("20250130143022"));
+
+    let output = context.run_command(&["edit", "20250130143022", "--last", "1"]);
+    assert_command_failure(&output);
+}
+
 #[test]
 fn test_edit_memo_with_front_matter() {
-    let script_path = create_mock_editor_script(&TestMemoTemplates::WITH_FRONT_MATTER);
+    let script_path = create_mock_editor_script(TestMemoTemplates::WITH_FRONT_MATTER);
     let context = TestContext::with_editor(script_path.to_str().unwrap());
 
     // フロントマター付きメモを作成
@@ -197,3 +277,42 @@ mod edit_integration_tests {
         assert_output_contains(&search_output, "Edited content for search test");
     }
 }
+
+#[test]
+fn test_edit_create_missing_memo() {
+    let script_path = create_mock_editor_script("# Daily\nwritten by script");
+    let context = TestContext::with_editor(script_path.to_str().unwrap());
+
+    let output = context.run_command(&["edit", "20250130000000"]);
+    assert_command_failure(&output);
+    assert_memo_not_exists(&context, "2025-01/30/20250130000000.md");
+
+    let output = context.run_command(&["edit", "20250130000000", "--create"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Memo created: 20250130000000");
+    let memo_path = context.memo_dir().join("2025-01/30/20250130000000.md");
+    assert!(
+        fs::read_to_string(&memo_path)
+            .unwrap()
+            .contains("written by script")
+    );
+
+    // 2 回目は既存のメモを開く
+    let output = context.run_command(&["edit", "20250130000000", "--create"]);
+    assert_command_success(&output);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Memo created"));
+
+    // 短縮 ID からは作らない
+    let output = context.run_command(&["edit", "0131000000", "--create"]);
+    assert_command_error(&output, "full 14-digit ID");
+}
+
+#[test]
+fn test_edit_today_creates_daily_memo() {
+    let context = TestContext::new();
+    let today = chrono::Local::now().format("%Y%m%d").to_string();
+
+    let output = context.run_command(&["edit", "today"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, &format!("Memo created: {}000000", today));
+}
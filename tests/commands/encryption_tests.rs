@@ -0,0 +1,100 @@
+use crate::utils::{TestContext, assertions::*, mocks::*};
+use age::secrecy::ExposeSecret;
+use std::fs;
+use std::path::PathBuf;
+
+/// age の鍵を作って `[encryption]` に設定する
+fn setup_key(context: &TestContext, index: bool) {
+    let identity_path = context.config_home().join("identity.txt");
+    fs::create_dir_all(context.config_home()).unwrap();
+    let identity = age::x25519::Identity::generate();
+    fs::write(&identity_path, identity.to_string().expose_secret()).unwrap();
+    context.write_config(&format!(
+        "[encryption]\nidentity = \"{}\"\nindex = {}\n",
+        identity_path.display(),
+        index
+    ));
+}
+
+/// メモディレクトリにある `.md.age` を1つ探す
+fn encrypted_memo(context: &TestContext) -> (String, PathBuf) {
+    fn find(dir: &std::path::Path) -> Option<PathBuf> {
+        for entry in fs::read_dir(dir).ok()? {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?.to_string();
+            if path.is_dir() && !name.starts_with('.') {
+                if let Some(found) = find(&path) {
+                    return Some(found);
+                }
+            } else if name.ends_with(".md.age") {
+                return Some(path);
+            }
+        }
+        None
+    }
+    let path = find(context.memo_dir()).expect("no encrypted memo");
+    let name = path.file_name().unwrap().to_str().unwrap();
+    (name.trim_end_matches(".md.age").to_string(), path)
+}
+
+fn search_count(context: &TestContext) -> String {
+    let output = context.run_command(&["search", "*", "--count"]);
+    assert_command_success(&output);
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn test_add_encrypted_memo() {
+    let context = TestContext::new();
+    setup_key(&context, false);
+
+    let output = context.run_command(&["add", "--encrypt", "-m", "secret plan"]);
+    assert_command_success(&output);
+
+    let (id, path) = encrypted_memo(&context);
+    let raw = fs::read(&path).unwrap();
+    assert!(!String::from_utf8_lossy(&raw).contains("secret plan"));
+
+    let output = context.run_command(&["show", &id]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "secret plan");
+    assert_output_contains(&context.run_command(&["list"]), &id);
+
+    // 既定では平文をインデックスに入れない
+    assert_eq!(search_count(&context), "0");
+}
+
+#[test]
+fn test_index_encrypted_memo_when_enabled() {
+    let context = TestContext::new();
+    setup_key(&context, true);
+
+    assert_command_success(&context.run_command(&["add", "--encrypt", "-m", "secret plan"]));
+    assert_eq!(search_count(&context), "1");
+}
+
+#[test]
+fn test_add_encrypt_without_key() {
+    let context = TestContext::new();
+    let output = context.run_command(&["add", "--encrypt", "-m", "secret plan"]);
+    assert_command_error(&output, "[encryption] identity");
+}
+
+#[test]
+fn test_edit_encrypted_memo() {
+    let script_path = create_mock_append_editor_script("appended");
+    let context = TestContext::with_editor(script_path.to_str().unwrap());
+    setup_key(&context, false);
+    assert_command_success(&context.run_command(&["add", "--encrypt", "-m", "secret plan"]));
+    let (id, path) = encrypted_memo(&context);
+
+    let output = context.run_command(&["edit", &id]);
+    assert_command_success(&output);
+    assert_output_contains(&output, &format!("Memo edited: {}", id));
+
+    let raw = fs::read(&path).unwrap();
+    assert!(!String::from_utf8_lossy(&raw).contains("appended"));
+    let output = context.run_command(&["show", &id]);
+    assert_output_contains(&output, "secret plan");
+    assert_output_contains(&output, "appended");
+}
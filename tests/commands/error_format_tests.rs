@@ -0,0 +1,44 @@
+use crate::utils::{TestContext, assertions::*};
+use std::fs;
+
+#[test]
+fn test_error_format_json() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["show", "20250130143022", "--error-format", "json"]);
+    assert_command_failure(&output);
+    let error = assert_valid_json(String::from_utf8_lossy(&output.stderr).trim());
+    assert_eq!(error["code"], "MEMO_NOT_FOUND");
+    assert_eq!(error["message"], "Memo with ID '20250130143022' not found");
+    assert_eq!(error["context"]["id"], "20250130143022");
+
+    // 既定は人が読む形
+    let output = context.run_command(&["show", "20250130143022"]);
+    assert_command_error(&output, "Error: Memo with ID '20250130143022' not found");
+}
+
+#[test]
+fn test_error_format_json_for_config_errors() {
+    let context = TestContext::new();
+    context.write_config("[list]\nunknown_key = 1\n");
+
+    let output = context.run_command(&["--error-format=json", "list"]);
+    assert_command_failure(&output);
+    let error = assert_valid_json(String::from_utf8_lossy(&output.stderr).trim());
+    assert_eq!(error["code"], "CONFIG_ERROR");
+}
+
+#[test]
+fn test_io_error_names_the_file() {
+    let context = TestContext::new();
+    // メモのファイル名のディレクトリは読めない
+    fs::create_dir_all(context.memo_dir().join("2025-01/30/20250130143022.md")).unwrap();
+
+    let output = context.run_command(&["show", "20250130143022", "--error-format", "json"]);
+    assert_command_failure(&output);
+    let error = assert_valid_json(String::from_utf8_lossy(&output.stderr).trim());
+    assert_eq!(error["code"], "IO_ERROR");
+    let path = error["context"]["path"].as_str().unwrap();
+    assert!(path.ends_with("2025-01/30/20250130143022.md"));
+    assert!(error["message"].as_str().unwrap().contains(path));
+}
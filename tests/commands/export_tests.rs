@@ -0,0 +1,91 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_export_jsonl_with_filters() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+
+    let output = context.run_command(&["export"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let ids: Vec<String> = stdout
+        .lines()
+        .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+        .collect();
+    // 古い順
+    assert_eq!(
+        ids,
+        vec![
+            "20250128090000",
+            "20250129120000",
+            "20250130143022",
+            "20250130151545"
+        ]
+    );
+
+    let output = context.run_command(&["export", "--tag", "@frontmatter"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1);
+    let record = assert_valid_json(stdout.lines().next().unwrap());
+    assert_eq!(record["metadata"]["priority"], 1);
+    assert!(
+        record["created_at"]
+            .as_str()
+            .unwrap()
+            .starts_with("2025-01-30T15:15:45")
+    );
+
+    let output = context.run_command(&["export", "--since", "2025-01-29", "--until", "2025-01-29"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "20250129120000");
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 1);
+}
+
+#[test]
+fn test_export_to_files() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    let out_dir = tempfile::TempDir::new().unwrap();
+
+    let bundle = out_dir.path().join("memos.md");
+    let output = context.run_command(&[
+        "export",
+        "--to",
+        "markdown-bundle",
+        "--output",
+        bundle.to_str().unwrap(),
+    ]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Exported 4 memo(s)");
+    let content = std::fs::read_to_string(&bundle).unwrap();
+    assert_eq!(content.matches("<!-- memo: ").count(), 4);
+    assert!(content.contains("title: Test Memo with Frontmatter"));
+
+    let archive = out_dir.path().join("memos.zip");
+    let output = context.run_command(&["export", "--to", "zip", "-o", archive.to_str().unwrap()]);
+    assert_command_success(&output);
+    assert!(std::fs::metadata(&archive).unwrap().len() > 0);
+
+    let vault = out_dir.path().join("vault");
+    let output =
+        context.run_command(&["export", "--to", "obsidian", "-o", vault.to_str().unwrap()]);
+    assert_command_success(&output);
+    let note = std::fs::read_to_string(vault.join("2025-01/30/20250130143022.md")).unwrap();
+    assert!(note.contains("#test #basic"));
+}
+
+#[test]
+fn test_export_errors() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+
+    let output = context.run_command(&["export", "--to", "zip"]);
+    assert_command_error(&output, "--output is required for --to zip");
+
+    let output = context.run_command(&["export", "--format", "json"]);
+    assert_command_error(&output, "use `memo export --to <FORMAT>`");
+
+    let output = context.run_command(&["export", "--since", "someday"]);
+    assert_command_error(&output, "invalid date: someday");
+}
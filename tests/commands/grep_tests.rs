@@ -0,0 +1,34 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_grep_without_index() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "# Release\nprepare\nDeploy to staging\nverify\n",
+    );
+    context.create_memo("2025-01/29/20250129120000.md", "# Old\ndeploy v1\n");
+    context.create_memo("2025-01/28/20250128090000.md", "# Unrelated\n");
+
+    let output = context.run_command(&["grep", "-i", "deploy", "-C", "1"]);
+    assert_command_success(&output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "20250130143022\n2-prepare\n3:Deploy to staging\n4-verify\n\n20250129120000\n1-# Old\n2:deploy v1\n"
+    );
+
+    let output = context.run_command(&["grep", "Deploy"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("3:Deploy to staging"));
+    assert!(!stdout.contains("20250129120000"));
+}
+
+#[test]
+fn test_grep_invalid_regex() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["grep", "(unclosed"]);
+    assert_command_failure(&output);
+    assert_command_error(&output, "invalid regex");
+}
@@ -0,0 +1,56 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_help_lists_topics() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["help"]);
+
+    assert_command_success(&output);
+    assert_output_contains(&output, "Usage: memo");
+    assert_output_contains(&output, "Help topics:");
+    assert_output_contains(&output, "query-syntax");
+}
+
+#[test]
+fn test_help_topic() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["help", "query-syntax"]);
+
+    assert_command_success(&output);
+    assert_output_contains(&output, "tags:@todo");
+}
+
+#[test]
+fn test_help_subcommand() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["help", "list"]);
+
+    assert_command_success(&output);
+    assert_output_contains(&output, "Usage: memo list");
+    assert_output_contains(&output, "--where");
+}
+
+#[test]
+fn test_help_unknown_topic() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["help", "nope"]);
+
+    assert_command_error(&output, "unknown help topic or command 'nope'");
+}
+
+#[test]
+fn test_man_page() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["man"]);
+
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with(".ie"));
+    assert!(stdout.contains(".TH memo 1"));
+    assert!(stdout.contains(".SH \"QUERY SYNTAX\""));
+}
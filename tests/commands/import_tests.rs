@@ -0,0 +1,84 @@
+use crate::utils::{TestContext, assertions::*};
+use std::fs;
+
+#[test]
+fn test_import_markdown_directory() {
+    let context = TestContext::new();
+    let source = tempfile::TempDir::new().unwrap();
+    fs::create_dir_all(source.path().join("2024-12/31")).unwrap();
+    fs::write(source.path().join("2024-12/31/235959.md"), "# Old layout\n").unwrap();
+    fs::write(
+        source.path().join("meeting.md"),
+        "---\ncreated: 2025-02-01 09:30\n---\n# Meeting\n",
+    )
+    .unwrap();
+    fs::write(source.path().join("notes.txt"), "not markdown").unwrap();
+
+    let output = context.run_command(&["import", source.path().to_str().unwrap()]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Imported 2 memo(s)");
+
+    assert!(
+        context
+            .memo_dir()
+            .join("2024-12/31/20241231235959.md")
+            .exists()
+    );
+    let meeting = context.memo_dir().join("2025-02/01/20250201093000.md");
+    assert_eq!(
+        fs::read_to_string(meeting).unwrap(),
+        "---\ncreated: 2025-02-01 09:30\n---\n# Meeting\n"
+    );
+
+    // 取り込んだメモは索引済み
+    let output = context.run_command(&["search", "*", "--count"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2");
+}
+
+#[test]
+fn test_import_export_roundtrip() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    let out_dir = tempfile::TempDir::new().unwrap();
+    let jsonl = out_dir.path().join("memos.jsonl");
+    let bundle = out_dir.path().join("memos.md");
+
+    let output = context.run_command(&["export", "-o", jsonl.to_str().unwrap()]);
+    assert_command_success(&output);
+    let output = context.run_command(&[
+        "export",
+        "--to",
+        "markdown-bundle",
+        "-o",
+        bundle.to_str().unwrap(),
+    ]);
+    assert_command_success(&output);
+
+    for source in [&jsonl, &bundle] {
+        let target = TestContext::new();
+        let output = target.run_command(&["import", source.to_str().unwrap()]);
+        assert_command_success(&output);
+        assert_output_contains(&output, "Imported 4 memo(s)");
+
+        let output = target.run_command(&["show", "20250130151545"]);
+        assert_command_success(&output);
+        assert_output_contains(&output, "Test Memo with Frontmatter");
+        let output = target.run_command(&["list", "--tag", "@frontmatter", "--json"]);
+        assert_output_contains(&output, "20250130151545");
+    }
+}
+
+#[test]
+fn test_import_errors() {
+    let context = TestContext::new();
+    let source = tempfile::TempDir::new().unwrap();
+    let text = source.path().join("notes.txt");
+    fs::write(&text, "text").unwrap();
+
+    let output = context.run_command(&["import", text.to_str().unwrap()]);
+    assert_command_error(&output, "expected a directory, .md or .jsonl file");
+
+    let output = context.run_command(&["import", source.path().to_str().unwrap()]);
+    assert_command_error(&output, "no markdown files to import");
+}
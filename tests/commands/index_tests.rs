@@ -231,7 +231,7 @@ Testing large metadata structures."#,
         serde_json::to_string(&(0..100).collect::<Vec<i32>>()).unwrap()
     );
 
-    context.create_memo("2025-01/30/20250130143022.md", &edge_case_metadata);
+    context.create_memo("2025-01/30/20250130143022.md", edge_case_metadata);
     context.create_memo("2025-01/30/20250130151545.md", &large_metadata);
 
     let output = context.run_command(&["index"]);
@@ -303,3 +303,168 @@ fn test_index_metadata_consistency_across_rebuilds() {
     assert_command_success(&search_output2);
     assert_output_contains(&search_output2, "143022");
 }
+
+#[test]
+fn test_index_update() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntags: [\"@a\"]\n---\nFirst",
+    );
+    context.create_memo("2025-01/30/20250130151545.md", "Second");
+
+    // 索引が無ければ全件
+    let output = context.run_command(&["index", "--update"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "2 added, 0 modified, 0 removed");
+
+    let output = context.run_command(&["index", "--update"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "0 added, 0 modified, 0 removed");
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntags: [\"@b\"]\n---\nFirst",
+    );
+    context.create_memo("2025-01/31/20250131090000.md", "Third");
+    std::fs::remove_file(context.memo_dir().join("2025-01/30/20250130151545.md")).unwrap();
+
+    let output = context.run_command(&["index", "--update"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "1 added, 1 modified, 1 removed");
+
+    let output = context.run_command(&["search", "*"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("20250130143022"));
+    assert!(stdout.contains("20250131090000"));
+    assert!(!stdout.contains("20250130151545"));
+
+    let output = context.run_command(&["search", "tag:@b"]);
+    assert_output_contains(&output, "20250130143022");
+    let output = context.run_command(&["search", "tag:@a"]);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("20250130143022"));
+}
+
+#[test]
+fn test_index_update_after_full_build() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+
+    let output = context.run_command(&["index"]);
+    assert_command_success(&output);
+
+    let output = context.run_command(&["index", "--update"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "0 added, 0 modified, 0 removed");
+}
+
+#[test]
+fn test_index_removes_old_indexes() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    let index_dir = context.memo_context.index_dir();
+    for version in ["20200101_000000", "20200102_000000", "20200103_000000"] {
+        std::fs::create_dir_all(index_dir.join(version)).unwrap();
+    }
+    std::fs::create_dir_all(index_dir.join("keep-me")).unwrap();
+
+    let output = context.run_command(&["index"]);
+    assert_command_success(&output);
+
+    // 今の索引と 1 つ前のものだけが残る
+    assert!(index_dir.join("20200103_000000").exists());
+    assert!(!index_dir.join("20200102_000000").exists());
+    assert!(!index_dir.join("20200101_000000").exists());
+    assert!(index_dir.join("keep-me").exists());
+
+    std::fs::create_dir_all(index_dir.join("20200104_000000")).unwrap();
+    let output = context.run_command(&["index", "--gc"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Removed 1 old index(es)");
+    assert!(!index_dir.join("20200103_000000").exists());
+    assert!(index_dir.join("20200104_000000").exists());
+
+    let search_output = context.run_command(&["search", "*"]);
+    assert_command_success(&search_output);
+}
+
+#[test]
+fn test_failed_rebuild_keeps_current_index() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    context.build_search_index().unwrap();
+
+    let manager = context.memo_context.search_manager();
+    let version = manager.get_version().unwrap();
+    let result =
+        manager.create_new_index(|_| Err(memo::MemoError::Search("interrupted".to_string())));
+    assert!(result.is_err());
+    assert_eq!(manager.get_version().unwrap(), version);
+    // 作りかけの索引は残らない
+    let leftovers: Vec<_> = std::fs::read_dir(context.memo_context.index_dir())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty());
+
+    let search_output = context.run_command(&["search", "*"]);
+    assert_command_success(&search_output);
+    assert_output_contains(&search_output, "20250130143022");
+}
+
+#[test]
+fn test_index_rollback() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", "First memo");
+
+    let output = context.run_command(&["index", "--rollback"]);
+    assert_command_failure(&output);
+
+    context.build_search_index().unwrap();
+    let manager = context.memo_context.search_manager();
+    let first = manager.get_version().unwrap().unwrap();
+
+    context.create_memo("2025-01/30/20250130151545.md", "Second memo");
+    context.build_search_index().unwrap();
+    assert_ne!(manager.get_version().unwrap().unwrap(), first);
+
+    let output = context.run_command(&["index", "--rollback"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, &format!("rolled back to {}", first));
+    assert_eq!(manager.get_version().unwrap().unwrap(), first);
+}
+
+#[test]
+fn test_build_index_reports_progress() {
+    use memo::commands::index::{IndexProgress, build_index};
+
+    #[derive(Default)]
+    struct Recorder {
+        total: Option<usize>,
+        done: Vec<usize>,
+    }
+
+    impl IndexProgress for Recorder {
+        fn started(&mut self, total: usize) {
+            self.total = Some(total);
+        }
+
+        fn indexed(&mut self, done: usize, _total: usize) {
+            self.done.push(done);
+        }
+    }
+
+    let context = TestContext::new();
+    context.setup_test_memos();
+
+    let mut recorder = Recorder::default();
+    build_index(&context.memo_context, &mut recorder).unwrap();
+    assert_eq!(recorder.total, Some(4));
+    assert_eq!(recorder.done, vec![1, 2, 3, 4]);
+
+    let search_output = context.run_command(&["search", "*"]);
+    assert_command_success(&search_output);
+    assert_output_contains(&search_output, "20250128090000");
+}
@@ -0,0 +1,71 @@
+use crate::utils::{TestContext, assertions::*};
+
+fn create_linked_memos(context: &TestContext) {
+    context.create_memo("2025-01/30/20250130100000.md", "# Design\nthe plan");
+    context.create_memo(
+        "2025-01/30/20250130110000.md",
+        "# Review\nsee [[20250130100000|design]] and [[2025-01/29/090000]]",
+    );
+    context.create_memo(
+        "2025-01/30/20250130120000.md",
+        "# Retro\nfollows [[2025-01/30/100000]]",
+    );
+}
+
+#[test]
+fn test_links_and_backlinks() {
+    let context = TestContext::new();
+    create_linked_memos(&context);
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["links", "20250130110000"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("  -> 20250130100000  Design"));
+    assert!(stdout.contains("  -> 20250129090000  (missing)"));
+    assert!(stdout.contains("backlinks:\n  (none)"));
+
+    let output = context.run_command(&["links", "20250130100000"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("links:\n  (none)\nbacklinks:\n"));
+    assert!(stdout.contains("  <- 20250130110000  Review\n  <- 20250130120000  Retro"));
+}
+
+#[test]
+fn test_backlinks_without_index() {
+    let context = TestContext::new();
+    create_linked_memos(&context);
+
+    let output = context.run_command(&["links", "20250130100000"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("  <- 20250130110000  Review"));
+    assert!(stdout.contains("  <- 20250130120000  Retro"));
+}
+
+#[test]
+fn test_archive_and_restore_warn_about_links() {
+    let context = TestContext::new();
+    create_linked_memos(&context);
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["archive", "20250130100000"]);
+    assert_command_success(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "Warning: 20250130100000 is linked from 20250130110000, 20250130120000; those links will break"
+    ));
+
+    // 同時にアーカイブするメモからのリンクは警告しない
+    let output = context.run_command(&["archive", "20250130110000", "20250130120000"]);
+    assert_command_success(&output);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("links will break"));
+
+    let output = context.run_command(&["restore", "20250130120000"]);
+    assert_command_success(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "Warning: 20250130120000 links to 20250130100000, which is not in the memo directory"
+    ));
+}
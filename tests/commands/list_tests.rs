@@ -173,6 +173,39 @@ fn test_list_preview_truncation() {
     assert!(stdout.len() < long_content.len());
 }
 
+#[test]
+fn test_list_preview_fits_terminal_width() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", &"A".repeat(500));
+
+    let output = context.run_command_with_env(&["list"], &[("COLUMNS", "60")]);
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let preview = stdout
+        .lines()
+        .find(|line| line.starts_with("preview: "))
+        .unwrap();
+    assert_eq!(preview.chars().count(), 60);
+    assert!(preview.ends_with("..."));
+}
+
+#[test]
+fn test_list_table_fits_terminal_width() {
+    let context = TestContext::new();
+
+    let long_title = format!("# {}", "Long title ".repeat(20));
+    context.create_memo("2025-01/30/20250130143022.md", &long_title);
+
+    let output = context.run_command_with_env(&["list", "--table"], &[("COLUMNS", "70")]);
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.lines().all(|line| line.chars().count() <= 70));
+    assert!(stdout.contains('…'));
+}
+
 #[test]
 fn test_list_with_broken_frontmatter() {
     let context = TestContext::new();
@@ -221,3 +254,439 @@ This memo has broken frontmatter."#;
     // 具体的な動作は実装に依存するため、JSONが有効であることのみ確認
     assert!(json.get("id").is_some());
 }
+
+#[test]
+fn test_list_outline() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/29/20250129120000.md", TestMemoTemplates::MULTILINE);
+
+    let output = context.run_command(&["list", "--outline"]);
+
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(
+        "outline:\n  - Multiline Test Memo\n    - Section 1\n    - Section 2\n      - Subsection\n---\n"
+    ));
+    assert!(!stdout.contains("preview:"));
+}
+
+#[test]
+fn test_list_sort_by_metadata() {
+    let context = TestContext::new();
+
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\npriority: 3\n---\nLow priority",
+    );
+    context.create_memo("2025-01/30/20250130151545.md", "No priority");
+    context.create_memo(
+        "2025-01/29/20250129120000.md",
+        "---\npriority: 1\n---\nHigh priority",
+    );
+
+    let output = context.run_command(&["list", "--json", "--sort-by", "meta.priority"]);
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let ids: Vec<String> = stdout
+        .lines()
+        .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        ids,
+        vec!["20250129120000", "20250130143022", "20250130151545"]
+    );
+}
+
+#[test]
+fn test_list_sort_title_and_reverse() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", "# beta");
+    context.create_memo("2025-01/30/20250130151545.md", "# Charlie");
+    context.create_memo("2025-01/29/20250129120000.md", "# alpha");
+
+    let ids = |args: &[&str]| -> Vec<String> {
+        let output = context.run_command(args);
+        assert_command_success(&output);
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    assert_eq!(
+        ids(&["list", "--json", "--sort", "title"]),
+        vec!["20250129120000", "20250130143022", "20250130151545"]
+    );
+    assert_eq!(
+        ids(&["list", "--json", "--sort", "title", "--reverse"]),
+        vec!["20250130151545", "20250130143022", "20250129120000"]
+    );
+    // --sort が無ければ既定の新しい順を逆にする
+    assert_eq!(
+        ids(&["list", "--json", "--reverse", "--limit", "2"]),
+        vec!["20250129120000", "20250130143022"]
+    );
+}
+
+#[test]
+fn test_list_sort_by_invalid_key() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["list", "--sort-by", "priority"]);
+    assert_command_error(&output, "unsupported sort key");
+}
+
+#[test]
+fn test_list_where_expression() {
+    let context = TestContext::new();
+
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\npriority: 3\nstatus: done\n---\nDone memo",
+    );
+    context.create_memo(
+        "2025-01/30/20250130151545.md",
+        "---\npriority: 2\nstatus: active\n---\nActive memo",
+    );
+    context.create_memo("2025-01/29/20250129120000.md", "No front matter");
+
+    let output = context.run_command(&[
+        "list",
+        "--json",
+        "--where",
+        "priority >= 2 && status != 'done'",
+    ]);
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(assert_valid_json(lines[0])["id"], "20250130151545");
+}
+
+#[test]
+fn test_list_where_invalid_expression() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["list", "--where", "priority >="]);
+    assert_command_error(&output, "invalid --where expression");
+}
+
+#[test]
+fn test_list_json_select() {
+    let context = TestContext::new();
+
+    context.create_memo(
+        "2025-01/30/20250130151545.md",
+        TestMemoTemplates::WITH_FRONT_MATTER,
+    );
+
+    let output = context.run_command(&["list", "--json", "--select", "id,metadata.priority"]);
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = assert_valid_json(stdout.trim());
+    assert_eq!(
+        json,
+        serde_json::json!({"id": "20250130151545", "metadata": {"priority": 1}})
+    );
+}
+
+#[test]
+fn test_list_template() {
+    let context = TestContext::new();
+
+    context.create_memo(
+        "2025-01/30/20250130151545.md",
+        TestMemoTemplates::WITH_FRONT_MATTER,
+    );
+    context.create_memo("2025-01/29/20250129120000.md", TestMemoTemplates::BASIC);
+
+    let template_path = context.temp_dir.path().join("digest.hbs");
+    std::fs::write(
+        &template_path,
+        "{{#each memos}}- {{id}} {{#if metadata.title}}{{metadata.title}}{{else}}(untitled){{/if}}\n{{/each}}",
+    )
+    .unwrap();
+
+    let output = context.run_command(&["list", "--template", template_path.to_str().unwrap()]);
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "- 20250130151545 Test Memo with Frontmatter\n- 20250129120000 (untitled)\n"
+    );
+}
+
+#[test]
+fn test_list_template_missing_file() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["list", "--template", "/nonexistent/digest.hbs"]);
+    assert_command_error(&output, "Template error");
+}
+
+#[test]
+fn test_list_table() {
+    let context = TestContext::new();
+
+    context.create_memo(
+        "2025-01/30/20250130151545.md",
+        TestMemoTemplates::WITH_FRONT_MATTER,
+    );
+    context.create_memo("2025-01/29/20250129120000.md", TestMemoTemplates::BASIC);
+
+    let output = context.run_command(&["list", "--table"]);
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("ID"));
+    assert!(lines[0].contains("DATE"));
+    assert!(lines[0].ends_with("TAGS"));
+    assert!(lines[1].starts_with("20250130151545"));
+    assert!(lines[1].contains("Test Memo with Frontmatter"));
+    assert!(lines[1].ends_with("@test @frontmatter"));
+    assert!(lines[2].starts_with("20250129120000"));
+    assert!(lines[2].contains("Basic Memo"));
+
+    let title_column = lines[0].find("TITLE").unwrap();
+    assert_eq!(&lines[1][title_column..title_column + 4], "Test");
+    assert_eq!(&lines[2][title_column..title_column + 5], "Basic");
+}
+
+#[test]
+fn test_list_format() {
+    let context = TestContext::new();
+
+    context.create_memo(
+        "2025-01/30/20250130151545.md",
+        TestMemoTemplates::WITH_FRONT_MATTER,
+    );
+    context.create_memo("2025-01/29/20250129120000.md", TestMemoTemplates::BASIC);
+
+    let output = context.run_command(&["--format", "compact", "list"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("20250130151545  "));
+    assert!(lines[0].ends_with("Test Memo with Frontmatter  @test @frontmatter"));
+
+    let output = context.run_command(&["list", "--format", "yaml"]);
+    assert_command_success(&output);
+    let records: Vec<serde_yaml::Value> = serde_yaml::from_slice(&output.stdout).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["id"].as_str(), Some("20250130151545"));
+    assert!(records[1]["content"].as_str().unwrap().contains("Basic Memo"));
+
+    // --json と同じ
+    let output = context.run_command(&["list", "--format", "json"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2);
+    assert_valid_json(stdout.lines().next().unwrap());
+}
+
+#[test]
+fn test_list_color_flag() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+
+    // パイプ出力ではデフォルトで色を付けない
+    let output = context.run_command(&["list", "--table"]);
+    assert_command_success(&output);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains('\x1b'));
+
+    let output = context.run_command(&["--color=always", "list", "--table"]);
+    assert_command_success(&output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("\x1b[1mID"));
+
+    // --color always は NO_COLOR より優先
+    let output = context.run_command_with_env(
+        &["list", "--table", "--color", "always"],
+        &[("NO_COLOR", "1")],
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains('\x1b'));
+
+    let output = context.run_command(&["list", "--table", "--color", "never"]);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains('\x1b'));
+}
+
+#[test]
+fn test_list_theme_colors() {
+    let context = TestContext::new();
+
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntags: [\"@work\"]\n---\n# Themed",
+    );
+
+    let output = context.run_command(&["--color=always", "list", "--format", "compact"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("\x1b[33m20250130143022\x1b[0m  \x1b[34m"));
+    assert!(stdout.contains("\x1b[36m@work\x1b[0m"));
+
+    context.write_config("[theme]\nid = \"bold green\"\ntag = \"none\"\n");
+    let output = context.run_command(&["--color=always", "list"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("id: \x1b[1;32m20250130143022\x1b[0m"));
+    assert!(stdout.contains("tags: [@work]"));
+}
+
+#[test]
+fn test_list_date_format() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+    let year = chrono::Local::now().format("%Y").to_string();
+
+    let output = context.run_command_with_env(&["list"], &[("MEMO_DATE_FORMAT", "%Y|%m")]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("modified: {}|", year)));
+
+    let output = context.run_command_with_env(&["list", "--table"], &[("LC_ALL", "ja_JP.UTF-8")]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("{}年", year)));
+}
+
+#[test]
+fn test_list_priority() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130100000.md", "---\npriority: 1\n---\nlow");
+    context.create_memo(
+        "2025-01/30/20250130110000.md",
+        "---\npriority: 5\n---\nurgent",
+    );
+    context.create_memo("2025-01/30/20250130120000.md", "no priority");
+
+    let output = context.run_command(&["list", "--json", "--sort", "priority"]);
+    assert_command_success(&output);
+    let ids: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        ids,
+        vec!["20250130110000", "20250130100000", "20250130120000"]
+    );
+
+    let output = context.run_command(&["list", "--json", "--min-priority", "2"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains("20250130110000"));
+}
+
+#[test]
+fn test_list_since_until_limit() {
+    let context = TestContext::new();
+    context.create_memo("2024-12/31/20241231230000.md", "old");
+    context.create_memo("2025-01/01/20250101090000.md", "new year");
+    context.create_memo("2025-01/31/20250131235959.md", "end of january");
+    context.create_memo("2025-02/01/20250201000000.md", "february");
+
+    let list_ids = |args: &[&str]| -> Vec<String> {
+        let output = context.run_command(&[&["list", "--json"], args].concat());
+        assert_command_success(&output);
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // --until の日付はその日を含む
+    assert_eq!(
+        list_ids(&["--since", "2025-01-01", "--until", "2025-01-31"]),
+        vec!["20250131235959", "20250101090000"]
+    );
+    assert_eq!(
+        list_ids(&["--limit", "2"]),
+        vec!["20250201000000", "20250131235959"]
+    );
+    assert_eq!(
+        list_ids(&["--since", "2025-01-01", "--limit", "1"]),
+        vec!["20250201000000"]
+    );
+
+    let output = context.run_command(&["list", "--since", "yesterday-ish"]);
+    assert_command_error(&output, "invalid date");
+}
+
+#[test]
+fn test_list_offset_and_page() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2024-12/31/20241231230000.md",
+        "---\ntags: [\"@work\"]\n---\nold",
+    );
+    context.create_memo("2025-01/01/20250101090000.md", "new year");
+    context.create_memo(
+        "2025-01/31/20250131235959.md",
+        "---\ntags: [\"@work\"]\n---\nend of january",
+    );
+    context.create_memo(
+        "2025-02/01/20250201000000.md",
+        "---\ntags: [\"@work\"]\n---\nfebruary",
+    );
+
+    let list_ids = |args: &[&str]| -> Vec<String> {
+        let output = context.run_command(&[&["list", "--json"], args].concat());
+        assert_command_success(&output);
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    assert_eq!(
+        list_ids(&["--offset", "1", "--limit", "2"]),
+        vec!["20250131235959", "20250101090000"]
+    );
+    assert_eq!(
+        list_ids(&["--page", "2", "--limit", "3"]),
+        vec!["20241231230000"]
+    );
+    // 絞り込んだ後でページを切る
+    assert_eq!(
+        list_ids(&["--tag", "@work", "--page", "2", "--limit", "2"]),
+        vec!["20241231230000"]
+    );
+    assert_eq!(list_ids(&["--page", "1"]).len(), 4);
+
+    let output = context.run_command(&["list", "--page", "0"]);
+    assert_command_failure(&output);
+    let output = context.run_command(&["list", "--limit", "0"]);
+    assert_command_failure(&output);
+    let output = context.run_command(&["list", "--page", "1", "--offset", "1"]);
+    assert_command_failure(&output);
+}
+
+#[test]
+fn test_list_tag_includes_inline_tags() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntags: [\"@work\"]\n---\nfront matter",
+    );
+    context.create_memo("2025-01/31/20250131090000.md", "# Inline\n\nmeeting @work");
+    context.create_memo("2025-02/01/20250201000000.md", "no tags");
+
+    let output = context.run_command(&["list", "--json", "--tag", "@work"]);
+    assert_command_success(&output);
+    let ids: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(ids, vec!["20250131090000", "20250130143022"]);
+}
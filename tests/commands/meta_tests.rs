@@ -0,0 +1,54 @@
+use crate::utils::{TestContext, assertions::*};
+use std::fs;
+
+const MEMO: &str = "2025-01/30/20250130143022.md";
+
+#[test]
+fn test_meta_set_and_get() {
+    let context = TestContext::new();
+    context.create_memo(MEMO, "---\ntitle: Report\n---\n# Body\n\nkeep me\n");
+    context.build_search_index().unwrap();
+
+    let output = context.run_command(&[
+        "meta",
+        "set",
+        "20250130143022",
+        "priority=2",
+        "author.name=me",
+    ]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Set priority = 2 in 20250130143022");
+
+    let content = fs::read_to_string(context.memo_dir().join(MEMO)).unwrap();
+    assert!(content.starts_with("---\nauthor:\n  name: me\npriority: 2\ntitle: Report\n"));
+    assert!(content.ends_with("---\n# Body\n\nkeep me\n"));
+
+    let output = context.run_command(&["meta", "get", "20250130143022", "author.name"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "me");
+
+    let output = context.run_command(&["meta", "get", "20250130143022", "missing"]);
+    assert_command_success(&output);
+    assert!(output.stdout.is_empty());
+
+    // インデックスにも反映される
+    let output = context.run_command(&["search", "keep", "--min-priority", "2"]);
+    assert_output_contains(&output, "20250130143022");
+}
+
+#[test]
+fn test_meta_set_rejects_invalid_input() {
+    let context = TestContext::new();
+    context.create_memo(MEMO, "---\ntitle: [broken\n---\nbody\n");
+
+    let output = context.run_command(&["meta", "set", "20250130143022", "priority"]);
+    assert_command_error(&output, "expected key=value");
+
+    // 読めない front matter は書き換えない
+    let output = context.run_command(&["meta", "set", "20250130143022", "priority=2"]);
+    assert_command_failure(&output);
+    assert_eq!(
+        fs::read_to_string(context.memo_dir().join(MEMO)).unwrap(),
+        "---\ntitle: [broken\n---\nbody\n"
+    );
+}
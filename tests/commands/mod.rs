@@ -1,8 +1,43 @@
+pub mod alias_tests;
 pub mod add_tests;
+pub mod api_tests;
 pub mod archive_tests;
+pub mod attach_tests;
+pub mod cat_tests;
+pub mod clock_tests;
+pub mod complete_tests;
+pub mod config_tests;
+pub mod daemon_tests;
+pub mod delete_tests;
 pub mod dir_tests;
+pub mod doctor_tests;
+pub mod due_tests;
 pub mod edit_tests;
+pub mod encryption_tests;
+pub mod error_format_tests;
+pub mod export_tests;
+pub mod grep_tests;
+pub mod help_tests;
+pub mod import_tests;
 pub mod index_tests;
+pub mod links_tests;
 pub mod list_tests;
+pub mod meta_tests;
+pub mod mv_tests;
+pub mod notebook_tests;
+pub mod open_tests;
+pub mod recent_tests;
 pub mod search_tests;
+pub mod serve_tests;
+pub mod shell_tests;
 pub mod show_tests;
+pub mod similar_tests;
+pub mod stats_tests;
+pub mod status_tests;
+pub mod sync_tests;
+pub mod tags_tests;
+pub mod tui_tests;
+pub mod undo_tests;
+pub mod view_tests;
+pub mod watch_tests;
+pub mod webhook_tests;
@@ -0,0 +1,48 @@
+use crate::utils::{TestContext, assertions::*};
+use std::fs;
+
+#[test]
+fn test_mv_memo_to_new_datetime() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ncreated_at: 2025-01-30T14:30:22+09:00\n---\n# Imported\nbody",
+    );
+    context.create_memo("2025-01/30/20250130150000.md", "see [[20250130143022]]");
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["mv", "20250130143022", "2024-12-24 09:15", "--created-at"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Moved 20250130143022 -> 20241224091500");
+    assert!(String::from_utf8_lossy(&output.stderr).contains(
+        "Warning: 20250130143022 is linked from 20250130150000; update those links to [[20241224091500]]"
+    ));
+
+    assert_memo_not_exists(&context, "2025-01/30/20250130143022.md");
+    assert_memo_exists(&context, "2024-12/24/20241224091500.md");
+    let content =
+        fs::read_to_string(context.memo_dir().join("2024-12/24/20241224091500.md")).unwrap();
+    assert!(content.starts_with("---\ncreated_at: 2024-12-24T09:15:00"));
+    assert!(content.ends_with("# Imported\nbody"));
+
+    // 検索インデックスも新しい ID になる
+    let output = context.run_command(&["search", "Imported"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("20241224091500"));
+    assert!(!stdout.contains("20250130143022"));
+}
+
+#[test]
+fn test_mv_refuses_existing_id() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", "first");
+    context.create_memo("2025-01/31/20250131100000.md", "second");
+
+    let output = context.run_command(&["mv", "20250130143022", "20250131100000"]);
+    assert_command_error(&output, "already exists");
+    assert_memo_exists(&context, "2025-01/30/20250130143022.md");
+
+    let output = context.run_command(&["mv", "20250130143022", "someday"]);
+    assert_command_error(&output, "invalid date/time");
+}
@@ -0,0 +1,60 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+
+#[test]
+fn test_notebook_create_use_and_list() {
+    let context = TestContext::new();
+    let work_dir = context.temp_dir.path().join("memo-work");
+
+    let output = context.run_command(&["notebook", "create", "work"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Created notebook work");
+    assert!(work_dir.is_dir());
+
+    let output = context.run_command(&["notebook", "create", "work"]);
+    assert_command_error(&output, "notebook 'work' already exists");
+
+    let output = context.run_command(&["notebook", "list"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("* default  {}", context.memo_dir().display())));
+    assert!(stdout.contains(&format!("  work     {}", work_dir.display())));
+
+    assert_command_success(&context.run_command(&["notebook", "use", "work"]));
+    let output = context.run_command(&["dir"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        work_dir.to_string_lossy()
+    );
+    let output = context.run_command(&["notebook", "list"]);
+    assert_output_contains(&output, "* work");
+
+    let output = context.run_command(&["notebook", "use", "home"]);
+    assert_command_error(&output, "notebook 'home' is not listed in [notebooks]");
+}
+
+#[test]
+fn test_notebook_flag_partitions_memos() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+    assert_command_success(&context.run_command(&["notebook", "create", "work"]));
+
+    let output = context.run_command(&["--notebook", "work", "add", "-m", "work only"]);
+    assert_command_success(&output);
+    assert_command_success(&context.run_command(&["--notebook", "work", "index"]));
+
+    // ノートブックごとにメモと .index が分かれる
+    let work_dir = context.temp_dir.path().join("memo-work");
+    assert!(work_dir.join(".index").is_dir());
+    let output = context.run_command(&["list", "--notebook", "work", "--format", "json"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains("work only"));
+
+    let output = context.run_command(&["list", "--format", "json"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("20250130143022"));
+    assert!(!stdout.contains("work only"));
+
+    let output = context.run_command(&["--notebook", "home", "list"]);
+    assert_command_error(&output, "unknown notebook 'home'");
+}
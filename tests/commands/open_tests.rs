@@ -0,0 +1,32 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+
+#[test]
+fn test_open_prints_memo_path() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+
+    let output = context.run_command(&["open", "20250130143022"]);
+    assert_command_success(&output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        context
+            .memo_dir()
+            .join("2025-01/30/20250130143022.md")
+            .to_string_lossy()
+    );
+
+    let output = context.run_command(&["open", "--dir", "20250130143022"]);
+    assert_command_success(&output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        context.memo_dir().join("2025-01/30").to_string_lossy()
+    );
+}
+
+#[test]
+fn test_open_unknown_id() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["open", "20250101000000"]);
+    assert_command_failure(&output);
+}
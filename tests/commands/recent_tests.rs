@@ -0,0 +1,61 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+use chrono::{Days, Local};
+use memo::MemoId;
+
+fn relative_path(at: chrono::DateTime<Local>) -> String {
+    MemoId::from_datetime(at)
+        .to_relative_path()
+        .to_string_lossy()
+        .to_string()
+}
+
+#[test]
+fn test_today_and_yesterday() {
+    let context = TestContext::new();
+    let now = Local::now();
+    let yesterday = now.checked_sub_days(Days::new(1)).unwrap();
+    let today_id = MemoId::from_datetime(now).as_str();
+    let yesterday_id = MemoId::from_datetime(yesterday).as_str();
+    context.create_memo(&relative_path(now), "# Today's note");
+    context.create_memo(&relative_path(yesterday), "# Yesterday's note");
+
+    let output = context.run_command(&["today", "--format", "compact"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&today_id));
+    assert!(!stdout.contains(&yesterday_id));
+
+    let output = context.run_command(&["yesterday", "--format", "compact"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&yesterday_id));
+    assert!(!stdout.contains(&today_id));
+}
+
+#[test]
+fn test_today_add_creates_memo() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["today", "--add"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Memo created:");
+
+    let output = context.run_command(&["today", "--format", "json"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 1);
+}
+
+#[test]
+fn test_last_shows_most_recently_modified() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/29/20250129120000.md", TestMemoTemplates::BASIC);
+    context.create_memo("2025-01/30/20250130143022.md", "newest id");
+    // 作成日時ではなく更新日時で選ぶ
+    assert_command_success(&context.run_command(&["touch", "20250129120000"]));
+
+    let output = context.run_command(&["last"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "This is a basic test memo.");
+
+    let empty = TestContext::new();
+    assert_command_error(&empty.run_command(&["last"]), "no memos yet");
+}
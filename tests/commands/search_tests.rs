@@ -289,3 +289,373 @@ This memo has special characters in metadata."#;
     assert_command_success(&output);
     assert_output_contains(&output, "151545");
 }
+
+#[test]
+fn test_search_all_notebooks() {
+    let context = TestContext::new();
+    let work = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntags: [\"@todo\"]\n---\nHome memo",
+    );
+    work.create_memo(
+        "2025-01/29/20250129120000.md",
+        "---\ntags: [\"@todo\"]\n---\nWork memo",
+    );
+    work.create_memo("2025-01/28/20250128090000.md", "Untagged work memo");
+    context.write_config(&format!(
+        "[notebooks]\nwork = {:?}\n",
+        work.memo_dir().display().to_string()
+    ));
+    assert_command_success(&context.run_command(&["index"]));
+    assert_command_success(&work.run_command(&["index"]));
+
+    let output = context.run_command(&["search", "tag:@todo"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Found 1 results"));
+    assert!(!stdout.contains("notebook:"));
+
+    let output = context.run_command(&["search", "tag:@todo", "--all-notebooks"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Found 2 results"));
+    assert!(stdout.contains("notebook: default\nid: 20250130143022"));
+    assert!(stdout.contains("notebook: work\nid: 20250129120000"));
+    assert!(!stdout.contains("20250128090000"));
+    let output = context.run_command(&["search", "tag:@todo", "--all-notebooks", "--count"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "2\n");
+}
+
+#[test]
+fn test_search_group_by_month() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", "January memo");
+    context.create_memo("2024-12/31/20241231090000.md", "December memo");
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["search", "*", "--group-by", "month", "--color", "never"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let january = stdout.find("2025-01 (1)").unwrap();
+    let december = stdout.find("2024-12 (1)").unwrap();
+    assert!(january < stdout.find("id: 20250130143022").unwrap());
+    assert!(january < december);
+    assert!(december < stdout.find("id: 20241231090000").unwrap());
+
+    let output = context.run_command(&["search", "*", "--group-by", "year"]);
+    assert_command_failure(&output);
+}
+
+#[test]
+fn test_search_count() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["search", "*", "--count"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "4\n");
+
+    let output = context.run_command(&["search", "tags:@nothing", "--count"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0\n");
+}
+
+#[test]
+fn test_search_invalid_query_message() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["search", "meeting (notes"]);
+    assert_command_error(&output, "Invalid query: expected ')'");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("  meeting (notes\n                ^\n"));
+    assert!(stderr.contains("hint: quote the text"));
+}
+
+#[test]
+fn test_search_query_validation() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["search", "tittle:meeting"]);
+    assert_command_error(&output, "Invalid query: unknown field 'tittle'");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("hint: did you mean 'title:'?"));
+    assert!(stderr.contains("memo search --help-syntax"));
+
+    let output = context.run_command(&["search", "\"meeting notes"]);
+    assert_command_error(&output, "Invalid query: unclosed quote");
+}
+
+#[test]
+fn test_search_help_syntax() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["search", "--help-syntax"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("metadata.status:done"));
+    assert!(stdout.contains("tags.facet:"));
+
+    assert_command_failure(&context.run_command(&["search", "meeting", "--help-syntax"]));
+}
+
+#[test]
+fn test_search_query_file() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    assert_command_success(&context.run_command(&["index"]));
+
+    let query_file = context.memo_dir().join("saved-query.txt");
+    std::fs::write(&query_file, "+tag:@test\n-tag:\"@nothing here\"\n").unwrap();
+    let output = context.run_command(&[
+        "search",
+        "--query-file",
+        query_file.to_str().unwrap(),
+        "--count",
+    ]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+
+    let output = context.run_command_with_input(&["search", "--query-file", "-", "--count"], "*\n");
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "4\n");
+
+    let output = context.run_command(&["search", "*", "--query-file", "-"]);
+    assert_command_failure(&output);
+    let output = context.run_command(&["search"]);
+    assert_command_failure(&output);
+}
+
+#[test]
+fn test_search_json() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["search", "*", "--json"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<serde_json::Value> = stdout.lines().map(assert_valid_json).collect();
+    assert_eq!(lines.len(), 4);
+    for line in &lines {
+        assert!(line["id"].as_str().unwrap().len() == 14);
+        assert!(line["path"].as_str().unwrap().ends_with(".md"));
+        assert!(line["score"].is_number());
+        assert!(line["preview"].is_string());
+        assert!(line.get("notebook").is_none());
+    }
+    let with_front_matter = lines
+        .iter()
+        .find(|line| line["id"] == "20250130151545")
+        .unwrap();
+    assert!(with_front_matter["metadata"].is_object());
+
+    let output = context.run_command(&["search", "*", "--json", "--select", "id"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first = assert_valid_json(stdout.lines().next().unwrap());
+    let keys: Vec<_> = first.as_object().unwrap().keys().collect();
+    assert_eq!(keys, vec!["id"]);
+
+    // 結果が無ければ何も出さない
+    let output = context.run_command(&["search", "tags:@nothing", "--json"]);
+    assert_command_success(&output);
+    assert!(output.stdout.is_empty());
+
+    let output = context.run_command(&["search", "*", "--select", "id"]);
+    assert_command_failure(&output);
+}
+
+#[test]
+fn test_search_tag_facet() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntags: [\"@work\", \"@todo\"]\n---\n# Deploy",
+    );
+    context.create_memo(
+        "2025-01/29/20250129120000.md",
+        "---\ntags: [\"@work\"]\n---\n# Review",
+    );
+    context.create_memo("2025-01/28/20250128090000.md", "# Plain\n@work inline only");
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["search", "*", "--tag", "@work", "--count"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2");
+
+    let output = context.run_command(&["search", "*", "--tag", "@work", "--tag", "@todo"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("20250130143022"));
+    assert!(!stdout.contains("20250129120000"));
+}
+
+#[test]
+fn test_search_format() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntags: [\"@work\"]\n---\n# Deploy",
+    );
+    context.create_memo("2025-01/29/20250129120000.md", "# Review");
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["search", "*", "--format", "yaml"]);
+    assert_command_success(&output);
+    let items: Vec<serde_yaml::Value> = serde_yaml::from_slice(&output.stdout).unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|item| item["score"].as_f64().is_some()));
+
+    let output = context.run_command(&["--format", "compact", "search", "*"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines: Vec<&str> = stdout.lines().collect();
+    lines.sort();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("20250129120000  ") && lines[0].ends_with("  Review"));
+    assert!(lines[1].ends_with("  Deploy  @work"));
+}
+
+#[test]
+fn test_search_sort_date_and_boost_recent() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    assert_command_success(&context.run_command(&["index"]));
+
+    let ids = |args: &[&str]| -> Vec<String> {
+        let output = context.run_command(args);
+        assert_command_success(&output);
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+    let newest_first = vec![
+        "20250130151545",
+        "20250130143022",
+        "20250129120000",
+        "20250128090000",
+    ];
+    assert_eq!(
+        ids(&["search", "*", "--json", "--sort", "date"]),
+        newest_first
+    );
+    assert_eq!(
+        ids(&["search", "*", "--json", "--boost-recent", "365"]),
+        newest_first
+    );
+    // 値を省略すると半減期 30 日
+    assert_eq!(ids(&["search", "*", "--json", "--boost-recent"]).len(), 4);
+
+    let output = context.run_command(&["search", "*", "--boost-recent", "0"]);
+    assert_command_error(&output, "half-life must be a positive number");
+}
+
+#[test]
+fn test_search_limit_offset_page() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    assert_command_success(&context.run_command(&["index"]));
+
+    let ids = |args: &[&str]| -> Vec<String> {
+        let output =
+            context.run_command(&[&["search", "*", "--json", "--sort", "date"], args].concat());
+        assert_command_success(&output);
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| assert_valid_json(line)["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    assert_eq!(ids(&["--limit", "1"]), vec!["20250130151545"]);
+    assert_eq!(
+        ids(&["--limit", "2", "--offset", "1"]),
+        vec!["20250130143022", "20250129120000"]
+    );
+    assert_eq!(
+        ids(&["--limit", "3", "--page", "2"]),
+        vec!["20250128090000"]
+    );
+    assert!(ids(&["--offset", "4"]).is_empty());
+
+    let output = context.run_command(&["search", "*", "--limit", "0"]);
+    assert_command_failure(&output);
+}
+
+#[test]
+fn test_search_date_range_query() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    assert_command_success(&context.run_command(&["index"]));
+
+    let count = |query: &str| {
+        let output = context.run_command(&["search", query, "--count"]);
+        assert_command_success(&output);
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    assert_eq!(count("created_at:[2025-01-29 TO 2025-01-30]"), "3");
+    assert_eq!(count("created_at:{2025-01-29 TO *]"), "2");
+    assert_eq!(count("created_at:<2025-01-29"), "1");
+    assert_eq!(count("created_at:>=2025-01-30 AND tag:@frontmatter"), "1");
+
+    let output = context.run_command(&["search", "created_at:[2025-01-32 TO *]"]);
+    assert_command_error(&output, "invalid date '2025-01-32'");
+}
+
+#[test]
+fn test_search_include_archived() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130100000.md",
+        "# Active\nkubernetes upgrade",
+    );
+    context.create_memo(
+        "2025-01/29/20250129100000.md",
+        "# Old\nkubernetes migration",
+    );
+    assert_command_success(&context.run_command(&["archive", "20250129100000"]));
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["search", "kubernetes", "--json"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 1);
+
+    let output = context.run_command(&["search", "kubernetes", "--include-archived", "--json"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let hits: Vec<_> = stdout.lines().map(assert_valid_json).collect();
+    assert_eq!(hits.len(), 2);
+    let archived = hits
+        .iter()
+        .find(|hit| hit["id"] == "20250129100000")
+        .unwrap();
+    assert_eq!(archived["archived"], true);
+    assert!(
+        hits.iter()
+            .find(|hit| hit["id"] == "20250130100000")
+            .unwrap()
+            .get("archived")
+            .is_none()
+    );
+
+    let output = context.run_command(&["search", "migration", "--include-archived", "--count"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1");
+
+    // 戻したメモはアーカイブの索引からも消える
+    assert_command_success(&context.run_command(&["restore", "20250129100000"]));
+    let output = context.run_command(&["search", "migration", "--include-archived", "--json"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(
+        assert_valid_json(stdout.lines().next().unwrap())
+            .get("archived")
+            .is_none()
+    );
+}
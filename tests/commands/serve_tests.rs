@@ -0,0 +1,103 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::Child;
+use std::time::{Duration, Instant};
+
+/// 空いているポートで `memo serve` を起動し、接続できるまで待つ
+fn start_server(context: &TestContext) -> (Child, u16) {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let child = context.spawn_command(&["serve", "--port", &port.to_string()]);
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while TcpStream::connect(("127.0.0.1", port)).is_err() {
+        assert!(Instant::now() < deadline, "memo serve did not start");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    (child, port)
+}
+
+/// リクエストを1つ送り、ステータスコードと JSON を返す
+fn request(port: u16, method: &str, path: &str, body: &str) -> (u16, serde_json::Value) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost:{}\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        port,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(
+        head.to_ascii_lowercase()
+            .contains("content-type: application/json")
+    );
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn test_serve_list_create_and_show() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        TestMemoTemplates::WITH_FRONT_MATTER,
+    );
+    let (mut child, port) = start_server(&context);
+
+    let (status, memos) = request(port, "GET", "/memos", "");
+    assert_eq!(status, 200);
+    assert_eq!(memos[0]["id"], "20250130143022");
+
+    let (status, created) = request(port, "POST", "/memos", r##"{"content": "# From http\n"}"##);
+    assert_eq!(status, 201);
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let (status, memo) = request(port, "GET", &format!("/memos/{}", id), "");
+    assert_eq!(status, 200);
+    assert_eq!(memo["content"], "# From http\n");
+
+    let (status, body) = request(port, "GET", "/memos/20240101000000", "");
+    assert_eq!(status, 404);
+    assert!(body["error"].is_string());
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    let output = context.run_command(&["show", &id]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "From http");
+}
+
+#[test]
+fn test_serve_archive() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        TestMemoTemplates::WITH_FRONT_MATTER,
+    );
+    let (mut child, port) = start_server(&context);
+
+    let (status, _) = request(port, "POST", "/memos/20250130143022/archive", "");
+    assert_eq!(status, 200);
+    let (_, memos) = request(port, "GET", "/memos", "");
+    assert_eq!(memos.as_array().unwrap().len(), 0);
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!(
+        context
+            .archive_dir()
+            .join("2025-01/30/20250130143022.md")
+            .exists()
+    );
+}
@@ -0,0 +1,79 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+
+#[test]
+fn test_shell_runs_commands() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+
+    let output = context.run_command_with_input(
+        &["shell"],
+        "list --json\nshow 20250130143022\nshow 99999999999999\nexit\nlist\n",
+    );
+
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"id\":\"20250130143022\""));
+    assert!(stdout.contains("This is a basic test memo."));
+    // exit 以降は実行しない
+    assert!(!stdout.contains("Recent memos"));
+
+    // エラーが出てもシェルは続行する
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Error: Memo with ID '99999999999999' not found"));
+
+    assert!(context.memo_dir().join(".shell_history").exists());
+}
+
+#[test]
+fn test_shell_search_and_aliases() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+    context.write_config("[alias]\nls = \"list --table\"\n");
+
+    let output = context.run_command_with_input(&["shell"], "index\nsearch basic\nls\n");
+
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // 辞書の有無で結果が変わるので、検索が実行されたことだけ確認
+    assert!(stdout.contains("for query: basic"));
+    assert!(stdout.contains("ID"));
+    assert!(stdout.contains("Basic Memo"));
+}
+
+#[test]
+fn test_shell_updates_index_after_search() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+
+    // 検索で開いたインデックスが書き込みロックを握ったままにしない
+    let output =
+        context.run_command_with_input(&["shell"], "index\nsearch basic\ntouch 20250130143022\n");
+
+    assert_command_success(&output);
+    assert_output_contains(&output, "Memo touched: 20250130143022");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("Error"), "unexpected error: {}", stderr);
+}
+
+#[test]
+fn test_shell_help_and_global_flags() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+
+    let output = context.run_command_with_input(
+        &["shell"],
+        "help\nlist --notebook other\nlist --color always\nshow 20250130143022\n",
+    );
+
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Commands:"), "help not shown: {}", stdout);
+    // シェル全体の設定は行ごとに変えられない
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stderr.matches("apply to the whole shell").count(), 2);
+    assert!(stdout.contains("This is a basic test memo."));
+}
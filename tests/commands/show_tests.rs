@@ -146,6 +146,80 @@ This memo has broken frontmatter but should still be displayable."#;
     );
 }
 
+#[test]
+fn test_show_outline() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::MULTILINE);
+
+    let output = context.run_command(&["show", "20250130143022", "--outline"]);
+
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "- Multiline Test Memo\n  - Section 1\n  - Section 2\n    - Subsection\n"
+    );
+}
+
+#[test]
+fn test_show_section() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::MULTILINE);
+
+    let output = context.run_command(&["show", "20250130143022", "--section", "Section 2"]);
+
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "## Section 2\nContent of section 2 with more information.\n\n### Subsection\nEven more nested content.\n\n@test @multiline @sections\n"
+    );
+}
+
+#[test]
+fn test_show_section_not_found() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::MULTILINE);
+
+    let output = context.run_command(&["show", "20250130143022", "--section", "Nope"]);
+
+    assert_command_error(&output, "Section 'Nope' not found");
+}
+
+#[test]
+fn test_show_template() {
+    let context = TestContext::new();
+
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::MULTILINE);
+
+    let template_path = context.temp_dir.path().join("minutes.hbs");
+    std::fs::write(
+        &template_path,
+        "{{id}}\n{{#each outline}}{{level}} {{text}}\n{{/each}}",
+    )
+    .unwrap();
+
+    let output = context.run_command(&[
+        "show",
+        "20250130143022",
+        "--template",
+        template_path.to_str().unwrap(),
+    ]);
+
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "20250130143022\n1 Multiline Test Memo\n2 Section 1\n2 Section 2\n3 Subsection\n"
+    );
+}
+
 #[cfg(test)]
 mod show_integration_tests {
     use super::*;
@@ -177,3 +251,97 @@ mod show_integration_tests {
         assert_output_contains(&show_output, "Content created by add command");
     }
 }
+
+#[test]
+fn test_show_short_id() {
+    let context = TestContext::new();
+    let id = memo::MemoId::new();
+    context.create_memo(id.to_relative_path().to_str().unwrap(), "# Today's memo");
+    let full = id.to_string();
+
+    // hhmmss / DDhhmmss / MMDDhhmmss
+    for short in [&full[8..], &full[6..], &full[4..]] {
+        let output = context.run_command(&["show", short]);
+        assert_command_success(&output);
+        assert_output_contains(&output, "Today's memo");
+    }
+}
+
+#[test]
+fn test_show_ambiguous_short_id() {
+    let context = TestContext::new();
+    context.create_memo("2024-01/30/20240130143022.md", "# First");
+    context.create_memo("2024-02/15/20240215143022.md", "# Second");
+
+    let output = context.run_command(&["show", "143022"]);
+    assert_command_error(&output, "Ambiguous memo ID");
+    assert_command_error(&output, "20240130143022");
+    assert_command_error(&output, "20240215143022");
+
+    let output = context.run_command(&["show", "0215143022"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Second");
+}
+
+#[test]
+fn test_show_pretty() {
+    let context = TestContext::new();
+
+    let content =
+        "---\ntags: [\"@todo\"]\n---\n# Plan\n\n- **first**\n- second\n\n```sh\necho hi\n```\n";
+    context.create_memo("2025-01/30/20250130143022.md", content);
+
+    // パイプ出力なので色は付かない
+    let output = context.run_command(&["show", "20250130143022", "--pretty"]);
+    assert_command_success(&output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "# Plan\n\n• first\n• second\n\necho hi\n"
+    );
+
+    let output = context.run_command(&["show", "20250130143022"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), content);
+}
+
+#[test]
+fn test_show_json() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntitle: Design\ntags: [work]\n---\n# Design\nbody",
+    );
+    context.create_memo(
+        "2025-01/30/20250130151545.md",
+        "---\ninvalid: [unclosed\n---\nbody",
+    );
+
+    let output = context.run_command(&["show", "20250130143022", "--json"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1);
+    let json = assert_valid_json(&stdout);
+    assert_eq!(json["id"], "20250130143022");
+    assert!(
+        json["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("2025-01/30/20250130143022.md")
+    );
+    assert!(
+        json["created_at"]
+            .as_str()
+            .unwrap()
+            .starts_with("2025-01-30T14:30:22")
+    );
+    assert!(json["modified"].is_string());
+    assert_eq!(json["metadata"]["title"], "Design");
+    assert!(json["metadata_error"].is_null());
+    assert_eq!(json["content"], "# Design\nbody");
+
+    let output = context.run_command(&["show", "20250130151545", "--json"]);
+    assert_command_success(&output);
+    let json = assert_valid_json(&String::from_utf8_lossy(&output.stdout));
+    assert!(json["metadata"].is_null());
+    assert!(json["metadata_error"].is_string());
+}
@@ -0,0 +1,43 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_similar_memos() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130100000.md",
+        "# Deploy checklist\nkubernetes rollout canary",
+    );
+    context.create_memo(
+        "2025-01/30/20250130110000.md",
+        "canary rollout went fine on kubernetes",
+    );
+    context.create_memo("2025-01/30/20250130120000.md", "lunch ideas: ramen");
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["similar", "20250130100000", "--json"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<_> = stdout.lines().map(assert_valid_json).collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["id"], "20250130110000");
+    assert!(lines[0]["score"].as_f64().unwrap() > 0.0);
+
+    let output = context.run_command(&["similar", "20250130100000"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("Memos similar to 20250130100000 (Deploy checklist):"));
+    assert!(stdout.contains("score: "));
+    assert!(!stdout.contains("20250130120000"));
+}
+
+#[test]
+fn test_similar_requires_indexed_memo() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130100000.md", "first indexed memo");
+    context.create_memo("2025-01/30/20250130110000.md", "second indexed memo");
+    assert_command_success(&context.run_command(&["index"]));
+    context.create_memo("2025-01/30/20250130120000.md", "added after indexing");
+
+    let output = context.run_command(&["similar", "20250130120000"]);
+    assert_command_error(&output, "memo index");
+}
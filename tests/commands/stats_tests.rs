@@ -0,0 +1,48 @@
+use crate::utils::{TestContext, assertions::*};
+
+fn setup_memos(context: &TestContext) {
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntags: [\"@work\"]\n---\nstandup notes",
+    );
+    context.create_memo("2025-01/30/20250130160000.md", "@work @idea");
+    context.create_memo("2025-02/01/20250201090000.md", "plain");
+}
+
+#[test]
+fn test_stats_text() {
+    let context = TestContext::new();
+    setup_memos(&context);
+
+    let output = context.run_command(&["stats"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "memos: 3 (");
+    assert_output_contains(&output, "archive: 0 memos, 0 B");
+    assert_output_contains(&output, "  2025-01     2");
+    assert_output_contains(&output, "  2025-01-30     2");
+    assert_output_contains(&output, "     2  @work");
+}
+
+#[test]
+fn test_stats_json() {
+    let context = TestContext::new();
+    setup_memos(&context);
+    assert_command_success(&context.run_command(&["archive", "20250201090000"]));
+    context.build_search_index().unwrap();
+
+    let output = context.run_command(&["stats", "--json"]);
+    assert_command_success(&output);
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["memos"], 2);
+    assert_eq!(stats["by_month"], serde_json::json!({ "2025-01": 2 }));
+    assert_eq!(stats["by_day"]["2025-01-30"], 2);
+    assert_eq!(
+        stats["tags"][0],
+        serde_json::json!({ "tag": "@work", "count": 2 })
+    );
+    assert_eq!(
+        stats["archive"],
+        serde_json::json!({ "files": 1, "bytes": 5 })
+    );
+    assert!(stats["index"]["bytes"].as_u64().unwrap() > 0);
+}
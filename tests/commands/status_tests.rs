@@ -0,0 +1,45 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_status_set_and_board() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntitle: Write report\n---\nbody",
+    );
+    context.create_memo(
+        "2025-01/29/20250129120000.md",
+        "---\ntitle: Review slides\nstatus: inbox\n---\nbody",
+    );
+
+    let output = context.run_command(&["status", "20250130143022", "active"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Memo 20250130143022 is now active");
+
+    let output = context.run_command(&["status", "20250130143022"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "active");
+
+    let output = context.run_command(&["board"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let inbox = stdout.find("inbox (1)").unwrap();
+    let active = stdout.find("active (1)").unwrap();
+    let done = stdout.find("done (0)").unwrap();
+    assert!(inbox < active && active < done);
+    assert!(stdout[inbox..active].contains("20250129120000  Review slides"));
+    assert!(stdout[active..done].contains("20250130143022  Write report"));
+}
+
+#[test]
+fn test_status_rejects_unknown_state() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", "body");
+
+    let output = context.run_command(&["status", "20250130143022", "someday"]);
+    assert_command_error(&output, "unknown status 'someday'");
+
+    context.write_config("[status]\nstates = [\"someday\", \"done\"]\n");
+    let output = context.run_command(&["status", "20250130143022", "someday"]);
+    assert_command_success(&output);
+}
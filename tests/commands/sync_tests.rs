@@ -0,0 +1,112 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+use std::path::Path;
+use std::process::Command;
+
+/// git のユーザー設定が無い環境でもコミットできるように
+const GIT_ENV: &[(&str, &str)] = &[
+    ("GIT_AUTHOR_NAME", "memo"),
+    ("GIT_AUTHOR_EMAIL", "memo@example.com"),
+    ("GIT_COMMITTER_NAME", "memo"),
+    ("GIT_COMMITTER_EMAIL", "memo@example.com"),
+];
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .envs(GIT_ENV.iter().copied())
+        .output()
+        .expect("Failed to run git");
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_sync_initializes_and_commits() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+    context.build_search_index().unwrap();
+
+    let output = context.run_command_with_env(&["sync"], GIT_ENV);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Initialized git repository");
+    assert_output_contains(&output, "No remote 'origin' configured");
+
+    let files = git(context.memo_dir(), &["ls-files"]);
+    assert!(files.contains("2025-01/30/20250130143022.md"));
+    assert!(files.contains(".gitignore"));
+    assert!(!files.contains(".index/"));
+    assert!(git(context.memo_dir(), &["log", "-1", "--format=%s"]).starts_with("memo: sync ("));
+
+    // 変更が無ければコミットしない
+    let output = context.run_command_with_env(&["sync"], GIT_ENV);
+    assert_command_success(&output);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Committed"));
+}
+
+#[test]
+fn test_auto_commit() {
+    let context = TestContext::new();
+    context.write_config("[git]\nauto_commit = true\n");
+
+    // リポジトリが無いうちは何もしない
+    assert_command_success(&context.run_command_with_env(&["add", "-m", "before"], GIT_ENV));
+    assert!(!context.memo_dir().join(".git").exists());
+
+    assert_command_success(&context.run_command_with_env(&["sync"], GIT_ENV));
+    assert_command_success(&context.run_command_with_env(&["add", "-m", "after"], GIT_ENV));
+
+    let subject = git(context.memo_dir(), &["log", "-1", "--format=%s"]);
+    assert!(subject.starts_with("memo: add "), "{}", subject);
+    assert!(git(context.memo_dir(), &["status", "--porcelain"]).is_empty());
+}
+
+#[test]
+fn test_sync_pushes_and_pulls() {
+    let context = TestContext::new();
+    let remote = context.memo_dir().parent().unwrap().join("remote.git");
+    git(
+        context.memo_dir(),
+        &["init", "--quiet", "--bare", remote.to_str().unwrap()],
+    );
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+    git(context.memo_dir(), &["init", "--quiet"]);
+    git(
+        context.memo_dir(),
+        &["remote", "add", "origin", remote.to_str().unwrap()],
+    );
+
+    let output = context.run_command_with_env(&["sync"], GIT_ENV);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Pushed to origin");
+
+    // 別の端末で追加されたメモを取り込み、検索にも反映する
+    let other = context.memo_dir().parent().unwrap().join("other");
+    git(
+        context.memo_dir(),
+        &[
+            "clone",
+            "--quiet",
+            remote.to_str().unwrap(),
+            other.to_str().unwrap(),
+        ],
+    );
+    std::fs::create_dir_all(other.join("2025-01/31")).unwrap();
+    std::fs::write(
+        other.join("2025-01/31/20250131090000.md"),
+        "# From elsewhere",
+    )
+    .unwrap();
+    git(&other, &["add", "--all"]);
+    git(&other, &["commit", "--quiet", "-m", "add"]);
+    git(&other, &["push", "--quiet"]);
+
+    let output = context.run_command_with_env(&["sync"], GIT_ENV);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Pulled from origin");
+    assert_memo_exists(&context, "2025-01/31/20250131090000.md");
+
+    let output = context.run_command(&["search", "*", "--count"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2");
+}
@@ -0,0 +1,65 @@
+use crate::utils::{TestContext, assertions::*};
+use std::fs;
+
+fn setup_tagged_memos(context: &TestContext) {
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        "---\ntags: [\"@work\", \"@mtg\"]\n---\n# Standup\n@idea later",
+    );
+    context.create_memo("2025-01/29/20250129120000.md", "# Notes\n@mtg with team\n");
+    context.create_memo("2025-01/28/20250128090000.md", "# Plain\nno tags");
+}
+
+#[test]
+fn test_tags_counts() {
+    let context = TestContext::new();
+    setup_tagged_memos(&context);
+
+    let output = context.run_command(&["tags"]);
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, vec!["   2  @mtg", "   1  @idea", "   1  @work"]);
+}
+
+#[test]
+fn test_tags_empty() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/28/20250128090000.md", "# Plain\nno tags");
+
+    let output = context.run_command(&["tags"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "No tags found.");
+}
+
+#[test]
+fn test_tags_rename() {
+    let context = TestContext::new();
+    setup_tagged_memos(&context);
+    context.build_search_index().unwrap();
+
+    let output = context.run_command(&["tags", "rename", "@mtg", "@meeting"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Renamed @mtg to @meeting in 2 memo(s)");
+
+    let first =
+        fs::read_to_string(context.memo_dir().join("2025-01/30/20250130143022.md")).unwrap();
+    assert!(first.starts_with("---\ntags: [\"@work\", \"@meeting\"]\n---\n"));
+    let second =
+        fs::read_to_string(context.memo_dir().join("2025-01/29/20250129120000.md")).unwrap();
+    assert_eq!(second, "# Notes\n@meeting with team\n");
+
+    // front matter のタグは検索インデックスにも反映される
+    let output = context.run_command(&["search", "tag:@meeting"]);
+    assert_output_contains(&output, "20250130143022");
+}
+
+#[test]
+fn test_tags_rename_unknown_tag() {
+    let context = TestContext::new();
+    setup_tagged_memos(&context);
+
+    let output = context.run_command(&["tags", "rename", "@nope", "@yes"]);
+    assert_command_failure(&output);
+    assert_command_error(&output, "no memos are tagged '@nope'");
+}
@@ -0,0 +1,10 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_tui_requires_terminal() {
+    let context = TestContext::new();
+
+    // テストの標準出力はパイプなので端末ではない
+    let output = context.run_command(&["tui"]);
+    assert_command_error(&output, "memo tui needs a terminal");
+}
@@ -0,0 +1,70 @@
+use crate::utils::{TestContext, assertions::*, search_helpers::*};
+
+#[test]
+fn test_undo_archive_and_delete() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    context.build_search_index().unwrap();
+
+    assert_command_success(&context.run_command(&["archive", "20250130143022", "20250130151545"]));
+    assert_command_success(&context.run_command(&["delete", "20250129120000"]));
+
+    // 新しい操作から 1 つずつ戻す
+    let output = context.run_command(&["undo"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Undid delete of 1 memo(s)");
+    assert_memo_exists(&context, "2025-01/29/20250129120000.md");
+    assert_memo_archived(&context, "2025-01/30/20250130143022.md");
+
+    let output = context.run_command(&["undo", "--dry-run"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, ".archive/2025-01/30/20250130143022.md -> ");
+    assert_memo_archived(&context, "2025-01/30/20250130143022.md");
+
+    let output = context.run_command(&["undo"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Undid archive of 2 memo(s)");
+    assert_memo_exists(&context, "2025-01/30/20250130143022.md");
+    assert_memo_exists(&context, "2025-01/30/20250130151545.md");
+    search_and_assert_results(
+        &context,
+        "*",
+        &["20250130143022", "20250130151545", "20250129120000"],
+    );
+
+    let output = context.run_command(&["undo"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Nothing to undo");
+}
+
+#[test]
+fn test_undo_mv() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", "# Misdated\nbody");
+    assert_command_success(&context.run_command(&["index"]));
+    assert_command_success(&context.run_command(&["mv", "20250130143022", "2024-12-24 09:15"]));
+
+    let output = context.run_command(&["undo"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Undid mv of 1 memo(s)");
+    assert_memo_exists(&context, "2025-01/30/20250130143022.md");
+    assert_memo_not_exists(&context, "2024-12/24/20241224091500.md");
+    search_and_assert_results(&context, "Misdated", &["20250130143022"]);
+}
+
+#[test]
+fn test_undo_refuses_when_target_is_taken() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", "archived");
+    assert_command_success(&context.run_command(&["archive", "20250130143022"]));
+    context.create_memo("2025-01/30/20250130143022.md", "written later");
+
+    let output = context.run_command(&["undo"]);
+    assert_command_error(&output, "already exists");
+    assert!(
+        context
+            .archive_dir()
+            .join("2025-01/30/20250130143022.md")
+            .exists()
+    );
+}
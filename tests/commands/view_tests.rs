@@ -0,0 +1,55 @@
+use crate::utils::{TestContext, assertions::*};
+
+#[test]
+fn test_view_save_run_and_delete() {
+    let context = TestContext::new();
+    context.setup_test_memos();
+    assert_command_success(&context.run_command(&["index"]));
+
+    let output = context.run_command(&["view"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "No saved views");
+
+    let output = context.run_command(&["view", "save", "fm", "tag:@test"]);
+    assert_command_success(&output);
+    assert_output_contains(&output, "Saved view fm");
+    let config = std::fs::read_to_string(context.config_home().join("memo/config.toml")).unwrap();
+    assert!(config.contains("[views]\nfm = \"tag:@test\""));
+
+    let output = context.run_command(&["view", "fm", "--count"]);
+    assert_command_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+    let output = context.run_command(&["view", "fm", "--json"]);
+    assert_output_contains(&output, "\"id\":\"20250130151545\"");
+
+    // --view は検索語でさらに絞り込む
+    let output = context.run_command(&["search", "--view", "fm", "--count"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+    let output = context.run_command(&["search", "nonexistentword", "--view", "fm", "--count"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0\n");
+
+    let output = context.run_command(&["view"]);
+    assert_output_contains(&output, "fm  tag:@test");
+
+    let output = context.run_command(&["view", "save", "fm", "tag:@basic"]);
+    assert_command_error(&output, "view 'fm' already exists");
+    let output = context.run_command(&["view", "save", "fm", "tag:@basic", "--force"]);
+    assert_command_success(&output);
+
+    assert_command_success(&context.run_command(&["view", "delete", "fm"]));
+    let output = context.run_command(&["view", "fm"]);
+    assert_command_error(&output, "unknown view 'fm'");
+    let output = context.run_command(&["view", "delete", "fm"]);
+    assert_command_error(&output, "unknown view 'fm'");
+}
+
+#[test]
+fn test_view_save_rejects_bad_input() {
+    let context = TestContext::new();
+
+    let output = context.run_command(&["view", "save", "open work", "tag:@todo"]);
+    assert_command_error(&output, "invalid view name 'open work'");
+    let output = context.run_command(&["view", "save", "open", "tittle:todo"]);
+    assert_command_error(&output, "unknown field 'tittle'");
+    assert!(!context.config_home().join("memo/config.toml").exists());
+}
@@ -0,0 +1,37 @@
+use crate::utils::{TestContext, TestMemoTemplates};
+use std::time::{Duration, Instant};
+
+/// `search * --count` が `expected` になるまで待つ
+fn wait_for_count(context: &TestContext, expected: &str) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let output = context.run_command(&["search", "*", "--count"]);
+        let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if count == expected {
+            return;
+        }
+        if Instant::now() > deadline {
+            panic!("expected {} indexed memos, got {}", expected, count);
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[test]
+fn test_watch_reindexes_changes() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+
+    let mut child = context.spawn_command(&["watch"]);
+    // 起動時に既存のメモを取り込む
+    wait_for_count(&context, "1");
+
+    let created = context.create_memo("2025-01/31/20250131090000.md", "# Written elsewhere");
+    wait_for_count(&context, "2");
+
+    std::fs::remove_file(&created.path).unwrap();
+    wait_for_count(&context, "1");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
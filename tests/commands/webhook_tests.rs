@@ -0,0 +1,72 @@
+use crate::utils::{TestContext, TestMemoTemplates, assertions::*};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+/// 1リクエストだけ受け取ってボディを返す
+fn receive_one(listener: TcpListener) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end().to_ascii_lowercase();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+            .unwrap();
+        String::from_utf8(body).unwrap()
+    })
+}
+
+#[test]
+fn test_webhook_on_archive() {
+    let context = TestContext::new();
+    context.create_memo(
+        "2025-01/30/20250130143022.md",
+        TestMemoTemplates::WITH_FRONT_MATTER,
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    context.write_config(&format!(
+        "[[webhook]]\nurl = \"http://{}/hook\"\nevents = [\"archive\"]\n",
+        listener.local_addr().unwrap()
+    ));
+    let server = receive_one(listener);
+
+    let output = context.run_command(&["archive", "20250130143022"]);
+    assert_command_success(&output);
+
+    let payload: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+    assert_eq!(payload["event"], "archive");
+    assert_eq!(payload["memo"]["id"], "20250130143022");
+    assert!(
+        payload["memo"]["path"]
+            .as_str()
+            .unwrap()
+            .contains(".archive")
+    );
+}
+
+#[test]
+fn test_webhook_failure_does_not_fail_command() {
+    let context = TestContext::new();
+    context.create_memo("2025-01/30/20250130143022.md", TestMemoTemplates::BASIC);
+    context.write_config("[[webhook]]\nurl = \"http://127.0.0.1:1/hook\"\n");
+
+    let output = context.run_command(&["archive", "20250130143022"]);
+
+    assert_command_success(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning: webhook http://127.0.0.1:1/hook failed"));
+}
@@ -1,7 +1,8 @@
 use memo::{MemoContext, MemoFile};
 use std::fs;
+use std::io::Write;
 use std::path::PathBuf;
-use std::process::{Command, Output};
+use std::process::{Child, Command, Output, Stdio};
 use tempfile::TempDir;
 
 /// テスト用のコンテキスト管理構造体
@@ -11,6 +12,12 @@ pub struct TestContext {
     pub binary_path: PathBuf,
 }
 
+impl Default for TestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TestContext {
     /// 新しいテストコンテキストを作成
     pub fn new() -> Self {
@@ -21,6 +28,7 @@ impl TestContext {
         let memo_context = MemoContext {
             memo_dir,
             editor: "echo".to_string(),
+            ..Default::default()
         };
 
         let binary_path = get_binary_path();
@@ -48,13 +56,58 @@ impl TestContext {
 
     /// コマンドを実行
     pub fn run_command(&self, args: &[&str]) -> Output {
+        self.run_command_with_env(args, &[])
+    }
+
+    /// 標準入力を与えてコマンドを実行
+    pub fn run_command_with_input(&self, args: &[&str], input: &str) -> Output {
+        let mut child = self
+            .command(args, &[])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to execute command");
+        child
+            .stdin
+            .take()
+            .expect("Failed to open stdin")
+            .write_all(input.as_bytes())
+            .expect("Failed to write stdin");
+        child
+            .wait_with_output()
+            .expect("Failed to wait for command")
+    }
+
+    /// コマンドをバックグラウンドで起動（`memo daemon` など）
+    pub fn spawn_command(&self, args: &[&str]) -> Child {
+        self.command(args, &[])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn command")
+    }
+
+    /// 環境変数を追加してコマンドを実行
+    pub fn run_command_with_env(&self, args: &[&str], envs: &[(&str, &str)]) -> Output {
+        self.command(args, envs)
+            .output()
+            .expect("Failed to execute command")
+    }
+
+    fn command(&self, args: &[&str], envs: &[(&str, &str)]) -> Command {
         let mut cmd = Command::new(&self.binary_path);
         cmd.args(args)
             .env("XDG_DATA_HOME", self.temp_dir.path())
+            .env("XDG_CONFIG_HOME", self.config_home())
             .env("EDITOR", &self.memo_context.editor)
+            .env_remove("COLUMNS") // 端末幅に依存しないように
+            .env_remove("MEMO_DATE_FORMAT")
+            .env("LC_ALL", "C") // 日付表示をロケールに依存させない
+            .envs(envs.iter().copied())
             .current_dir(self.temp_dir.path()); // 作業ディレクトリも設定
-
-        cmd.output().expect("Failed to execute command")
+        cmd
     }
 
     /// 複数のテストメモを一括作成
@@ -72,6 +125,18 @@ impl TestContext {
         memo::commands::index::run_index(&self.memo_context)
     }
 
+    /// XDG_CONFIG_HOME として使うディレクトリ
+    pub fn config_home(&self) -> PathBuf {
+        self.temp_dir.path().join("config")
+    }
+
+    /// 設定ファイル (config.toml) を書き込む
+    pub fn write_config(&self, content: &str) {
+        let config_dir = self.config_home().join("memo");
+        fs::create_dir_all(&config_dir).expect("Failed to create config directory");
+        fs::write(config_dir.join("config.toml"), content).expect("Failed to write config");
+    }
+
     /// メモディレクトリのパスを取得
     pub fn memo_dir(&self) -> &std::path::Path {
         &self.memo_context.memo_dir
@@ -297,14 +362,26 @@ pub mod mocks {
 
     /// 特定の内容を書き込むエディタスクリプトを作成
     pub fn create_mock_editor_script(content: &str) -> std::path::PathBuf {
-        use std::io::Write;
-
-        let script_content = format!(
+        write_mock_editor(&format!(
             r#"#!/bin/bash
 echo '{}' > "$1"
 "#,
             content.replace('\'', "'\"'\"'")
-        );
+        ))
+    }
+
+    /// 渡された全てのファイルの末尾に1行追記するエディタスクリプトを作成
+    pub fn create_mock_append_editor_script(line: &str) -> std::path::PathBuf {
+        write_mock_editor(&format!(
+            r#"#!/bin/bash
+for f in "$@"; do echo '{}' >> "$f"; done
+"#,
+            line.replace('\'', "'\"'\"'")
+        ))
+    }
+
+    fn write_mock_editor(script_content: &str) -> std::path::PathBuf {
+        use std::io::Write;
 
         // 一意なファイル名を生成
         let timestamp = std::time::SystemTime::now()